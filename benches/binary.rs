@@ -0,0 +1,125 @@
+/*
+ * benches/binary.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Compares the speed of the binary tree format against JSON.
+//!
+//! Usage: `cargo bench --bench binary`
+//!
+//! The inputs of the test fixtures are parsed, and the resulting trees
+//! are serialized and deserialized in each format. The total size of each
+//! format is printed before the benchmarks are run.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use ftml::data::{PageInfo, ScoreValue};
+use ftml::layout::Layout;
+use ftml::settings::{WikitextMode, WikitextSettings};
+use ftml::tree::SyntaxTree;
+use std::borrow::Cow;
+use std::fs;
+use std::hint::black_box;
+
+fn parse_fixtures() -> Vec<SyntaxTree<'static>> {
+    let page_info = PageInfo {
+        page: Cow::Borrowed("binary-bench"),
+        category: None,
+        site: Cow::Borrowed("test"),
+        title: Cow::Borrowed("Binary Benchmark"),
+        alt_title: None,
+        score: ScoreValue::Integer(0),
+        tags: vec![],
+        language: Cow::Borrowed("default"),
+    };
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let mut trees = Vec::new();
+
+    for entry in fs::read_dir("test").expect("Unable to read test directory") {
+        let path = entry.expect("Unable to read directory entry").path();
+        if path.extension().is_some_and(|ext| ext == "json") {
+            let contents = fs::read_to_string(&path).expect("Unable to read file");
+            let value: serde_json::Value =
+                serde_json::from_str(&contents).expect("Invalid JSON");
+
+            if let Some(input) = value["input"].as_str() {
+                let mut input = input.to_owned();
+                ftml::preprocess(&mut input, &page_info, &settings);
+                let tokens = ftml::tokenize(&input);
+                let (tree, _errors) = ftml::parse(&tokens, &page_info, &settings).into();
+
+                trees.push(tree.to_owned());
+            }
+        }
+    }
+
+    trees
+}
+
+fn binary(c: &mut Criterion) {
+    let trees = parse_fixtures();
+    let json: Vec<_> = trees
+        .iter()
+        .map(|tree| serde_json::to_vec(tree).unwrap())
+        .collect();
+    let binary: Vec<_> = trees.iter().map(SyntaxTree::to_bytes).collect();
+
+    let json_size: usize = json.iter().map(Vec::len).sum();
+    let binary_size: usize = binary.iter().map(Vec::len).sum();
+    println!(
+        "{} trees, JSON {json_size} bytes, binary {binary_size} bytes ({:.1}%)",
+        trees.len(),
+        binary_size as f64 / json_size as f64 * 100.0,
+    );
+
+    let mut group = c.benchmark_group("write");
+    group.bench_function("json", |b| {
+        b.iter(|| {
+            for tree in &trees {
+                black_box(serde_json::to_vec(tree).unwrap());
+            }
+        })
+    });
+    group.bench_function("binary", |b| {
+        b.iter(|| {
+            for tree in &trees {
+                black_box(tree.to_bytes());
+            }
+        })
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("read");
+    group.bench_function("json", |b| {
+        b.iter(|| {
+            for bytes in &json {
+                black_box(serde_json::from_slice::<SyntaxTree>(bytes).unwrap());
+            }
+        })
+    });
+    group.bench_function("binary", |b| {
+        b.iter(|| {
+            for bytes in &binary {
+                black_box(SyntaxTree::from_bytes(bytes).unwrap());
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, binary);
+criterion_main!(benches);
@@ -0,0 +1,69 @@
+/*
+ * benches/tokenizer.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Measures how tokenization time grows on worst-case inputs.
+//!
+//! Usage: `cargo bench --bench tokenizer`
+//!
+//! Each input is a single pattern repeated without whitespace, which is
+//! where the lexer is most likely to scan ahead and backtrack. Each is
+//! benchmarked at several sizes, so that the throughput can be compared.
+//! For linear tokenization, it should stay about the same as the input grows.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::hint::black_box;
+
+const SIZES: [usize; 3] = [1 << 12, 1 << 14, 1 << 16];
+
+const PATTERNS: [(&str, &str); 11] = [
+    ("left-brackets", "["),
+    ("right-brackets", "]"),
+    ("dashes", "-"),
+    ("headings", "+"),
+    ("asterisks", "*"),
+    ("tildes", "~~~<"),
+    ("at-signs", "a@b."),
+    ("email-local-parts", "a."),
+    ("escaped-quotes", "\"\\"),
+    ("unclosed-variables", "{$a|"),
+    ("unclosed-comments", "[!-"),
+];
+
+fn tokenizer(c: &mut Criterion) {
+    for (name, pattern) in PATTERNS {
+        let mut group = c.benchmark_group(name);
+
+        for bytes in SIZES {
+            let input = pattern.repeat(bytes / pattern.len());
+
+            group.throughput(Throughput::Bytes(input.len() as u64));
+            group.bench_with_input(
+                BenchmarkId::from_parameter(bytes),
+                &input,
+                |b, input| b.iter(|| black_box(ftml::tokenize(input))),
+            );
+        }
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, tokenizer);
+criterion_main!(benches);
@@ -69,7 +69,7 @@ fn main() {
             }
         };
 
-        let outcome = Outcome::Success(syntax_tree);
+        let outcome = Outcome::Success(Box::new(syntax_tree));
         results.push((path, outcome));
     }
 
@@ -105,14 +105,14 @@ fn write_result(
 
 #[derive(Debug)]
 enum Outcome {
-    Success(ftml::tree::SyntaxTree<'static>),
+    Success(Box<ftml::tree::SyntaxTree<'static>>),
     Failure(String),
     Error(String),
 }
 
 impl Outcome {
     fn write(&self, buffer: &mut Buffer, path: &Path) -> io::Result<()> {
-        buffer.set_color(&ColorSpec::new().set_bold(true).set_fg(Some(self.color())))?;
+        buffer.set_color(ColorSpec::new().set_bold(true).set_fg(Some(self.color())))?;
 
         match self {
             Outcome::Success(_) => write!(buffer, " pass")?,
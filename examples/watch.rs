@@ -0,0 +1,301 @@
+/*
+ * examples/watch.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Renders a wikitext file and serves it locally, re-rendering whenever it changes.
+//!
+//! Usage: `cargo run --example watch -- <file> [port]`
+//!
+//! Then open `http://localhost:<port>/` (by default port 8000). The page reloads
+//! itself whenever the file, or any page it includes, is modified.
+//!
+//! Included pages are read from the same directory as the file, named after
+//! the page with a `.ftml` extension. For instance, `[[include-messy component:box]]`
+//! reads `component:box.ftml`.
+
+extern crate ftml;
+
+use ftml::budget::WorkBudget;
+use ftml::data::{PageInfo, PageRef, ScoreValue};
+use ftml::includes::{FetchedPage, IncludeRecursion, IncludeRef, Includer};
+use ftml::layout::Layout;
+use ftml::render::html::{HtmlHooks, HtmlRender};
+use ftml::settings::{WikitextMode, WikitextSettings};
+use std::borrow::Cow;
+use std::convert::Infallible;
+use std::env;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+const DEFAULT_PORT: u16 = 8000;
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+const RELOAD_SCRIPT: &str = r#"<script>
+(function () {
+  let version = null;
+  setInterval(async () => {
+    try {
+      const response = await fetch("/version");
+      const current = await response.text();
+      if (version === null) {
+        version = current;
+      } else if (version !== current) {
+        location.reload();
+      }
+    } catch (_) {}
+  }, 500);
+})();
+</script>"#;
+
+#[derive(Debug, Default)]
+struct Page {
+    html: String,
+    version: u64,
+}
+
+fn main() {
+    let mut args = env::args_os().skip(1);
+    let path = match args.next() {
+        Some(path) => PathBuf::from(path),
+        None => {
+            eprintln!("Usage: watch <file> [port]");
+            process::exit(1);
+        }
+    };
+    let port = match args.next() {
+        Some(port) => port
+            .to_str()
+            .and_then(|port| port.parse().ok())
+            .unwrap_or_else(|| {
+                eprintln!("Invalid port: {}", port.to_string_lossy());
+                process::exit(1);
+            }),
+        None => DEFAULT_PORT,
+    };
+
+    let page = Arc::new(Mutex::new(Page::default()));
+
+    // Re-render on changes
+    {
+        let page = Arc::clone(&page);
+        thread::spawn(move || watch(&path, &page));
+    }
+
+    // Serve the rendered page
+    let listener = TcpListener::bind(("127.0.0.1", port)).unwrap_or_else(|error| {
+        eprintln!("Unable to listen on port {port}: {error}");
+        process::exit(1);
+    });
+
+    println!("Serving on http://localhost:{port}/");
+
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                if let Err(error) = respond(stream, &page) {
+                    eprintln!("Error responding to request: {error}");
+                }
+            }
+            Err(error) => eprintln!("Error accepting connection: {error}"),
+        }
+    }
+}
+
+/// Polls the file and its included pages, rendering whenever any are modified.
+fn watch(path: &Path, page: &Mutex<Page>) {
+    let mut watched = vec![path.to_owned()];
+    let mut last_modified = Vec::new();
+
+    loop {
+        let modified: Vec<Option<SystemTime>> = watched
+            .iter()
+            .map(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+            .collect();
+
+        if modified != last_modified {
+            let (html, included) = render(path);
+            let mut page = page.lock().unwrap();
+            page.html = html;
+            page.version += 1;
+
+            watched.truncate(1);
+            watched.extend(included);
+            last_modified = watched
+                .iter()
+                .map(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+                .collect();
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Renders the file as an HTML document.
+///
+/// Also returns the paths of all the included pages.
+fn render(path: &Path) -> (String, Vec<PathBuf>) {
+    let input = match fs::read_to_string(path) {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!("Unable to read {}: {error}", path.display());
+            let html = format!("<pre>Unable to read {}: {error}</pre>", path.display());
+            return (html, Vec::new());
+        }
+    };
+
+    let name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let page_info = PageInfo {
+        page: Cow::Owned(name.clone()),
+        category: None,
+        site: Cow::Borrowed("localhost"),
+        title: Cow::Owned(name),
+        alt_title: None,
+        score: ScoreValue::Integer(0),
+        tags: vec![],
+        language: Cow::Borrowed("default"),
+    };
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let directory = path.parent().unwrap_or(Path::new("."));
+    let includer = FileIncluder { directory };
+
+    let budget = WorkBudget::unlimited();
+    let (mut text, pages, _errors) =
+        ftml::include_page(&input, &page_info, &settings, includer, &budget);
+
+    ftml::preprocess(&mut text, &page_info, &settings);
+    let tokens = ftml::tokenize(&text);
+    let (tree, errors) = ftml::parse(&tokens, &page_info, &settings).into();
+    let mut html =
+        HtmlRender.render_document(&tree, &page_info, &settings, HtmlHooks::default());
+
+    println!(
+        "Rendered {} ({} bytes, {} errors)",
+        path.display(),
+        html.len(),
+        errors.len(),
+    );
+
+    for error in &errors {
+        println!(
+            "  {} at {}..{} ({})",
+            error.kind().name(),
+            error.span().start,
+            error.span().end,
+            error.rule(),
+        );
+    }
+
+    // Reload the page when the file changes
+    let body_end = html.rfind("</body>").unwrap_or(html.len());
+    html.insert(body_end, '\n');
+    html.insert_str(body_end, RELOAD_SCRIPT);
+
+    let included = pages
+        .iter()
+        .map(|page_ref| FileIncluder { directory }.path(page_ref))
+        .collect();
+
+    (html, included)
+}
+
+/// Responds to a single HTTP request.
+fn respond(mut stream: TcpStream, page: &Mutex<Page>) -> std::io::Result<()> {
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line)?;
+
+    let path = request_line.split_whitespace().nth(1).unwrap_or("/");
+    let (status, content_type, body) = {
+        let page = page.lock().unwrap();
+
+        match path {
+            "/" => ("200 OK", "text/html; charset=utf-8", page.html.clone()),
+            "/version" => ("200 OK", "text/plain", page.version.to_string()),
+            _ => ("404 Not Found", "text/plain", String::from("Not found")),
+        }
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{body}",
+        body.len(),
+    )
+}
+
+/// Includes pages from files in a directory.
+#[derive(Debug)]
+struct FileIncluder<'a> {
+    directory: &'a Path,
+}
+
+impl FileIncluder<'_> {
+    fn path(&self, page_ref: &PageRef) -> PathBuf {
+        self.directory.join(format!("{}.ftml", page_ref.page()))
+    }
+}
+
+impl<'t> Includer<'t> for FileIncluder<'_> {
+    type Error = Infallible;
+
+    fn include_pages(
+        &mut self,
+        includes: &[IncludeRef<'t>],
+    ) -> Result<Vec<FetchedPage<'t>>, Infallible> {
+        let pages = includes
+            .iter()
+            .map(|include| {
+                let page_ref = include.page_ref().clone();
+                let content = fs::read_to_string(self.path(&page_ref))
+                    .ok()
+                    .map(Cow::Owned);
+                FetchedPage { page_ref, content }
+            })
+            .collect();
+
+        Ok(pages)
+    }
+
+    fn no_such_include(
+        &mut self,
+        page_ref: &PageRef<'t>,
+    ) -> Result<Cow<'t, str>, Infallible> {
+        Ok(Cow::Owned(format!(
+            "[[div class=\"wj-error\"]]\nNo such page '{page_ref}' ({})\n[[/div]]",
+            self.path(page_ref).display(),
+        )))
+    }
+
+    fn include_recursion(
+        &mut self,
+        recursion: &IncludeRecursion<'t>,
+    ) -> Result<Cow<'t, str>, Infallible> {
+        Ok(Cow::Owned(format!(
+            "[[div class=\"wj-error\"]]\n{recursion}\n[[/div]]",
+        )))
+    }
+}
@@ -0,0 +1,28 @@
+/*
+ * analysis/mod.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Analysis of syntax trees, for information about a page beyond rendering it.
+//!
+//! These are intended for hosts to show information about a page's content,
+//! such as readability metrics on drafts.
+
+mod text_statistics;
+
+pub use self::text_statistics::{text_statistics, ParagraphStatistics, TextStatistics};
@@ -0,0 +1,250 @@
+/*
+ * analysis/text_statistics.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Sentence and word counts of a page's prose.
+//!
+//! Only paragraphs are considered, since headings, lists, and tables
+//! usually aren't prose. The counts are approximate: words are separated
+//! by whitespace, and sentences end with terminal punctuation (such as `.`
+//! or `。`) or at the end of the paragraph. This means that abbreviations
+//! like "Dr." are counted as ending a sentence.
+
+use crate::tree::visit::{walk_container, walk_element, Visitor};
+use crate::tree::{Container, ContainerType, Element, LinkLabel, SyntaxTree};
+
+/// Text statistics for a whole page.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct TextStatistics {
+    /// Statistics for each paragraph containing text, in order.
+    pub paragraphs: Vec<ParagraphStatistics>,
+
+    /// The total number of sentences in all paragraphs.
+    pub sentence_count: usize,
+
+    /// The total number of words in all paragraphs.
+    pub word_count: usize,
+
+    /// The mean number of words per sentence.
+    ///
+    /// This is `0.0` if there are no sentences.
+    pub average_sentence_length: f64,
+}
+
+/// Text statistics for a single paragraph.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct ParagraphStatistics {
+    pub sentence_count: usize,
+    pub word_count: usize,
+}
+
+/// Computes text statistics from the paragraphs in a syntax tree.
+pub fn text_statistics(tree: &SyntaxTree) -> TextStatistics {
+    #[derive(Debug, Default)]
+    struct Collector {
+        paragraphs: Vec<ParagraphStatistics>,
+        buffer: Option<String>,
+    }
+
+    impl<'t> Visitor<'t> for Collector {
+        fn visit_element(&mut self, element: &Element<'t>) {
+            if let Some(buffer) = &mut self.buffer {
+                match element {
+                    Element::Text(text) | Element::Raw(text) => buffer.push_str(text),
+                    Element::Link {
+                        label: LinkLabel::Text(text) | LinkLabel::Url(Some(text)),
+                        ..
+                    } => buffer.push_str(text),
+                    Element::LineBreak | Element::LineBreaks(_) => buffer.push(' '),
+                    _ => (),
+                }
+            }
+
+            walk_element(self, element);
+        }
+
+        fn visit_container(&mut self, container: &Container<'t>) {
+            match container.ctype() {
+                // Gather the text of this paragraph, then count it
+                ContainerType::Paragraph if self.buffer.is_none() => {
+                    self.buffer = Some(String::new());
+                    walk_container(self, container);
+
+                    let text = self.buffer.take().unwrap_or_default();
+                    let statistics = count_text(&text);
+                    if statistics.word_count > 0 {
+                        self.paragraphs.push(statistics);
+                    }
+                }
+
+                // Ruby annotations are not part of the sentence
+                ContainerType::RubyText => (),
+
+                _ => walk_container(self, container),
+            }
+        }
+    }
+
+    // Only the main body is walked, like with headings.
+    let mut collector = Collector::default();
+    collector.visit_elements(&tree.elements);
+
+    let paragraphs = collector.paragraphs;
+    let sentence_count = paragraphs.iter().map(|p| p.sentence_count).sum();
+    let word_count = paragraphs.iter().map(|p| p.word_count).sum();
+    let average_sentence_length = if sentence_count > 0 {
+        word_count as f64 / sentence_count as f64
+    } else {
+        0.0
+    };
+
+    TextStatistics {
+        paragraphs,
+        sentence_count,
+        word_count,
+        average_sentence_length,
+    }
+}
+
+/// Counts the sentences and words in a run of text.
+fn count_text(text: &str) -> ParagraphStatistics {
+    let mut statistics = ParagraphStatistics::default();
+    let mut in_word = false;
+    let mut in_sentence = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if ch.is_whitespace() {
+            in_word = false;
+            continue;
+        }
+
+        if ch.is_alphanumeric() {
+            if !in_word {
+                statistics.word_count += 1;
+                in_word = true;
+            }
+
+            in_sentence = true;
+            continue;
+        }
+
+        // Full-width punctuation needs no following space,
+        // but otherwise "3.14" or "example.com" do not end a sentence.
+        let ends_sentence = match ch {
+            '。' | '！' | '？' => true,
+            '.' | '!' | '?' | '…' => {
+                // Skip over closing quotes and brackets
+                while chars
+                    .peek()
+                    .is_some_and(|&ch| matches!(ch, '"' | '\'' | '”' | '’' | ')' | ']'))
+                {
+                    chars.next();
+                }
+
+                chars.peek().is_none_or(|ch| ch.is_whitespace())
+            }
+            _ => false,
+        };
+
+        if ends_sentence && in_sentence {
+            statistics.sentence_count += 1;
+            in_sentence = false;
+        }
+    }
+
+    // Trailing sentence without punctuation
+    if in_sentence {
+        statistics.sentence_count += 1;
+    }
+
+    statistics
+}
+
+#[test]
+fn count() {
+    macro_rules! check {
+        ($text:expr, $sentences:expr, $words:expr $(,)?) => {{
+            let statistics = count_text($text);
+
+            assert_eq!(
+                statistics,
+                ParagraphStatistics {
+                    sentence_count: $sentences,
+                    word_count: $words,
+                },
+                "Text statistics for {:?} don't match expected",
+                $text,
+            );
+        }};
+    }
+
+    check!("", 0, 0);
+    check!("...", 0, 0);
+    check!("Apple", 1, 1);
+    check!("Apple banana.", 1, 2);
+    check!("Apple banana. Cherry!", 2, 3);
+    check!("Is it 3.14? Yes, it's pi.", 2, 6);
+    check!("He said \"stop.\" Then he left", 2, 6);
+    check!("Wait... what?!", 2, 2);
+    check!("See example.com for more", 1, 4);
+    check!("日本語です。次の文。", 2, 1);
+}
+
+#[test]
+fn statistics() {
+    use crate::data::PageInfo;
+    use crate::layout::Layout;
+    use crate::settings::{WikitextMode, WikitextSettings};
+
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+
+    let text = "+ Heading Text\n\nThe **quick** brown fox. It jumps over the [[[lazy-dog|lazy dog]]]!\n\n* List item\n\n[[div]]\nA second paragraph\n[[/div]]";
+    let tokens = crate::tokenize(text);
+    let (tree, _) = crate::parse(&tokens, &page_info, &settings).into();
+
+    assert_eq!(
+        text_statistics(&tree),
+        TextStatistics {
+            paragraphs: vec![
+                ParagraphStatistics {
+                    sentence_count: 2,
+                    word_count: 10,
+                },
+                ParagraphStatistics {
+                    sentence_count: 1,
+                    word_count: 3,
+                },
+            ],
+            sentence_count: 3,
+            word_count: 13,
+            average_sentence_length: 13.0 / 3.0,
+        },
+        "Text statistics don't match expected",
+    );
+
+    assert_eq!(
+        text_statistics(&SyntaxTree::default()),
+        TextStatistics::default(),
+        "Text statistics for an empty tree aren't empty",
+    );
+}
@@ -0,0 +1,176 @@
+/*
+ * budget.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Shared limit on the amount of work done processing a document.
+//!
+//! A [`WorkBudget`] is passed through each stage of the pipeline (include,
+//! preprocess, tokenize, parse, and render) via their `*_with_budget()` variants.
+//! Each stage spends from the same budget, and once it is exhausted, any stage
+//! aborts with [`BudgetExceeded`]. This gives hosts a single knob to bound the total
+//! amount of processing for a document, regardless of which stage is expensive.
+//! (Includes instead stop substituting pages, and report an
+//! [`IncludeError`](crate::includes::IncludeError). Parsing instead stops and
+//! returns the partial syntax tree, with the rest of the input as text.)
+//!
+//! The cost units are approximate, and roughly correspond to:
+//! * Include: one unit per byte of included page content.
+//! * Preprocess: one unit per byte of input.
+//! * Tokenize: one unit per byte of input.
+//! * Parse: one unit per token stepped over, including when backtracking.
+//! * Render: one unit per element in the syntax tree.
+//!
+//! The stage-specific depth limits (such as for recursion or list nesting)
+//! still apply, as they protect against stack exhaustion rather than total cost.
+
+use crate::tree::visit::{walk_element, Visitor};
+use crate::tree::{Element, SyntaxTree};
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// A limit on the total amount of work to be done, shared across pipeline stages.
+///
+/// This is thread-safe, so the same budget may be used by work happening in parallel.
+#[derive(Debug)]
+pub struct WorkBudget {
+    limit: usize,
+    spent: AtomicUsize,
+}
+
+impl WorkBudget {
+    /// Creates a new budget which permits up to `limit` units of work.
+    #[inline]
+    pub fn new(limit: usize) -> Self {
+        WorkBudget {
+            limit,
+            spent: AtomicUsize::new(0),
+        }
+    }
+
+    /// Creates a new budget which never runs out.
+    #[inline]
+    pub fn unlimited() -> Self {
+        WorkBudget::new(usize::MAX)
+    }
+
+    #[inline]
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    #[inline]
+    pub fn spent(&self) -> usize {
+        self.spent.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.limit.saturating_sub(self.spent())
+    }
+
+    #[inline]
+    pub fn is_exhausted(&self) -> bool {
+        self.spent() > self.limit
+    }
+
+    /// Spends the given amount of work from this budget.
+    ///
+    /// Returns an error if this causes the budget to be exceeded.
+    /// Once the budget is exceeded, all further calls will also fail.
+    pub fn spend(&self, cost: usize) -> Result<(), BudgetExceeded> {
+        let spent = self
+            .spent
+            .fetch_add(cost, Ordering::Relaxed)
+            .saturating_add(cost);
+
+        if spent > self.limit {
+            // Avoid wrapping if there are many further calls
+            self.spent.store(spent, Ordering::Relaxed);
+
+            warn!("Work budget exceeded (spent {spent}, limit {})", self.limit);
+            return Err(BudgetExceeded { limit: self.limit });
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for WorkBudget {
+    #[inline]
+    fn default() -> Self {
+        WorkBudget::unlimited()
+    }
+}
+
+/// Error returned when a [`WorkBudget`] has been exhausted.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct BudgetExceeded {
+    /// The limit of the budget which was exceeded.
+    pub limit: usize,
+}
+
+impl Display for BudgetExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "work budget of {} units exceeded", self.limit)
+    }
+}
+
+impl Error for BudgetExceeded {}
+
+/// Determines the cost of rendering a syntax tree.
+///
+/// This is the number of elements it contains, at any depth.
+pub(crate) fn render_cost(tree: &SyntaxTree) -> usize {
+    #[derive(Debug)]
+    struct ElementCounter(usize);
+
+    impl<'t> Visitor<'t> for ElementCounter {
+        fn visit_element(&mut self, element: &Element<'t>) {
+            self.0 += 1;
+            walk_element(self, element);
+        }
+    }
+
+    let mut counter = ElementCounter(0);
+    counter.visit_syntax_tree(tree);
+    counter.0
+}
+
+#[test]
+fn budget() {
+    let budget = WorkBudget::new(10);
+    assert_eq!(budget.remaining(), 10);
+    assert!(!budget.is_exhausted());
+
+    budget.spend(4).expect("Budget exceeded early");
+    budget.spend(6).expect("Budget exceeded early");
+    assert_eq!(budget.spent(), 10);
+    assert_eq!(budget.remaining(), 0);
+    assert!(!budget.is_exhausted());
+
+    assert_eq!(budget.spend(1), Err(BudgetExceeded { limit: 10 }));
+    assert!(budget.is_exhausted());
+    assert!(budget.spend(0).is_err(), "Exhausted budget permitted work");
+
+    let budget = WorkBudget::unlimited();
+    budget.spend(usize::MAX).expect("Unlimited budget exceeded");
+    budget.spend(0).expect("Unlimited budget exceeded");
+}
@@ -19,19 +19,233 @@
  */
 
 use crate::data::PageRef;
+use crate::render::Handle;
+use crate::settings::WikitextSettings;
+use crate::tree::visit::{walk_element, Visitor};
+use crate::tree::{Element, LinkLocation, LinkType, SyntaxTree};
+use crate::url::{is_url_with_settings, BuildSiteUrl, SiteLocation};
 use std::borrow::Cow;
 
+/// Collects the backlinks of a syntax tree, without rendering it.
+///
+/// This finds the same links and included pages as rendering does,
+/// except that each is recorded once, even if rendering would emit it
+/// several times (such as links in footnotes).
+pub fn extract(tree: &SyntaxTree, settings: &WikitextSettings) -> Backlinks<'static> {
+    #[derive(Debug)]
+    struct Collector<'s> {
+        backlinks: Backlinks<'static>,
+        settings: &'s WikitextSettings,
+    }
+
+    impl<'t> Visitor<'t> for Collector<'_> {
+        fn visit_element(&mut self, element: &Element<'t>) {
+            match element {
+                Element::Link {
+                    ltype, link, extra, ..
+                } => {
+                    let extra = extra.as_deref();
+                    self.backlinks
+                        .add_link(link, extra, *ltype, self.settings, &Handle);
+                }
+                Element::Include { location, .. } => {
+                    self.backlinks.included_pages.push(location.to_owned());
+                }
+                _ => (),
+            }
+
+            walk_element(self, element);
+        }
+    }
+
+    let mut collector = Collector {
+        backlinks: Backlinks::new(),
+        settings,
+    };
+
+    collector.visit_elements(&tree.elements);
+
+    for footnote in &tree.footnotes {
+        collector.visit_elements(footnote);
+    }
+
+    collector.backlinks
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq, Default)]
 #[serde(rename_all = "kebab-case")]
 pub struct Backlinks<'a> {
     pub included_pages: Vec<PageRef<'a>>,
-    pub internal_links: Vec<PageRef<'a>>,
-    pub external_links: Vec<Cow<'a, str>>,
+    pub internal_links: Vec<InternalLink<'a>>,
+    pub external_links: Vec<ExternalLink<'a>>,
 }
 
-impl Backlinks<'_> {
+impl<'a> Backlinks<'a> {
     #[inline]
     pub fn new() -> Self {
         Backlinks::default()
     }
+
+    /// Moves all entries from `other` into this instance, leaving it empty.
+    pub fn append(&mut self, other: &mut Backlinks<'a>) {
+        self.included_pages.append(&mut other.included_pages);
+        self.internal_links.append(&mut other.internal_links);
+        self.external_links.append(&mut other.external_links);
+    }
+}
+
+impl Backlinks<'static> {
+    /// Records a link, as either internal or external.
+    pub(crate) fn add_link(
+        &mut self,
+        link: &LinkLocation,
+        extra: Option<&str>,
+        ltype: LinkType,
+        settings: &WikitextSettings,
+        helper: &dyn BuildSiteUrl,
+    ) {
+        // TODO: set to internal link if domain matches site
+        // See https://scuttle.atlassian.net/browse/WJ-24
+
+        match link {
+            LinkLocation::Page(page) => match page.site() {
+                // Pages on sites outside this farm are external
+                Some(site) if helper.site_location(site) == SiteLocation::External => {
+                    let url = helper.build_url(site, page.page());
+
+                    self.external_links.push(ExternalLink {
+                        url: Cow::Owned(format!("{url}{}", extra.unwrap_or(""))),
+                        ltype,
+                    });
+                }
+                _ => {
+                    self.internal_links.push(InternalLink {
+                        page: page.to_owned(),
+                        extra: extra.map(|extra| Cow::Owned(str!(extra))),
+                        ltype,
+                    });
+                }
+            },
+            LinkLocation::Url(link) => {
+                let mut link: &str = link;
+
+                if link == "javascript:;" {
+                    return;
+                }
+
+                // Also support [ links pointing to local pages.
+                // e.g. [/scp-001 SCP-001] in addition to [[[SCP-001]]].
+                if link.starts_with('/') {
+                    link = &link[1..];
+                }
+
+                if is_url_with_settings(link, settings) {
+                    self.external_links.push(ExternalLink {
+                        url: Cow::Owned(str!(link)),
+                        ltype,
+                    });
+                } else {
+                    // Separate the page from any anchor or path after it,
+                    // e.g. [/scp-001#toc0 SCP-001].
+                    let extra = LinkLocation::parse_extra(cow!(link), settings)
+                        .or_else(|| extra.map(|extra| cow!(extra)));
+
+                    let page = match LinkLocation::parse(cow!(link), settings) {
+                        LinkLocation::Page(page_ref) => page_ref,
+                        LinkLocation::Url(_) => PageRef::page_only(cow!(link)),
+                    };
+
+                    self.internal_links.push(InternalLink {
+                        page: page.to_owned(),
+                        extra: extra.map(|extra| Cow::Owned(extra.into_owned())),
+                        ltype,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// A link to a page on a wiki, as recorded in [`Backlinks`].
+#[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct InternalLink<'a> {
+    /// The page being linked to.
+    pub page: PageRef<'a>,
+
+    /// The portion of the link after the page name, if any.
+    ///
+    /// For instance, `#toc0` in `[[[scp-001#toc0]]]`,
+    /// or `/comments/show` in `[[[scp-001/comments/show]]]`.
+    pub extra: Option<Cow<'a, str>>,
+
+    /// What kind of link syntax produced this entry.
+    #[serde(rename = "type")]
+    pub ltype: LinkType,
+}
+
+/// A link to an external URL, as recorded in [`Backlinks`].
+#[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct ExternalLink<'a> {
+    /// The URL being linked to.
+    pub url: Cow<'a, str>,
+
+    /// What kind of link syntax produced this entry.
+    ///
+    /// Links produced through interwiki substitution
+    /// are marked as [`LinkType::Interwiki`].
+    #[serde(rename = "type")]
+    pub ltype: LinkType,
+}
+
+#[test]
+fn extract_backlinks() {
+    use crate::data::PageInfo;
+    use crate::layout::Layout;
+    use crate::settings::WikitextMode;
+
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let text = "[[[scp-001#toc0|SCP-001]]] [/scp-002/comments SCP-002] [[[:external:scp-003]]]\n\n[[include-elements component:box]]\n[[include-elements component:box]]\n\nApple[[footnote]]See [https://example.com/ here].[[/footnote]]";
+
+    let tokens = crate::tokenize(text);
+    let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+    let backlinks = extract(&tree, &settings);
+
+    assert_eq!(
+        backlinks.internal_links,
+        vec![
+            InternalLink {
+                page: PageRef::page_only(cow!("scp-001")),
+                extra: Some(cow!("#toc0")),
+                ltype: LinkType::Page,
+            },
+            InternalLink {
+                page: PageRef::page_only(cow!("scp-002")),
+                extra: Some(cow!("/comments")),
+                ltype: LinkType::Direct,
+            },
+        ],
+    );
+    assert_eq!(
+        backlinks.external_links,
+        vec![
+            ExternalLink {
+                url: cow!("https://external.wikijump.com/scp-003"),
+                ltype: LinkType::Page,
+            },
+            ExternalLink {
+                url: cow!("https://example.com/"),
+                ltype: LinkType::Direct,
+            },
+        ],
+    );
+    assert_eq!(
+        backlinks.included_pages,
+        vec![
+            PageRef::page_only(cow!("component:box")),
+            PageRef::page_only(cow!("component:box")),
+        ],
+    );
 }
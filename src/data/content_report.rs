@@ -0,0 +1,86 @@
+/*
+ * data/content_report.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use crate::data::PageRef;
+use crate::parsing::ParseError;
+use crate::tree::{ImageSource, LinkType};
+
+/// Problems with a page's content which were found while rendering it.
+///
+/// Each entry is recorded every time it occurs, so a page linking
+/// to the same missing page twice has two dead links.
+#[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct ContentReport<'a> {
+    /// Links to pages which do not exist.
+    pub dead_links: Vec<DeadLink<'a>>,
+
+    /// Pages included via `[[include-elements]]` which do not exist.
+    ///
+    /// Since `[[include-messy]]` is substituted before parsing, missing pages
+    /// there are instead passed to [`Includer::no_such_include()`].
+    ///
+    /// [`Includer::no_such_include()`]: crate::includes::Includer::no_such_include
+    pub missing_includes: Vec<PageRef<'a>>,
+
+    /// Images whose source could not be used, for instance a local file
+    /// when `allow_local_paths` is disabled in the settings.
+    pub blocked_images: Vec<ImageSource<'a>>,
+
+    /// Errors from parsing the wikitext of expanded modules.
+    ///
+    /// See [`ModuleRenderer`](crate::render::html::ModuleRenderer).
+    pub module_errors: Vec<ParseError>,
+}
+
+impl<'a> ContentReport<'a> {
+    #[inline]
+    pub fn new() -> Self {
+        ContentReport::default()
+    }
+
+    /// Whether no problems were found.
+    pub fn is_empty(&self) -> bool {
+        self.dead_links.is_empty()
+            && self.missing_includes.is_empty()
+            && self.blocked_images.is_empty()
+            && self.module_errors.is_empty()
+    }
+
+    /// Moves all entries from `other` into this instance, leaving it empty.
+    pub fn append(&mut self, other: &mut ContentReport<'a>) {
+        self.dead_links.append(&mut other.dead_links);
+        self.missing_includes.append(&mut other.missing_includes);
+        self.blocked_images.append(&mut other.blocked_images);
+        self.module_errors.append(&mut other.module_errors);
+    }
+}
+
+/// A link to a page which does not exist, as recorded in [`ContentReport`].
+#[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct DeadLink<'a> {
+    /// The page being linked to.
+    pub page: PageRef<'a>,
+
+    /// What kind of link syntax produced this entry.
+    #[serde(rename = "type")]
+    pub ltype: LinkType,
+}
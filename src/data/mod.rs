@@ -20,14 +20,18 @@
 
 //! This module defines POD (plain old data) structs.
 
-mod backlinks;
+mod content_report;
 mod karma;
 mod page_info;
 mod page_ref;
 mod score;
 mod user_info;
 
-pub use self::backlinks::Backlinks;
+pub mod backlinks;
+pub mod wire;
+
+pub use self::backlinks::{Backlinks, ExternalLink, InternalLink};
+pub use self::content_report::{ContentReport, DeadLink};
 pub use self::karma::KarmaLevel;
 pub use self::page_info::PageInfo;
 pub use self::page_ref::{PageRef, PageRefParseError};
@@ -63,6 +63,35 @@ pub struct PageInfo<'a> {
 }
 
 impl PageInfo<'_> {
+    /// Gets the value of a page variable, such as `%%title%%`.
+    ///
+    /// Only variables which can be derived from this structure are supported:
+    /// * `title`
+    /// * `name` &mdash; The page slug, without its category.
+    /// * `category` &mdash; The category, or `_default` if none.
+    /// * `fullname` &mdash; The page slug, with its category if it has one.
+    /// * `rating`
+    /// * `tags` &mdash; All tags, separated by spaces.
+    pub fn variable(&self, name: &str) -> Option<Cow<'_, str>> {
+        let value = match name {
+            "title" => Cow::Borrowed(self.title.as_ref()),
+            "name" => Cow::Borrowed(self.page.as_ref()),
+            "category" => Cow::Borrowed(self.category.as_deref().unwrap_or("_default")),
+            "fullname" => match self.category {
+                Some(ref category) => Cow::Owned(format!("{}:{}", category, self.page)),
+                None => Cow::Borrowed(self.page.as_ref()),
+            },
+            "rating" => match self.score {
+                ScoreValue::Integer(value) => Cow::Owned(value.to_string()),
+                ScoreValue::Float(value) => Cow::Owned(value.to_string()),
+            },
+            "tags" => Cow::Owned(self.tags.join(" ")),
+            _ => return None,
+        };
+
+        Some(value)
+    }
+
     /// Generate a dummy PageInfo instance for tests.
     #[cfg(test)]
     pub fn dummy() -> Self {
@@ -78,3 +107,28 @@ impl PageInfo<'_> {
         }
     }
 }
+
+#[test]
+fn variables() {
+    let mut info = PageInfo::dummy();
+
+    assert_eq!(
+        info.variable("title").as_deref(),
+        Some("A page for the age")
+    );
+    assert_eq!(info.variable("name").as_deref(), Some("some-page"));
+    assert_eq!(info.variable("category").as_deref(), Some("_default"));
+    assert_eq!(info.variable("fullname").as_deref(), Some("some-page"));
+    assert_eq!(info.variable("rating").as_deref(), Some("69"));
+    assert_eq!(info.variable("tags").as_deref(), Some("tale _cc"));
+    assert_eq!(info.variable("created_by"), None);
+
+    info.category = Some(cow!("fragment"));
+    info.score = ScoreValue::Integer(-3);
+    assert_eq!(info.variable("category").as_deref(), Some("fragment"));
+    assert_eq!(
+        info.variable("fullname").as_deref(),
+        Some("fragment:some-page")
+    );
+    assert_eq!(info.variable("rating").as_deref(), Some("-3"));
+}
@@ -0,0 +1,424 @@
+/*
+ * data/wire.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Versioned serialization of syntax trees for external consumers.
+//!
+//! Syntax trees are serialized as JSON (see `docs/Serialization.md`), which
+//! is consumed by code not written in Rust. Since the tree changes as features
+//! are added, each serialized tree records the schema version it was written
+//! with in its `schema-version` field. Trees written with an older version can
+//! be read with [`from_json()`] or [`from_value()`], which upgrade them to the
+//! current schema before deserializing.
+//!
+//! # Schema history
+//!
+//! * Version 1 &mdash; Trees written before the schema was versioned,
+//!   which have no `schema-version` field.
+//! * Version 2 &mdash; Adds the `schema-version` field. Table cells have a
+//!   `row-span` (previously always `1`), and images have a `lazy` flag
+//!   (previously always `false`).
+//! * Version 3 &mdash; Adds the optional `heading-anchors` field, with slugified
+//!   heading IDs. Trees without it use numbered IDs, as before.
+//! * Version 4 &mdash; Bibliographies are objects with a citation `style` and
+//!   their `references`, rather than just the list of references.
+//! * Version 5 &mdash; Variables are objects with a `name` and an optional
+//!   `default` value, rather than just the name.
+//! * Version 6 &mdash; Code blocks have their `language`, a stable content
+//!   `hash`, and an optional `normalized` form of their contents.
+//! * Version 7 &mdash; Table cells have an optional `valign` and `width`.
+//! * Version 8 &mdash; Tables of contents have an optional `min-level` and `max-level`.
+//! * Version 9 &mdash; Adds `mention` elements, and the optional `mentions` field
+//!   listing the users mentioned.
+//! * Version 10 &mdash; Collapsibles have an optional `remember` flag.
+//! * Version 11 &mdash; HTML blocks have optional `attributes` and a `resize` flag.
+//! * Version 12 &mdash; Adds the `blockquote-attribution` container type.
+//! * Version 13 &mdash; Adds `page-variable` elements.
+//! * Version 14 &mdash; Math blocks have an optional `env`, the LaTeX environment
+//!   they are wrapped in.
+//! * Version 15 &mdash; Headings have a `collapsible` flag (previously always `false`).
+//! * Version 16 &mdash; Images have an optional `link-extra`, as with the `extra`
+//!   field of links.
+//! * Version 17 &mdash; Adds `pagination` elements.
+//! * Version 18 &mdash; HTML blocks have an optional `width` and `height`, which are
+//!   each a length or `auto`, rather than `attributes`.
+
+use crate::tree::{content_hash, Dimension, SyntaxTree};
+use serde_json::{json, Map, Value};
+use std::error::Error;
+use std::fmt::{self, Display};
+
+/// The current schema version, which all newly produced trees have.
+pub const SCHEMA_VERSION: u32 = 18;
+
+/// The schema version of trees which do not specify one.
+pub const LEGACY_SCHEMA_VERSION: u32 = 1;
+
+/// Serializes a syntax tree as a JSON value in the current schema.
+#[inline]
+pub fn to_value(tree: &SyntaxTree) -> Value {
+    serde_json::to_value(tree).expect("Unable to serialize syntax tree")
+}
+
+/// Deserializes a syntax tree from a JSON string in any supported schema version.
+pub fn from_json(json: &str) -> Result<SyntaxTree<'static>, WireError> {
+    let value = serde_json::from_str(json)
+        .map_err(|error| WireError::Deserialize(error.to_string()))?;
+
+    from_value(value)
+}
+
+/// Deserializes a syntax tree from a JSON value in any supported schema version.
+pub fn from_value(mut value: Value) -> Result<SyntaxTree<'static>, WireError> {
+    upgrade(&mut value)?;
+    serde_json::from_value(value)
+        .map_err(|error| WireError::Deserialize(error.to_string()))
+}
+
+/// Upgrades a serialized syntax tree in-place to the current schema version.
+///
+/// Returns the schema version the tree originally had.
+pub fn upgrade(value: &mut Value) -> Result<u32, WireError> {
+    let tree = match value {
+        Value::Object(tree) => tree,
+        _ => return Err(WireError::Deserialize(str!("syntax tree is not an object"))),
+    };
+
+    let version = match tree.get("schema-version") {
+        None => LEGACY_SCHEMA_VERSION,
+        Some(version) => match version.as_u64().and_then(|v| u32::try_from(v).ok()) {
+            Some(version) => version,
+            None => {
+                return Err(WireError::Deserialize(str!(
+                    "schema version is not an integer"
+                )))
+            }
+        },
+    };
+
+    if !(LEGACY_SCHEMA_VERSION..=SCHEMA_VERSION).contains(&version) {
+        return Err(WireError::UnsupportedVersion(version));
+    }
+
+    if version < 2 {
+        debug!("Upgrading syntax tree from schema version 1");
+        upgrade_value(value, &upgrade_v1_object);
+    }
+
+    // Version 3 only adds an optional field, so version 2 trees need no changes.
+
+    if version < 4 {
+        debug!("Upgrading syntax tree from schema version 3");
+        upgrade_v3_tree(value);
+    }
+
+    if version < 5 {
+        debug!("Upgrading syntax tree from schema version 4");
+        upgrade_value(value, &upgrade_v4_object);
+    }
+
+    if version < 6 {
+        debug!("Upgrading syntax tree from schema version 5");
+        upgrade_v5_tree(value);
+    }
+
+    // Versions 7 through 17 only add optional fields and new elements,
+    // so version 6 trees need no changes.
+
+    if version < 18 {
+        debug!("Upgrading syntax tree from schema version 17");
+        upgrade_value(value, &upgrade_v17_object);
+    }
+
+    if let Value::Object(tree) = value {
+        tree.insert(str!("schema-version"), Value::from(SCHEMA_VERSION));
+    }
+
+    Ok(version)
+}
+
+/// Applies the given upgrade to every object within the value.
+fn upgrade_value(value: &mut Value, f: &dyn Fn(&mut Map<String, Value>)) {
+    match value {
+        Value::Object(object) => {
+            f(object);
+
+            for child in object.values_mut() {
+                upgrade_value(child, f);
+            }
+        }
+        Value::Array(array) => {
+            for child in array {
+                upgrade_value(child, f);
+            }
+        }
+        _ => (),
+    }
+}
+
+fn upgrade_v1_object(object: &mut Map<String, Value>) {
+    // Table cells, which are the only objects with "column-span"
+    if object.contains_key("column-span") && !object.contains_key("row-span") {
+        object.insert(str!("row-span"), Value::from(1));
+    }
+
+    // Images
+    if object.get("element").and_then(Value::as_str) == Some("image") {
+        if let Some(Value::Object(data)) = object.get_mut("data") {
+            data.entry("lazy").or_insert(Value::Bool(false));
+        }
+    }
+}
+
+fn upgrade_v3_tree(value: &mut Value) {
+    // Bibliographies were only the list of references, all numeric
+    if let Some(Value::Array(bibliographies)) = value.get_mut("bibliographies") {
+        for bibliography in bibliographies {
+            if bibliography.is_array() {
+                let references = bibliography.take();
+                *bibliography = json!({
+                    "style": "numeric",
+                    "references": references,
+                });
+            }
+        }
+    }
+}
+
+fn upgrade_v4_object(object: &mut Map<String, Value>) {
+    // Variables were only their name
+    if object.get("element").and_then(Value::as_str) == Some("variable") {
+        if let Some(data) = object.get_mut("data") {
+            if data.is_string() {
+                let name = data.take();
+                *data = json!({ "name": name, "default": null });
+            }
+        }
+    }
+}
+
+fn upgrade_v5_tree(value: &mut Value) {
+    // Code blocks had no language or hash
+    if let Some(Value::Array(code_blocks)) = value.get_mut("code-blocks") {
+        for block in code_blocks {
+            if let Value::Object(block) = block {
+                let hash = match block.get("contents").and_then(Value::as_str) {
+                    Some(contents) => content_hash(contents),
+                    None => continue,
+                };
+
+                block.entry("language").or_insert(Value::Null);
+                block.entry("hash").or_insert(Value::String(hash));
+            }
+        }
+    }
+}
+
+fn upgrade_v17_object(object: &mut Map<String, Value>) {
+    // HTML blocks had their size as attributes, which were not validated
+    if object.get("element").and_then(Value::as_str) == Some("html") {
+        if let Some(Value::Object(data)) = object.get_mut("data") {
+            let attributes = match data.remove("attributes") {
+                Some(Value::Object(attributes)) => attributes,
+                _ => return,
+            };
+
+            for key in ["width", "height"] {
+                let dimension = attributes
+                    .get(key)
+                    .and_then(Value::as_str)
+                    .and_then(Dimension::parse);
+
+                if let Some(dimension) = dimension {
+                    data.insert(str!(key), json!(dimension));
+                }
+            }
+        }
+    }
+}
+
+/// Error returned when a serialized syntax tree cannot be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WireError {
+    /// The tree was written with a schema version this library doesn't support.
+    ///
+    /// This is typically a newer version, from a later release of ftml.
+    UnsupportedVersion(u32),
+
+    /// The data is not a valid syntax tree.
+    Deserialize(String),
+}
+
+impl Display for WireError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WireError::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported schema version {version} (expected {LEGACY_SCHEMA_VERSION} to {SCHEMA_VERSION})",
+            ),
+            WireError::Deserialize(message) => {
+                write!(f, "unable to deserialize syntax tree: {message}")
+            }
+        }
+    }
+}
+
+impl Error for WireError {}
+
+#[test]
+fn wire() {
+    // Current version round-trips
+    let tree = SyntaxTree::default();
+    let value = to_value(&tree);
+    assert_eq!(value["schema-version"], json!(SCHEMA_VERSION));
+    assert_eq!(from_value(value).unwrap(), tree);
+
+    // Legacy trees are upgraded
+    let legacy = json!({
+        "elements": [
+            {
+                "element": "table",
+                "data": {
+                    "attributes": {},
+                    "rows": [
+                        {
+                            "attributes": {},
+                            "cells": [
+                                {
+                                    "header": false,
+                                    "column-span": 2,
+                                    "align": null,
+                                    "attributes": {},
+                                    "elements": [],
+                                },
+                            ],
+                        },
+                    ],
+                },
+            },
+            {
+                "element": "image",
+                "data": {
+                    "source": { "type": "url", "data": "https://example.com/a.png" },
+                    "link": null,
+                    "alignment": null,
+                    "attributes": {},
+                },
+            },
+            { "element": "variable", "data": "name" },
+            {
+                "element": "html",
+                "data": {
+                    "contents": "<p>Hello</p>",
+                    "attributes": { "width": "100%", "height": "-1", "onload": "x" },
+                    "resize": false,
+                },
+            },
+        ],
+        "table-of-contents": [],
+        "html-blocks": [],
+        "code-blocks": [{ "contents": "x = 1", "name": null }],
+        "footnotes": [],
+        "bibliographies": [
+            [["apple", [{ "element": "text", "data": "Apple" }]]],
+        ],
+    });
+
+    let mut upgraded = legacy.clone();
+    assert_eq!(upgrade(&mut upgraded), Ok(LEGACY_SCHEMA_VERSION));
+    assert_eq!(upgraded["schema-version"], json!(SCHEMA_VERSION));
+    assert_eq!(
+        upgraded["elements"][0]["data"]["rows"][0]["cells"][0]["row-span"],
+        json!(1),
+    );
+    assert_eq!(upgraded["elements"][1]["data"]["lazy"], json!(false));
+    assert_eq!(
+        upgraded["elements"][2]["data"],
+        json!({ "name": "name", "default": null }),
+    );
+    assert_eq!(
+        upgraded["elements"][3]["data"],
+        json!({
+            "contents": "<p>Hello</p>",
+            "width": { "length": { "value": 100.0, "unit": "percent" } },
+            "resize": false,
+        }),
+    );
+    assert_eq!(upgraded["bibliographies"][0]["style"], json!("numeric"));
+    assert_eq!(upgraded["code-blocks"][0]["language"], json!(null));
+    assert_eq!(
+        upgraded["code-blocks"][0]["hash"],
+        json!(content_hash("x = 1")),
+    );
+    assert_eq!(
+        upgraded["bibliographies"][0]["references"][0][0],
+        json!("apple"),
+    );
+
+    let tree = from_json(&legacy.to_string()).expect("Unable to read legacy tree");
+    assert_eq!(tree.schema_version, SCHEMA_VERSION);
+    assert_eq!(tree.elements.len(), 4);
+
+    // Unset sizes and resizing are left out when written again
+    assert_eq!(
+        to_value(&tree)["elements"][3]["data"],
+        json!({
+            "contents": "<p>Hello</p>",
+            "width": { "length": { "value": 100.0, "unit": "percent" } },
+        }),
+    );
+    assert!(tree.bibliographies.get_reference("apple").is_some());
+
+    // Trees from before optional fields were added are still read
+    let tree = from_value(json!({
+        "schema-version": 13,
+        "elements": [
+            {
+                "element": "math",
+                "data": { "name": null, "latex-source": "x^2" },
+            },
+            {
+                "element": "image",
+                "data": {
+                    "source": { "type": "url", "data": "https://example.com/a.png" },
+                    "link": null,
+                    "alignment": null,
+                    "lazy": false,
+                    "attributes": {},
+                },
+            },
+        ],
+        "table-of-contents": [],
+        "html-blocks": [],
+        "code-blocks": [],
+        "footnotes": [],
+        "bibliographies": [],
+    }))
+    .expect("Unable to read version 13 tree");
+    assert_eq!(tree.schema_version, SCHEMA_VERSION);
+    assert_eq!(tree.elements.len(), 2);
+
+    // Unsupported versions
+    assert_eq!(
+        from_value(json!({ "schema-version": 999, "elements": [] })),
+        Err(WireError::UnsupportedVersion(999)),
+    );
+    assert!(from_value(json!({ "schema-version": "two" })).is_err());
+    assert!(from_value(json!([])).is_err());
+}
@@ -0,0 +1,277 @@
+/*
+ * ffi.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! C API for calling ftml from other languages.
+//!
+//! All structured values are passed as JSON strings, using the same
+//! serialization as the rest of the crate. This keeps the interface small
+//! and stable, since changes to the syntax tree do not affect the ABI.
+//!
+//! The declarations, for use in a C header or PHP's `FFI::cdef()`, are:
+//!
+//! ```c
+//! typedef struct {
+//!     char *json;
+//!     char *error;
+//! } FtmlResult;
+//!
+//! FtmlResult ftml_parse(const char *text, const char *page_info, const char *settings);
+//! FtmlResult ftml_render_html(const char *syntax_tree, const char *page_info, const char *settings);
+//! char *ftml_version(void);
+//! void ftml_free_result(FtmlResult result);
+//! void ftml_free_string(char *string);
+//! ```
+//!
+//! Every string returned by this module is owned by the caller,
+//! and must be released using the matching `ftml_free_*` function.
+
+#![allow(unsafe_code)]
+
+use crate::data::PageInfo;
+use crate::info;
+use crate::parsing::ParseError;
+use crate::render::html::HtmlRender;
+use crate::render::Render;
+use crate::settings::WikitextSettings;
+use crate::tree::SyntaxTree;
+use serde::{Deserialize, Serialize};
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+/// The result of a call, where exactly one of the fields is non-null.
+///
+/// On success, `json` is the output serialized as JSON.
+/// On failure, `error` is a message describing the problem.
+#[repr(C)]
+#[derive(Debug)]
+pub struct FtmlResult {
+    pub json: *mut c_char,
+    pub error: *mut c_char,
+}
+
+impl FtmlResult {
+    fn from_fn<F>(f: F) -> Self
+    where
+        F: FnOnce() -> Result<String, String>,
+    {
+        // Unwinding across the FFI boundary is undefined behavior
+        let result = panic::catch_unwind(AssertUnwindSafe(f))
+            .unwrap_or_else(|_| Err(str!("panic while running ftml")));
+
+        match result {
+            Ok(json) => FtmlResult {
+                json: into_c_string(json),
+                error: ptr::null_mut(),
+            },
+            Err(error) => FtmlResult {
+                json: ptr::null_mut(),
+                error: into_c_string(error),
+            },
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+struct ParseOutput<'a, 't> {
+    syntax_tree: &'a SyntaxTree<'t>,
+    errors: &'a [ParseError],
+}
+
+/// Preprocesses, tokenizes, and parses the given wikitext.
+///
+/// The output is an object with `syntax-tree` and `errors` fields.
+///
+/// # Safety
+/// Each argument must be a valid pointer to a nul-terminated string,
+/// which is not modified for the duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn ftml_parse(
+    text: *const c_char,
+    page_info: *const c_char,
+    settings: *const c_char,
+) -> FtmlResult {
+    FtmlResult::from_fn(|| {
+        let mut text = str!(read_str(text, "text")?);
+        let page_info: PageInfo = read_json(page_info, "page info")?;
+        let settings: WikitextSettings = read_json(settings, "settings")?;
+
+        crate::preprocess(&mut text, &page_info, &settings);
+        let tokens = crate::tokenize(&text);
+        let (tree, errors) =
+            crate::parse_infallible(&tokens, &page_info, &settings).into();
+
+        write_json(&ParseOutput {
+            syntax_tree: &tree,
+            errors: &errors,
+        })
+    })
+}
+
+/// Renders the given syntax tree, as produced by [`ftml_parse()`], to HTML.
+///
+/// The output is the serialized [`HtmlOutput`].
+///
+/// # Safety
+/// Each argument must be a valid pointer to a nul-terminated string,
+/// which is not modified for the duration of the call.
+///
+/// [`HtmlOutput`]: crate::render::html::HtmlOutput
+#[no_mangle]
+pub unsafe extern "C" fn ftml_render_html(
+    syntax_tree: *const c_char,
+    page_info: *const c_char,
+    settings: *const c_char,
+) -> FtmlResult {
+    FtmlResult::from_fn(|| {
+        let tree: SyntaxTree = read_json(syntax_tree, "syntax tree")?;
+        let page_info: PageInfo = read_json(page_info, "page info")?;
+        let settings: WikitextSettings = read_json(settings, "settings")?;
+
+        let output = HtmlRender.render(&tree, &page_info, &settings);
+        write_json(&output)
+    })
+}
+
+/// Returns the version string of this library.
+#[no_mangle]
+pub extern "C" fn ftml_version() -> *mut c_char {
+    into_c_string(info::VERSION.clone())
+}
+
+/// Releases the strings within a result returned by this module.
+///
+/// # Safety
+/// The result must have been returned by this module, and not already freed.
+#[no_mangle]
+pub unsafe extern "C" fn ftml_free_result(result: FtmlResult) {
+    ftml_free_string(result.json);
+    ftml_free_string(result.error);
+}
+
+/// Releases a string returned by this module. Null pointers are ignored.
+///
+/// # Safety
+/// The string must have been returned by this module, and not already freed.
+#[no_mangle]
+pub unsafe extern "C" fn ftml_free_string(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+// Helpers
+
+unsafe fn read_str<'a>(value: *const c_char, name: &str) -> Result<&'a str, String> {
+    if value.is_null() {
+        return Err(format!("{name} is null"));
+    }
+
+    CStr::from_ptr(value)
+        .to_str()
+        .map_err(|error| format!("{name} is not valid UTF-8: {error}"))
+}
+
+unsafe fn read_json<'a, T>(value: *const c_char, name: &str) -> Result<T, String>
+where
+    T: Deserialize<'a>,
+{
+    let value = read_str(value, name)?;
+    serde_json::from_str(value).map_err(|error| format!("{name} is not valid: {error}"))
+}
+
+fn write_json<T: Serialize>(value: &T) -> Result<String, String> {
+    serde_json::to_string(value)
+        .map_err(|error| format!("unable to serialize output: {error}"))
+}
+
+fn into_c_string(value: String) -> *mut c_char {
+    // JSON and error messages cannot contain a nul byte
+    // unless it was in the input, so strip it if so.
+    let value = CString::new(value).unwrap_or_else(|error| {
+        let mut bytes = error.into_vec();
+        bytes.retain(|&b| b != 0);
+        CString::new(bytes).expect("Nul bytes not removed")
+    });
+
+    value.into_raw()
+}
+
+#[test]
+fn ffi() {
+    use crate::layout::Layout;
+    use crate::settings::WikitextMode;
+
+    let page_info = serde_json::to_string(&PageInfo::dummy()).unwrap();
+    let page_info = CString::new(page_info).unwrap();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let settings = CString::new(serde_json::to_string(&settings).unwrap()).unwrap();
+    let text = CString::new("**Apple** //banana//").unwrap();
+
+    unsafe {
+        // Parse
+        let result = ftml_parse(text.as_ptr(), page_info.as_ptr(), settings.as_ptr());
+        assert!(result.error.is_null(), "Parse returned an error");
+
+        let output = CStr::from_ptr(result.json).to_str().unwrap();
+        let output: serde_json::Value = serde_json::from_str(output).unwrap();
+        let tree = CString::new(output["syntax-tree"].to_string()).unwrap();
+        assert_eq!(output["errors"], serde_json::json!([]));
+        ftml_free_result(result);
+
+        // Render
+        let result =
+            ftml_render_html(tree.as_ptr(), page_info.as_ptr(), settings.as_ptr());
+        assert!(result.error.is_null(), "Render returned an error");
+
+        let output = CStr::from_ptr(result.json).to_str().unwrap();
+        let output: serde_json::Value = serde_json::from_str(output).unwrap();
+        assert_eq!(
+            output["body"],
+            "<wj-body class=\"wj-body\"><p><strong>Apple</strong> <em>banana</em></p></wj-body>",
+        );
+        ftml_free_result(result);
+
+        // Errors
+        let invalid = CString::new("{").unwrap();
+        let result = ftml_parse(text.as_ptr(), invalid.as_ptr(), settings.as_ptr());
+        assert!(
+            result.json.is_null(),
+            "Invalid input did not return an error"
+        );
+
+        let error = CStr::from_ptr(result.error).to_str().unwrap();
+        assert!(error.starts_with("page info is not valid"));
+        ftml_free_result(result);
+
+        let result = ftml_parse(ptr::null(), page_info.as_ptr(), settings.as_ptr());
+        assert_eq!(
+            CStr::from_ptr(result.error).to_str().unwrap(),
+            "text is null"
+        );
+        ftml_free_result(result);
+
+        // Version
+        let version = ftml_version();
+        assert_eq!(CStr::from_ptr(version).to_str().unwrap(), *info::VERSION);
+        ftml_free_string(version);
+    }
+}
@@ -0,0 +1,825 @@
+/*
+ * fuzz.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Helpers for fuzzing ftml.
+//!
+//! Fuzzers, such as `cargo fuzz`, provide raw bytes as input. These can be
+//! given to the parser as wikitext, via [`parse_infallible()`](crate::parse_infallible)
+//! if a panic should be reported as an error rather than a crash. Or, to
+//! exercise the renderers directly, [`arbitrary_tree()`] builds a syntax
+//! tree from the bytes, including elements which the parser would not produce.
+//!
+//! Trees are generated using the [`arbitrary`] crate, so [`SyntaxTree`] also
+//! implements [`Arbitrary`] for fuzz targets which take structured input.
+//! Any input produces a tree, and the same input always produces the same tree.
+//!
+//! Besides the elements, the footnotes, bibliographies, table of contents, and
+//! other lists in the tree are generated independently of them. So the tree may
+//! refer to footnotes, references, or headings which do not exist, as can
+//! happen with trees which are deserialized rather than parsed.
+
+use crate::data::wire::SCHEMA_VERSION;
+use crate::data::PageRef;
+use crate::parsing::{ElementCondition, ElementConditionType};
+use crate::tree::attribute::SAFE_ATTRIBUTES;
+use crate::tree::{
+    Alignment, AnchorTarget, AttributeMap, Bibliography, BibliographyList, CitationStyle,
+    ClearFloat, CodeBlock, Container, ContainerType, DateItem, DefinitionListItem,
+    Dimension, Element, Embed, FloatAlignment, Heading, HeadingLevel, ImageSource,
+    Length, LengthUnit, LinkLabel, LinkLocation, LinkType, ListItem, ListType,
+    MathEnvironment, Module, PartialElement, RubyText, SyntaxTree, Tab, Table, TableCell,
+    TableRow, VariableMap, VerticalAlignment,
+};
+use arbitrary::{Arbitrary, Result, Unstructured};
+use once_cell::sync::Lazy;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::num::NonZeroU32;
+use time::{Date, Time, UtcOffset};
+
+/// The safe attributes, in a stable order so that generation is deterministic.
+static SAFE_ATTRIBUTES_SORTED: Lazy<Vec<&'static str>> = Lazy::new(|| {
+    let mut attributes: Vec<_> = SAFE_ATTRIBUTES.iter().map(|s| s.into_inner()).collect();
+    attributes.sort_unstable();
+    attributes
+});
+
+/// How deeply elements may be nested in a generated tree.
+const MAX_DEPTH: usize = 5;
+
+/// The greatest number of elements in a single list of elements.
+const MAX_ELEMENTS: usize = 16;
+
+/// The greatest number of entries in the other lists, such as footnotes.
+const MAX_ENTRIES: usize = 4;
+
+/// The greatest length of a generated string, in bytes.
+const MAX_STRING_LEN: usize = 64;
+
+/// The number of variants of [`Element`].
+const ELEMENT_VARIANTS: usize = 43;
+
+/// The number of those variants which do not contain other elements.
+///
+/// These are listed first in [`TreeBuilder::element()`], so that only
+/// they are chosen once the maximum depth is reached.
+const LEAF_ELEMENT_VARIANTS: usize = 32;
+
+/// Builds a syntax tree from arbitrary bytes.
+///
+/// This is intended to be called from a fuzz target, for instance:
+///
+/// ```
+/// # use ftml::data::PageInfo;
+/// # use ftml::render::{html::HtmlRender, Render};
+/// # use ftml::settings::WikitextSettings;
+/// fn fuzz_target(data: &[u8], page_info: &PageInfo, settings: &WikitextSettings) {
+///     let tree = ftml::fuzz::arbitrary_tree(data);
+///     let _ = HtmlRender.render(&tree, page_info, settings);
+/// }
+/// ```
+pub fn arbitrary_tree(data: &[u8]) -> SyntaxTree<'static> {
+    let mut u = Unstructured::new(data);
+
+    // Generation only reads bounded amounts of input and never chooses
+    // from an empty list, so it cannot fail once the input is exhausted.
+    SyntaxTree::arbitrary(&mut u).expect("Unable to generate syntax tree")
+}
+
+impl<'a> Arbitrary<'a> for SyntaxTree<'static> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        // The bibliographies are chosen first, so that
+        // citations can refer to their references.
+        let mut builder = TreeBuilder::new(u)?;
+        let elements = builder.elements(0)?;
+        let table_of_contents = builder.elements(MAX_DEPTH - 1)?;
+        let heading_anchors = builder.list(|b| b.string().map(Cow::into_owned))?;
+        let html_blocks = builder.list(TreeBuilder::string)?;
+        let code_blocks = builder.list(|b| {
+            Ok(CodeBlock::new(
+                b.string()?,
+                b.optional_string()?,
+                b.optional_string()?,
+            ))
+        })?;
+        let footnotes = builder.list(|b| b.elements(1))?;
+        let bibliographies = builder.bibliographies()?;
+        let mentions = builder.list(TreeBuilder::string)?;
+        let element_spans = builder.list(|b| {
+            let start = b.int(1024)?;
+            let end = start + b.int(1024)?;
+            Ok(start..end)
+        })?;
+        let wikitext_len = builder.int(1024)?;
+
+        Ok(SyntaxTree {
+            elements,
+            table_of_contents,
+            heading_anchors,
+            html_blocks,
+            code_blocks,
+            footnotes,
+            bibliographies,
+            mentions,
+            element_spans,
+            wikitext_len,
+            schema_version: SCHEMA_VERSION,
+        })
+    }
+}
+
+/// Generates the parts of a syntax tree from the fuzzer's input.
+#[derive(Debug)]
+struct TreeBuilder<'u, 'a> {
+    u: &'u mut Unstructured<'a>,
+
+    /// The labels of the references in each bibliography.
+    references: Vec<Vec<Cow<'static, str>>>,
+}
+
+impl<'u, 'a> TreeBuilder<'u, 'a> {
+    fn new(u: &'u mut Unstructured<'a>) -> Result<Self> {
+        let mut builder = TreeBuilder {
+            u,
+            references: Vec::new(),
+        };
+
+        builder.references = builder.list(|b| b.list(TreeBuilder::string))?;
+        Ok(builder)
+    }
+
+    // Primitives
+
+    #[inline]
+    fn bool(&mut self) -> Result<bool> {
+        bool::arbitrary(self.u)
+    }
+
+    /// Gets an integer in the range `0..=max`.
+    #[inline]
+    fn int(&mut self, max: usize) -> Result<usize> {
+        self.u.int_in_range(0..=max)
+    }
+
+    fn choose<T: Copy>(&mut self, items: &[T]) -> Result<T> {
+        self.u.choose(items).copied()
+    }
+
+    fn non_zero(&mut self, max: u32) -> Result<NonZeroU32> {
+        let value = self.u.int_in_range(1..=max)?;
+        Ok(NonZeroU32::new(value).expect("Generated integer is zero"))
+    }
+
+    fn string(&mut self) -> Result<Cow<'static, str>> {
+        let len = self.int(MAX_STRING_LEN)?.min(self.u.len());
+        let bytes = self.u.bytes(len)?;
+        Ok(Cow::Owned(String::from_utf8_lossy(bytes).into_owned()))
+    }
+
+    fn optional<T, F>(&mut self, mut f: F) -> Result<Option<T>>
+    where
+        F: FnMut(&mut Self) -> Result<T>,
+    {
+        if self.bool()? {
+            f(self).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    #[inline]
+    fn optional_string(&mut self) -> Result<Option<Cow<'static, str>>> {
+        self.optional(TreeBuilder::string)
+    }
+
+    /// Gets a list of up to [`MAX_ENTRIES`] values.
+    fn list<T, F>(&mut self, mut f: F) -> Result<Vec<T>>
+    where
+        F: FnMut(&mut Self) -> Result<T>,
+    {
+        (0..self.int(MAX_ENTRIES)?).map(|_| f(self)).collect()
+    }
+
+    fn string_map(&mut self) -> Result<HashMap<Cow<'static, str>, Cow<'static, str>>> {
+        self.list(|b| Ok((b.string()?, b.string()?)))
+            .map(|entries| entries.into_iter().collect())
+    }
+
+    // Tree data
+
+    /// Gets a name for an arbitrary attribute.
+    ///
+    /// These are restricted to the characters the parser permits.
+    fn attribute_name(&mut self) -> Result<Cow<'static, str>> {
+        const CHARACTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789-";
+
+        let name = (0..=self.int(7)?)
+            .map(|_| self.choose(CHARACTERS).map(char::from))
+            .collect::<Result<String>>()?;
+
+        Ok(Cow::Owned(name))
+    }
+
+    fn attributes(&mut self) -> Result<AttributeMap<'static>> {
+        let mut map = BTreeMap::new();
+
+        for _ in 0..self.int(4)? {
+            let key = if self.bool()? {
+                Cow::Borrowed(self.choose(&SAFE_ATTRIBUTES_SORTED)?)
+            } else {
+                self.attribute_name()?
+            };
+
+            map.insert(key, self.string()?);
+        }
+
+        Ok(AttributeMap::from(map))
+    }
+
+    fn alignment(&mut self) -> Result<Alignment> {
+        self.choose(&[
+            Alignment::Left,
+            Alignment::Right,
+            Alignment::Center,
+            Alignment::Justify,
+        ])
+    }
+
+    fn heading_level(&mut self) -> Result<HeadingLevel> {
+        self.choose(&[
+            HeadingLevel::One,
+            HeadingLevel::Two,
+            HeadingLevel::Three,
+            HeadingLevel::Four,
+            HeadingLevel::Five,
+            HeadingLevel::Six,
+        ])
+    }
+
+    fn anchor_target(&mut self) -> Result<Option<AnchorTarget>> {
+        self.optional(|b| {
+            b.choose(&[
+                AnchorTarget::NewTab,
+                AnchorTarget::Parent,
+                AnchorTarget::Top,
+                AnchorTarget::Same,
+            ])
+        })
+    }
+
+    fn page_ref(&mut self) -> Result<PageRef<'static>> {
+        Ok(PageRef {
+            site: self.optional_string()?,
+            page: self.string()?,
+        })
+    }
+
+    fn link_location(&mut self) -> Result<LinkLocation<'static>> {
+        if self.bool()? {
+            Ok(LinkLocation::Page(self.page_ref()?))
+        } else {
+            Ok(LinkLocation::Url(self.string()?))
+        }
+    }
+
+    fn image_source(&mut self) -> Result<ImageSource<'static>> {
+        Ok(match self.int(3)? {
+            0 => ImageSource::Url(self.string()?),
+            1 => ImageSource::File1 {
+                file: self.string()?,
+            },
+            2 => ImageSource::File2 {
+                page: self.string()?,
+                file: self.string()?,
+            },
+            _ => ImageSource::File3 {
+                site: self.string()?,
+                page: self.string()?,
+                file: self.string()?,
+            },
+        })
+    }
+
+    fn length(&mut self) -> Result<Length> {
+        // Lengths are never negative or NaN
+        let value = self.int(10_000)? as f64 / 10.0;
+        let unit = self.choose(&[
+            LengthUnit::Pixels,
+            LengthUnit::Points,
+            LengthUnit::Em,
+            LengthUnit::Rem,
+            LengthUnit::Ex,
+            LengthUnit::Ch,
+            LengthUnit::Percent,
+            LengthUnit::ViewportWidth,
+            LengthUnit::ViewportHeight,
+            LengthUnit::Centimeters,
+            LengthUnit::Millimeters,
+            LengthUnit::Inches,
+        ])?;
+
+        Ok(Length { value, unit })
+    }
+
+    fn dimension(&mut self) -> Result<Dimension> {
+        if self.bool()? {
+            Ok(Dimension::Auto)
+        } else {
+            self.length().map(Dimension::Length)
+        }
+    }
+
+    fn date(&mut self) -> Result<DateItem> {
+        let julian_day = self
+            .u
+            .int_in_range(Date::MIN.to_julian_day()..=Date::MAX.to_julian_day())?;
+        let date = Date::from_julian_day(julian_day).unwrap_or(Date::MIN);
+        let time = Time::from_hms(
+            self.u.int_in_range(0..=23)?,
+            self.u.int_in_range(0..=59)?,
+            self.u.int_in_range(0..=59)?,
+        )
+        .unwrap_or(Time::MIDNIGHT);
+
+        Ok(match self.int(2)? {
+            0 => DateItem::Date(date),
+            1 => DateItem::DateTime(date.with_time(time)),
+            _ => {
+                let offset = self.u.int_in_range(-86_399..=86_399)?;
+                let offset =
+                    UtcOffset::from_whole_seconds(offset).unwrap_or(UtcOffset::UTC);
+                DateItem::DateTimeTz(date.with_time(time).assume_offset(offset))
+            }
+        })
+    }
+
+    fn module(&mut self) -> Result<Module<'static>> {
+        Ok(match self.int(5)? {
+            0 => Module::Backlinks {
+                page: self.optional_string()?,
+            },
+            1 => Module::Categories {
+                include_hidden: self.bool()?,
+            },
+            2 => Module::Join {
+                button_text: self.optional_string()?,
+                attributes: self.attributes()?,
+            },
+            3 => Module::ListPages {
+                arguments: self.string_map()?,
+                body: self.string()?,
+            },
+            4 => Module::PageTree {
+                root: self.optional_string()?,
+                show_root: self.bool()?,
+                depth: self.optional(|b| b.non_zero(10))?,
+            },
+            _ => Module::Rate,
+        })
+    }
+
+    fn embed(&mut self) -> Result<Embed<'static>> {
+        // The IDs may be invalid, which renderers must check for
+        Ok(match self.int(3)? {
+            0 => Embed::Youtube {
+                video_id: self.string()?,
+            },
+            1 => Embed::Vimeo {
+                video_id: self.string()?,
+            },
+            2 => Embed::GithubGist {
+                username: self.string()?,
+                hash: self.string()?,
+            },
+            _ => Embed::GitlabSnippet {
+                snippet_id: self.string()?,
+            },
+        })
+    }
+
+    /// Gets a citation label, which usually refers to some reference.
+    fn citation_label(&mut self) -> Result<Cow<'static, str>> {
+        let labels = self.references.concat();
+        if labels.is_empty() || self.int(3)? == 0 {
+            return self.string();
+        }
+
+        self.u.choose(&labels).cloned()
+    }
+
+    fn bibliographies(&mut self) -> Result<BibliographyList<'static>> {
+        let mut list = BibliographyList::new();
+
+        for labels in self.references.clone() {
+            let style =
+                self.choose(&[CitationStyle::Numeric, CitationStyle::AuthorYear])?;
+            let mut bibliography = Bibliography::with_style(style);
+
+            for label in labels {
+                bibliography.add(label, self.elements(MAX_DEPTH - 1)?);
+            }
+
+            list.push(bibliography);
+        }
+
+        Ok(list)
+    }
+
+    // Elements
+
+    fn elements(&mut self, depth: usize) -> Result<Vec<Element<'static>>> {
+        (0..self.int(MAX_ELEMENTS)?)
+            .map(|_| self.element(depth))
+            .collect()
+    }
+
+    fn element(&mut self, depth: usize) -> Result<Element<'static>> {
+        // Only leaf elements are produced once the maximum depth is reached
+        let choices = if depth < MAX_DEPTH {
+            ELEMENT_VARIANTS
+        } else {
+            LEAF_ELEMENT_VARIANTS
+        };
+
+        let depth = depth + 1;
+        let element = match self.int(choices - 1)? {
+            // Leaf elements
+            0 => Element::Text(self.string()?),
+            1 => Element::Raw(self.string()?),
+            2 => Element::Email(self.string()?),
+            3 => Element::LineBreak,
+            4 => Element::LineBreaks(self.non_zero(100)?),
+            5 => Element::HorizontalRule,
+            6 => Element::ClearFloat(self.choose(&[
+                ClearFloat::Both,
+                ClearFloat::Left,
+                ClearFloat::Right,
+            ])?),
+            7 => Element::Code {
+                contents: self.string()?,
+                language: self.optional_string()?,
+            },
+            8 => Element::CheckBox {
+                checked: self.bool()?,
+                attributes: self.attributes()?,
+            },
+            9 => self.link()?,
+            10 => Element::Module(self.module()?),
+            11 => Element::Variable {
+                name: self.string()?,
+                default: self.optional_string()?,
+            },
+            12 => Element::PageVariable {
+                name: self.string()?,
+            },
+            13 => Element::AnchorName(self.string()?),
+            14 => Element::Image {
+                source: self.image_source()?,
+                link: self.optional(TreeBuilder::link_location)?,
+                link_extra: self.optional_string()?,
+                alignment: self.optional(|b| {
+                    Ok(FloatAlignment {
+                        align: b.alignment()?,
+                        float: b.bool()?,
+                    })
+                })?,
+                lazy: self.bool()?,
+                attributes: self.attributes()?,
+            },
+            15 => Element::RadioButton {
+                name: self.string()?,
+                checked: self.bool()?,
+                attributes: self.attributes()?,
+            },
+            16 => Element::TableOfContents {
+                attributes: self.attributes()?,
+                align: self.optional(TreeBuilder::alignment)?,
+                min_level: self.optional(TreeBuilder::heading_level)?,
+                max_level: self.optional(TreeBuilder::heading_level)?,
+            },
+            17 => Element::Pagination {
+                attributes: self.attributes()?,
+            },
+            18 => Element::Footnote,
+            19 => Element::FootnoteBlock {
+                title: self.optional_string()?,
+                hide: self.bool()?,
+            },
+            20 => Element::BibliographyCite {
+                label: self.citation_label()?,
+                brackets: self.bool()?,
+            },
+            21 => Element::BibliographyBlock {
+                // May be past the end of the bibliography list
+                index: self.int(self.references.len())?,
+                title: self.optional_string()?,
+                hide: self.bool()?,
+            },
+            22 => Element::User {
+                name: self.string()?,
+                show_avatar: self.bool()?,
+            },
+            23 => Element::Mention {
+                name: self.string()?,
+            },
+            24 => Element::Date {
+                value: self.date()?,
+                format: self.optional_string()?,
+                hover: self.bool()?,
+            },
+            25 => Element::Math {
+                name: self.optional_string()?,
+                latex_source: self.string()?,
+                env: self.optional(|b| {
+                    b.choose(&[
+                        MathEnvironment::Align,
+                        MathEnvironment::Gather,
+                        MathEnvironment::Cases,
+                        MathEnvironment::Matrix,
+                        MathEnvironment::PMatrix,
+                        MathEnvironment::BMatrix,
+                        MathEnvironment::VMatrix,
+                    ])
+                })?,
+            },
+            26 => Element::MathInline {
+                latex_source: self.string()?,
+            },
+            27 => Element::EquationReference(self.string()?),
+            28 => Element::Embed(self.embed()?),
+            29 => Element::Html {
+                contents: self.string()?,
+                width: self.optional(TreeBuilder::dimension)?,
+                height: self.optional(TreeBuilder::dimension)?,
+                resize: self.bool()?,
+            },
+            30 => Element::Iframe {
+                attributes: self.attributes()?,
+                url: self.string()?,
+            },
+            31 => Element::Style(self.string()?),
+
+            // Elements containing other elements
+            32 => self.container(depth)?,
+            33 => self.list_element(depth)?,
+            34 => Element::Collapsible {
+                elements: self.elements(depth)?,
+                attributes: self.attributes()?,
+                start_open: self.bool()?,
+                show_text: self.optional_string()?,
+                hide_text: self.optional_string()?,
+                show_top: self.bool()?,
+                show_bottom: self.bool()?,
+                remember: self.bool()?,
+            },
+            35 => Element::Color {
+                color: self.string()?,
+                elements: self.elements(depth)?,
+            },
+            36 => Element::Table(Table {
+                attributes: self.attributes()?,
+                rows: self.list(|b| b.table_row(depth))?,
+            }),
+            37 => Element::TabView(self.list(|b| b.tab(depth))?),
+            38 => Element::Anchor {
+                target: self.anchor_target()?,
+                attributes: self.attributes()?,
+                elements: self.elements(depth)?,
+            },
+            39 => Element::DefinitionList(self.list(|b| {
+                Ok(DefinitionListItem {
+                    key_string: b.string()?,
+                    key_elements: b.elements(depth)?,
+                    value_elements: b.elements(depth)?,
+                })
+            })?),
+            40 => Element::Include {
+                paragraph_safe: self.bool()?,
+                variables: self.string_map()? as VariableMap,
+                location: self.page_ref()?,
+                elements: self.elements(depth)?,
+            },
+            41 => Element::Conditional {
+                paragraph_safe: self.bool()?,
+                conditions: self.list(|b| {
+                    Ok(ElementCondition {
+                        ctype: b.choose(&[
+                            ElementConditionType::Required,
+                            ElementConditionType::Prohibited,
+                            ElementConditionType::Present,
+                        ])?,
+                        value: b.string()?,
+                    })
+                })?,
+                elements: self.elements(depth)?,
+            },
+            _ => Element::Partial(self.partial(depth)?),
+        };
+
+        Ok(element)
+    }
+
+    fn link(&mut self) -> Result<Element<'static>> {
+        let ltype = self.choose(&[
+            LinkType::Direct,
+            LinkType::Page,
+            LinkType::Interwiki,
+            LinkType::Anchor,
+            LinkType::TableOfContents,
+        ])?;
+
+        let label = match self.int(2)? {
+            0 => LinkLabel::Text(self.string()?),
+            1 => LinkLabel::Url(self.optional_string()?),
+            _ => LinkLabel::Page,
+        };
+
+        Ok(Element::Link {
+            ltype,
+            link: self.link_location()?,
+            extra: self.optional_string()?,
+            label,
+            target: self.anchor_target()?,
+        })
+    }
+
+    fn container(&mut self, depth: usize) -> Result<Element<'static>> {
+        let ctype = match self.int(21)? {
+            0 => ContainerType::Bold,
+            1 => ContainerType::Italics,
+            2 => ContainerType::Underline,
+            3 => ContainerType::Superscript,
+            4 => ContainerType::Subscript,
+            5 => ContainerType::Strikethrough,
+            6 => ContainerType::Monospace,
+            7 => ContainerType::Span,
+            8 => ContainerType::Div,
+            9 => ContainerType::Mark,
+            10 => ContainerType::Blockquote,
+            11 => ContainerType::BlockquoteAttribution,
+            12 => ContainerType::Insertion,
+            13 => ContainerType::Deletion,
+            14 => ContainerType::Hidden,
+            15 => ContainerType::Invisible,
+            16 => ContainerType::Size,
+            17 => ContainerType::Ruby,
+            18 => ContainerType::RubyText,
+            19 => ContainerType::Paragraph,
+            20 => ContainerType::Align(self.alignment()?),
+            _ => ContainerType::Header(Heading {
+                level: self.heading_level()?,
+                has_toc: self.bool()?,
+                collapsible: self.bool()?,
+            }),
+        };
+
+        let elements = self.elements(depth)?;
+        let attributes = self.attributes()?;
+        Ok(Element::Container(Container::new(
+            ctype, elements, attributes,
+        )))
+    }
+
+    fn list_element(&mut self, depth: usize) -> Result<Element<'static>> {
+        let items = (0..self.int(MAX_ELEMENTS)?)
+            .map(|_| self.list_item(depth))
+            .collect::<Result<_>>()?;
+
+        Ok(Element::List {
+            ltype: self.choose(&[ListType::Bullet, ListType::Numbered])?,
+            items,
+            attributes: self.attributes()?,
+        })
+    }
+
+    fn list_item(&mut self, depth: usize) -> Result<ListItem<'static>> {
+        if depth < MAX_DEPTH && self.bool()? {
+            let element = Box::new(self.list_element(depth + 1)?);
+            Ok(ListItem::SubList { element })
+        } else {
+            Ok(ListItem::Elements {
+                elements: self.elements(depth)?,
+                attributes: self.attributes()?,
+            })
+        }
+    }
+
+    fn table_row(&mut self, depth: usize) -> Result<TableRow<'static>> {
+        Ok(TableRow {
+            attributes: self.attributes()?,
+            cells: self.list(|b| b.table_cell(depth))?,
+        })
+    }
+
+    fn table_cell(&mut self, depth: usize) -> Result<TableCell<'static>> {
+        Ok(TableCell {
+            header: self.bool()?,
+            column_span: self.non_zero(4)?,
+            row_span: self.non_zero(4)?,
+            align: self.optional(TreeBuilder::alignment)?,
+            valign: self.optional(|b| {
+                b.choose(&[
+                    VerticalAlignment::Top,
+                    VerticalAlignment::Middle,
+                    VerticalAlignment::Bottom,
+                    VerticalAlignment::Baseline,
+                ])
+            })?,
+            width: self.optional(TreeBuilder::length)?,
+            attributes: self.attributes()?,
+            elements: self.elements(depth)?,
+        })
+    }
+
+    fn tab(&mut self, depth: usize) -> Result<Tab<'static>> {
+        Ok(Tab {
+            label: self.string()?,
+            elements: self.elements(depth)?,
+        })
+    }
+
+    fn partial(&mut self, depth: usize) -> Result<PartialElement<'static>> {
+        Ok(match self.int(4)? {
+            0 => PartialElement::ListItem(self.list_item(depth)?),
+            1 => PartialElement::TableRow(self.table_row(depth)?),
+            2 => PartialElement::TableCell(self.table_cell(depth)?),
+            3 => PartialElement::Tab(self.tab(depth)?),
+            _ => PartialElement::RubyText(RubyText {
+                attributes: self.attributes()?,
+                elements: self.elements(depth)?,
+            }),
+        })
+    }
+}
+
+#[test]
+fn arbitrary_trees() {
+    use crate::data::PageInfo;
+    use crate::layout::Layout;
+    use crate::render::{html::HtmlRender, text::TextRender, Render};
+    use crate::settings::{WikitextMode, WikitextSettings};
+    use crate::tree::visit::{walk_element, Visitor};
+    use std::collections::HashSet;
+    use std::mem::{self, Discriminant};
+
+    struct VariantCollector(HashSet<Discriminant<Element<'static>>>);
+
+    impl<'t> Visitor<'t> for VariantCollector {
+        fn visit_element(&mut self, element: &Element<'t>) {
+            // Discriminants don't depend on lifetimes, so compare with an owned copy
+            self.0.insert(mem::discriminant(&element.to_owned()));
+            walk_element(self, element);
+        }
+    }
+
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let mut variants = VariantCollector(HashSet::new());
+
+    // Pseudo-random bytes, so the test is reproducible
+    let mut state: u32 = 0x2545_f491;
+    let mut data = Vec::new();
+
+    for _ in 0..128 {
+        let tree = arbitrary_tree(&data);
+        assert_eq!(
+            tree,
+            arbitrary_tree(&data),
+            "Tree generation is not deterministic"
+        );
+
+        variants.visit_syntax_tree(&tree);
+        let _ = HtmlRender.render(&tree, &page_info, &settings);
+        let _ = TextRender.render(&tree, &page_info, &settings);
+
+        for _ in 0..256 {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            data.push(state.to_le_bytes()[0]);
+        }
+    }
+
+    assert_eq!(
+        variants.0.len(),
+        ELEMENT_VARIANTS,
+        "Not every element variant was generated",
+    );
+}
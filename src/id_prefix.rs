@@ -20,14 +20,13 @@
 
 //! Utility to prefix HTML IDs for isolation.
 //!
-//! This adds `u-` to all IDs in the string (if non-empty)
-//! to ensure non-collision with generated elements.
+//! This adds a prefix, by default `u-`, to all IDs in the string
+//! (if non-empty) to ensure non-collision with generated elements.
 //!
-//! However it is intelligent, and doesn't add the `u-` if
+//! However it is intelligent, and doesn't add the prefix if
 //! it's already prefixed with that.
 
-pub fn isolate_ids(id_string: &str) -> String {
-    const PREFIX: &str = "u-";
+pub fn isolate_ids(id_string: &str, prefix: &str) -> String {
     let mut isolated_ids = String::new();
 
     for class in id_string.split_whitespace() {
@@ -37,8 +36,8 @@ pub fn isolate_ids(id_string: &str) -> String {
         }
 
         // Prefix if not already present
-        if !class.starts_with(PREFIX) {
-            isolated_ids.push_str(PREFIX);
+        if !class.starts_with(prefix) {
+            isolated_ids.push_str(prefix);
         }
 
         isolated_ids.push_str(class);
@@ -52,7 +51,7 @@ fn test_isolate_ids() {
     macro_rules! check {
         ($input:expr, $expected:expr) => {
             assert_eq!(
-                isolate_ids($input),
+                isolate_ids($input, "u-"),
                 $expected,
                 "Actual isolated ID string doesn't match expected",
             );
@@ -74,4 +73,10 @@ fn test_isolate_ids() {
     check!("u-apple u-banana u-cherry", "u-apple u-banana u-cherry");
     check!("apple u-banana cherry", "u-apple u-banana u-cherry");
     check!("u-u-apple", "u-u-apple");
+
+    assert_eq!(
+        isolate_ids("apple user-banana", "user-"),
+        "user-apple user-banana"
+    );
+    assert_eq!(isolate_ids("apple banana", ""), "apple banana");
 }
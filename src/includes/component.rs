@@ -0,0 +1,171 @@
+/*
+ * includes/component.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Detection of theme and component includes.
+//!
+//! Many pages include shared components, such as `:scp-wiki:component:image-block`,
+//! or themes, such as `:scp-wiki:theme:black-highlighter-theme`. Hosts may want
+//! to know which of these a page uses, for instance to preload their stylesheets.
+//!
+//! This takes the list of pages returned by the include pass and picks out these
+//! includes, classifying them by their category.
+
+use crate::data::PageRef;
+use std::borrow::Cow;
+
+/// What kind of page a recognized include is.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ComponentKind {
+    /// A theme, which restyles the whole page.
+    ///
+    /// These are pages in the `theme:` category, or pages in the
+    /// `component:` category whose names end in `-theme`.
+    Theme,
+
+    /// Any other page in the `component:` category.
+    Component,
+}
+
+/// A theme or component which was included by a page.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct ComponentInclude<'t> {
+    /// What kind of include this is.
+    pub kind: ComponentKind,
+
+    /// The name of the page, without its category.
+    ///
+    /// For `:scp-wiki:theme:black-highlighter-theme`, this is `black-highlighter-theme`.
+    pub name: Cow<'t, str>,
+
+    /// The page which was included.
+    pub page_ref: PageRef<'t>,
+}
+
+impl<'t> ComponentInclude<'t> {
+    /// Determines if this page reference is a theme or component.
+    pub fn from_page_ref(page_ref: &PageRef<'t>) -> Option<Self> {
+        // Borrow from the original string if possible
+        let (kind, name) = match page_ref.page {
+            Cow::Borrowed(page) => {
+                let (kind, name) = classify(page)?;
+                (kind, Cow::Borrowed(name))
+            }
+            Cow::Owned(ref page) => {
+                let (kind, name) = classify(page)?;
+                (kind, Cow::Owned(str!(name)))
+            }
+        };
+
+        Some(ComponentInclude {
+            kind,
+            name,
+            page_ref: page_ref.clone(),
+        })
+    }
+
+    pub fn to_owned(&self) -> ComponentInclude<'static> {
+        ComponentInclude {
+            kind: self.kind,
+            name: Cow::Owned(self.name.as_ref().to_owned()),
+            page_ref: self.page_ref.to_owned(),
+        }
+    }
+}
+
+/// Gets the kind and name of a page, if it's a theme or component.
+fn classify(page: &str) -> Option<(ComponentKind, &str)> {
+    let (category, name) = page.split_once(':')?;
+    let name = name.trim();
+
+    if name.is_empty() {
+        return None;
+    }
+
+    let kind = if category.eq_ignore_ascii_case("theme") {
+        ComponentKind::Theme
+    } else if category.eq_ignore_ascii_case("component") {
+        if name.to_ascii_lowercase().ends_with("-theme") {
+            ComponentKind::Theme
+        } else {
+            ComponentKind::Component
+        }
+    } else {
+        return None;
+    };
+
+    Some((kind, name))
+}
+
+/// Finds all the themes and components in a list of included pages.
+///
+/// The pages are typically those returned by [`include()`](super::include).
+/// They are returned in the order they were included, with any repeated
+/// includes of the same page omitted.
+pub fn find_components<'t>(pages: &[PageRef<'t>]) -> Vec<ComponentInclude<'t>> {
+    let mut components: Vec<ComponentInclude> = Vec::new();
+
+    for page_ref in pages {
+        if let Some(component) = ComponentInclude::from_page_ref(page_ref) {
+            if !components.iter().any(|c| c.page_ref == component.page_ref) {
+                components.push(component);
+            }
+        }
+    }
+
+    components
+}
+
+#[test]
+fn components() {
+    let pages = [
+        PageRef::page_and_site("scp-wiki", "component:black-highlighter-theme"),
+        PageRef::page_only("fragment:scp-001-1"),
+        PageRef::page_and_site("scp-wiki", "component:image-block"),
+        PageRef::page_only("theme:basalt"),
+        PageRef::page_and_site("scp-wiki", "component:image-block"),
+        PageRef::page_only("main"),
+        PageRef::page_only("component:"),
+    ];
+
+    let components = find_components(&pages);
+
+    assert_eq!(
+        components,
+        vec![
+            ComponentInclude {
+                kind: ComponentKind::Theme,
+                name: Cow::Borrowed("black-highlighter-theme"),
+                page_ref: pages[0].clone(),
+            },
+            ComponentInclude {
+                kind: ComponentKind::Component,
+                name: Cow::Borrowed("image-block"),
+                page_ref: pages[2].clone(),
+            },
+            ComponentInclude {
+                kind: ComponentKind::Theme,
+                name: Cow::Borrowed("basalt"),
+                page_ref: pages[3].clone(),
+            },
+        ],
+    );
+}
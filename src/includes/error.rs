@@ -0,0 +1,95 @@
+/*
+ * includes/error.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::IncludeRecursion;
+use crate::budget::BudgetExceeded;
+use std::error::Error;
+use std::fmt::{self, Display};
+
+/// A problem which occurred while substituting include blocks.
+///
+/// These do not abort [`include()`](super::include). Instead, any include blocks
+/// which could not be substituted are left as-is, and the remainder of the text
+/// is processed as usual. The parser then reports any leftover blocks as
+/// [`ParseErrorKind::InvalidInclude`](crate::parsing::ParseErrorKind::InvalidInclude).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IncludeError<'t, E> {
+    /// The includer returned an error.
+    ///
+    /// This can occur while fetching pages, or while producing the
+    /// replacement text for a missing page or recursive include.
+    FetchFailed(E),
+
+    /// The pages returned by the includer do not match those requested.
+    MismatchedResponse,
+
+    /// An include was not performed because it would recurse.
+    ///
+    /// This is either a cycle or an include chain deeper than the
+    /// `max_include_depth` setting. Unlike the other errors, the include block
+    /// is still replaced, using [`Includer::include_recursion()`].
+    ///
+    /// [`Includer::include_recursion()`]: super::Includer::include_recursion
+    Recursion(IncludeRecursion<'t>),
+
+    /// The work budget was exhausted by the included content.
+    BudgetExceeded(BudgetExceeded),
+
+    /// More pages would be included than the `max_include_count` setting permits.
+    TooManyIncludes { limit: usize },
+
+    /// The included content would exceed the `max_include_size` setting.
+    TooLarge { limit: usize },
+}
+
+impl<E> Display for IncludeError<'_, E>
+where
+    E: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IncludeError::FetchFailed(error) => write!(f, "includer failed: {error}"),
+            IncludeError::MismatchedResponse => {
+                write!(f, "includer returned pages which were not requested")
+            }
+            IncludeError::Recursion(recursion) => write!(f, "{recursion}"),
+            IncludeError::BudgetExceeded(error) => write!(f, "{error}"),
+            IncludeError::TooManyIncludes { limit } => {
+                write!(f, "more than {limit} pages included")
+            }
+            IncludeError::TooLarge { limit } => {
+                write!(f, "more than {limit} bytes of content included")
+            }
+        }
+    }
+}
+
+impl<E> Error for IncludeError<'_, E>
+where
+    E: Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            IncludeError::FetchFailed(error) => Some(error),
+            IncludeError::BudgetExceeded(error) => Some(error),
+            _ => None,
+        }
+    }
+}
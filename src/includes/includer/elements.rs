@@ -0,0 +1,41 @@
+/*
+ * includes/includer/elements.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use crate::data::PageRef;
+use crate::tree::SyntaxTree;
+use std::fmt::Debug;
+
+/// A trait that provides already-parsed pages for `[[include-elements]]`.
+///
+/// Unlike [`Includer`](super::Includer), which substitutes the source of included
+/// pages before parsing, this returns the syntax tree of each included page, such
+/// as from a cache. Its elements are then grafted into the tree being parsed,
+/// along with its footnotes, code blocks, and so on. This avoids parsing pages
+/// which are included frequently, such as theme headers, over and over again.
+///
+/// This is passed to [`parse_with_includer()`](crate::parsing::parse_with_includer).
+pub trait ElementsIncluder: Debug {
+    /// Gets the syntax tree of the given page.
+    ///
+    /// The tree must be owned, which a borrowed tree can be converted
+    /// to with [`SyntaxTree::to_owned()`]. Returns `None` if the page
+    /// does not exist, in which case the include block fails to parse.
+    fn include_elements(&self, page_ref: &PageRef) -> Option<SyntaxTree<'static>>;
+}
@@ -22,18 +22,20 @@
 //! block.
 
 mod debug;
+mod elements;
 mod null;
 
 mod prelude {
     pub use crate::data::PageRef;
-    pub use crate::includes::{FetchedPage, IncludeRef, Includer};
+    pub use crate::includes::{FetchedPage, IncludeRecursion, IncludeRef, Includer};
     pub use std::borrow::Cow;
 }
 
-use crate::includes::{IncludeRef, PageRef};
+use crate::includes::{IncludeRecursion, IncludeRef, PageRef};
 use std::borrow::Cow;
 
 pub use self::debug::DebugIncluder;
+pub use self::elements::ElementsIncluder;
 pub use self::null::NullIncluder;
 
 /// A type used by [`Includer`] which represents a page that is ready to be included.
@@ -59,4 +61,28 @@ pub trait Includer<'t> {
         &mut self,
         page_ref: &PageRef<'t>,
     ) -> Result<Cow<'t, str>, Self::Error>;
+
+    /// Whether variables in included pages which were not given a value are removed.
+    ///
+    /// Variables such as `{$name}` which the include block does not set,
+    /// and which lack a default (as in `{$name|default}`), are left as-is
+    /// by default, as Wikidot does.
+    fn strip_missing_variables(&self) -> bool {
+        false
+    }
+
+    /// Handles an include which was not performed because it would recurse.
+    ///
+    /// This occurs if the page is already being included further up the chain,
+    /// or if the chain exceeds the `max_include_depth` setting. Implementations
+    /// may substitute a message listing the chain, or return an error to abort.
+    ///
+    /// By default, the include block is replaced with nothing.
+    fn include_recursion(
+        &mut self,
+        recursion: &IncludeRecursion<'t>,
+    ) -> Result<Cow<'t, str>, Self::Error> {
+        warn!("Not performing recursive include ({recursion})");
+        Ok(Cow::Borrowed(""))
+    }
 }
@@ -28,15 +28,24 @@
 #[cfg(test)]
 mod test;
 
+mod component;
+mod error;
 mod include_ref;
 mod includer;
 mod parse;
+mod recursion;
 
+pub use self::component::{find_components, ComponentInclude, ComponentKind};
+pub use self::error::IncludeError;
 pub use self::include_ref::IncludeRef;
-pub use self::includer::{DebugIncluder, FetchedPage, Includer, NullIncluder};
+pub use self::includer::{
+    DebugIncluder, ElementsIncluder, FetchedPage, Includer, NullIncluder,
+};
+pub use self::recursion::{IncludeRecursion, IncludeRecursionKind};
 
 use self::parse::parse_include_block;
-use crate::data::PageRef;
+use crate::budget::WorkBudget;
+use crate::data::{PageInfo, PageRef};
 use crate::settings::WikitextSettings;
 use crate::tree::VariableMap;
 use once_cell::sync::Lazy;
@@ -50,123 +59,380 @@ static INCLUDE_REGEX: Lazy<Regex> = Lazy::new(|| {
         .build()
         .unwrap()
 });
-static VARIABLE_REGEX: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"\{\$(?P<name>[a-zA-Z0-9_\-]+)\}").unwrap());
+static VARIABLE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\{\$(?P<name>[a-zA-Z0-9_\-]+)(?:\|(?P<default>[^}\n]*))?\}").unwrap()
+});
 
 /// Replaces the include blocks in a string with the content of the pages referenced by those
 /// blocks.
-pub fn include<'t, I, E, F>(
+///
+/// Returns the substituted text, the pages which were included, and any problems which
+/// occurred. Problems with one include do not affect the others, see [`IncludeError`].
+pub fn include<'t, I, E>(
     input: &'t str,
     settings: &WikitextSettings,
-    mut includer: I,
-    invalid_return: F,
-) -> Result<(String, Vec<PageRef<'t>>), E>
+    includer: I,
+) -> (String, Vec<PageRef<'t>>, Vec<IncludeError<'t, E>>)
+where
+    I: Includer<'t, Error = E>,
+{
+    let budget = WorkBudget::unlimited();
+    include_with_budget(input, settings, includer, &budget)
+}
+
+/// Like [`include()`], but spends from the given work budget.
+///
+/// Each byte of included page content costs one unit.
+/// Once the budget is exhausted, no further pages are substituted.
+/// See [`WorkBudget`] for more information.
+pub fn include_with_budget<'t, I, E>(
+    input: &'t str,
+    settings: &WikitextSettings,
+    includer: I,
+    budget: &WorkBudget,
+) -> (String, Vec<PageRef<'t>>, Vec<IncludeError<'t, E>>)
+where
+    I: Includer<'t, Error = E>,
+{
+    include_inner(input, None, settings, includer, budget)
+}
+
+/// Like [`include_with_budget()`], but for the page described by `page_info`.
+///
+/// Since the functions above do not know which page is being expanded,
+/// a page which includes itself is only detected as a cycle once it has
+/// been included a second time. Here the page begins the include chain,
+/// so any include of it is detected as a cycle immediately.
+pub fn include_page<'t, I, E>(
+    input: &'t str,
+    page_info: &PageInfo,
+    settings: &WikitextSettings,
+    includer: I,
+    budget: &WorkBudget,
+) -> (String, Vec<PageRef<'t>>, Vec<IncludeError<'t, E>>)
+where
+    I: Includer<'t, Error = E>,
+{
+    let page = match page_info.category {
+        Some(ref category) if category != "_default" => {
+            format!("{}:{}", category, page_info.page)
+        }
+        _ => page_info.page.to_string(),
+    };
+
+    let root = PageRef::page_only(page);
+    include_inner(input, Some(root), settings, includer, budget)
+}
+
+fn include_inner<'t, I, E>(
+    input: &'t str,
+    root: Option<PageRef<'t>>,
+    settings: &WikitextSettings,
+    includer: I,
+    budget: &WorkBudget,
+) -> (String, Vec<PageRef<'t>>, Vec<IncludeError<'t, E>>)
 where
     I: Includer<'t, Error = E>,
-    F: FnOnce() -> E,
 {
     if !settings.enable_page_syntax {
         debug!("Includes are disabled for this input, skipping");
 
         let output = str!(input);
         let pages = vec![];
-        return Ok((output, pages));
+        let errors = vec![];
+        return (output, pages, errors);
     }
 
     info!(
         "Inserting text for all include blocks in text ({} bytes)",
         input.len(),
     );
+    enter_span!("include", bytes = input.len());
+
+    let mut expander = IncludeExpander {
+        settings,
+        includer,
+        budget,
+        root: root.is_some(),
+        chain: root.into_iter().collect(),
+        count: 0,
+        size: 0,
+        pages: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    let output = expander.expand(input, |include| include);
+    (output, expander.pages, expander.errors)
+}
+
+/// Helper to substitute include blocks, recursing into the included pages.
+#[derive(Debug)]
+struct IncludeExpander<'s, 't, I, E> {
+    settings: &'s WikitextSettings,
+    includer: I,
+    budget: &'s WorkBudget,
 
-    let mut ranges = Vec::new();
-    let mut includes = Vec::new();
+    /// Whether the chain begins with the page being expanded.
+    ///
+    /// This page does not count towards the include depth.
+    root: bool,
 
-    // Get include references
-    for mtch in INCLUDE_REGEX.find_iter(input) {
-        let start = mtch.start();
+    /// The pages currently being included, from outermost to innermost.
+    chain: Vec<PageRef<'t>>,
 
-        trace!(
-            "Found include regex match (start {}, slice '{}')",
-            start,
-            mtch.as_str(),
-        );
+    /// How many pages have been fetched so far.
+    count: usize,
 
-        match parse_include_block(input, start, settings) {
-            Ok((include, end)) => {
-                ranges.push(start..end);
-                includes.push(include);
+    /// How many bytes of page content have been fetched so far.
+    size: usize,
+
+    /// All the pages which were included, in order.
+    pages: Vec<PageRef<'t>>,
+
+    /// All the problems which occurred, in order.
+    errors: Vec<IncludeError<'t, E>>,
+}
+
+impl<'t, I, E> IncludeExpander<'_, 't, I, E>
+where
+    I: Includer<'t, Error = E>,
+{
+    /// Replaces all the include blocks in the given text.
+    ///
+    /// Since page references borrow from the text, `convert` is used to
+    /// produce ones which outlive it. At the top level this is the identity,
+    /// but within included pages it must produce owned values.
+    fn expand<'a>(
+        &mut self,
+        input: &'a str,
+        convert: fn(IncludeRef<'a>) -> IncludeRef<'t>,
+    ) -> String {
+        let mut ranges = Vec::new();
+        let mut recursions = Vec::new();
+        let mut includes = Vec::new();
+
+        // Get include references
+        for mtch in INCLUDE_REGEX.find_iter(input) {
+            let start = mtch.start();
+
+            trace!(
+                "Found include regex match (start {}, slice '{}')",
+                start,
+                mtch.as_str(),
+            );
+
+            match parse_include_block(input, start, self.settings) {
+                Ok((include, end)) => {
+                    let include = convert(include);
+
+                    // Only fetch pages which don't recurse
+                    let recursion = self.check_recursion(include.page_ref());
+                    if recursion.is_none() {
+                        includes.push(include);
+                    }
+
+                    ranges.push(start..end);
+                    recursions.push(recursion);
+                }
+                Err(_) => warn!("Unable to parse include regex match"),
             }
-            Err(_) => warn!("Unable to parse include regex match"),
         }
-    }
 
-    // Retrieve included pages
-    let fetched_pages = includer.include_pages(&includes)?;
+        // Retrieve included pages
+        //
+        // If this fails, then each of these include blocks is left as-is.
+        let mut fetched_iter = self.fetch_pages(&includes).map(Vec::into_iter);
+        let mut includes_iter = includes.into_iter();
+
+        // Substitute inclusions
+        //
+        // The output is built up as we go, rather than replacing in-place,
+        // since included content is itself expanded first.
+        let mut output = String::with_capacity(input.len());
+        let mut last_end = 0;
+
+        for (range, recursion) in ranges.into_iter().zip(recursions) {
+            debug!(
+                "Replacing range for included page ({}..{})",
+                range.start, range.end,
+            );
+
+            output.push_str(&input[last_end..range.start]);
+            last_end = range.end;
+
+            // Include would recurse, get message
+            if let Some(recursion) = recursion {
+                match self.includer.include_recursion(&recursion) {
+                    Ok(replace_with) => output.push_str(&replace_with),
+                    Err(error) => {
+                        self.errors.push(IncludeError::FetchFailed(error));
+                        output.push_str(&input[range]);
+                    }
+                }
+
+                self.errors.push(IncludeError::Recursion(recursion));
+                continue;
+            }
 
-    // Ensure it matches up with the request
-    if includes.len() != fetched_pages.len() {
-        return Err(invalid_return());
-    }
+            // Both lists have the same length, checked in fetch_pages()
+            let include = includes_iter
+                .next()
+                .expect("Fewer includes than non-recursive include blocks");
+
+            let fetched = match fetched_iter {
+                Some(ref mut iter) => {
+                    iter.next().expect("Fewer fetched pages than includes")
+                }
+                None => {
+                    output.push_str(&input[range]);
+                    continue;
+                }
+            };
+
+            let (page_ref, variables) = include.into();
+
+            // Ensure the returned page reference matches
+            if page_ref != fetched.page_ref {
+                warn!(
+                    "Includer returned page '{}' instead of '{page_ref}'",
+                    fetched.page_ref,
+                );
+
+                self.errors.push(IncludeError::MismatchedResponse);
+                output.push_str(&input[range]);
+                continue;
+            }
 
-    // Substitute inclusions
-    //
-    // We must iterate backwards for all the indices to be valid
+            // Append page to final list
+            self.pages.push(page_ref.clone());
+
+            match fetched.content {
+                // Take fetched content, replace variables,
+                // then substitute any includes it has in turn
+                Some(mut content) => {
+                    let strip = self.includer.strip_missing_variables();
+                    replace_variables(content.to_mut(), &variables, strip);
+
+                    self.chain.push(page_ref);
+                    let result = self.expand(&content, owned_include);
+                    self.chain.pop();
+
+                    output.push_str(&result);
+                }
+
+                // Include not found, use premade template
+                None => match self.includer.no_such_include(&page_ref) {
+                    Ok(replace_with) => output.push_str(&replace_with),
+                    Err(error) => {
+                        self.errors.push(IncludeError::FetchFailed(error));
+                        output.push_str(&input[range]);
+                    }
+                },
+            }
+        }
 
-    let ranges_iter = ranges.into_iter();
-    let includes_iter = includes.into_iter();
-    let fetched_iter = fetched_pages.into_iter();
+        output.push_str(&input[last_end..]);
+        output
+    }
 
-    let joined_iter = ranges_iter.zip(includes_iter).zip(fetched_iter).rev();
+    /// Fetches the given pages from the includer, spending their cost from the budget.
+    ///
+    /// If this fails, the error is recorded and `None` is returned.
+    fn fetch_pages(
+        &mut self,
+        includes: &[IncludeRef<'t>],
+    ) -> Option<Vec<FetchedPage<'t>>> {
+        let limit = self.settings.max_include_count;
+        if self.count + includes.len() > limit {
+            warn!(
+                "Including {} more pages would exceed the limit",
+                includes.len()
+            );
+            self.errors.push(IncludeError::TooManyIncludes { limit });
+            return None;
+        }
 
-    // Borrowing from the original text and doing in-place insertions
-    // will not work here. We are trying to both return the page names
-    // (slices from the input string), and replace it with new content.
-    let mut output = String::from(input);
-    let mut pages = Vec::new();
+        let fetched_pages = match self.includer.include_pages(includes) {
+            Ok(fetched_pages) => fetched_pages,
+            Err(error) => {
+                warn!("Includer failed to fetch {} pages", includes.len());
+                self.errors.push(IncludeError::FetchFailed(error));
+                return None;
+            }
+        };
 
-    for ((range, include), fetched) in joined_iter {
-        let (page_ref, variables) = include.into();
+        // Ensure it matches up with the request
+        if includes.len() != fetched_pages.len() {
+            warn!(
+                "Includer returned {} pages, but {} were requested",
+                fetched_pages.len(),
+                includes.len(),
+            );
 
-        debug!(
-            "Replacing range for included page ({}..{})",
-            range.start, range.end,
-        );
+            self.errors.push(IncludeError::MismatchedResponse);
+            return None;
+        }
 
-        // Ensure the returned page reference matches
-        if page_ref != fetched.page_ref {
-            return Err(invalid_return());
+        // Spend the cost of the included content
+        let cost: usize = fetched_pages
+            .iter()
+            .map(|fetched| fetched.content.as_ref().map(|c| c.len()).unwrap_or(0))
+            .sum();
+
+        let limit = self.settings.max_include_size;
+        if self.size + cost > limit {
+            warn!("Including {cost} more bytes would exceed the limit");
+            self.errors.push(IncludeError::TooLarge { limit });
+            return None;
         }
 
-        // Get replaced content, or error message
-        let replace_with = match fetched.content {
-            // Take fetched content, replace variables
-            Some(mut content) => {
-                replace_variables(content.to_mut(), &variables);
-                content
-            }
+        if let Err(error) = self.budget.spend(cost) {
+            self.errors.push(IncludeError::BudgetExceeded(error));
+            return None;
+        }
+
+        self.count += includes.len();
+        self.size += cost;
+
+        Some(fetched_pages)
+    }
 
-            // Include not found, return premade template
-            None => includer.no_such_include(&page_ref)?,
+    fn check_recursion(&self, page_ref: &PageRef<'t>) -> Option<IncludeRecursion<'t>> {
+        let kind = if self.chain.contains(page_ref) {
+            IncludeRecursionKind::Cycle
+        } else if self.depth() >= self.settings.max_include_depth {
+            IncludeRecursionKind::TooDeep
+        } else {
+            return None;
         };
 
-        // Append page to final list
-        pages.push(page_ref);
+        let mut chain = self.chain.clone();
+        chain.push(page_ref.clone());
 
-        // Perform the substitution
-        output.replace_range(range, &replace_with);
+        warn!("Include of page '{page_ref}' would recurse ({kind:?})");
+        Some(IncludeRecursion { kind, chain })
     }
 
-    // Since we iterate in reverse order, the pages are reversed.
-    pages.reverse();
+    /// How deeply nested the current include is.
+    #[inline]
+    fn depth(&self) -> usize {
+        self.chain.len() - usize::from(self.root)
+    }
+}
 
-    // Return
-    Ok((output, pages))
+#[inline]
+fn owned_include<'t>(include: IncludeRef<'_>) -> IncludeRef<'t> {
+    include.to_owned()
 }
 
 /// Replaces all specified variables in the content to be included.
 ///
+/// Variables not specified use their default, as in `{$name|default}`.
+/// If they have none, then they are removed if `strip` is set,
+/// or left as-is otherwise.
+///
 /// Read <https://www.wikidot.com/doc-wiki-syntax:include> for more details.
-fn replace_variables(content: &mut String, variables: &VariableMap) {
+fn replace_variables(content: &mut String, variables: &VariableMap, strip: bool) {
     let mut matches = Vec::new();
 
     // Find all variables
@@ -174,15 +440,20 @@ fn replace_variables(content: &mut String, variables: &VariableMap) {
         let mtch = capture.get(0).unwrap();
         let name = &capture["name"];
 
-        if let Some(value) = variables.get(name) {
-            matches.push((value, mtch.range()));
-        }
+        let value = match (variables.get(name), capture.name("default")) {
+            (Some(value), _) => value.to_string(),
+            (None, Some(default)) => default.as_str().to_string(),
+            (None, None) if strip => String::new(),
+            (None, None) => continue,
+        };
+
+        matches.push((value, mtch.range()));
     }
 
     // Replace the variables
     // Iterates backwards so indices stay valid
     matches.reverse();
     for (value, range) in matches {
-        content.replace_range(range, value);
+        content.replace_range(range, &value);
     }
 }
@@ -0,0 +1,73 @@
+/*
+ * includes/recursion.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use crate::data::PageRef;
+use std::error::Error;
+use std::fmt::{self, Display};
+
+/// Describes an include which was not performed because it would recurse.
+///
+/// This is passed to [`Includer::include_recursion()`], which decides whether
+/// to substitute a message in place of the include block, or to fail entirely.
+///
+/// [`Includer::include_recursion()`]: super::Includer::include_recursion
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct IncludeRecursion<'t> {
+    /// Why this include was not performed.
+    pub kind: IncludeRecursionKind,
+
+    /// The chain of pages being included, from outermost to innermost.
+    ///
+    /// The last entry is the page which was not included.
+    pub chain: Vec<PageRef<'t>>,
+}
+
+impl Display for IncludeRecursion<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            IncludeRecursionKind::Cycle => write!(f, "include cycle: ")?,
+            IncludeRecursionKind::TooDeep => write!(f, "include depth exceeded: ")?,
+        }
+
+        for (i, page_ref) in self.chain.iter().enumerate() {
+            if i > 0 {
+                write!(f, " -> ")?;
+            }
+
+            write!(f, "{page_ref}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for IncludeRecursion<'_> {}
+
+/// The reason an [`IncludeRecursion`] occurred.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum IncludeRecursionKind {
+    /// The page is already being included further up the chain.
+    Cycle,
+
+    /// The chain is longer than the `max_include_depth` setting.
+    TooDeep,
+}
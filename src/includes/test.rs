@@ -18,7 +18,9 @@
  * along with this program. If not, see <http://www.gnu.org/licenses/>.
  */
 
-use super::{include, DebugIncluder, PageRef};
+use super::{include, include_page, DebugIncluder, PageRef};
+use crate::budget::WorkBudget;
+use crate::data::PageInfo;
 use crate::layout::Layout;
 use crate::settings::{WikitextMode, WikitextSettings};
 
@@ -29,8 +31,7 @@ fn includes() {
     macro_rules! test {
         ($text:expr, $expected:expr $(,)?) => {{
             let mut text = str!($text);
-            let result = include(&mut text, &settings, DebugIncluder, || panic!());
-            let (output, actual) = result.expect("Fetching pages failed");
+            let (output, actual, errors) = include(&mut text, &settings, DebugIncluder);
             let expected = $expected;
 
             println!("Input:  '{}'", $text);
@@ -43,6 +44,7 @@ fn includes() {
                 &actual, &expected,
                 "Actual pages to include doesn't match expected"
             );
+            assert!(errors.is_empty(), "Errors produced: {errors:?}");
         }};
     }
 
@@ -271,3 +273,376 @@ fn includes() {
         vec![],
     );
 }
+
+#[test]
+fn recursive_includes() {
+    use super::{FetchedPage, IncludeRecursion, IncludeRef, Includer};
+    use std::borrow::Cow;
+    use std::collections::HashMap;
+    use std::convert::Infallible;
+
+    #[derive(Debug)]
+    struct PageIncluder(HashMap<&'static str, &'static str>);
+
+    impl<'t> Includer<'t> for PageIncluder {
+        type Error = Infallible;
+
+        fn include_pages(
+            &mut self,
+            includes: &[IncludeRef<'t>],
+        ) -> Result<Vec<FetchedPage<'t>>, Infallible> {
+            let pages = includes
+                .iter()
+                .map(|include| {
+                    let page_ref = include.page_ref().clone();
+                    let content = self.0.get(page_ref.page()).map(|&c| Cow::Borrowed(c));
+                    FetchedPage { page_ref, content }
+                })
+                .collect();
+
+            Ok(pages)
+        }
+
+        fn no_such_include(
+            &mut self,
+            page_ref: &PageRef<'t>,
+        ) -> Result<Cow<'t, str>, Infallible> {
+            Ok(Cow::Owned(format!("<MISSING {page_ref}>")))
+        }
+
+        fn include_recursion(
+            &mut self,
+            recursion: &IncludeRecursion<'t>,
+        ) -> Result<Cow<'t, str>, Infallible> {
+            Ok(Cow::Owned(format!("<{recursion}>")))
+        }
+    }
+
+    let includer = || {
+        PageIncluder(hashmap! {
+            "apple" => "A\n[[include-messy banana]]",
+            "banana" => "B\n[[include-messy cherry]]",
+            "cherry" => "C",
+            "durian" => "D\n[[include-messy durian]]",
+            "fig" => "F\n[[include-messy grape]]",
+            "grape" => "G\n[[include-messy fig]]\n{$x}",
+            "lemon" => "[[include-messy lemon]]",
+        })
+    };
+
+    let mut settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+
+    macro_rules! test {
+        ($text:expr, $expected_output:expr, $expected_pages:expr $(,)?) => {{
+            let (output, pages, _errors) = include($text, &settings, includer());
+            let pages: Vec<&str> = pages.iter().map(|page_ref| page_ref.page()).collect();
+
+            assert_eq!(output, $expected_output, "Output doesn't match expected");
+            assert_eq!(pages, $expected_pages, "Pages don't match expected");
+        }};
+    }
+
+    // Nested
+    test!(
+        "[[include-messy apple]]",
+        "A\nB\nC",
+        vec!["apple", "banana", "cherry"]
+    );
+    test!("[[include-messy kiwi]]", "<MISSING kiwi>", vec!["kiwi"]);
+
+    // Cycles
+    test!(
+        "[[include-messy durian]]",
+        "D\n<include cycle: durian -> durian>",
+        vec!["durian"],
+    );
+    test!(
+        "[[include-messy fig]]",
+        "F\nG\n<include cycle: fig -> grape -> fig>\n{$x}",
+        vec!["fig", "grape"],
+    );
+    test!(
+        "[[include-messy grape x=1]]",
+        "G\nF\n<include cycle: grape -> fig -> grape>\n1",
+        vec!["grape", "fig"],
+    );
+
+    // Depth
+    settings.max_include_depth = 2;
+    test!(
+        "[[include-messy apple]]",
+        "A\nB\n<include depth exceeded: apple -> banana -> cherry>",
+        vec!["apple", "banana"],
+    );
+
+    settings.max_include_depth = 0;
+    test!(
+        "X\n[[include-messy lemon]]",
+        "X\n<include depth exceeded: lemon>",
+        Vec::<&str>::new(),
+    );
+
+    // Starting from the root page
+    settings.max_include_depth = 5;
+    let page_info = PageInfo {
+        page: cow!("durian"),
+        ..PageInfo::dummy()
+    };
+    let budget = WorkBudget::unlimited();
+    let (output, pages, _errors) = include_page(
+        "D\n[[include-messy durian]]",
+        &page_info,
+        &settings,
+        includer(),
+        &budget,
+    );
+
+    assert_eq!(output, "D\n<include cycle: durian -> durian>");
+    assert!(pages.is_empty(), "Root page was included");
+
+    let page_info = PageInfo {
+        page: cow!("banana"),
+        ..PageInfo::dummy()
+    };
+    let (output, pages, _errors) = include_page(
+        "[[include-messy cherry]]",
+        &page_info,
+        &settings,
+        includer(),
+        &budget,
+    );
+
+    assert_eq!(output, "C");
+    assert_eq!(pages, vec![PageRef::page_only("cherry")]);
+}
+
+#[test]
+fn include_limits() {
+    use super::{FetchedPage, IncludeError, IncludeRef, Includer};
+    use std::borrow::Cow;
+    use std::convert::Infallible;
+
+    /// Every page includes three others, named after it.
+    #[derive(Debug)]
+    struct FanIncluder;
+
+    impl<'t> Includer<'t> for FanIncluder {
+        type Error = Infallible;
+
+        fn include_pages(
+            &mut self,
+            includes: &[IncludeRef<'t>],
+        ) -> Result<Vec<FetchedPage<'t>>, Infallible> {
+            let pages = includes
+                .iter()
+                .map(|include| {
+                    let page_ref = include.page_ref().clone();
+                    let page = page_ref.page();
+                    let content = format!(
+                        "X\n[[include-messy {page}a]]\n[[include-messy {page}b]]\n[[include-messy {page}c]]",
+                    );
+
+                    FetchedPage {
+                        page_ref,
+                        content: Some(Cow::Owned(content)),
+                    }
+                })
+                .collect();
+
+            Ok(pages)
+        }
+
+        fn no_such_include(
+            &mut self,
+            _page_ref: &PageRef<'t>,
+        ) -> Result<Cow<'t, str>, Infallible> {
+            Ok(Cow::Borrowed(""))
+        }
+    }
+
+    let input = "[[include-messy a]]\n[[include-messy b]]\n[[include-messy c]]";
+    let mut settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+
+    // Without limits, this includes 3 + 9 + 27 + 81 + 243 pages,
+    // stopping only at the maximum depth
+    settings.max_include_count = usize::MAX;
+    settings.max_include_size = usize::MAX;
+    let (_output, pages, errors) = include(input, &settings, FanIncluder);
+    assert_eq!(pages.len(), 363);
+    assert!(errors
+        .iter()
+        .all(|error| matches!(error, IncludeError::Recursion(_))));
+
+    // But by default, this is limited
+    settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    settings.max_include_depth = 8;
+    let (_output, pages, errors) = include(input, &settings, FanIncluder);
+    assert!(
+        pages.len() <= 500,
+        "Too many pages included: {}",
+        pages.len()
+    );
+    assert!(errors.contains(&IncludeError::TooManyIncludes { limit: 500 }));
+
+    settings.max_include_count = 20;
+    let (_output, pages, errors) = include(input, &settings, FanIncluder);
+    assert!(
+        pages.len() <= 20,
+        "Too many pages included: {}",
+        pages.len()
+    );
+    assert!(errors.contains(&IncludeError::TooManyIncludes { limit: 20 }));
+
+    settings.max_include_count = usize::MAX;
+    settings.max_include_size = 1000;
+    let (output, _pages, errors) = include(input, &settings, FanIncluder);
+    assert!(output.len() < 2000, "Output too large: {}", output.len());
+    assert!(errors.contains(&IncludeError::TooLarge { limit: 1000 }));
+}
+
+#[test]
+fn include_errors() {
+    use super::{FetchedPage, IncludeError, IncludeRef, Includer};
+    use crate::budget::{BudgetExceeded, WorkBudget};
+    use crate::includes::include_with_budget;
+    use std::borrow::Cow;
+
+    /// Fails to fetch any page named "broken", and returns "wrong" for "swapped".
+    #[derive(Debug)]
+    struct FlakyIncluder;
+
+    impl<'t> Includer<'t> for FlakyIncluder {
+        type Error = &'static str;
+
+        fn include_pages(
+            &mut self,
+            includes: &[IncludeRef<'t>],
+        ) -> Result<Vec<FetchedPage<'t>>, &'static str> {
+            let mut pages = Vec::new();
+
+            for include in includes {
+                let page_ref = match include.page_ref().page() {
+                    "broken" => return Err("connection lost"),
+                    "swapped" => PageRef::page_only("wrong"),
+                    _ => include.page_ref().clone(),
+                };
+
+                let content = Some(Cow::Owned(format!("<{}>", page_ref.page())));
+                pages.push(FetchedPage { page_ref, content });
+            }
+
+            Ok(pages)
+        }
+
+        fn no_such_include(
+            &mut self,
+            _page_ref: &PageRef<'t>,
+        ) -> Result<Cow<'t, str>, &'static str> {
+            Err("no template")
+        }
+    }
+
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+
+    // Includes in the top level are fetched together,
+    // so one failure affects all of them.
+    let (output, pages, errors) = include(
+        "A\n[[include-messy apple]]\n[[include-messy broken]]",
+        &settings,
+        FlakyIncluder,
+    );
+
+    assert_eq!(
+        output,
+        "A\n[[include-messy apple]]\n[[include-messy broken]]",
+    );
+    assert!(pages.is_empty());
+    assert_eq!(errors, vec![IncludeError::FetchFailed("connection lost")]);
+
+    // A wrong page only affects its own include
+    let (output, pages, errors) = include(
+        "[[include-messy apple]]\n[[include-messy swapped]]",
+        &settings,
+        FlakyIncluder,
+    );
+
+    assert_eq!(output, "<apple>\n[[include-messy swapped]]");
+    assert_eq!(pages, vec![PageRef::page_only("apple")]);
+    assert_eq!(errors, vec![IncludeError::MismatchedResponse]);
+
+    // Budget exhausted
+    let budget = WorkBudget::new(3);
+    let (output, pages, errors) = include_with_budget(
+        "B\n[[include-messy apple]]",
+        &settings,
+        FlakyIncluder,
+        &budget,
+    );
+
+    assert_eq!(output, "B\n[[include-messy apple]]");
+    assert!(pages.is_empty());
+    assert_eq!(
+        errors,
+        vec![IncludeError::BudgetExceeded(BudgetExceeded { limit: 3 })],
+    );
+}
+
+#[test]
+fn include_variables() {
+    use super::{FetchedPage, IncludeRef, Includer};
+    use std::borrow::Cow;
+
+    /// Returns the same page source for every include.
+    #[derive(Debug)]
+    struct VariableIncluder {
+        strip: bool,
+    }
+
+    impl<'t> Includer<'t> for VariableIncluder {
+        type Error = ();
+
+        fn include_pages(
+            &mut self,
+            includes: &[IncludeRef<'t>],
+        ) -> Result<Vec<FetchedPage<'t>>, ()> {
+            Ok(includes
+                .iter()
+                .map(|include| FetchedPage {
+                    page_ref: include.page_ref().clone(),
+                    content: Some(Cow::Borrowed("{$a} {$b|fallback} {$c|} {$d}")),
+                })
+                .collect())
+        }
+
+        fn no_such_include(
+            &mut self,
+            _page_ref: &PageRef<'t>,
+        ) -> Result<Cow<'t, str>, ()> {
+            Err(())
+        }
+
+        fn strip_missing_variables(&self) -> bool {
+            self.strip
+        }
+    }
+
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+
+    macro_rules! test {
+        ($text:expr, $strip:expr, $expected:expr $(,)?) => {{
+            let includer = VariableIncluder { strip: $strip };
+            let (output, _, errors) = include($text, &settings, includer);
+
+            assert_eq!(
+                output, $expected,
+                "Substituted output doesn't match expected"
+            );
+            assert!(errors.is_empty(), "Errors produced: {errors:?}");
+        }};
+    }
+
+    test!("[[include-messy page]]", false, "{$a} fallback  {$d}");
+    test!("[[include-messy page]]", true, " fallback  ");
+    test!("[[include-messy page a=1|b=2|c=3]]", false, "1 2 3 {$d}",);
+    test!("[[include-messy page | d=4]]", true, " fallback  4");
+}
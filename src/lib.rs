@@ -44,15 +44,39 @@
 //! Rendering is performed by the trait [`Render`].
 //! There are two main implementations of note,
 //! [`TextRender`] and [`HtmlRender`], which render to
-//! plain text and full HTML respectively.
+//! plain text and full HTML respectively. There is also
+//! [`EpubRender`], which produces a self-contained XHTML document
+//! for EPUB packaging or offline archives.
 //!
 //! # Features
-//! This crate has one feature of note:
+//! This crate has several features of note:
 //!
 //! The `mathml` feature pulls in the `latex2mathml` library,
 //! which renders LaTeX blocks using MathML. It is enabled
 //! by default.
 //!
+//! The `syntax-highlight` feature pulls in the `syntect` library,
+//! which highlights code blocks using CSS classes. It is disabled
+//! by default.
+//!
+//! The `parallel` feature pulls in the `rayon` library,
+//! which is used to render top-level elements in parallel. It is
+//! disabled by default.
+//!
+//! The `ffi` feature exposes a C API in the `ffi` module, which
+//! passes values as JSON strings. It is disabled by default.
+//!
+//! The `python` feature pulls in the `pyo3` library, which is used
+//! to build the crate as a Python module. It is disabled by default.
+//!
+//! The `fuzz` feature pulls in the `arbitrary` library, and exposes the
+//! `fuzz` module, which builds syntax trees from a fuzzer's input for
+//! testing renderers. It is disabled by default.
+//!
+//! The `tracing` feature pulls in the `tracing` library, which is used
+//! to emit spans for each stage of processing and each parse rule.
+//! It is disabled by default.
+//!
 //! # Targets
 //! The library supports being compiled into WebAssembly.
 //! (target `wasm32-unknown-unknown`, see [`wasm-pack`] for more information)
@@ -69,6 +93,7 @@
 //! [`Render`]: ./render/trait.Render.html
 //! [`TextRender`]: ./render/html/struct.HtmlRender.html
 //! [`HtmlRender`]: ./render/text/struct.TextRender.html
+//! [`EpubRender`]: ./render/epub/struct.EpubRender.html
 //! [`serde`]: https://docs.rs/serde
 //! [`wasm-pack`]: https://rustwasm.github.io/docs/wasm-pack/
 
@@ -111,12 +136,22 @@ mod id_prefix;
 mod next_index;
 mod non_empty_vec;
 mod text;
-mod url;
 mod utf16;
 
 #[cfg(target_arch = "wasm32")]
 pub mod wasm;
 
+#[cfg(all(feature = "ffi", not(target_arch = "wasm32")))]
+pub mod ffi;
+
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
+
+#[cfg(all(feature = "python", not(target_arch = "wasm32")))]
+pub mod python;
+
+pub mod analysis;
+pub mod budget;
 pub mod data;
 pub mod includes;
 pub mod info;
@@ -127,11 +162,15 @@ pub mod render;
 pub mod settings;
 pub mod tokenizer;
 pub mod tree;
-
-pub use self::includes::include;
-pub use self::parsing::parse;
-pub use self::preproc::preprocess;
-pub use self::tokenizer::{tokenize, Tokenization};
+pub mod url;
+
+pub use self::budget::{BudgetExceeded, WorkBudget};
+pub use self::includes::{include, include_page, include_with_budget};
+pub use self::parsing::{
+    parse, parse_infallible, parse_with_budget, parse_with_includer,
+};
+pub use self::preproc::{preprocess, preprocess_with_budget};
+pub use self::tokenizer::{tokenize, tokenize_with_budget, TokenCursor, Tokenization};
 pub use self::utf16::Utf16IndexMap;
 
 /// This module collects commonly used traits from this crate.
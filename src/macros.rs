@@ -66,3 +66,14 @@ macro_rules! str_writeln {
         writeln!($dest, $($arg)*).expect("Writing to string failed");
     }};
 }
+
+/// Enters a `tracing` span, which lasts until the end of the current scope.
+///
+/// This takes the same arguments as `tracing::info_span!()`.
+/// If the `tracing` feature is disabled, it does nothing.
+macro_rules! enter_span {
+    ($name:literal $(, $($fields:tt)*)?) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!($name $(, $($fields)*)?).entered();
+    };
+}
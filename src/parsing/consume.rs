@@ -27,7 +27,7 @@
 //! as raw text as a fallback, which is how Wikidot does it.
 
 use super::prelude::*;
-use super::rule::{get_rules_for_token, impls::RULE_FALLBACK};
+use super::rule::impls::RULE_FALLBACK;
 use super::Parser;
 use std::mem;
 
@@ -49,8 +49,9 @@ pub fn consume<'r, 't>(parser: &mut Parser<'r, 't>) -> ParseResult<'r, 't, Eleme
     trace!("Looking for valid rules");
     let mut all_errors = Vec::new();
     let current = parser.current();
+    let rules = parser.rules();
 
-    for &rule in get_rules_for_token(current) {
+    for rule in rules.rules_for_token(current) {
         trace!("Trying rule consumption for tokens (rule {})", rule.name());
 
         let old_remaining = parser.remaining();
@@ -75,6 +76,10 @@ pub fn consume<'r, 't>(parser: &mut Parser<'r, 't>) -> ParseResult<'r, 't, Eleme
 
                 return Ok(output);
             }
+            Err(error) if error.kind() == ParseErrorKind::BudgetExceeded => {
+                error!("Work budget exceeded, failing");
+                return Err(error);
+            }
             Err(error) => {
                 warn!("Rule failed, returning error: '{}'", error.kind().name());
                 all_errors.push(error);
@@ -104,6 +104,13 @@ impl<'t> ElementCondition<'t> {
 
         required && prohibited && present
     }
+
+    pub fn to_owned(&self) -> ElementCondition<'static> {
+        ElementCondition {
+            ctype: self.ctype,
+            value: Cow::Owned(self.value.to_string()),
+        }
+    }
 }
 
 #[derive(
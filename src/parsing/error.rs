@@ -19,11 +19,15 @@
  */
 
 use super::{rule::Rule, ExtractedToken, Token};
+use crate::tokenizer::Tokenization;
 use crate::utf16::Utf16IndexMap;
 use std::borrow::Cow;
 use std::ops::Range;
 use strum_macros::IntoStaticStr;
 
+/// How many bytes of the line to include on either side of an error in its excerpt.
+const EXCERPT_CONTEXT: usize = 40;
+
 /// An issue that occurred during parsing.
 ///
 /// These refer to circumstances where a rule was attempted, but did not
@@ -31,13 +35,28 @@ use strum_macros::IntoStaticStr;
 ///
 /// However, as outlined by the crate's philosophy, no parsing issue is fatal.
 /// Instead a fallback rules is applied and parsing continues.
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+/// If the `error_level` setting is strict, then the resultant syntax
+/// tree is discarded if there are any errors.
+///
+/// Errors returned from parsing also have an excerpt of the source around
+/// them and the tokens it consists of, and where possible a [`ParseSuggestion`]
+/// describing how to fix the issue, for use by editors.
+#[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub struct ParseError {
     token: Token,
     rule: Cow<'static, str>,
     span: Range<usize>,
     kind: ParseErrorKind,
+
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    excerpt: String,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tokens: Vec<Token>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    suggestion: Option<ParseSuggestion>,
 }
 
 impl ParseError {
@@ -46,15 +65,62 @@ impl ParseError {
         let token = current.token;
         let span = Range::clone(&current.span);
         let rule = cow!(rule.name());
+        let suggestion = ParseSuggestion::for_kind(kind);
 
         ParseError {
             token,
             rule,
             span,
             kind,
+            excerpt: String::new(),
+            tokens: Vec::new(),
+            suggestion,
         }
     }
 
+    /// Replaces the suggestion for fixing this error.
+    #[inline]
+    #[must_use]
+    pub fn with_suggestion(mut self, suggestion: ParseSuggestion) -> Self {
+        self.suggestion = Some(suggestion);
+        self
+    }
+
+    /// Fills in the excerpt and token sequence from the source.
+    ///
+    /// The excerpt is the line the error is on, limited to
+    /// [`EXCERPT_CONTEXT`] bytes on either side of the error's span.
+    /// This is only done for errors returned from parsing, since
+    /// most errors produced are discarded when a rule fails.
+    pub(crate) fn add_context(&mut self, tokenization: &Tokenization) {
+        let text = tokenization.full_text().inner();
+        let Range { start, end } = self.span;
+
+        let mut excerpt_start = text[..start].rfind('\n').map_or(0, |i| i + 1);
+        excerpt_start = excerpt_start.max(start.saturating_sub(EXCERPT_CONTEXT));
+        while !text.is_char_boundary(excerpt_start) {
+            excerpt_start += 1;
+        }
+
+        let mut excerpt_end = text[end..].find('\n').map_or(text.len(), |i| end + i);
+        excerpt_end = excerpt_end.min(end.saturating_add(EXCERPT_CONTEXT));
+        while !text.is_char_boundary(excerpt_end) {
+            excerpt_end -= 1;
+        }
+
+        self.excerpt = str!(text[excerpt_start..excerpt_end]);
+        self.tokens = tokenization
+            .tokens()
+            .iter()
+            .filter(|token| {
+                token.span.start >= excerpt_start
+                    && token.span.end <= excerpt_end
+                    && !token.span.is_empty()
+            })
+            .map(|token| token.token)
+            .collect();
+    }
+
     #[inline]
     pub fn token(&self) -> Token {
         self.token
@@ -75,40 +141,98 @@ impl ParseError {
         self.kind
     }
 
+    /// The source surrounding this error, if available.
+    ///
+    /// See [`add_context()`](Self::add_context) for what this contains.
+    #[inline]
+    pub fn excerpt(&self) -> &str {
+        &self.excerpt
+    }
+
+    /// The kinds of the tokens in [`excerpt()`](Self::excerpt), in order.
+    #[inline]
+    pub fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+
+    #[inline]
+    pub fn suggestion(&self) -> Option<&ParseSuggestion> {
+        self.suggestion.as_ref()
+    }
+
     #[must_use]
     pub fn to_utf16_indices(&self, map: &Utf16IndexMap) -> Self {
         // Copy fields
-        let ParseError {
-            token,
-            rule,
-            span,
-            kind,
-        } = self.clone();
+        let mut error = self.clone();
 
         // Map indices to UTF-16
-        let start = map.get_index(span.start);
-        let end = map.get_index(span.end);
-        let span = start..end;
+        error.span = map.get_range(error.span);
 
         // Output new error
-        ParseError {
-            token,
-            rule,
-            span,
-            kind,
+        error
+    }
+}
+
+/// A machine-readable description of how to fix a [`ParseError`].
+///
+/// Editors can use this to offer quick fixes.
+#[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case", tag = "type")]
+pub enum ParseSuggestion {
+    /// The block is never closed, and needs an end block, such as `[[/div]]`.
+    UnclosedBlock { expected: String },
+
+    /// The comment needs to be closed with `--]`.
+    UnclosedComment,
+
+    /// The star (`*`) needs to be removed from the block name.
+    RemoveStar,
+
+    /// The score (`_`) needs to be removed from the block name.
+    RemoveScore,
+}
+
+impl ParseSuggestion {
+    /// Gets the suggestion for errors of this kind, if it needs no other context.
+    fn for_kind(kind: ParseErrorKind) -> Option<Self> {
+        match kind {
+            ParseErrorKind::UnclosedComment => Some(ParseSuggestion::UnclosedComment),
+            ParseErrorKind::BlockDisallowsStar => Some(ParseSuggestion::RemoveStar),
+            ParseErrorKind::BlockDisallowsScore => Some(ParseSuggestion::RemoveScore),
+            _ => None,
         }
     }
 }
 
-#[derive(Serialize, Deserialize, IntoStaticStr, Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(
+    Serialize, Deserialize, IntoStaticStr, Debug, Copy, Clone, Hash, PartialEq, Eq,
+)]
 #[serde(rename_all = "kebab-case")]
 pub enum ParseErrorKind {
     /// The self-enforced recursion limit has been passed, giving up.
     RecursionDepthExceeded,
 
+    /// The work budget for this document has been exhausted, giving up.
+    ///
+    /// Everything parsed up to this point is kept, with the remainder
+    /// of the input added as text. See [`WorkBudget`](crate::WorkBudget).
+    BudgetExceeded,
+
+    /// The parser panicked, so the input was kept as text.
+    ///
+    /// This indicates a bug in ftml, and is only produced by
+    /// [`parse_infallible()`](crate::parsing::parse_infallible).
+    InternalError,
+
     /// Attempting to process this rule failed because the end of input was reached.
     EndOfInput,
 
+    /// This comment was not closed before the end of input.
+    ///
+    /// This is only produced as a warning, the comment is closed
+    /// automatically, hiding the remainder of the input.
+    UnclosedComment,
+
     /// No rules match for these tokens, returning as plain text.
     NoRulesMatch,
 
@@ -158,6 +282,9 @@ pub enum ParseErrorKind {
     TabOutsideTabView,
 
     /// Footnotes are not permitted from inside footnotes.
+    ///
+    /// The contents of the nested footnote are placed
+    /// inline in the enclosing footnote instead.
     FootnotesNested,
 
     /// This native blockquote tries to nest too deeply.
@@ -175,6 +302,9 @@ pub enum ParseErrorKind {
     /// There is no rule for the block name specified.
     NoSuchBlock,
 
+    /// This block has been disabled in the settings.
+    BlockDisabled,
+
     /// This block does not allow star (`*`) invocation.
     BlockDisallowsStar,
 
@@ -202,6 +332,33 @@ pub enum ParseErrorKind {
     /// No embed with this name exists.
     NoSuchEmbed,
 
+    /// This embed's provider is not permitted by the settings.
+    EmbedNotAllowed,
+
+    /// No HTML entity or character with this name exists.
+    ///
+    /// This is only produced as a warning, when lenient `[[char]]` parsing
+    /// is enabled. Otherwise an unknown entity is `BlockMalformedArguments`.
+    NoSuchCharacter,
+
+    /// The number of lines requested exceeds the maximum.
+    ///
+    /// This is only produced as a warning, the number of lines is
+    /// reduced to the `max_line_breaks` setting.
+    TooManyLineBreaks,
+
+    /// The size given to `[[size]]` exceeds the maximum.
+    ///
+    /// This is only produced as a warning, the size is reduced
+    /// to the limit in the `size` setting.
+    SizeClamped,
+
+    /// A link's label is empty or only whitespace.
+    ///
+    /// This is only produced as a warning. The label is derived from the
+    /// link's target instead, or if there is no target, the link is removed.
+    LinkLabelEmpty,
+
     /// This no rule for the module name specified.
     NoSuchModule,
 
@@ -224,3 +381,43 @@ impl ParseErrorKind {
         self.into()
     }
 }
+
+#[test]
+fn context() {
+    use crate::data::PageInfo;
+    use crate::layout::Layout;
+    use crate::settings::{WikitextMode, WikitextSettings};
+
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+
+    // Long lines are truncated around the error
+    let input = format!("{}[[span]]{}\nB", "é".repeat(30), "x".repeat(60));
+    let tokens = crate::tokenize(&input);
+    let (_, errors) = crate::parse(&tokens, &page_info, &settings).into();
+    let error = errors
+        .iter()
+        .find(|error| error.kind() == ParseErrorKind::NoRulesMatch)
+        .expect("No fallback error");
+
+    assert_eq!(error.span(), 60..62);
+    assert_eq!(
+        error.excerpt(),
+        format!("{}[[span]]{}", "é".repeat(20), "x".repeat(34)),
+    );
+    let mut expected_tokens = vec![Token::Other; 20];
+    expected_tokens.extend([Token::LeftBlock, Token::Identifier, Token::RightBlock]);
+    assert_eq!(error.tokens(), expected_tokens);
+    assert_eq!(error.suggestion(), None);
+
+    // Unclosed blocks suggest their end block
+    let tokens = crate::tokenize("[[div]]\nA");
+    let (_, errors) = crate::parse(&tokens, &page_info, &settings).into();
+    let expected = str!("[[/div]]");
+
+    assert_eq!(
+        errors[0].suggestion(),
+        Some(&ParseSuggestion::UnclosedBlock { expected }),
+    );
+    assert_eq!(errors[0].excerpt(), "A");
+}
@@ -0,0 +1,153 @@
+/*
+ * parsing/footnote_block.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use crate::settings::FootnoteBlockPlacement;
+use crate::tree::{ContainerType, Element};
+
+/// Adds an automatic footnote block to the elements, as per the given placement.
+pub fn append_footnote_block(
+    elements: &mut Vec<Element>,
+    placement: &FootnoteBlockPlacement,
+) {
+    let footnote_block = Element::FootnoteBlock {
+        title: None,
+        hide: false,
+    };
+
+    let target = match placement {
+        FootnoteBlockPlacement::None => {
+            debug!("No footnote block in elements, but appending is disabled");
+            return;
+        }
+        FootnoteBlockPlacement::End => None,
+        FootnoteBlockPlacement::LastContainer => match elements.last_mut() {
+            Some(Element::Container(container))
+                if container.ctype() == ContainerType::Div =>
+            {
+                Some(container.elements_mut())
+            }
+            _ => None,
+        },
+        FootnoteBlockPlacement::Container(id) => find_container(elements, id),
+    };
+
+    match target {
+        Some(container_elements) => {
+            debug!("No footnote block in elements, appending one to container");
+            container_elements.push(footnote_block);
+        }
+        None => {
+            debug!("No footnote block in elements, appending one");
+            elements.push(footnote_block);
+        }
+    }
+}
+
+fn find_container<'a, 't>(
+    elements: &'a mut [Element<'t>],
+    id: &str,
+) -> Option<&'a mut Vec<Element<'t>>> {
+    for element in elements {
+        if let Element::Container(container) = element {
+            let matches = container.attributes().get().get("id").map(|v| v.as_ref());
+            if matches == Some(id) {
+                return Some(container.elements_mut());
+            }
+
+            if let Some(found) = find_container(container.elements_mut(), id) {
+                return Some(found);
+            }
+        }
+    }
+
+    None
+}
+
+#[test]
+fn footnote_block_placement() {
+    use crate::tree::{AttributeMap, Container};
+
+    macro_rules! div {
+        ($id:expr, $elements:expr) => {{
+            let mut attributes = AttributeMap::new();
+            if let Some(id) = $id {
+                attributes.insert("id", cow!(id));
+            }
+
+            Element::Container(Container::new(ContainerType::Div, $elements, attributes))
+        }};
+    }
+
+    macro_rules! check {
+        ($placement:expr, $input:expr, $expected:expr $(,)?) => {{
+            let mut elements = $input;
+            append_footnote_block(&mut elements, &$placement);
+            assert_eq!(
+                elements, $expected,
+                "Footnote block placement did not match expected",
+            );
+        }};
+    }
+
+    let block = || Element::FootnoteBlock {
+        title: None,
+        hide: false,
+    };
+
+    check!(
+        FootnoteBlockPlacement::None,
+        vec![text!("A")],
+        vec![text!("A")]
+    );
+    check!(
+        FootnoteBlockPlacement::End,
+        vec![text!("A")],
+        vec![text!("A"), block()],
+    );
+    check!(
+        FootnoteBlockPlacement::LastContainer,
+        vec![text!("A"), div!(None::<&str>, vec![text!("B")])],
+        vec![text!("A"), div!(None::<&str>, vec![text!("B"), block()])],
+    );
+    check!(
+        FootnoteBlockPlacement::LastContainer,
+        vec![div!(None::<&str>, vec![text!("A")]), text!("B")],
+        vec![div!(None::<&str>, vec![text!("A")]), text!("B"), block()],
+    );
+    check!(
+        FootnoteBlockPlacement::Container(str!("notes")),
+        vec![
+            div!(None::<&str>, vec![div!(Some("notes"), vec![text!("A")])]),
+            text!("B"),
+        ],
+        vec![
+            div!(
+                None::<&str>,
+                vec![div!(Some("notes"), vec![text!("A"), block()])]
+            ),
+            text!("B"),
+        ],
+    );
+    check!(
+        FootnoteBlockPlacement::Container(str!("missing")),
+        vec![div!(Some("notes"), vec![text!("A")])],
+        vec![div!(Some("notes"), vec![text!("A")]), block()],
+    );
+}
@@ -29,12 +29,14 @@ mod consume;
 mod depth;
 mod element_condition;
 mod error;
+mod footnote_block;
 mod outcome;
 mod paragraph;
 mod parser;
 mod parser_wrap;
 mod result;
 mod rule;
+mod statistics;
 mod string;
 mod strip;
 mod token;
@@ -49,27 +51,37 @@ mod prelude {
 }
 
 use self::depth::{process_depths, DepthItem, DepthList};
-use self::element_condition::{ElementCondition, ElementConditionType};
-use self::paragraph::{gather_paragraphs, NO_CLOSE_CONDITION};
+use self::footnote_block::append_footnote_block;
+use self::paragraph::{
+    gather_paragraphs, gather_paragraphs_with_spans, NO_CLOSE_CONDITION,
+};
 use self::parser::Parser;
 use self::parser_wrap::ParserWrap;
 use self::rule::impls::RULE_PAGE;
 use self::string::parse_string;
 use self::strip::{strip_newlines, strip_whitespace};
+use crate::budget::WorkBudget;
 use crate::data::PageInfo;
+use crate::includes::ElementsIncluder;
 use crate::next_index::{NextIndex, TableOfContentsIndex};
-use crate::settings::WikitextSettings;
+use crate::settings::{ErrorLevel, WikitextSettings};
 use crate::tokenizer::Tokenization;
 use crate::tree::{
-    AttributeMap, BibliographyList, CodeBlock, Element, LinkLabel, LinkLocation,
-    LinkType, ListItem, ListType, SyntaxTree,
+    build_heading_anchors, heading_anchor, AttributeMap, BibliographyList, CodeBlock,
+    Element, LinkLabel, LinkLocation, LinkType, ListItem, ListType, SyntaxTree,
 };
 use std::borrow::Cow;
+use std::ops::Range;
+use std::panic::{self, AssertUnwindSafe};
 
 pub use self::boolean::{parse_boolean, NonBooleanValue};
-pub use self::error::{ParseError, ParseErrorKind};
+pub use self::element_condition::{ElementCondition, ElementConditionType};
+pub use self::error::{ParseError, ParseErrorKind, ParseSuggestion};
 pub use self::outcome::ParseOutcome;
 pub use self::result::{ParseResult, ParseSuccess};
+pub use self::rule::impls::{block_accepts_score, get_score_block_names};
+pub use self::rule::RuleSet;
+pub use self::statistics::{ParseStatistics, RuleStatistics};
 pub use self::token::{ExtractedToken, Token};
 
 /// Parse through the given tokens and produce an AST.
@@ -83,6 +95,201 @@ pub fn parse<'r, 't>(
 where
     'r: 't,
 {
+    // Apply any settings overlays for this page's category
+    if let Cow::Owned(settings) = settings.resolve(page_info) {
+        let outcome = parse_inner(tokenization, page_info, &settings, None, None);
+        return apply_error_level(outcome_to_owned(outcome), &settings);
+    }
+
+    let outcome = parse_inner(tokenization, page_info, settings, None, None);
+    apply_error_level(outcome, settings)
+}
+
+/// Parse through the given tokens, spending from the given work budget.
+///
+/// Each token the parser steps over costs one unit, including any repeated
+/// steps from backtracking. If the budget is exceeded, parsing stops, and the
+/// syntax tree contains whatever was parsed so far, followed by the rest of the
+/// input as text, with a `budget-exceeded` error. The budget remains exhausted,
+/// so any later stages will fail. See [`WorkBudget`] for more information.
+pub fn parse_with_budget<'r, 't>(
+    tokenization: &'r Tokenization<'t>,
+    page_info: &'r PageInfo<'t>,
+    settings: &'r WikitextSettings,
+    budget: &'r WorkBudget,
+) -> ParseOutcome<SyntaxTree<'t>>
+where
+    'r: 't,
+{
+    // Apply any settings overlays for this page's category
+    let resolved = settings.resolve(page_info);
+    let outcome = match resolved {
+        Cow::Owned(ref settings) => outcome_to_owned(parse_inner(
+            tokenization,
+            page_info,
+            settings,
+            Some(budget),
+            None,
+        )),
+        Cow::Borrowed(settings) => {
+            parse_inner(tokenization, page_info, settings, Some(budget), None)
+        }
+    };
+
+    apply_error_level(outcome, &resolved)
+}
+
+/// Parse through the given tokens, grafting in pre-parsed pages for `[[include-elements]]`.
+///
+/// Each included page's syntax tree is requested from the given [`ElementsIncluder`],
+/// rather than the page being parsed again. Without an includer, as in [`parse()`],
+/// these blocks produce placeholder text.
+pub fn parse_with_includer<'r, 't>(
+    tokenization: &'r Tokenization<'t>,
+    page_info: &'r PageInfo<'t>,
+    settings: &'r WikitextSettings,
+    includer: &'r dyn ElementsIncluder,
+) -> ParseOutcome<SyntaxTree<'t>>
+where
+    'r: 't,
+{
+    // Apply any settings overlays for this page's category
+    if let Cow::Owned(settings) = settings.resolve(page_info) {
+        let outcome =
+            parse_inner(tokenization, page_info, &settings, None, Some(includer));
+        return apply_error_level(outcome_to_owned(outcome), &settings);
+    }
+
+    let outcome = parse_inner(tokenization, page_info, settings, None, Some(includer));
+    apply_error_level(outcome, settings)
+}
+
+/// Parse through the given tokens, without panicking.
+///
+/// This is the same as [`parse()`], except that if the parser panics, which
+/// would be a bug, the input is kept as text with an
+/// [`InternalError`](ParseErrorKind::InternalError) error rather than the
+/// panic unwinding into the caller. This is intended for callers which cannot
+/// recover from a panic, such as across the FFI boundary. Parsing is
+/// deterministic, so the same input always produces the same result.
+///
+/// Panics can only be caught if the crate is built with `panic = "unwind"`.
+pub fn parse_infallible<'r, 't>(
+    tokenization: &'r Tokenization<'t>,
+    page_info: &'r PageInfo<'t>,
+    settings: &'r WikitextSettings,
+) -> ParseOutcome<SyntaxTree<'t>>
+where
+    'r: 't,
+{
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        parse(tokenization, page_info, settings)
+    }));
+
+    match result {
+        Ok(outcome) => outcome,
+        Err(_) => {
+            error!("Parser panicked, returning input as text");
+
+            let token = tokenization
+                .tokens()
+                .first()
+                .expect("Parsed tokens list was empty (expected at least one element)");
+
+            let mut error =
+                ParseError::new(ParseErrorKind::InternalError, RULE_PAGE, token);
+            error.add_context(tokenization);
+
+            let wikitext = tokenization.full_text().inner();
+            SyntaxTree::from_element_result(
+                (vec![text!(wikitext)], vec![]),
+                vec![error],
+                (vec![], vec![]),
+                (vec![], vec![]),
+                vec![],
+                BibliographyList::new(),
+                wikitext.len(),
+            )
+        }
+    }
+}
+
+/// Detaches a syntax tree from the settings used to parse it.
+///
+/// Parsing borrows the settings for as long as the tree, so when they
+/// are resolved from overlays, the tree must be converted to be owned.
+fn outcome_to_owned(
+    outcome: ParseOutcome<SyntaxTree>,
+) -> ParseOutcome<SyntaxTree<'static>> {
+    let statistics = outcome.statistics().cloned();
+    let (tree, errors) = outcome.into();
+    ParseOutcome::new(tree.to_owned(), errors).with_statistics(statistics)
+}
+
+/// Adjusts the outcome of parsing according to the [`ErrorLevel`] setting.
+///
+/// Suppressed errors are removed first, and downgraded ones
+/// do not cause the syntax tree to be rejected.
+fn apply_error_level<'t>(
+    outcome: ParseOutcome<SyntaxTree<'t>>,
+    settings: &WikitextSettings,
+) -> ParseOutcome<SyntaxTree<'t>> {
+    let error_kinds = &settings.error_kinds;
+    let outcome = if error_kinds.suppress.is_empty() {
+        outcome
+    } else {
+        let statistics = outcome.statistics().cloned();
+        let (tree, mut errors) = outcome.into();
+        errors.retain(|error| !error_kinds.is_suppressed(error.kind()));
+        ParseOutcome::new(tree, errors).with_statistics(statistics)
+    };
+
+    match settings.error_level {
+        ErrorLevel::Warn => outcome,
+        ErrorLevel::Lenient => {
+            let statistics = outcome.statistics().cloned();
+            let (tree, _) = outcome.into();
+            ParseOutcome::new(tree, vec![]).with_statistics(statistics)
+        }
+        ErrorLevel::Strict
+            if outcome
+                .errors()
+                .iter()
+                .all(|error| error_kinds.is_downgraded(error.kind())) =>
+        {
+            outcome
+        }
+        ErrorLevel::Strict => {
+            let statistics = outcome.statistics().cloned();
+            let (tree, errors) = outcome.into();
+
+            info!(
+                "Rejecting syntax tree in strict mode ({} errors)",
+                errors.len(),
+            );
+
+            let tree = SyntaxTree {
+                wikitext_len: tree.wikitext_len,
+                ..SyntaxTree::default()
+            };
+
+            ParseOutcome::new(tree, errors).with_statistics(statistics)
+        }
+    }
+}
+
+fn parse_inner<'r, 't>(
+    tokenization: &'r Tokenization<'t>,
+    page_info: &'r PageInfo<'t>,
+    settings: &'r WikitextSettings,
+    budget: Option<&'r WorkBudget>,
+    includer: Option<&'r dyn ElementsIncluder>,
+) -> ParseOutcome<SyntaxTree<'t>>
+where
+    'r: 't,
+{
+    enter_span!("parse", site = %page_info.site, page = %page_info.page);
+
     // Run parsing, get raw results
     let UnstructuredParseResult {
         result,
@@ -92,16 +299,18 @@ where
         footnotes,
         has_footnote_block,
         bibliographies,
-    } = parse_internal(page_info, settings, tokenization);
+        element_spans,
+        statistics,
+    } = parse_unstructured(page_info, settings, tokenization, budget, includer);
 
     // For producing table of contents indexes
     let mut incrementer = Incrementer(0);
 
     debug!("Finished paragraph gathering, matching on consumption");
-    match result {
+    let outcome = match result {
         Ok(ParseSuccess {
             item: mut elements,
-            errors,
+            mut errors,
             ..
         }) => {
             debug!(
@@ -109,6 +318,17 @@ where
                 errors.len(),
             );
 
+            // Derive heading IDs from their names, if requested
+            let heading_anchors = if settings.slugify_heading_ids {
+                build_heading_anchors(
+                    table_of_contents_depths
+                        .iter()
+                        .map(|(_, name)| name.as_str()),
+                )
+            } else {
+                vec![]
+            };
+
             // process_depths() wants a "list type", so we map in a () for each.
             let table_of_contents_depths = table_of_contents_depths
                 .into_iter()
@@ -117,25 +337,27 @@ where
             // Convert TOC depth lists
             let table_of_contents = process_depths((), table_of_contents_depths)
                 .into_iter()
-                .map(|(_, items)| build_toc_list_element(&mut incrementer, items))
+                .map(|(_, items)| {
+                    build_toc_list_element(&mut incrementer, &heading_anchors, items)
+                })
                 .collect::<Vec<_>>();
 
-            // Add a footnote block at the end,
+            // Add source excerpts for the errors which remain
+            for error in &mut errors {
+                error.add_context(tokenization);
+            }
+
+            // Add a footnote block,
             // if the user doesn't have one already
             if !has_footnote_block {
-                debug!("No footnote block in elements, appending one");
-
-                elements.push(Element::FootnoteBlock {
-                    title: None,
-                    hide: false,
-                });
+                append_footnote_block(&mut elements, &settings.footnote_block);
             }
 
             SyntaxTree::from_element_result(
-                elements,
+                (elements, element_spans),
                 errors,
                 (html_blocks, code_blocks),
-                table_of_contents,
+                (table_of_contents, heading_anchors),
                 footnotes,
                 bibliographies,
                 tokenization.full_text().len(),
@@ -150,22 +372,27 @@ where
             error!("Fatal error occurred at highest-level parsing: {error:#?}");
             let wikitext = tokenization.full_text().inner();
             let elements = vec![text!(wikitext)];
-            let errors = vec![error];
+            let mut errors = vec![error];
+            errors[0].add_context(tokenization);
             let table_of_contents = vec![];
+            let heading_anchors = vec![];
             let footnotes = vec![];
             let bibliographies = BibliographyList::new();
+            let element_spans = vec![];
 
             SyntaxTree::from_element_result(
-                elements,
+                (elements, element_spans),
                 errors,
                 (html_blocks, code_blocks),
-                table_of_contents,
+                (table_of_contents, heading_anchors),
                 footnotes,
                 bibliographies,
                 tokenization.full_text().len(),
             )
         }
-    }
+    };
+
+    outcome.with_statistics(statistics)
 }
 
 /// Runs the parser, but returns the raw internal results prior to conversion.
@@ -174,14 +401,35 @@ pub fn parse_internal<'r, 't>(
     settings: &'r WikitextSettings,
     tokenization: &'r Tokenization<'t>,
 ) -> UnstructuredParseResult<'r, 't>
+where
+    'r: 't,
+{
+    parse_unstructured(page_info, settings, tokenization, None, None)
+}
+
+fn parse_unstructured<'r, 't>(
+    page_info: &'r PageInfo<'t>,
+    settings: &'r WikitextSettings,
+    tokenization: &'r Tokenization<'t>,
+    budget: Option<&'r WorkBudget>,
+    includer: Option<&'r dyn ElementsIncluder>,
+) -> UnstructuredParseResult<'r, 't>
 where
     'r: 't,
 {
     let mut parser = Parser::new(tokenization, page_info, settings);
+    parser.set_budget(budget);
+    parser.set_elements_includer(includer);
 
     // At the top level, we gather elements into paragraphs
     info!("Running parser on {} tokens", tokenization.tokens().len());
-    let result = gather_paragraphs(&mut parser, RULE_PAGE, NO_CLOSE_CONDITION);
+    let (result, mut element_spans) =
+        gather_paragraphs_with_spans(&mut parser, RULE_PAGE, NO_CLOSE_CONDITION);
+
+    // Only retain spans if they were requested
+    if !settings.source_spans {
+        element_spans.clear();
+    }
 
     // Build and return
     let html_blocks = parser.remove_html_blocks();
@@ -190,6 +438,7 @@ where
     let footnotes = parser.remove_footnotes();
     let has_footnote_block = parser.has_footnote_block();
     let bibliographies = parser.remove_bibliographies();
+    let statistics = parser.remove_statistics();
 
     UnstructuredParseResult {
         result,
@@ -199,6 +448,8 @@ where
         footnotes,
         has_footnote_block,
         bibliographies,
+        element_spans,
+        statistics,
     }
 }
 
@@ -206,14 +457,15 @@ where
 
 fn build_toc_list_element(
     incr: &mut Incrementer,
+    anchors: &[String],
     list: DepthList<(), String>,
 ) -> Element<'static> {
     let build_item = |item| match item {
         DepthItem::List(_, list) => ListItem::SubList {
-            element: Box::new(build_toc_list_element(incr, list)),
+            element: Box::new(build_toc_list_element(incr, anchors, list)),
         },
         DepthItem::Item(name) => {
-            let anchor = format!("#toc{}", incr.next());
+            let anchor = format!("#{}", heading_anchor(anchors, incr.next()));
             let link = Element::Link {
                 ltype: LinkType::TableOfContents,
                 link: LinkLocation::Url(Cow::Owned(anchor)),
@@ -282,4 +534,155 @@ pub struct UnstructuredParseResult<'r, 't> {
     ///
     /// See `src/tree/bibliography.rs`.
     pub bibliographies: BibliographyList<'t>,
+
+    /// The source spans of each top-level element.
+    ///
+    /// This is only populated if `source_spans` is enabled in the settings.
+    pub element_spans: Vec<Range<usize>>,
+
+    /// Statistics about the parse.
+    ///
+    /// This is only present if `collect_stats` is enabled in the settings.
+    pub statistics: Option<ParseStatistics>,
+}
+
+#[test]
+fn error_level() {
+    use crate::layout::Layout;
+    use crate::settings::WikitextMode;
+
+    let page_info = PageInfo::dummy();
+    let mut settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+
+    macro_rules! check {
+        ($text:expr, $error_level:expr, $has_elements:expr, $error_count:expr $(,)?) => {{
+            settings.error_level = $error_level;
+
+            let tokens = crate::tokenize($text);
+            let (tree, errors) = parse(&tokens, &page_info, &settings).into();
+
+            assert_eq!(
+                !tree.elements.is_empty(),
+                $has_elements,
+                "Presence of elements for {:?} at {:?} doesn't match expected",
+                $text,
+                $error_level,
+            );
+            assert_eq!(
+                errors.len(),
+                $error_count,
+                "Error count for {:?} at {:?} doesn't match expected",
+                $text,
+                $error_level,
+            );
+        }};
+    }
+
+    let valid = "**Apple**";
+    let invalid = "[[div]]\nBanana";
+
+    check!(valid, ErrorLevel::Lenient, true, 0);
+    check!(valid, ErrorLevel::Warn, true, 0);
+    check!(valid, ErrorLevel::Strict, true, 0);
+    check!(invalid, ErrorLevel::Lenient, true, 0);
+    check!(invalid, ErrorLevel::Warn, true, 3);
+    check!(invalid, ErrorLevel::Strict, false, 3);
+}
+
+#[test]
+fn error_kinds() {
+    use crate::layout::Layout;
+    use crate::settings::WikitextMode;
+
+    let page_info = PageInfo::dummy();
+    let mut settings =
+        WikitextSettings::from_mode(WikitextMode::ForumPost, Layout::Wikidot);
+
+    macro_rules! check {
+        ($error_level:expr, $has_elements:expr, $errors:expr $(,)?) => {{
+            settings.error_level = $error_level;
+
+            let tokens = crate::tokenize("[[toc]]\nApple");
+            let (tree, errors) = parse(&tokens, &page_info, &settings).into();
+            let error_kinds: Vec<_> = errors.iter().map(|error| error.kind()).collect();
+
+            assert_eq!(
+                !tree.elements.is_empty(),
+                $has_elements,
+                "Presence of elements at {:?} doesn't match expected",
+                $error_level,
+            );
+            assert_eq!(
+                error_kinds, $errors,
+                "Errors at {:?} don't match expected",
+                $error_level,
+            );
+        }};
+    }
+
+    let reported = vec![
+        ParseErrorKind::NotSupportedMode,
+        ParseErrorKind::NoRulesMatch,
+        ParseErrorKind::NoRulesMatch,
+    ];
+
+    // Default, reported normally
+    check!(ErrorLevel::Warn, true, reported);
+    check!(ErrorLevel::Strict, false, reported);
+
+    // Downgraded, reported but does not reject
+    settings.error_kinds.downgrade = vec![
+        ParseErrorKind::NotSupportedMode,
+        ParseErrorKind::NoRulesMatch,
+    ];
+    check!(ErrorLevel::Warn, true, reported);
+    check!(ErrorLevel::Strict, true, reported);
+
+    // Partially downgraded, rejects
+    settings.error_kinds.downgrade = vec![ParseErrorKind::NotSupportedMode];
+    check!(ErrorLevel::Strict, false, reported);
+
+    // Suppressed, not reported at all
+    settings.error_kinds.suppress = vec![ParseErrorKind::NoRulesMatch];
+    check!(
+        ErrorLevel::Warn,
+        true,
+        vec![ParseErrorKind::NotSupportedMode]
+    );
+    check!(
+        ErrorLevel::Strict,
+        true,
+        vec![ParseErrorKind::NotSupportedMode]
+    );
+}
+
+#[test]
+fn infallible() {
+    use crate::layout::Layout;
+    use crate::settings::WikitextMode;
+
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+
+    for input in [
+        "",
+        "**Apple** [[span]]banana",
+        "[[[[[[[[[[ ]]]]]]]]",
+        "[[*collapsible_ show=\"+\"]]\n* A\n[[/collapsible]]",
+        "[[footnote]][[footnote]]A[[/footnote]][[/footnote]]",
+    ] {
+        let tokens = crate::tokenize(input);
+        let outcome = parse(&tokens, &page_info, &settings);
+        let infallible = parse_infallible(&tokens, &page_info, &settings);
+
+        assert_eq!(
+            infallible, outcome,
+            "Infallible parse differs from regular parse",
+        );
+        assert_eq!(
+            parse_infallible(&tokens, &page_info, &settings),
+            infallible,
+            "Parsing is not deterministic",
+        );
+    }
 }
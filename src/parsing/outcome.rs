@@ -18,13 +18,16 @@
  * along with this program. If not, see <http://www.gnu.org/licenses/>.
  */
 
-use super::ParseError;
+use super::{ParseError, ParseStatistics};
 use std::borrow::{Borrow, BorrowMut};
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub struct ParseOutcome<T> {
     value: T,
     errors: Vec<ParseError>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    statistics: Option<ParseStatistics>,
 }
 
 impl<T> ParseOutcome<T> {
@@ -36,9 +39,17 @@ impl<T> ParseOutcome<T> {
         ParseOutcome {
             value,
             errors: errors.into(),
+            statistics: None,
         }
     }
 
+    /// Attaches statistics about the parse which produced this outcome.
+    #[inline]
+    pub fn with_statistics(mut self, statistics: Option<ParseStatistics>) -> Self {
+        self.statistics = statistics;
+        self
+    }
+
     // Getters
     #[inline]
     pub fn value(&self) -> &T {
@@ -49,6 +60,12 @@ impl<T> ParseOutcome<T> {
     pub fn errors(&self) -> &[ParseError] {
         &self.errors
     }
+
+    /// Statistics about the parse, if `collect_stats` was enabled in the settings.
+    #[inline]
+    pub fn statistics(&self) -> Option<&ParseStatistics> {
+        self.statistics.as_ref()
+    }
 }
 
 impl<U> ParseOutcome<Vec<U>> {
@@ -67,6 +84,7 @@ where
         ParseOutcome {
             value: self.value.clone(),
             errors: self.errors.clone(),
+            statistics: self.statistics.clone(),
         }
     }
 }
@@ -80,6 +98,7 @@ where
         ParseOutcome {
             value: T::default(),
             errors: Vec::new(),
+            statistics: None,
         }
     }
 }
@@ -101,7 +120,7 @@ impl<T> BorrowMut<T> for ParseOutcome<T> {
 impl<T> From<ParseOutcome<T>> for (T, Vec<ParseError>) {
     #[inline]
     fn from(outcome: ParseOutcome<T>) -> (T, Vec<ParseError>) {
-        let ParseOutcome { value, errors } = outcome;
+        let ParseOutcome { value, errors, .. } = outcome;
 
         (value, errors)
     }
@@ -27,6 +27,7 @@ use super::parser::Parser;
 use super::prelude::*;
 use super::rule::Rule;
 use super::token::Token;
+use std::ops::Range;
 
 /// Wrapper type to satisfy the issue with generic closure types.
 ///
@@ -51,8 +52,42 @@ type CloseConditionFn = fn(&mut Parser) -> Result<bool, ParseError>;
 pub fn gather_paragraphs<'r, 't, F>(
     parser: &mut Parser<'r, 't>,
     rule: Rule,
-    mut close_condition_fn: Option<F>,
+    close_condition_fn: Option<F>,
 ) -> ParseResult<'r, 't, Vec<Element<'t>>>
+where
+    'r: 't,
+    F: FnMut(&mut Parser<'r, 't>) -> Result<bool, ParseError>,
+{
+    gather_paragraph_stack(parser, rule, close_condition_fn)?.into_result()
+}
+
+/// Like [`gather_paragraphs`], but also returns the source span of each element.
+///
+/// Each span is the byte range of the tokens which produced the element
+/// at the same index in the returned list. If parsing fails, no spans are returned.
+pub fn gather_paragraphs_with_spans<'r, 't, F>(
+    parser: &mut Parser<'r, 't>,
+    rule: Rule,
+    close_condition_fn: Option<F>,
+) -> (ParseResult<'r, 't, Vec<Element<'t>>>, Vec<Range<usize>>)
+where
+    'r: 't,
+    F: FnMut(&mut Parser<'r, 't>) -> Result<bool, ParseError>,
+{
+    match gather_paragraph_stack(parser, rule, close_condition_fn) {
+        Ok(mut stack) => {
+            let spans = stack.take_spans();
+            (stack.into_result(), spans)
+        }
+        Err(error) => (Err(error), vec![]),
+    }
+}
+
+fn gather_paragraph_stack<'r, 't, F>(
+    parser: &mut Parser<'r, 't>,
+    rule: Rule,
+    mut close_condition_fn: Option<F>,
+) -> Result<ParagraphStack<'t>, ParseError>
 where
     'r: 't,
     F: FnMut(&mut Parser<'r, 't>) -> Result<bool, ParseError>,
@@ -66,7 +101,8 @@ where
     let mut stack = ParagraphStack::new();
 
     loop {
-        let (elements, mut errors, paragraph_safe) = match parser.current().token {
+        let start = parser.current().span.start;
+        let result = match parser.current().token {
             Token::InputEnd => {
                 if close_condition_fn.is_some() {
                     // There was a close condition, but it was not satisfied
@@ -94,8 +130,10 @@ where
 
                 // We must manually bump up this pointer because
                 // we 'continue' here, skipping the usual pointer update.
-                parser.step()?;
-                continue;
+                match parser.step() {
+                    Ok(_) => continue,
+                    Err(error) => Err(error),
+                }
             }
 
             // Determine if we're ending the paragraph here,
@@ -112,11 +150,39 @@ where
                 trace!("Trying to consume tokens to produce element");
                 consume(parser)
             }
-        }?
-        .into();
+        };
+
+        let (elements, mut errors, paragraph_safe) = match result {
+            Ok(success) => success.into(),
+
+            // If the work budget runs out while gathering a whole document,
+            // then keep what has been parsed, and add the rest of the input as text.
+            Err(error)
+                if error.kind() == ParseErrorKind::BudgetExceeded
+                    && close_condition_fn.is_none() =>
+            {
+                warn!("Work budget exceeded, adding remaining input as text");
+
+                let start = parser.current().span.start;
+                let text = &parser.full_text().inner()[start..];
+                if !text.is_empty() {
+                    stack.set_span(start..start + text.len());
+                    push_elements(&mut stack, Elements::Single(text!(text)), true);
+                }
+
+                stack.push_errors(&mut vec![error]);
+                break;
+            }
+
+            Err(error) => return Err(error),
+        };
 
         trace!("Tokens consumed to produce element");
 
+        // Record the source span of the consumed tokens.
+        // Tokens are contiguous, so this ends where the next one begins.
+        stack.set_span(start..parser.current().span.start);
+
         // Add new elements to the list
         push_elements(&mut stack, elements, paragraph_safe);
 
@@ -124,7 +190,7 @@ where
         stack.push_errors(&mut errors);
     }
 
-    stack.into_result()
+    Ok(stack)
 }
 
 fn push_elements<'t>(
@@ -21,6 +21,7 @@
 use crate::parsing::prelude::*;
 use crate::tree::{AttributeMap, Container, ContainerType};
 use std::mem;
+use std::ops::Range;
 
 #[derive(Debug, Default)]
 pub struct ParagraphStack<'t> {
@@ -32,6 +33,19 @@ pub struct ParagraphStack<'t> {
 
     /// Gathered errors from paragraph parsing.
     errors: Vec<ParseError>,
+
+    /// The source span of the elements about to be pushed.
+    ///
+    /// Only set by callers which track spans, see [`ParagraphStack::set_span`].
+    span: Option<Range<usize>>,
+
+    /// The source span covered by elements in the current paragraph.
+    current_span: Option<Range<usize>>,
+
+    /// The source spans of each element in `finished`.
+    ///
+    /// This is only meaningful if a span was set before every push.
+    spans: Vec<Range<usize>>,
 }
 
 impl<'t> ParagraphStack<'t> {
@@ -50,6 +64,12 @@ impl<'t> ParagraphStack<'t> {
         self.current.reserve(additional);
     }
 
+    /// Sets the source span for subsequently-pushed elements.
+    #[inline]
+    pub fn set_span(&mut self, span: Range<usize>) {
+        self.span = Some(span);
+    }
+
     #[inline]
     pub fn push_element(&mut self, element: Element<'t>, paragraph_safe: bool) {
         debug!(
@@ -61,11 +81,20 @@ impl<'t> ParagraphStack<'t> {
         if paragraph_safe {
             // Add it to the current (or new) paragraph. Nothing special.
             self.current.push(element);
+
+            // Extend the paragraph's span to cover this element
+            if let Some(ref span) = self.span {
+                self.current_span = match self.current_span.take() {
+                    Some(current) => Some(current.start..span.end),
+                    None => Some(span.clone()),
+                };
+            }
         } else {
             // This has to be its own "finished" element, outside of any
             // paragraph wrapper. So finish up what we have, then add this element.
             self.end_paragraph();
             self.finished.push(element);
+            self.spans.push(self.span.clone().unwrap_or_default());
         }
     }
 
@@ -116,11 +145,22 @@ impl<'t> ParagraphStack<'t> {
     pub fn end_paragraph(&mut self) {
         trace!("Ending the current paragraph to push as a completed element");
 
+        let span = self.current_span.take();
         if let Some(paragraph) = self.build_paragraph() {
             self.finished.push(paragraph);
+            self.spans.push(span.unwrap_or_default());
         }
     }
 
+    /// Removes the source spans of all finished elements.
+    ///
+    /// The returned list is parallel to the final element list, provided
+    /// that a span was set before each element was pushed.
+    pub fn take_spans(&mut self) -> Vec<Range<usize>> {
+        self.end_paragraph();
+        mem::take(&mut self.spans)
+    }
+
     /// Convert all paragraph context into a `ParseResult.`
     ///
     /// This returns all collected elements, errors, and returns the final
@@ -133,9 +173,9 @@ impl<'t> ParagraphStack<'t> {
 
         // Deconstruct stack
         let ParagraphStack {
-            current: _,
             finished: elements,
             errors,
+            ..
         } = self;
 
         // If this has any paragraphs in it, or other incompatible elements,
@@ -20,16 +20,20 @@
 
 use super::condition::ParseCondition;
 use super::prelude::*;
-use super::rule::Rule;
+use super::rule::{Rule, RuleSet};
+use super::statistics::{ParseStatistics, RuleTimer};
 use super::RULE_PAGE;
+use crate::budget::WorkBudget;
 use crate::data::PageInfo;
+use crate::includes::ElementsIncluder;
 use crate::render::text::TextRender;
+use crate::settings::InterwikiSettings;
 use crate::tokenizer::Tokenization;
 use crate::tree::{
     AcceptsPartial, Bibliography, BibliographyList, CodeBlock, HeadingLevel,
 };
 use std::borrow::Cow;
-use std::cell::RefCell;
+use std::cell::{Ref, RefCell};
 use std::rc::Rc;
 use std::{mem, ptr};
 
@@ -51,6 +55,15 @@ pub struct Parser<'r, 't> {
     rule: Rule,
     depth: usize,
 
+    // Shared limit on total work, if any
+    budget: Option<&'r WorkBudget>,
+
+    // Source of pre-parsed pages for [[include-elements]], if any
+    elements_includer: Option<&'r dyn ElementsIncluder>,
+
+    // Parse statistics, if requested in the settings
+    statistics: Option<Rc<RefCell<ParseStatistics>>>,
+
     // Table of Contents
     //
     // Schema: Vec<(depth, _, name)>
@@ -79,6 +92,15 @@ pub struct Parser<'r, 't> {
     // overriding later ones.
     bibliographies: Rc<RefCell<BibliographyList<'t>>>,
 
+    // Interwiki prefixes
+    //
+    // Initially a copy of the prefixes in the settings,
+    // but [[interwiki]] blocks may add more as parsing proceeds.
+    interwiki: Rc<RefCell<InterwikiSettings>>,
+
+    // Which rules may be used, per the settings
+    rules: Rc<RuleSet>,
+
     // Flags
     accepts_partial: AcceptsPartial,
     in_footnote: bool, // Whether we're currently inside [[footnote]] ... [[/footnote]].
@@ -102,6 +124,13 @@ impl<'r, 't> Parser<'r, 't> {
             .split_first()
             .expect("Parsed tokens list was empty (expected at least one element)");
 
+        let statistics = if settings.collect_stats {
+            let statistics = ParseStatistics::new(tokenization.tokens().len());
+            Some(Rc::new(RefCell::new(statistics)))
+        } else {
+            None
+        };
+
         Parser {
             page_info,
             settings,
@@ -110,11 +139,16 @@ impl<'r, 't> Parser<'r, 't> {
             full_text,
             rule: RULE_PAGE,
             depth: 0,
+            budget: None,
+            elements_includer: None,
+            statistics,
             table_of_contents: make_shared_vec(),
             html_blocks: make_shared_vec(),
             code_blocks: make_shared_vec(),
             footnotes: make_shared_vec(),
             bibliographies: Rc::new(RefCell::new(BibliographyList::new())),
+            interwiki: Rc::new(RefCell::new(settings.interwiki.clone())),
+            rules: Rc::new(RuleSet::from_settings(settings)),
             accepts_partial: AcceptsPartial::None,
             in_footnote: false,
             has_footnote_block: false,
@@ -133,6 +167,21 @@ impl<'r, 't> Parser<'r, 't> {
         self.settings
     }
 
+    #[inline]
+    pub fn elements_includer(&self) -> Option<&'r dyn ElementsIncluder> {
+        self.elements_includer
+    }
+
+    #[inline]
+    pub fn interwiki(&self) -> Ref<'_, InterwikiSettings> {
+        self.interwiki.borrow()
+    }
+
+    #[inline]
+    pub fn rules(&self) -> Rc<RuleSet> {
+        Rc::clone(&self.rules)
+    }
+
     #[inline]
     pub fn full_text(&self) -> FullText<'t> {
         self.full_text
@@ -179,6 +228,10 @@ impl<'r, 't> Parser<'r, 't> {
         self.depth += 1;
         trace!("Incrementing recursion depth to {}", self.depth);
 
+        if let Some(ref statistics) = self.statistics {
+            statistics.borrow_mut().add_depth(self.depth);
+        }
+
         if self.depth > MAX_RECURSION_DEPTH {
             return Err(self.make_err(ParseErrorKind::RecursionDepthExceeded));
         }
@@ -247,6 +300,28 @@ impl<'r, 't> Parser<'r, 't> {
         mem::take(&mut self.table_of_contents.borrow_mut())
     }
 
+    // Statistics
+    /// Starts timing a rule, if statistics are being collected.
+    #[inline]
+    pub fn start_rule_timer(&self) -> Option<RuleTimer> {
+        self.statistics.as_ref().map(|_| RuleTimer::start())
+    }
+
+    pub fn record_rule(&self, rule: Rule, success: bool, timer: Option<RuleTimer>) {
+        if let (Some(statistics), Some(timer)) = (&self.statistics, timer) {
+            statistics
+                .borrow_mut()
+                .add_rule(rule.name(), success, timer.elapsed());
+        }
+    }
+
+    #[cold]
+    pub fn remove_statistics(&mut self) -> Option<ParseStatistics> {
+        self.statistics
+            .as_ref()
+            .map(|statistics| mem::take(&mut *statistics.borrow_mut()))
+    }
+
     // Footnotes
     pub fn push_footnote(&mut self, contents: Vec<Element<'t>>) {
         self.footnotes.borrow_mut().push(contents);
@@ -298,6 +373,16 @@ impl<'r, 't> Parser<'r, 't> {
         mem::take(&mut self.bibliographies.borrow_mut())
     }
 
+    // Interwiki
+    pub fn push_interwiki_prefix(&mut self, prefix: String, url: String) {
+        debug!("Adding interwiki prefix '{prefix}' => '{url}'");
+
+        self.interwiki
+            .borrow_mut()
+            .prefixes
+            .insert(Cow::Owned(prefix), Cow::Owned(url));
+    }
+
     // Special for [[include]], appending a SyntaxTree
     pub fn append_shared_items(
         &mut self,
@@ -431,11 +516,35 @@ impl<'r, 't> Parser<'r, 't> {
         ptr::eq(self.remaining, old_remaining)
     }
 
+    #[inline]
+    pub fn set_budget(&mut self, budget: Option<&'r WorkBudget>) {
+        self.budget = budget;
+    }
+
+    #[inline]
+    pub fn set_elements_includer(
+        &mut self,
+        elements_includer: Option<&'r dyn ElementsIncluder>,
+    ) {
+        self.elements_includer = elements_includer;
+    }
+
     /// Move the token pointer forward one step.
     #[inline]
     pub fn step(&mut self) -> Result<&'r ExtractedToken<'t>, ParseError> {
         trace!("Stepping to the next token");
 
+        // Spend from the work budget, if any.
+        if let Some(budget) = self.budget {
+            if budget.spend(1).is_err() {
+                return Err(self.make_err(ParseErrorKind::BudgetExceeded));
+            }
+        }
+
+        if let Some(ref statistics) = self.statistics {
+            statistics.borrow_mut().add_step();
+        }
+
         // Set the start-of-line flag.
         self.start_of_line = matches!(
             self.current.token,
@@ -57,8 +57,9 @@ fn try_consume_fn<'r, 't>(
     )?;
 
     // Isolate ID if requested
-    let name = if parser.settings().isolate_user_ids {
-        Cow::Owned(isolate_ids(name))
+    let settings = parser.settings();
+    let name = if settings.isolate_user_ids {
+        Cow::Owned(isolate_ids(name, &settings.id_isolation.prefix))
     } else {
         cow!(name)
     };
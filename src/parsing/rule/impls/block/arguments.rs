@@ -112,8 +112,8 @@ impl<'t> Arguments<'t> {
     /// if that is enabled, and so needs `WikitextSettings` to be passed in.
     #[inline]
     pub fn to_attribute_map(&self, settings: &WikitextSettings) -> AttributeMap<'t> {
-        let mut map = AttributeMap::from_arguments(&self.inner);
-        map.isolate_id(settings);
+        let mut map = AttributeMap::from_arguments(&self.inner, settings);
+        map.isolate_ids(settings);
         map
     }
 }
@@ -31,7 +31,7 @@ macro_rules! make_align_block {
             name: $block_name,
             accepts_names: &[$symbol],
             accepts_star: false,
-            accepts_score: false,
+            accepts_score: true,
             accepts_newlines: true,
             parse_fn,
         };
@@ -64,20 +64,20 @@ pub fn parse_alignment_block<'r, 't>(
     in_head: bool,
 ) -> ParseResult<'r, 't, Elements<'t>> {
     debug!(
-        "Parsing alignment block (name '{}', block-rule '{}', alignment '{}', in-head {})",
+        "Parsing alignment block (name '{}', block-rule '{}', alignment '{}', in-head {}, score {})",
         name,
         block_rule.name,
         alignment.name(),
         in_head,
+        flag_score,
     );
     assert!(!flag_star, "Alignment block doesn't allow star flag");
-    assert!(!flag_score, "Alignment block doesn't allow score flag");
-    assert_block_name(block_rule, name);
+    check_block_name(parser, block_rule, name)?;
 
     parser.get_head_none(block_rule, in_head)?;
 
-    // Get body content, with paragraphs
-    let (elements, errors, _) = parser.get_body_elements(block_rule, true)?.into();
+    // Get body content, with paragraphs unless the score flag is set
+    let (elements, errors, _) = parser.get_body_elements(block_rule, !flag_score)?.into();
 
     // Build element
     let element = Element::Container(Container::new(
@@ -39,7 +39,7 @@ fn parse_fn<'r, 't>(
     in_head: bool,
 ) -> ParseResult<'r, 't, Elements<'t>> {
     debug!("Parsing anchor block (name '{name}', in-head {in_head}, star {flag_star})");
-    assert_block_name(&BLOCK_ANCHOR, name);
+    check_block_name(parser, &BLOCK_ANCHOR, name)?;
 
     let arguments = parser.get_head_map(&BLOCK_ANCHOR, in_head)?;
     let attributes = arguments.to_attribute_map(parser.settings());
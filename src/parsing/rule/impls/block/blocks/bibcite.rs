@@ -40,7 +40,7 @@ fn parse_fn<'r, 't>(
         "Parsing bibcite block (name '{name}', in-head {in_head}, score {flag_score})",
     );
     assert!(!flag_star, "Bibcite doesn't allow star flag");
-    assert_block_name(&BLOCK_BIBCITE, name);
+    check_block_name(parser, &BLOCK_BIBCITE, name)?;
 
     let label =
         parser.get_head_value(&BLOCK_BIBCITE, in_head, |parser, value| match value {
@@ -19,7 +19,7 @@
  */
 
 use super::prelude::*;
-use crate::tree::{Bibliography, DefinitionListItem};
+use crate::tree::{Bibliography, CitationStyle, DefinitionListItem};
 
 pub const BLOCK_BIBLIOGRAPHY: BlockRule = BlockRule {
     name: "block-bibliography",
@@ -40,12 +40,19 @@ fn parse_fn<'r, 't>(
     debug!("Parsing bibliography block (name '{name}', in-head {in_head}, score {flag_score})");
     assert!(!flag_star, "Bibliography doesn't allow star flag");
     assert!(!flag_score, "Bibliography doesn't allow score flag");
-    assert_block_name(&BLOCK_BIBLIOGRAPHY, name);
+    check_block_name(parser, &BLOCK_BIBLIOGRAPHY, name)?;
 
     let mut arguments = parser.get_head_map(&BLOCK_BIBLIOGRAPHY, in_head)?;
 
     let title = arguments.get("title");
     let hide = arguments.get_bool(parser, "hide")?.unwrap_or(false);
+    let style = match arguments.get("style") {
+        Some(value) => match CitationStyle::from_name(&value) {
+            Some(style) => style,
+            None => return Err(parser.make_err(ParseErrorKind::BlockMalformedArguments)),
+        },
+        None => CitationStyle::default(),
+    };
 
     // Get body content. The contents should only be a definition list, but
     // we use the regular elements parser to make it easy on us. If we find
@@ -60,7 +67,7 @@ fn parse_fn<'r, 't>(
     //
     // Look through to find definition lists, ignoring "space" type elements,
     // and adding definition list values to the bibliography as we find them.
-    let mut bibliography = Bibliography::new();
+    let mut bibliography = Bibliography::with_style(style);
 
     for element in elements {
         match element {
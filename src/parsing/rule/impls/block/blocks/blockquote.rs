@@ -19,12 +19,14 @@
  */
 
 use super::prelude::*;
+use crate::url::normalize_href_with_settings;
+use std::borrow::Cow;
 
 pub const BLOCK_BLOCKQUOTE: BlockRule = BlockRule {
     name: "block-blockquote",
     accepts_names: &["blockquote", "quote"],
     accepts_star: false,
-    accepts_score: false,
+    accepts_score: true,
     accepts_newlines: true,
     parse_fn,
 };
@@ -36,21 +38,34 @@ fn parse_fn<'r, 't>(
     flag_score: bool,
     in_head: bool,
 ) -> ParseResult<'r, 't, Elements<'t>> {
-    debug!("Parsing blockquote block (in-head {in_head})");
+    debug!("Parsing blockquote block (in-head {in_head}, score {flag_score})");
     assert!(!flag_star, "Blockquote doesn't allow star flag");
-    assert!(!flag_score, "Blockquote doesn't allow score flag");
-    assert_block_name(&BLOCK_BLOCKQUOTE, name);
+    check_block_name(parser, &BLOCK_BLOCKQUOTE, name)?;
 
-    let arguments = parser.get_head_map(&BLOCK_BLOCKQUOTE, in_head)?;
+    let mut arguments = parser.get_head_map(&BLOCK_BLOCKQUOTE, in_head)?;
+    let cite = arguments.get("cite");
+
+    // "blockquote" means we wrap in paragraphs, like normal
+    // "blockquote_" means we don't wrap it
+    let wrap_paragraphs = !flag_score;
 
     // Get body content, but discard paragraph_safe, since blockquotes never are.
-    let (elements, errors, _) = parser.get_body_elements(&BLOCK_BLOCKQUOTE, true)?.into();
+    let (elements, errors, _) = parser
+        .get_body_elements(&BLOCK_BLOCKQUOTE, wrap_paragraphs)?
+        .into();
+
+    // The source can be a URL or a page, so normalize it like a link
+    let mut attributes = arguments.to_attribute_map(parser.settings());
+    if let Some(cite) = cite {
+        let cite = normalize_href_with_settings(&cite, parser.settings()).into_owned();
+        attributes.insert("cite", Cow::Owned(cite));
+    }
 
     // Build element and return
     let element = Element::Container(Container::new(
         ContainerType::Blockquote,
         elements,
-        arguments.to_attribute_map(parser.settings()),
+        attributes,
     ));
 
     ok!(element, errors)
@@ -19,12 +19,13 @@
  */
 
 use super::prelude::*;
+use crate::parsing::strip_newlines;
 
 pub const BLOCK_BOLD: BlockRule = BlockRule {
     name: "block-bold",
     accepts_names: &["b", "bold", "strong"],
     accepts_star: false,
-    accepts_score: false,
+    accepts_score: true,
     accepts_newlines: false,
     parse_fn,
 };
@@ -36,17 +37,24 @@ fn parse_fn<'r, 't>(
     flag_score: bool,
     in_head: bool,
 ) -> ParseResult<'r, 't, Elements<'t>> {
-    debug!("Parsing bold block (name '{name}', in-head {in_head})");
+    debug!("Parsing bold block (name '{name}', in-head {in_head}, score {flag_score})");
     assert!(!flag_star, "Bold doesn't allow star flag");
-    assert!(!flag_score, "Bold doesn't allow score flag");
-    assert_block_name(&BLOCK_BOLD, name);
+    check_block_name(parser, &BLOCK_BOLD, name)?;
 
     let arguments = parser.get_head_map(&BLOCK_BOLD, in_head)?;
 
+    // "b" means we interpret as-is
+    // "b_" means we strip out any newlines or paragraph breaks
+    let strip_line_breaks = flag_score;
+
     // Get body content, without paragraphs
-    let (elements, errors, paragraph_safe) =
+    let (mut elements, errors, paragraph_safe) =
         parser.get_body_elements(&BLOCK_BOLD, false)?.into();
 
+    if strip_line_breaks {
+        strip_newlines(&mut elements);
+    }
+
     let element = Element::Container(Container::new(
         ContainerType::Bold,
         elements,
@@ -57,36 +57,54 @@ fn parse_fn<'r, 't>(
     debug!("Parsing character / HTML entity block (in-head {in_head})");
     assert!(!flag_star, "Char doesn't allow star flag");
     assert!(!flag_score, "Char doesn't allow score flag");
-    assert_block_name(&BLOCK_CHAR, name);
+    check_block_name(parser, &BLOCK_CHAR, name)?;
 
     // Parse the entity and get the string
-    let string = parser.get_head_value(&BLOCK_CHAR, in_head, parse_entity)?;
+    let (string, errors) = parser.get_head_value(&BLOCK_CHAR, in_head, parse_entity)?;
 
-    ok!(Element::Text(string))
+    ok!(Element::Text(string), errors)
 }
 
 fn parse_entity<'t>(
     parser: &Parser<'_, 't>,
     argument: Option<&'t str>,
-) -> Result<Cow<'t, str>, ParseError> {
+) -> Result<(Cow<'t, str>, Vec<ParseError>), ParseError> {
     let argument = match argument {
-        Some(arg) => strip_entity(arg),
+        Some(arg) => arg,
         None => return Err(parser.make_err(ParseErrorKind::BlockMissingArguments)),
     };
 
-    match find_entity(argument) {
-        Some(string) => Ok(string),
+    match find_entity(strip_entity(argument)) {
+        Some(string) => Ok((string, vec![])),
+        None if parser.settings().lenient_char_entities => {
+            // Emit the argument as-is, with a warning
+            warn!("No such entity '{argument}', emitting as literal text");
+
+            let string = cow!(argument.trim());
+            let error = parser.make_err(ParseErrorKind::NoSuchCharacter);
+            Ok((string, vec![error]))
+        }
         None => Err(parser.make_err(ParseErrorKind::BlockMalformedArguments)),
     }
 }
 
 /// Find the string corresponding to the passed entity, if any.
-fn find_entity(entity: &str) -> Option<Cow<str>> {
+fn find_entity(entity: &str) -> Option<Cow<'_, str>> {
     // Named entity
     if let Some(result) = ENTITY_MAPPING.get(entity) {
         return Some(cow!(result));
     }
 
+    // Unicode codepoint, e.g. "U+1F4AF"
+    if let Some(value) = entity
+        .strip_prefix("U+")
+        .or_else(|| entity.strip_prefix("u+"))
+    {
+        if let Some(result) = get_char(value, 16) {
+            return Some(result);
+        }
+    }
+
     // Hexadecimal entity
     if let Some(value) = entity.strip_prefix("#x") {
         if let Some(result) = get_char(value, 16) {
@@ -111,16 +129,13 @@ fn find_entity(entity: &str) -> Option<Cow<str>> {
 /// character, if one exists.
 ///
 /// Then converts the character into a string with only that value.
-fn get_char(value: &str, radix: u32) -> Option<Cow<str>> {
+fn get_char(value: &str, radix: u32) -> Option<Cow<'_, str>> {
     let codepoint = match u32::from_str_radix(value, radix) {
         Ok(codepoint) => codepoint,
         Err(_) => return None,
     };
 
-    let ch = match char::from_u32(codepoint) {
-        Some(ch) => ch,
-        None => return None,
-    };
+    let ch = char::from_u32(codepoint)?;
 
     Some(Cow::Owned(ch.to_string()))
 }
@@ -177,6 +192,69 @@ fn test_get_entity() {
     check!("#xff", Some(cow!("\u{ff}")));
     check!("#x1f4af", Some(cow!("💯")));
     check!("#x1fffff", None);
+
+    // Unicode codepoint
+    check!("U+20", Some(cow!(" ")));
+    check!("U+00FF", Some(cow!("\u{ff}")));
+    check!("U+1F4AF", Some(cow!("💯")));
+    check!("u+1f4af", Some(cow!("💯")));
+    check!("U+1FFFFF", None);
+    check!("U+", None);
+    check!("U+xyz", None);
+}
+
+#[test]
+fn test_lenient() {
+    use crate::data::PageInfo;
+    use crate::layout::Layout;
+    use crate::settings::WikitextMode;
+
+    let page_info = PageInfo::dummy();
+    let mut settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+
+    macro_rules! check {
+        ($input:expr, $expected:expr, $error:expr $(,)?) => {{
+            let tokens = crate::tokenize($input);
+            let (tree, errors) = crate::parse(&tokens, &page_info, &settings).into();
+            let error_kinds: Vec<_> = errors.iter().map(|error| error.kind()).collect();
+
+            assert_eq!(
+                tree.elements.first(),
+                Some(&Element::Container(crate::tree::Container::new(
+                    crate::tree::ContainerType::Paragraph,
+                    $expected,
+                    crate::tree::AttributeMap::new(),
+                ))),
+                "Elements do not match expected",
+            );
+            assert_eq!(error_kinds, $error, "Errors do not match expected");
+        }};
+    }
+
+    settings.lenient_char_entities = false;
+    check!(
+        "[[char bogus]]",
+        vec![
+            text!("[["),
+            text!("char"),
+            text!(" "),
+            text!("bogus"),
+            text!("]]"),
+        ],
+        vec![
+            ParseErrorKind::BlockMalformedArguments,
+            ParseErrorKind::NoRulesMatch,
+            ParseErrorKind::NoRulesMatch,
+        ],
+    );
+
+    settings.lenient_char_entities = true;
+    check!("[[char &copy;]]", vec![text!("©")], vec![]);
+    check!(
+        "[[char &bogus;]]",
+        vec![text!("&bogus;")],
+        vec![ParseErrorKind::NoSuchCharacter],
+    );
 }
 
 #[test]
@@ -38,7 +38,7 @@ fn parse_fn<'r, 't>(
 ) -> ParseResult<'r, 't, Elements<'t>> {
     debug!("Parsing checkbox block (name '{name}', in-head {in_head})");
     assert!(!flag_score, "Checkbox doesn't allow score flag");
-    assert_block_name(&BLOCK_CHECKBOX, name);
+    check_block_name(parser, &BLOCK_CHECKBOX, name)?;
 
     let arguments = parser.get_head_map(&BLOCK_CHECKBOX, in_head)?;
     parser.get_optional_space()?;
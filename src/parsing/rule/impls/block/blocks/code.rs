@@ -41,7 +41,7 @@ fn parse_fn<'r, 't>(
     debug!("Parsing code block (in-head {in_head})");
     assert!(!flag_star, "Code doesn't allow star flag");
     assert!(!flag_score, "Code doesn't allow score flag");
-    assert_block_name(&BLOCK_CODE, name);
+    check_block_name(parser, &BLOCK_CODE, name)?;
 
     let mut arguments = parser.get_head_map(&BLOCK_CODE, in_head)?;
 
@@ -56,14 +56,12 @@ fn parse_fn<'r, 't>(
     }
 
     let code = parser.get_body_text(&BLOCK_CODE)?;
+    let added_result =
+        parser.push_code_block(CodeBlock::new(cow!(code), name, language.clone()));
     let element = Element::Code {
         contents: cow!(code),
         language,
     };
-    let added_result = parser.push_code_block(CodeBlock {
-        contents: cow!(code),
-        name,
-    });
     if added_result.is_err() {
         return Err(parser.make_err(ParseErrorKind::CodeNonUniqueName));
     }
@@ -25,7 +25,7 @@ pub const BLOCK_COLLAPSIBLE: BlockRule = BlockRule {
     name: "block-collapsible",
     accepts_names: &["collapsible"],
     accepts_star: false,
-    accepts_score: false,
+    accepts_score: true,
     accepts_newlines: true,
     parse_fn,
 };
@@ -37,10 +37,9 @@ fn parse_fn<'r, 't>(
     flag_score: bool,
     in_head: bool,
 ) -> ParseResult<'r, 't, Elements<'t>> {
-    debug!("Parsing collapsible block (in-head {in_head})");
+    debug!("Parsing collapsible block (in-head {in_head}, score {flag_score})");
     assert!(!flag_star, "Collapsible doesn't allow star flag");
-    assert!(!flag_score, "Collapsible doesn't allow score flag");
-    assert_block_name(&BLOCK_COLLAPSIBLE, name);
+    check_block_name(parser, &BLOCK_COLLAPSIBLE, name)?;
 
     let mut arguments = parser.get_head_map(&BLOCK_COLLAPSIBLE, in_head)?;
 
@@ -57,10 +56,14 @@ fn parse_fn<'r, 't>(
         None => (true, false),
     };
 
-    // Get body content, with paragraphs.
+    // Whether the frontend should persist the open/closed state
+    let remember = arguments.get_bool(parser, "remember")?.unwrap_or(false);
+
+    // Get body content, with paragraphs unless the score flag is set.
     // Discard paragraph_safe, since collapsibles never are.
-    let (elements, errors, _) =
-        parser.get_body_elements(&BLOCK_COLLAPSIBLE, true)?.into();
+    let (elements, errors, _) = parser
+        .get_body_elements(&BLOCK_COLLAPSIBLE, !flag_score)?
+        .into();
 
     // Build element and return
     let element = Element::Collapsible {
@@ -71,6 +74,7 @@ fn parse_fn<'r, 't>(
         hide_text,
         show_top,
         show_bottom,
+        remember,
     };
 
     ok!(element, errors)
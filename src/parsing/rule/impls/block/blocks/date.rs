@@ -47,7 +47,7 @@ fn parse_fn<'r, 't>(
     debug!("Parsing date block (name '{name}', in-head {in_head}, score {flag_score})");
     assert!(!flag_star, "Date doesn't allow star flag");
     assert!(!flag_score, "Date doesn't allow score flag");
-    assert_block_name(&BLOCK_DATE, name);
+    check_block_name(parser, &BLOCK_DATE, name)?;
 
     let (value, mut arguments) = parser.get_head_name_map(&BLOCK_DATE, in_head)?;
     let format = arguments.get("format");
@@ -19,12 +19,13 @@
  */
 
 use super::prelude::*;
+use crate::parsing::strip_newlines;
 
 pub const BLOCK_DEL: BlockRule = BlockRule {
     name: "block-del",
     accepts_names: &["del", "deletion"],
     accepts_star: false,
-    accepts_score: false,
+    accepts_score: true,
     accepts_newlines: false,
     parse_fn,
 };
@@ -36,17 +37,26 @@ fn parse_fn<'r, 't>(
     flag_score: bool,
     in_head: bool,
 ) -> ParseResult<'r, 't, Elements<'t>> {
-    debug!("Parsing deletion block (name '{name}', in-head {in_head})");
+    debug!(
+        "Parsing deletion block (name '{name}', in-head {in_head}, score {flag_score})"
+    );
     assert!(!flag_star, "Deletion doesn't allow star flag");
-    assert!(!flag_score, "Deletion doesn't allow score flag");
-    assert_block_name(&BLOCK_DEL, name);
+    check_block_name(parser, &BLOCK_DEL, name)?;
 
     let arguments = parser.get_head_map(&BLOCK_DEL, in_head)?;
 
+    // "del" means we interpret as-is
+    // "del_" means we strip out any newlines or paragraph breaks
+    let strip_line_breaks = flag_score;
+
     // Get body content, without paragraphs
-    let (elements, errors, paragraph_safe) =
+    let (mut elements, errors, paragraph_safe) =
         parser.get_body_elements(&BLOCK_DEL, false)?.into();
 
+    if strip_line_breaks {
+        strip_newlines(&mut elements);
+    }
+
     // Build and return element
     let element = Element::Container(Container::new(
         ContainerType::Deletion,
@@ -38,7 +38,7 @@ fn parse_fn<'r, 't>(
 ) -> ParseResult<'r, 't, Elements<'t>> {
     debug!("Parsing div block (name '{name}', in-head {in_head}, score {flag_score})");
     assert!(!flag_star, "Div doesn't allow star flag");
-    assert_block_name(&BLOCK_DIV, name);
+    check_block_name(parser, &BLOCK_DIV, name)?;
 
     let arguments = parser.get_head_map(&BLOCK_DIV, in_head)?;
 
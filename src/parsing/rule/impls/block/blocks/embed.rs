@@ -45,7 +45,7 @@ fn parse_fn<'r, 't>(
     debug!("Parsing embed block (name '{name}', in-head {in_head})");
     assert!(!flag_star, "Embed doesn't allow star flag");
     assert!(!flag_score, "Embed doesn't allow star flag");
-    assert_block_name(&BLOCK_EMBED, name);
+    check_block_name(parser, &BLOCK_EMBED, name)?;
 
     let (name, mut arguments) = parser.get_head_name_map(&BLOCK_EMBED, in_head)?;
     let embed = build_embed(parser, name, &mut arguments)?;
@@ -66,6 +66,10 @@ where
 
     for &(embed_name, builder) in EMBED_BUILDERS {
         if embed_name.eq_ignore_ascii_case(name) {
+            if parser.settings().embed.provider(embed_name).is_none() {
+                return Err(parser.make_err(ParseErrorKind::EmbedNotAllowed));
+            }
+
             return builder(parser, arguments);
         }
     }
@@ -83,7 +87,12 @@ fn build_youtube<'p, 't>(
         .get("video")
         .ok_or_else(|| parser.make_err(ParseErrorKind::BlockMissingArguments))?;
 
-    Ok(Embed::Youtube { video_id })
+    let embed = Embed::Youtube { video_id };
+    if !embed.is_valid() {
+        return Err(parser.make_err(ParseErrorKind::BlockMalformedArguments));
+    }
+
+    Ok(embed)
 }
 
 fn build_vimeo<'p, 't>(
@@ -94,7 +103,12 @@ fn build_vimeo<'p, 't>(
         .get("video")
         .ok_or_else(|| parser.make_err(ParseErrorKind::BlockMissingArguments))?;
 
-    Ok(Embed::Vimeo { video_id })
+    let embed = Embed::Vimeo { video_id };
+    if !embed.is_valid() {
+        return Err(parser.make_err(ParseErrorKind::BlockMalformedArguments));
+    }
+
+    Ok(embed)
 }
 
 #[test]
@@ -39,7 +39,7 @@ fn parse_fn<'r, 't>(
     debug!("Parsing equation reference block (name '{name}', in-head {in_head})");
     assert!(!flag_star, "Equation reference doesn't allow start flag");
     assert!(!flag_score, "Equation reference doesn't allow score flag");
-    assert_block_name(&BLOCK_EQUATION_REF, name);
+    check_block_name(parser, &BLOCK_EQUATION_REF, name)?;
 
     let name =
         parser.get_head_value(
@@ -19,6 +19,7 @@
  */
 
 use super::prelude::*;
+use crate::tree::visit::VisitorMut;
 use std::ops::{Deref, DerefMut};
 
 pub const BLOCK_FOOTNOTE: BlockRule = BlockRule {
@@ -50,11 +51,15 @@ fn parse_footnote_ref<'r, 't>(
 
     // Check footnote flag
     //
-    // This is true if we're a [[footnote]] inside a [[footnote]],
-    // which is not allowed.
-    if parser.in_footnote() {
-        return Err(parser.make_err(ParseErrorKind::FootnotesNested));
-    }
+    // This is true if we're a [[footnote]] inside a [[footnote]].
+    // Footnotes cannot nest, so rather than failing (which would leave
+    // this block's [[/footnote]] to close the outer one), its contents
+    // are merged into the enclosing footnote, with an error noting this.
+    let nested_error = if parser.in_footnote() {
+        Some(parser.make_err(ParseErrorKind::FootnotesNested))
+    } else {
+        None
+    };
 
     // Set footnote ref flag
     let parser = &mut ParserWrap::new(parser);
@@ -62,10 +67,19 @@ fn parse_footnote_ref<'r, 't>(
     // Parse out block
     assert!(!flag_star, "Footnote reference doesn't allow star flag");
     assert!(!flag_score, "Footnote reference doesn't allow score flag");
-    assert_block_name(&BLOCK_FOOTNOTE, name);
+    check_block_name(parser, &BLOCK_FOOTNOTE, name)?;
 
     parser.get_head_none(&BLOCK_FOOTNOTE, in_head)?;
 
+    // Nested footnote, return its contents inline.
+    if let Some(error) = nested_error {
+        let (elements, mut errors, _) =
+            parser.get_body_elements(&BLOCK_FOOTNOTE, false)?.into();
+
+        errors.push(error);
+        return ok!(elements, errors);
+    }
+
     // Gather footnote contents with paragraphs.
     //
     // However, if there's only one, then we strip it
@@ -104,7 +118,7 @@ fn parse_footnote_block<'r, 't>(
     debug!("Parsing footnote list block (in-head {in_head})");
     assert!(!flag_star, "Footnote block doesn't allow star flag");
     assert!(!flag_score, "Footnote block doesn't allow score flag");
-    assert_block_name(&BLOCK_FOOTNOTE_BLOCK, name);
+    check_block_name(parser, &BLOCK_FOOTNOTE_BLOCK, name)?;
 
     // Parse arguments
     let mut arguments = parser.get_head_map(&BLOCK_FOOTNOTE_BLOCK, in_head)?;
@@ -124,21 +138,63 @@ fn parse_footnote_block<'r, 't>(
     ok!(Element::FootnoteBlock { title, hide })
 }
 
+/// Replaces footnote references with the contents of their footnotes.
+///
+/// This is used when elements containing footnotes end up inside
+/// another footnote, such as from `[[include-elements]]`. Since footnotes
+/// cannot nest, their contents are placed inline instead, consuming
+/// `footnotes` in order.
+pub fn inline_footnotes<'t>(
+    elements: &mut Vec<Element<'t>>,
+    footnotes: Vec<Vec<Element<'t>>>,
+) {
+    struct Inliner<'t> {
+        footnotes: std::vec::IntoIter<Vec<Element<'t>>>,
+    }
+
+    impl<'t> VisitorMut<'t> for Inliner<'t> {
+        fn visit_elements_mut(&mut self, elements: &mut Vec<Element<'t>>) {
+            let mut index = 0;
+            while index < elements.len() {
+                if matches!(elements[index], Element::Footnote) {
+                    let contents = self.footnotes.next().unwrap_or_default();
+                    let length = contents.len();
+                    elements.splice(index..=index, contents);
+                    index += length;
+                } else {
+                    self.visit_element_mut(&mut elements[index]);
+                    index += 1;
+                }
+            }
+        }
+    }
+
+    let mut inliner = Inliner {
+        footnotes: footnotes.into_iter(),
+    };
+
+    inliner.visit_elements_mut(elements);
+}
+
 /// Helper structure to set the `in_footnote` flag.
 ///
 /// This is only for `[[footnote]]`, the flag is meant
-/// to prevent nested `[[footnote]]`s.
+/// to prevent nested `[[footnote]]`s. The previous value
+/// is restored afterwards, since a nested footnote
+/// is still within the outer one.
 #[derive(Debug)]
 struct ParserWrap<'p, 'r, 't> {
     parser: &'p mut Parser<'r, 't>,
+    previous: bool,
 }
 
 impl<'p, 'r, 't> ParserWrap<'p, 'r, 't> {
     #[inline]
     fn new(parser: &'p mut Parser<'r, 't>) -> Self {
+        let previous = parser.in_footnote();
         parser.set_footnote_flag(true);
 
-        ParserWrap { parser }
+        ParserWrap { parser, previous }
     }
 }
 
@@ -160,6 +216,41 @@ impl<'r, 't> DerefMut for ParserWrap<'_, 'r, 't> {
 
 impl Drop for ParserWrap<'_, '_, '_> {
     fn drop(&mut self) {
-        self.parser.set_footnote_flag(false);
+        self.parser.set_footnote_flag(self.previous);
     }
 }
+
+#[test]
+fn inline() {
+    use crate::tree::AttributeMap;
+
+    let mut elements = vec![
+        text!("A"),
+        Element::Footnote,
+        Element::Container(Container::new(
+            ContainerType::Bold,
+            vec![text!("B"), Element::Footnote],
+            AttributeMap::new(),
+        )),
+    ];
+
+    inline_footnotes(
+        &mut elements,
+        vec![vec![text!("C"), text!("D")], vec![text!("E")]],
+    );
+
+    assert_eq!(
+        elements,
+        vec![
+            text!("A"),
+            text!("C"),
+            text!("D"),
+            Element::Container(Container::new(
+                ContainerType::Bold,
+                vec![text!("B"), text!("E")],
+                AttributeMap::new(),
+            )),
+        ],
+        "Inlined footnotes don't match expected",
+    );
+}
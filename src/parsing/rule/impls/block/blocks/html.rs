@@ -19,6 +19,7 @@
  */
 
 use super::prelude::*;
+use crate::tree::Dimension;
 
 pub const BLOCK_HTML: BlockRule = BlockRule {
     name: "block-html",
@@ -39,14 +40,60 @@ fn parse_fn<'r, 't>(
     debug!("Parsing HTML block (in-head {in_head})");
     assert!(!flag_star, "HTML doesn't allow star flag");
     assert!(!flag_score, "HTML doesn't allow score flag");
-    assert_block_name(&BLOCK_HTML, name);
+    check_block_name(parser, &BLOCK_HTML, name)?;
+
+    let mut arguments = parser.get_head_map(&BLOCK_HTML, in_head)?;
+
+    // Get sizing arguments for the iframe
+    let resize = match arguments.get("resize") {
+        Some(value) => parse_resize(&value, parser)?,
+        None => false,
+    };
+
+    let width = match arguments.get("width") {
+        Some(value) => Some(parse_dimension(&value, parser)?),
+        None => None,
+    };
+
+    let height = match arguments.get("height") {
+        Some(value) => Some(parse_dimension(&value, parser)?),
+        None => None,
+    };
+
+    if !arguments.is_empty() {
+        warn!("Unknown arguments in HTML block");
+        return Err(parser.make_err(ParseErrorKind::BlockMalformedArguments));
+    }
 
-    parser.get_head_none(&BLOCK_HTML, in_head)?;
     let html = parser.get_body_text(&BLOCK_HTML)?;
     let element = Element::Html {
         contents: cow!(html),
+        width,
+        height,
+        resize,
     };
     parser.push_html_block(cow!(html));
 
     ok!(element)
 }
+
+fn parse_dimension(s: &str, parser: &Parser) -> Result<Dimension, ParseError> {
+    match Dimension::parse(s) {
+        Some(dimension) => Ok(dimension),
+        None => {
+            warn!("Invalid size argument '{s}'");
+            Err(parser.make_err(ParseErrorKind::BlockMalformedArguments))
+        }
+    }
+}
+
+fn parse_resize(s: &str, parser: &Parser) -> Result<bool, ParseError> {
+    match s.trim() {
+        s if s.eq_ignore_ascii_case("auto") => Ok(true),
+        s if s.eq_ignore_ascii_case("none") => Ok(false),
+        s => {
+            warn!("Unknown resize argument '{s}'");
+            Err(parser.make_err(ParseErrorKind::BlockMalformedArguments))
+        }
+    }
+}
@@ -41,7 +41,7 @@ fn parse_fn<'r, 't>(
     debug!("Parsing ifcategory block (name '{name}', in-head {in_head})");
     assert!(!flag_star, "IfCategory doesn't allow star flag");
     assert!(!flag_score, "IfCategory doesn't allow score flag");
-    assert_block_name(&BLOCK_IFCATEGORY, name);
+    check_block_name(parser, &BLOCK_IFCATEGORY, name)?;
 
     // Parse out tag conditions
     let conditions =
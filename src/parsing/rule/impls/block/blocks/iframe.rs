@@ -19,7 +19,7 @@
  */
 
 use super::prelude::*;
-use crate::url::is_url;
+use crate::url::is_url_with_settings;
 
 pub const BLOCK_IFRAME: BlockRule = BlockRule {
     name: "block-iframe",
@@ -40,10 +40,10 @@ fn parse_fn<'r, 't>(
     debug!("Parsing iframe block (in-head {in_head})");
     assert!(!flag_star, "iframe doesn't allow star flag");
     assert!(!flag_score, "iframe doesn't allow score flag");
-    assert_block_name(&BLOCK_IFRAME, name);
+    check_block_name(parser, &BLOCK_IFRAME, name)?;
 
     let (url, arguments) = parser.get_head_name_map(&BLOCK_IFRAME, in_head)?;
-    if !is_url(url) {
+    if !is_url_with_settings(url, parser.settings()) {
         warn!("Iframe block references non-URL: {url}");
         return Err(parser.make_err(ParseErrorKind::BlockMalformedArguments));
     }
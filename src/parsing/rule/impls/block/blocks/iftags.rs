@@ -41,7 +41,7 @@ fn parse_fn<'r, 't>(
     debug!("Parsing iftags block (name '{name}', in-head {in_head})");
     assert!(!flag_star, "IfTags doesn't allow star flag");
     assert!(!flag_score, "IfTags doesn't allow score flag");
-    assert_block_name(&BLOCK_IFTAGS, name);
+    check_block_name(parser, &BLOCK_IFTAGS, name)?;
 
     // Parse out tag conditions
     let conditions =
@@ -60,6 +60,19 @@ fn parse_fn<'r, 't>(
         elements.len(),
     );
 
+    // If deferred, keep the conditions for the renderer to check
+    if parser.settings().defer_tag_conditions {
+        trace!("Conditions deferred, preserving in syntax tree");
+
+        let element = Element::Conditional {
+            paragraph_safe,
+            conditions,
+            elements,
+        };
+
+        return ok!(paragraph_safe; element, errors);
+    }
+
     // Return elements based on condition
     let elements = if check_iftags(parser.page_info(), &conditions) {
         trace!("Conditions passed, including elements");
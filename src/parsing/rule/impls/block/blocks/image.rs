@@ -40,14 +40,35 @@ fn parse_fn<'r, 't>(
     debug!("Parsing image block (name {name}, in-head {in_head})");
     assert!(!flag_star, "Image doesn't allow star flag");
     assert!(!flag_score, "Image doesn't allow score flag");
-    assert_block_name(&BLOCK_IMAGE, name);
+    check_block_name(parser, &BLOCK_IMAGE, name)?;
 
     let (source, mut arguments) = parser.get_head_name_map(&BLOCK_IMAGE, in_head)?;
-    let link = arguments.get("link").map(LinkLocation::parse);
+
+    // Parse the link the same way as link rules, see link_triple.rs
+    let (link, link_extra) = match arguments.get("link") {
+        Some(link) => {
+            let location = match LinkLocation::parse_interwiki_with(
+                link.clone(),
+                &parser.interwiki(),
+                parser.settings(),
+            ) {
+                Some((location, _)) => location,
+                None => {
+                    return Err(parser.make_err(ParseErrorKind::BlockMalformedArguments))
+                }
+            };
+
+            let extra = LinkLocation::parse_extra(link, parser.settings());
+            (Some(location), extra)
+        }
+        None => (None, None),
+    };
+
     let alignment = FloatAlignment::parse(name);
+    let lazy = arguments.get_bool(parser, "lazy")?.unwrap_or(false);
 
     // Parse the image source based on format
-    let source = match ImageSource::parse(source) {
+    let source = match ImageSource::parse(source, parser.settings()) {
         Some(source) => source,
         None => return Err(parser.make_err(ParseErrorKind::BlockMalformedArguments)),
     };
@@ -56,7 +77,9 @@ fn parse_fn<'r, 't>(
     let element = Element::Image {
         source,
         link,
+        link_extra,
         alignment,
+        lazy,
         attributes: arguments.to_attribute_map(parser.settings()),
     };
 
@@ -18,9 +18,14 @@
  * along with this program. If not, see <http://www.gnu.org/licenses/>.
  */
 
+use super::footnote::inline_footnotes;
 use super::prelude::*;
 use crate::data::PageRef;
 use crate::parsing::UnstructuredParseResult;
+use crate::render::text::TextRender;
+use crate::tree::visit::{walk_container, Visitor};
+use crate::tree::{Container, ContainerType, SyntaxTree};
+use std::mem;
 
 // TODO: maybe scrap this? we want to move to components anyways
 
@@ -48,7 +53,7 @@ fn parse_fn<'r, 't>(
     parser.check_page_syntax()?;
     assert!(!flag_star, "Include (elements) doesn't allow star flag");
     assert!(!flag_score, "Include (elements) doesn't allow score flag");
-    assert_block_name(&BLOCK_INCLUDE_ELEMENTS, name);
+    check_block_name(parser, &BLOCK_INCLUDE_ELEMENTS, name)?;
 
     // Parse block
     let (page_name, variables) =
@@ -68,6 +73,8 @@ fn parse_fn<'r, 't>(
         mut footnotes,
         has_footnote_block,
         mut bibliographies,
+        element_spans: _,
+        statistics: _,
     } = include_page(parser, &page_ref)?;
 
     if has_footnote_block {
@@ -76,12 +83,19 @@ fn parse_fn<'r, 't>(
 
     // Extract elements and errors
     let ParseSuccess {
-        item: elements,
-        errors,
+        item: mut elements,
+        mut errors,
         paragraph_safe,
         ..
     } = result?;
 
+    // If included within a footnote, the page's footnotes cannot
+    // be added as footnotes of their own, so they are placed inline.
+    if parser.in_footnote() && !footnotes.is_empty() {
+        inline_footnotes(&mut elements, mem::take(&mut footnotes));
+        errors.push(parser.make_err(ParseErrorKind::FootnotesNested));
+    }
+
     // Update parser state, build, and return
     parser.append_shared_items(
         &mut html_blocks,
@@ -104,15 +118,100 @@ fn parse_fn<'r, 't>(
 
 fn include_page<'r, 't>(
     parser: &Parser<'r, 't>,
-    _page: &PageRef,
+    page: &PageRef,
 ) -> Result<UnstructuredParseResult<'r, 't>, ParseError> {
-    // TODO stubbed
+    let includer = match parser.elements_includer() {
+        Some(includer) => includer,
+        None => return Ok(placeholder_page()),
+    };
 
-    if false {
-        return Err(parser.make_err(ParseErrorKind::NoSuchPage));
+    let tree = match includer.include_elements(page) {
+        Some(tree) => tree,
+        None => {
+            debug!("Included page '{page}' does not exist");
+            return Err(parser.make_err(ParseErrorKind::NoSuchPage));
+        }
+    };
+
+    let SyntaxTree {
+        mut elements,
+        html_blocks,
+        code_blocks,
+        footnotes,
+        bibliographies,
+        ..
+    } = tree;
+
+    // The page's own footnote block is replaced by this page's,
+    // unless it was placed explicitly before the end.
+    if let Some(Element::FootnoteBlock { .. }) = elements.last() {
+        elements.pop();
     }
 
+    let has_footnote_block = elements
+        .iter()
+        .any(|element| matches!(element, Element::FootnoteBlock { .. }));
+
+    let table_of_contents_depths = collect_headings(parser, &elements);
+    let paragraph_safe = elements.iter().all(Element::paragraph_safe);
+
     Ok(UnstructuredParseResult {
+        result: Ok(ParseSuccess::new(elements, vec![], paragraph_safe)),
+        html_blocks,
+        code_blocks,
+        table_of_contents_depths,
+        footnotes,
+        has_footnote_block,
+        bibliographies,
+        element_spans: vec![],
+        statistics: None,
+    })
+}
+
+/// Gets the table of contents entries for the headings of an included page.
+///
+/// These are the same as if the page's headings had been parsed here.
+fn collect_headings(parser: &Parser, elements: &[Element]) -> Vec<(usize, String)> {
+    struct HeadingCollector<'p, 'r, 't> {
+        parser: &'p Parser<'r, 't>,
+        headings: Vec<(usize, String)>,
+    }
+
+    impl<'t> Visitor<'t> for HeadingCollector<'_, '_, '_> {
+        fn visit_container(&mut self, container: &Container<'t>) {
+            if let ContainerType::Header(heading) = container.ctype() {
+                if heading.has_toc {
+                    // Headings are 1-indexed (e.g. H1), but depth lists are 0-indexed
+                    let level = usize::from(heading.level.value()) - 1;
+                    let name = TextRender.render_partial(
+                        container.elements(),
+                        self.parser.page_info(),
+                        self.parser.settings(),
+                        0,
+                    );
+
+                    self.headings.push((level, name));
+                }
+            }
+
+            walk_container(self, container);
+        }
+    }
+
+    let mut collector = HeadingCollector {
+        parser,
+        headings: vec![],
+    };
+
+    collector.visit_elements(elements);
+    collector.headings
+}
+
+/// Produces placeholder text, since no [`ElementsIncluder`] was provided.
+///
+/// [`ElementsIncluder`]: crate::includes::ElementsIncluder
+fn placeholder_page<'r, 't>() -> UnstructuredParseResult<'r, 't> {
+    UnstructuredParseResult {
         result: Ok(ParseSuccess::new(
             vec![text!("<INCLUDED PAGE (ELEMENTS)>")],
             vec![],
@@ -124,5 +223,63 @@ fn include_page<'r, 't>(
         footnotes: vec![],
         has_footnote_block: false,
         bibliographies: Default::default(),
-    })
+        element_spans: vec![],
+        statistics: None,
+    }
+}
+
+#[test]
+fn elements_includer() {
+    use crate::data::PageInfo;
+    use crate::includes::ElementsIncluder;
+    use crate::layout::Layout;
+    use crate::settings::{WikitextMode, WikitextSettings};
+    use std::collections::HashMap;
+
+    #[derive(Debug)]
+    struct CacheIncluder(HashMap<String, SyntaxTree<'static>>);
+
+    impl ElementsIncluder for CacheIncluder {
+        fn include_elements(&self, page_ref: &PageRef) -> Option<SyntaxTree<'static>> {
+            self.0.get(&page_ref.to_string()).cloned()
+        }
+    }
+
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+
+    // Parse the included page once, as it would be cached
+    let tokens = crate::tokenize("+ Box\n\nApple[[footnote]]A[[/footnote]]");
+    let (tree, _) = crate::parse(&tokens, &page_info, &settings).into();
+    let mut pages = HashMap::new();
+    pages.insert(str!("component:box"), tree.to_owned());
+    let includer = CacheIncluder(pages);
+
+    let input = "[[include-elements component:box]]\n\nBanana[[footnote]]B[[/footnote]]\n\n[[include-elements component:missing]]";
+    let tokens = crate::tokenize(input);
+    let (tree, errors) =
+        crate::parse_with_includer(&tokens, &page_info, &settings, &includer).into();
+
+    assert_eq!(
+        errors.first().map(ParseError::kind),
+        Some(ParseErrorKind::NoSuchPage),
+        "Missing page did not produce an error",
+    );
+
+    match &tree.elements[0] {
+        Element::Include { elements, .. } => {
+            assert_eq!(elements.len(), 2, "Included elements don't match expected");
+        }
+        element => panic!("First element is not an include: {element:#?}"),
+    }
+
+    let footnote_blocks = tree
+        .elements
+        .iter()
+        .filter(|element| matches!(element, Element::FootnoteBlock { .. }))
+        .count();
+
+    assert_eq!(footnote_blocks, 1, "Footnote block was not replaced");
+    assert_eq!(tree.footnotes.len(), 2, "Footnotes were not grafted");
+    assert_eq!(tree.table_of_contents.len(), 1, "Headings were not grafted");
 }
@@ -49,7 +49,7 @@ fn parse_fn<'r, 't>(
     parser.check_page_syntax()?;
     assert!(!flag_star, "Include (messy) doesn't allow star flag");
     assert!(!flag_score, "Include (messy) doesn't allow score flag");
-    assert_block_name(&BLOCK_INCLUDE_MESSY, name);
+    check_block_name(parser, &BLOCK_INCLUDE_MESSY, name)?;
 
     // Includes are handled specially, so we should never actually be
     // parsing a block here. So, we return an error.
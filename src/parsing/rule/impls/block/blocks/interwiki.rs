@@ -0,0 +1,80 @@
+/*
+ * parsing/rule/impls/block/blocks/interwiki.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Block to define additional interwiki prefixes from within wikitext.
+//!
+//! Once defined, the prefix is available to all interwiki links
+//! which come after it in the page, in the same way as prefixes
+//! provided via `InterwikiSettings`.
+//!
+//! This is only permitted if `allow_interwiki_definitions` is set.
+
+use super::prelude::*;
+use crate::url::is_url_with_settings;
+
+pub const BLOCK_INTERWIKI: BlockRule = BlockRule {
+    name: "block-interwiki",
+    accepts_names: &["interwiki"],
+    accepts_star: false,
+    accepts_score: false,
+    accepts_newlines: true,
+    parse_fn,
+};
+
+fn parse_fn<'r, 't>(
+    parser: &mut Parser<'r, 't>,
+    name: &'t str,
+    flag_star: bool,
+    flag_score: bool,
+    in_head: bool,
+) -> ParseResult<'r, 't, Elements<'t>> {
+    debug!("Parsing interwiki block (name '{name}', in-head {in_head})");
+    assert!(!flag_star, "Interwiki doesn't allow star flag");
+    assert!(!flag_score, "Interwiki doesn't allow score flag");
+    check_block_name(parser, &BLOCK_INTERWIKI, name)?;
+
+    if !parser.settings().allow_interwiki_definitions {
+        return Err(parser.make_err(ParseErrorKind::NotSupportedMode));
+    }
+
+    let mut arguments = parser.get_head_map(&BLOCK_INTERWIKI, in_head)?;
+
+    let prefix = arguments
+        .get("prefix")
+        .ok_or_else(|| parser.make_err(ParseErrorKind::BlockMissingArguments))?;
+
+    let url = arguments
+        .get("url")
+        .ok_or_else(|| parser.make_err(ParseErrorKind::BlockMissingArguments))?;
+
+    // Prefixes are matched up to the first colon, so they cannot contain one.
+    if prefix.is_empty() || prefix.contains(':') {
+        warn!("Interwiki block has invalid prefix: {prefix}");
+        return Err(parser.make_err(ParseErrorKind::BlockMalformedArguments));
+    }
+
+    if !is_url_with_settings(&url, parser.settings()) {
+        warn!("Interwiki block references non-URL: {url}");
+        return Err(parser.make_err(ParseErrorKind::InvalidUrl));
+    }
+
+    parser.push_interwiki_prefix(prefix.into_owned(), url.into_owned());
+    ok!(Elements::None)
+}
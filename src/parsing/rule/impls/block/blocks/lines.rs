@@ -40,27 +40,32 @@ fn parse_fn<'r, 't>(
     debug!("Parsing newlines block (in-head {in_head})");
     assert!(!flag_star, "Lines doesn't allow star flag");
     assert!(!flag_score, "Lines doesn't allow score flag");
-    assert_block_name(&BLOCK_LINES, name);
+    check_block_name(parser, &BLOCK_LINES, name)?;
 
-    let count = parser.get_head_value(&BLOCK_LINES, in_head, parse_count)?;
-    ok!(Element::LineBreaks(count))
+    let (count, errors) = parser.get_head_value(&BLOCK_LINES, in_head, parse_count)?;
+    ok!(Element::LineBreaks(count), errors)
 }
 
 fn parse_count<'t>(
     parser: &Parser<'_, 't>,
     argument: Option<&'t str>,
-) -> Result<NonZeroU32, ParseError> {
+) -> Result<(NonZeroU32, Vec<ParseError>), ParseError> {
     let argument = match argument {
         Some(arg) => arg.trim(),
         None => return Err(parser.make_err(ParseErrorKind::BlockMissingArguments)),
     };
 
+    let max = parser.settings().max_line_breaks;
+
     match argument.parse::<NonZeroU32>() {
-        Ok(value) if value.get() > 100 => {
-            warn!("Number of lines ({}) is too great (max 100)", value.get());
-            Err(parser.make_err(ParseErrorKind::BlockMalformedArguments))
+        Ok(value) if value.get() > max => {
+            warn!("Number of lines ({}) is too great (max {max})", value.get());
+
+            let value = NonZeroU32::new(max).unwrap_or(NonZeroU32::MIN);
+            let error = parser.make_err(ParseErrorKind::TooManyLineBreaks);
+            Ok((value, vec![error]))
         }
-        Ok(value) => Ok(value),
+        Ok(value) => Ok((value, vec![])),
         Err(error) => {
             warn!("Invalid numeric expression: {error}");
             Err(parser.make_err(ParseErrorKind::BlockMalformedArguments))
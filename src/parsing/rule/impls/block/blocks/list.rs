@@ -107,7 +107,7 @@ fn parse_list_block<'r, 't>(
     let parser = &mut ParserWrap::new(parser, AcceptsPartial::ListItem);
 
     assert!(!flag_star, "List block doesn't allow star flag");
-    assert_block_name(block_rule, name);
+    check_block_name(parser, block_rule, name)?;
 
     // "ul" means we wrap interpret as-is
     // "ul_" means we strip out any newlines or paragraph breaks
@@ -190,7 +190,7 @@ fn parse_list_item<'r, 't>(
         name, in_head, flag_score,
     );
     assert!(!flag_star, "List item block doesn't allow star flag");
-    assert_block_name(&BLOCK_LI, name);
+    check_block_name(parser, &BLOCK_LI, name)?;
 
     // "li" means we wrap interpret as-is
     // "li_" means we strip out any newlines or paragraph breaks
@@ -201,6 +201,10 @@ fn parse_list_item<'r, 't>(
     let attributes = arguments.to_attribute_map(parser.settings());
 
     // Get body elements
+    //
+    // List items cannot directly contain other list items,
+    // only within a nested [[ul]] or [[ol]].
+    let parser = &mut ParserWrap::new(parser, AcceptsPartial::None);
     let (mut elements, errors, _) = parser.get_body_elements(&BLOCK_LI, false)?.into();
 
     // Strip newlines, if desired
@@ -19,6 +19,8 @@
  */
 
 use super::prelude::*;
+use crate::tree::MathEnvironment;
+use std::borrow::Cow;
 
 pub const BLOCK_MATH: BlockRule = BlockRule {
     name: "block-math",
@@ -39,10 +41,38 @@ fn parse_fn<'r, 't>(
     debug!("Parsing math block (name '{name}', in-head {in_head})");
     assert!(!flag_star, "User doesn't allow star flag");
     assert!(!flag_score, "User doesn't allow score flag");
-    assert_block_name(&BLOCK_MATH, name);
+    check_block_name(parser, &BLOCK_MATH, name)?;
 
-    let name = parser.get_head_value(&BLOCK_MATH, in_head, |_, value| {
-        Ok(value.map(|s| cow!(s.trim())))
+    let (name, env) = parser.get_head_value(&BLOCK_MATH, in_head, |parser, value| {
+        let mut name = Vec::new();
+        let mut env = None;
+
+        // The value is the equation name, with an optional env="..." argument.
+        for part in value.unwrap_or("").split_whitespace() {
+            match part.strip_prefix("env=") {
+                Some(env_name) => {
+                    let env_name = env_name.trim_matches('"');
+                    match MathEnvironment::from_name(env_name) {
+                        Some(value) => env = Some(value),
+                        None => {
+                            warn!("Invalid math environment: '{env_name}'");
+                            return Err(
+                                parser.make_err(ParseErrorKind::BlockMalformedArguments)
+                            );
+                        }
+                    }
+                }
+                None => name.push(part),
+            }
+        }
+
+        let name = match name.as_slice() {
+            [] => None,
+            [part] => Some(cow!(*part)),
+            parts => Some(Cow::Owned(parts.join(" "))),
+        };
+
+        Ok((name, env))
     })?;
 
     let latex_source = parser.get_body_text(&BLOCK_MATH)?.trim();
@@ -53,6 +83,7 @@ fn parse_fn<'r, 't>(
     let element = Element::Math {
         name,
         latex_source: cow!(latex_source),
+        env,
     };
 
     ok!(element)
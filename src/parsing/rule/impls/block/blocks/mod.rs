@@ -25,30 +25,44 @@ mod prelude {
     pub use crate::parsing::ParseError;
     pub use crate::tree::{Container, ContainerType, Element};
 
-    #[cfg(debug_assertions)]
-    pub fn assert_generic_name(
+    /// Checks that the block name is one this rule accepts.
+    ///
+    /// A mismatch means the block rule mapping is wrong, rather than
+    /// any problem with the input. It is reported as an error in the log,
+    /// and as a parse error so the block falls back to text.
+    pub fn check_generic_name(
+        parser: &Parser,
         expected_names: &[&str],
         actual_name: &str,
         name_type: &str,
-    ) {
+        error_kind: ParseErrorKind,
+    ) -> Result<(), ParseError> {
         for name in expected_names {
             if name.eq_ignore_ascii_case(actual_name) {
-                return;
+                return Ok(());
             }
         }
 
-        panic!(
+        error!(
             "Actual {name_type} name doesn't match any expected: {expected_names:?} (was {actual_name})",
         );
-    }
 
-    #[cfg(not(debug_assertions))]
-    #[inline]
-    pub fn assert_generic_name(_: &[&str], _: &str, _: &str) {}
+        Err(parser.make_err(error_kind))
+    }
 
     #[inline]
-    pub fn assert_block_name(block_rule: &BlockRule, actual_name: &str) {
-        assert_generic_name(block_rule.accepts_names, actual_name, "block")
+    pub fn check_block_name(
+        parser: &Parser,
+        block_rule: &BlockRule,
+        actual_name: &str,
+    ) -> Result<(), ParseError> {
+        check_generic_name(
+            parser,
+            block_rule.accepts_names,
+            actual_name,
+            "block",
+            ParseErrorKind::NoSuchBlock,
+        )
     }
 }
 
@@ -83,6 +97,7 @@ mod image;
 mod include_elements;
 mod include_messy;
 mod ins;
+mod interwiki;
 mod invisible;
 mod italics;
 mod later;
@@ -92,6 +107,7 @@ mod mark;
 mod math;
 mod module;
 mod monospace;
+mod pagination;
 mod paragraph;
 mod radio;
 mod ruby;
@@ -135,6 +151,7 @@ pub use self::image::BLOCK_IMAGE;
 pub use self::include_elements::BLOCK_INCLUDE_ELEMENTS;
 pub use self::include_messy::BLOCK_INCLUDE_MESSY;
 pub use self::ins::BLOCK_INS;
+pub use self::interwiki::BLOCK_INTERWIKI;
 pub use self::invisible::BLOCK_INVISIBLE;
 pub use self::italics::BLOCK_ITALICS;
 pub use self::later::BLOCK_LATER;
@@ -144,6 +161,7 @@ pub use self::mark::BLOCK_MARK;
 pub use self::math::BLOCK_MATH;
 pub use self::module::BLOCK_MODULE;
 pub use self::monospace::BLOCK_MONOSPACE;
+pub use self::pagination::BLOCK_PAGINATION;
 pub use self::paragraph::BLOCK_PARAGRAPH;
 pub use self::radio::BLOCK_RADIO;
 pub use self::ruby::{BLOCK_RB, BLOCK_RT, BLOCK_RUBY};
@@ -23,11 +23,12 @@ use once_cell::sync::Lazy;
 use std::collections::HashMap;
 use unicase::UniCase;
 
-pub const MODULE_RULES: [ModuleRule; 6] = [
+pub const MODULE_RULES: [ModuleRule; 7] = [
     MODULE_BACKLINKS,
     MODULE_CATEGORIES,
     MODULE_CSS,
     MODULE_JOIN,
+    MODULE_LIST_PAGES,
     MODULE_PAGE_TREE,
     MODULE_RATE,
 ];
@@ -76,3 +77,21 @@ fn build_module_rule_map(module_rules: &'static [ModuleRule]) -> ModuleRuleMap {
 fn module_rule_map() {
     let _ = &*MODULE_RULE_MAP;
 }
+
+#[test]
+fn module_rule_names() {
+    // Every accepted name must map back to the rule which accepts it, in any case.
+    for module_rule in &MODULE_RULES {
+        for name in module_rule.accepts_names {
+            for name in [name.to_ascii_uppercase(), name.to_ascii_lowercase()] {
+                let found = get_module_rule_with_name(&name)
+                    .unwrap_or_else(|| panic!("No module rule found for name '{name}'"));
+
+                assert_eq!(
+                    found.name, module_rule.name,
+                    "Module name '{name}' maps to the wrong rule",
+                );
+            }
+        }
+    }
+}
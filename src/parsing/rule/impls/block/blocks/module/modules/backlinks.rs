@@ -27,12 +27,12 @@ pub const MODULE_BACKLINKS: ModuleRule = ModuleRule {
 };
 
 fn parse_fn<'r, 't>(
-    _parser: &mut Parser<'r, 't>,
+    parser: &mut Parser<'r, 't>,
     name: &'t str,
     mut arguments: Arguments<'t>,
 ) -> ParseResult<'r, 't, ModuleParseOutput<'t>> {
     debug!("Parsing backlinks module");
-    assert_module_name(&MODULE_BACKLINKS, name);
+    check_module_name(parser, &MODULE_BACKLINKS, name)?;
 
     let page = arguments.get("page");
     ok!(false; Module::Backlinks { page })
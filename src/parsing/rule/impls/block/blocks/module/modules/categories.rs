@@ -32,7 +32,7 @@ fn parse_fn<'r, 't>(
     mut arguments: Arguments<'t>,
 ) -> ParseResult<'r, 't, ModuleParseOutput<'t>> {
     debug!("Parsing categories module");
-    assert_module_name(&MODULE_CATEGORIES, name);
+    check_module_name(parser, &MODULE_CATEGORIES, name)?;
 
     let include_hidden = arguments
         .get_bool(parser, "includeHidden")?
@@ -32,7 +32,7 @@ fn parse_fn<'r, 't>(
     _arguments: Arguments<'t>,
 ) -> ParseResult<'r, 't, ModuleParseOutput<'t>> {
     debug!("Parsing categories module");
-    assert_module_name(&MODULE_CSS, name);
+    check_module_name(parser, &MODULE_CSS, name)?;
 
     let css = parser.get_body_text(&BLOCK_MODULE)?;
     let element = Element::Style(cow!(css));
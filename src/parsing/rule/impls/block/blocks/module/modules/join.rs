@@ -32,7 +32,7 @@ fn parse_fn<'r, 't>(
     mut arguments: Arguments<'t>,
 ) -> ParseResult<'r, 't, ModuleParseOutput<'t>> {
     debug!("Parsing join module");
-    assert_module_name(&MODULE_JOIN, name);
+    check_module_name(parser, &MODULE_JOIN, name)?;
 
     let button_text = arguments.get("button");
     let attributes = arguments.to_attribute_map(parser.settings());
@@ -0,0 +1,40 @@
+/*
+ * parsing/rule/impls/block/blocks/module/modules/list_pages.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::prelude::*;
+
+pub const MODULE_LIST_PAGES: ModuleRule = ModuleRule {
+    name: "module-list-pages",
+    accepts_names: &["ListPages"],
+    parse_fn,
+};
+
+fn parse_fn<'r, 't>(
+    parser: &mut Parser<'r, 't>,
+    name: &'t str,
+    arguments: Arguments<'t>,
+) -> ParseResult<'r, 't, ModuleParseOutput<'t>> {
+    debug!("Parsing list pages module");
+    check_module_name(parser, &MODULE_LIST_PAGES, name)?;
+
+    let arguments = arguments.to_hash_map();
+    let body = cow!(parser.get_body_text(&BLOCK_MODULE)?);
+    ok!(false; Module::ListPages { arguments, body })
+}
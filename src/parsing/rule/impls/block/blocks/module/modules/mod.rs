@@ -23,8 +23,18 @@ mod prelude {
     pub use crate::tree::Module;
 
     #[inline]
-    pub fn assert_module_name(module_rule: &ModuleRule, actual_name: &str) {
-        assert_generic_name(module_rule.accepts_names, actual_name, "module")
+    pub fn check_module_name(
+        parser: &Parser,
+        module_rule: &ModuleRule,
+        actual_name: &str,
+    ) -> Result<(), ParseError> {
+        check_generic_name(
+            parser,
+            module_rule.accepts_names,
+            actual_name,
+            "module",
+            ParseErrorKind::NoSuchModule,
+        )
     }
 }
 
@@ -32,6 +42,7 @@ mod backlinks;
 mod categories;
 mod css;
 mod join;
+mod list_pages;
 mod page_tree;
 mod rate;
 
@@ -39,5 +50,6 @@ pub use self::backlinks::MODULE_BACKLINKS;
 pub use self::categories::MODULE_CATEGORIES;
 pub use self::css::MODULE_CSS;
 pub use self::join::MODULE_JOIN;
+pub use self::list_pages::MODULE_LIST_PAGES;
 pub use self::page_tree::MODULE_PAGE_TREE;
 pub use self::rate::MODULE_RATE;
@@ -32,7 +32,7 @@ fn parse_fn<'r, 't>(
     mut arguments: Arguments<'t>,
 ) -> ParseResult<'r, 't, ModuleParseOutput<'t>> {
     debug!("Parsing PageTree module");
-    assert_module_name(&MODULE_PAGE_TREE, name);
+    check_module_name(parser, &MODULE_PAGE_TREE, name)?;
 
     let root = arguments.get("root");
     let depth = arguments.get_value(parser, "depth")?;
@@ -27,11 +27,11 @@ pub const MODULE_RATE: ModuleRule = ModuleRule {
 };
 
 fn parse_fn<'r, 't>(
-    _parser: &mut Parser<'r, 't>,
+    parser: &mut Parser<'r, 't>,
     name: &'t str,
     _arguments: Arguments<'t>,
 ) -> ParseResult<'r, 't, ModuleParseOutput<'t>> {
     debug!("Parsing categories module");
-    assert_module_name(&MODULE_RATE, name);
+    check_module_name(parser, &MODULE_RATE, name)?;
     ok!(false; Module::Rate)
 }
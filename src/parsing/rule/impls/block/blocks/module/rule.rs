@@ -41,7 +41,7 @@ fn parse_fn<'r, 't>(
     parser.check_page_syntax()?;
     assert!(!flag_star, "Module doesn't allow star flag");
     assert!(!flag_score, "Module doesn't allow score flag");
-    assert_block_name(&BLOCK_MODULE, name);
+    check_block_name(parser, &BLOCK_MODULE, name)?;
 
     // Get module name and arguments
     let (subname, arguments) = parser.get_head_name_map(&BLOCK_MODULE, in_head)?;
@@ -19,12 +19,13 @@
  */
 
 use super::prelude::*;
+use crate::parsing::strip_newlines;
 
 pub const BLOCK_MONOSPACE: BlockRule = BlockRule {
     name: "block-monospace",
     accepts_names: &["tt", "mono", "monospace"],
     accepts_star: false,
-    accepts_score: false,
+    accepts_score: true,
     accepts_newlines: false,
     parse_fn,
 };
@@ -36,17 +37,26 @@ fn parse_fn<'r, 't>(
     flag_score: bool,
     in_head: bool,
 ) -> ParseResult<'r, 't, Elements<'t>> {
-    debug!("Parsing monospace block (name '{name}', in-head {in_head})");
+    debug!(
+        "Parsing monospace block (name '{name}', in-head {in_head}, score {flag_score})"
+    );
     assert!(!flag_star, "Monospace doesn't allow star flag");
-    assert!(!flag_score, "Monospace doesn't allow score flag");
-    assert_block_name(&BLOCK_MONOSPACE, name);
+    check_block_name(parser, &BLOCK_MONOSPACE, name)?;
 
     let arguments = parser.get_head_map(&BLOCK_MONOSPACE, in_head)?;
 
+    // "tt" means we interpret as-is
+    // "tt_" means we strip out any newlines or paragraph breaks
+    let strip_line_breaks = flag_score;
+
     // Get body content, without paragraphs
-    let (elements, errors, paragraph_safe) =
+    let (mut elements, errors, paragraph_safe) =
         parser.get_body_elements(&BLOCK_MONOSPACE, false)?.into();
 
+    if strip_line_breaks {
+        strip_newlines(&mut elements);
+    }
+
     let element = Element::Container(Container::new(
         ContainerType::Monospace,
         elements,
@@ -0,0 +1,49 @@
+/*
+ * parsing/rule/impls/block/blocks/pagination.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::prelude::*;
+
+pub const BLOCK_PAGINATION: BlockRule = BlockRule {
+    name: "block-pagination",
+    accepts_names: &["pagination"],
+    accepts_star: false,
+    accepts_score: false,
+    accepts_newlines: true,
+    parse_fn,
+};
+
+fn parse_fn<'r, 't>(
+    parser: &mut Parser<'r, 't>,
+    name: &'t str,
+    flag_star: bool,
+    flag_score: bool,
+    in_head: bool,
+) -> ParseResult<'r, 't, Elements<'t>> {
+    debug!("Parsing pagination block (name '{name}', in-head {in_head})");
+    parser.check_page_syntax()?;
+    assert!(!flag_star, "Pagination doesn't allow star flag");
+    assert!(!flag_score, "Pagination doesn't allow score flag");
+    check_block_name(parser, &BLOCK_PAGINATION, name)?;
+
+    let arguments = parser.get_head_map(&BLOCK_PAGINATION, in_head)?;
+    let attributes = arguments.to_attribute_map(parser.settings());
+    let element = Element::Pagination { attributes };
+    ok!(false; element)
+}
@@ -40,7 +40,7 @@ fn parse_fn<'r, 't>(
     debug!("Parsing paragraph block (name '{name}', in-head {in_head})");
     assert!(!flag_star, "Paragraph doesn't allow star flag");
     assert!(!flag_score, "Paragraph doesn't allow score flag");
-    assert_block_name(&BLOCK_PARAGRAPH, name);
+    check_block_name(parser, &BLOCK_PARAGRAPH, name)?;
 
     // Gather paragraphs
     let arguments = parser.get_head_map(&BLOCK_PARAGRAPH, in_head)?;
@@ -40,7 +40,7 @@ fn parse_fn<'r, 't>(
         "Parsing radio button block (name '{name}', in-head {in_head}, star {flag_star})",
     );
     assert!(!flag_score, "Radio buttons don't allow score flag");
-    assert_block_name(&BLOCK_RADIO, name);
+    check_block_name(parser, &BLOCK_RADIO, name)?;
 
     let (name, arguments) = parser.get_head_name_map(&BLOCK_RADIO, in_head)?;
     parser.get_optional_space()?;
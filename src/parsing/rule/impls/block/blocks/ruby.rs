@@ -62,7 +62,13 @@ fn parse_block<'r, 't>(
     debug!("Parsing ruby block (name '{name}', in-head {in_head})");
     assert!(!flag_star, "Ruby doesn't allow star flag");
     assert!(!flag_score, "Ruby doesn't allow score flag");
-    assert_block_name(&BLOCK_RUBY, name);
+    check_block_name(parser, &BLOCK_RUBY, name)?;
+
+    // Compact form, such as [[ruby 漢字|かんじ]], which is the same as [[rb]]
+    if in_head && head_has_pipe(parser) {
+        debug!("Found pipe in ruby block head, parsing as shortcut");
+        return parse_shortcut_head(parser, in_head);
+    }
 
     let parser = &mut ParserWrap::new(parser, AcceptsPartial::Ruby);
     let arguments = parser.get_head_map(&BLOCK_RUBY, in_head)?;
@@ -131,7 +137,7 @@ fn parse_text<'r, 't>(
     debug!("Parsing ruby text block (name '{name}', in-head {in_head})");
     assert!(!flag_star, "Ruby text doesn't allow star flag");
     assert!(!flag_score, "Ruby text doesn't allow score flag");
-    assert_block_name(&BLOCK_RT, name);
+    check_block_name(parser, &BLOCK_RT, name)?;
 
     let arguments = parser.get_head_map(&BLOCK_RT, in_head)?;
 
@@ -161,8 +167,15 @@ fn parse_shortcut<'r, 't>(
     debug!("Parsing ruby shortcut block (name '{name}', in-head {in_head})");
     assert!(!flag_star, "Ruby shortcut doesn't allow star flag");
     assert!(!flag_score, "Ruby shortcut doesn't allow score flag");
-    assert_block_name(&BLOCK_RB, name);
+    check_block_name(parser, &BLOCK_RB, name)?;
+    parse_shortcut_head(parser, in_head)
+}
 
+/// Parses the rest of a shortcut block head, which has the form `base|ruby]]`.
+fn parse_shortcut_head<'r, 't>(
+    parser: &mut Parser<'r, 't>,
+    in_head: bool,
+) -> ParseResult<'r, 't, Elements<'t>> {
     let (base_text, ruby_text) =
         parser.get_head_value(&BLOCK_RB, in_head, |parser, value| match value {
             None => Err(parser.make_err(ParseErrorKind::BlockMissingArguments)),
@@ -192,3 +205,30 @@ fn parse_shortcut<'r, 't>(
 
     ok!(ruby)
 }
+
+/// Whether the rest of the block head contains a pipe, as in `[[ruby 漢字|かんじ]]`.
+///
+/// Pipes within quoted argument values are part of a string token,
+/// so they do not count.
+fn head_has_pipe(parser: &Parser) -> bool {
+    let mut token = parser.current();
+    let mut offset = 0;
+
+    loop {
+        match token.token {
+            Token::Pipe => return true,
+            Token::RightBlock
+            | Token::LineBreak
+            | Token::ParagraphBreak
+            | Token::InputEnd => return false,
+            _ => (),
+        }
+
+        token = match parser.look_ahead(offset) {
+            Some(token) => token,
+            None => return false,
+        };
+
+        offset += 1;
+    }
+}
@@ -19,14 +19,15 @@
  */
 
 use super::prelude::*;
-use crate::tree::AttributeMap;
+use crate::parsing::strip_newlines;
+use crate::tree::{AttributeMap, Length, LengthUnit};
 use std::borrow::Cow;
 
 pub const BLOCK_SIZE: BlockRule = BlockRule {
     name: "block-size",
     accepts_names: &["size"],
     accepts_star: false,
-    accepts_score: false,
+    accepts_score: true,
     accepts_newlines: false,
     parse_fn,
 };
@@ -38,24 +39,33 @@ fn parse_fn<'r, 't>(
     flag_score: bool,
     in_head: bool,
 ) -> ParseResult<'r, 't, Elements<'t>> {
-    debug!("Parsing size block (name '{name}', in-head {in_head})");
+    debug!("Parsing size block (name '{name}', in-head {in_head}, score {flag_score})");
     assert!(!flag_star, "Size doesn't allow star flag");
-    assert!(!flag_score, "Size doesn't allow score flag");
-    assert_block_name(&BLOCK_SIZE, name);
+    check_block_name(parser, &BLOCK_SIZE, name)?;
 
-    let size =
+    let (size, size_error) =
         parser.get_head_value(&BLOCK_SIZE, in_head, |parser, value| match value {
-            Some(size) => Ok(format!("font-size: {size};")),
+            Some(size) => parse_size(parser, size),
             None => Err(parser.make_err(ParseErrorKind::BlockMissingArguments)),
         })?;
 
+    // "size" means we interpret as-is
+    // "size_" means we strip out any newlines or paragraph breaks
+    let strip_line_breaks = flag_score;
+
     // Get body content, without paragraphs
-    let (elements, errors, paragraph_safe) =
+    let (mut elements, mut errors, paragraph_safe) =
         parser.get_body_elements(&BLOCK_SIZE, false)?.into();
 
+    errors.extend(size_error);
+
+    if strip_line_breaks {
+        strip_newlines(&mut elements);
+    }
+
     let attributes = {
         let mut map = AttributeMap::new();
-        map.insert("style", Cow::Owned(size));
+        map.insert("style", Cow::Owned(format!("font-size: {size};")));
         map
     };
 
@@ -64,3 +74,56 @@ fn parse_fn<'r, 't>(
 
     ok!(paragraph_safe; element, errors)
 }
+
+/// Validates the size given to the block, producing its CSS value.
+///
+/// Besides the size keywords, only lengths in pixels, points, percentages,
+/// `em`, and `rem` are accepted. Lengths beyond the limits in the settings
+/// are reduced to that limit, with a `SizeClamped` warning.
+fn parse_size(
+    parser: &Parser,
+    value: &str,
+) -> Result<(String, Option<ParseError>), ParseError> {
+    const KEYWORDS: [&str; 9] = [
+        "xx-small", "x-small", "small", "medium", "large", "x-large", "xx-large",
+        "larger", "smaller",
+    ];
+
+    let value = value.trim();
+    if let Some(keyword) = KEYWORDS
+        .iter()
+        .find(|keyword| keyword.eq_ignore_ascii_case(value))
+    {
+        return Ok((str!(keyword), None));
+    }
+
+    let mut length = match Length::parse(value) {
+        Some(length) => length,
+        None => {
+            warn!("Invalid size value: {value}");
+            return Err(parser.make_err(ParseErrorKind::BlockMalformedArguments));
+        }
+    };
+
+    let limits = parser.settings().size;
+    let max = match length.unit {
+        LengthUnit::Pixels => f64::from(limits.max_pixels),
+        LengthUnit::Points => f64::from(limits.max_pixels) * 0.75,
+        LengthUnit::Percent => f64::from(limits.max_percent),
+        LengthUnit::Em | LengthUnit::Rem => f64::from(limits.max_percent) / 100.0,
+        _ => {
+            warn!("Size unit '{}' is not permitted", length.unit.name());
+            return Err(parser.make_err(ParseErrorKind::BlockMalformedArguments));
+        }
+    };
+
+    if length.value > max {
+        warn!("Size ({length}) is too great (max {max})");
+        length.value = max;
+
+        let error = parser.make_err(ParseErrorKind::SizeClamped);
+        return Ok((length.to_string(), Some(error)));
+    }
+
+    Ok((length.to_string(), None))
+}
@@ -39,7 +39,7 @@ fn parse_fn<'r, 't>(
 ) -> ParseResult<'r, 't, Elements<'t>> {
     debug!("Parsing span block (name '{name}', in-head {in_head})");
     assert!(!flag_star, "Span doesn't allow star flag");
-    assert_block_name(&BLOCK_SPAN, name);
+    check_block_name(parser, &BLOCK_SPAN, name)?;
 
     let arguments = parser.get_head_map(&BLOCK_SPAN, in_head)?;
 
@@ -19,12 +19,13 @@
  */
 
 use super::prelude::*;
+use crate::parsing::strip_newlines;
 
 pub const BLOCK_STRIKETHROUGH: BlockRule = BlockRule {
     name: "block-strikethrough",
     accepts_names: &["s", "strikethrough"],
     accepts_star: false,
-    accepts_score: false,
+    accepts_score: true,
     accepts_newlines: false,
     parse_fn,
 };
@@ -36,18 +37,25 @@ fn parse_fn<'r, 't>(
     flag_score: bool,
     in_head: bool,
 ) -> ParseResult<'r, 't, Elements<'t>> {
-    debug!("Parsing strikethrough block (name '{name}', in-head {in_head})");
+    debug!("Parsing strikethrough block (name '{name}', in-head {in_head}, score {flag_score})");
     assert!(!flag_star, "Strikethrough doesn't allow star flag");
-    assert!(!flag_score, "Strikethrough doesn't allow score flag");
-    assert_block_name(&BLOCK_STRIKETHROUGH, name);
+    check_block_name(parser, &BLOCK_STRIKETHROUGH, name)?;
 
     let arguments = parser.get_head_map(&BLOCK_STRIKETHROUGH, in_head)?;
 
+    // "s" means we interpret as-is
+    // "s_" means we strip out any newlines or paragraph breaks
+    let strip_line_breaks = flag_score;
+
     // Get body content, without paragraphs
-    let (elements, errors, paragraph_safe) = parser
+    let (mut elements, errors, paragraph_safe) = parser
         .get_body_elements(&BLOCK_STRIKETHROUGH, false)?
         .into();
 
+    if strip_line_breaks {
+        strip_newlines(&mut elements);
+    }
+
     let element = Element::Container(Container::new(
         ContainerType::Strikethrough,
         elements,
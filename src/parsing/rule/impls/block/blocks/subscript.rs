@@ -19,12 +19,13 @@
  */
 
 use super::prelude::*;
+use crate::parsing::strip_newlines;
 
 pub const BLOCK_SUBSCRIPT: BlockRule = BlockRule {
     name: "block-subscript",
     accepts_names: &["sub", "subscript"],
     accepts_star: false,
-    accepts_score: false,
+    accepts_score: true,
     accepts_newlines: false,
     parse_fn,
 };
@@ -36,17 +37,26 @@ fn parse_fn<'r, 't>(
     flag_score: bool,
     in_head: bool,
 ) -> ParseResult<'r, 't, Elements<'t>> {
-    debug!("Parsing subscript block (name '{name}', in-head {in_head})");
+    debug!(
+        "Parsing subscript block (name '{name}', in-head {in_head}, score {flag_score})"
+    );
     assert!(!flag_star, "Subscript doesn't allow star flag");
-    assert!(!flag_score, "Subscript doesn't allow score flag");
-    assert_block_name(&BLOCK_SUBSCRIPT, name);
+    check_block_name(parser, &BLOCK_SUBSCRIPT, name)?;
 
     let arguments = parser.get_head_map(&BLOCK_SUBSCRIPT, in_head)?;
 
+    // "sub" means we interpret as-is
+    // "sub_" means we strip out any newlines or paragraph breaks
+    let strip_line_breaks = flag_score;
+
     // Get body content, without paragraphs
-    let (elements, errors, paragraph_safe) =
+    let (mut elements, errors, paragraph_safe) =
         parser.get_body_elements(&BLOCK_SUBSCRIPT, false)?.into();
 
+    if strip_line_breaks {
+        strip_newlines(&mut elements);
+    }
+
     let element = Element::Container(Container::new(
         ContainerType::Subscript,
         elements,
@@ -21,7 +21,8 @@
 use super::prelude::*;
 use crate::parsing::{strip_whitespace, ParserWrap};
 use crate::tree::{
-    AcceptsPartial, AttributeMap, PartialElement, Table, TableCell, TableRow,
+    AcceptsPartial, Alignment, AttributeMap, Length, PartialElement, Table, TableCell,
+    TableRow, VerticalAlignment,
 };
 use std::num::NonZeroU32;
 
@@ -91,7 +92,7 @@ where
         !flag_score,
         "Block for {description} doesn't allow score flag",
     );
-    assert_block_name(block_rule, name);
+    check_block_name(parser, block_rule, name)?;
 
     // Get attributes
     let arguments = parser.get_head_map(block_rule, in_head)?;
@@ -222,7 +223,7 @@ fn parse_cell_regular<'r, 't>(
         (&BLOCK_TABLE_CELL_REGULAR, "table cell (regular)"),
     )?;
 
-    parse_cell(elements, attributes, errors, false)
+    parse_cell(parser, elements, attributes, errors, false)
 }
 
 fn parse_cell_header<'r, 't>(
@@ -248,13 +249,14 @@ fn parse_cell_header<'r, 't>(
         (&BLOCK_TABLE_CELL_HEADER, "table cell (header)"),
     )?;
 
-    parse_cell(elements, attributes, errors, true)
+    parse_cell(parser, elements, attributes, errors, true)
 }
 
 fn parse_cell<'r, 't>(
+    parser: &Parser<'r, 't>,
     mut elements: Vec<Element<'t>>,
     mut attributes: AttributeMap<'t>,
-    errors: Vec<ParseError>,
+    mut errors: Vec<ParseError>,
     header: bool,
 ) -> ParseResult<'r, 't, Elements<'t>> {
     // Remove leading and trailing whitespace
@@ -263,14 +265,57 @@ fn parse_cell<'r, 't>(
     // Extract column-span if specified via attributes.
     // If not specified, then the default.
     let column_span = match attributes.remove("colspan") {
-        Some(value) => value.parse().unwrap_or(NonZeroU32::new(1).unwrap()),
-        None => NonZeroU32::new(1).unwrap(),
+        Some(value) => value.parse().unwrap_or(NonZeroU32::MIN),
+        None => NonZeroU32::MIN,
+    };
+
+    // Same for row-span.
+    let row_span = match attributes.remove("rowspan") {
+        Some(value) => value.parse().unwrap_or(NonZeroU32::MIN),
+        None => NonZeroU32::MIN,
     };
 
+    // Extract alignment if it's a known value.
+    // Otherwise it's left as a regular attribute.
+    let align = attributes
+        .get()
+        .get("align")
+        .and_then(|value| Alignment::from_name(value));
+
+    if align.is_some() {
+        attributes.remove("align");
+    }
+
+    // Extract vertical alignment and width.
+    // The width must be a valid CSS length.
+    //
+    // Unlike alignment, invalid values are removed rather than being
+    // passed through, since they would only be ignored by the browser.
+    let mut malformed = false;
+    let valign = attributes.remove("valign").and_then(|value| {
+        let valign = VerticalAlignment::from_name(&value);
+        malformed |= valign.is_none();
+        valign
+    });
+
+    let width = attributes.remove("width").and_then(|value| {
+        let width = Length::parse(&value);
+        malformed |= width.is_none();
+        width
+    });
+
+    if malformed {
+        warn!("Invalid vertical alignment or width for table cell");
+        errors.push(parser.make_err(ParseErrorKind::BlockMalformedArguments));
+    }
+
     let element = Element::Partial(PartialElement::TableCell(TableCell {
         header,
         column_span,
-        align: None,
+        row_span,
+        align,
+        valign,
+        width,
         elements,
         attributes,
     }));
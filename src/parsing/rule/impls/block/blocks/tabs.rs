@@ -35,7 +35,7 @@ pub const BLOCK_TAB: BlockRule = BlockRule {
     name: "block-tab",
     accepts_names: &["tab"],
     accepts_star: false,
-    accepts_score: false,
+    accepts_score: true,
     accepts_newlines: true,
     parse_fn: parse_tab,
 };
@@ -52,7 +52,7 @@ fn parse_tabview<'r, 't>(
     debug!("Parsing tabview block (name '{name}', in-head {in_head})");
     assert!(!flag_star, "Tabview doesn't allow star flag");
     assert!(!flag_score, "Tabview doesn't allow score flag");
-    assert_block_name(&BLOCK_TABVIEW, name);
+    check_block_name(parser, &BLOCK_TABVIEW, name)?;
 
     parser.get_head_none(&BLOCK_TABVIEW, in_head)?;
 
@@ -89,10 +89,9 @@ fn parse_tab<'r, 't>(
     flag_score: bool,
     in_head: bool,
 ) -> ParseResult<'r, 't, Elements<'t>> {
-    debug!("Parsing tab block (name '{name}', in-head {in_head})");
+    debug!("Parsing tab block (name '{name}', in-head {in_head}, score {flag_score})");
     assert!(!flag_star, "Tab doesn't allow star flag");
-    assert!(!flag_score, "Tab doesn't allow score flag");
-    assert_block_name(&BLOCK_TAB, name);
+    check_block_name(parser, &BLOCK_TAB, name)?;
 
     let label =
         parser.get_head_value(&BLOCK_TAB, in_head, |parser, value| match value {
@@ -100,7 +99,8 @@ fn parse_tab<'r, 't>(
             None => Err(parser.make_err(ParseErrorKind::BlockMissingArguments)),
         })?;
 
-    let (elements, errors, _) = parser.get_body_elements(&BLOCK_TAB, true)?.into();
+    // Get body content, with paragraphs unless the score flag is set
+    let (elements, errors, _) = parser.get_body_elements(&BLOCK_TAB, !flag_score)?.into();
 
     // Build element and return
     let element = Element::Partial(PartialElement::Tab(Tab {
@@ -41,7 +41,7 @@ fn parse_fn<'r, 't>(
     debug!("Parsing target block (name '{name}', in-head {in_head})");
     assert!(!flag_star, "Target doesn't allow star flag");
     assert!(!flag_score, "Target doesn't allow score flag");
-    assert_block_name(&BLOCK_TARGET, name);
+    check_block_name(parser, &BLOCK_TARGET, name)?;
 
     let name =
         parser.get_head_value(&BLOCK_TARGET, in_head, |parser, value| match value {
@@ -55,8 +55,9 @@ fn parse_fn<'r, 't>(
     }
 
     // Isolate ID if requested
-    let name = if parser.settings().isolate_user_ids {
-        Cow::Owned(isolate_ids(name))
+    let settings = parser.settings();
+    let name = if settings.isolate_user_ids {
+        Cow::Owned(isolate_ids(name, &settings.id_isolation.prefix))
     } else {
         cow!(name)
     };
@@ -19,7 +19,7 @@
  */
 
 use super::prelude::*;
-use crate::tree::FloatAlignment;
+use crate::tree::{FloatAlignment, HeadingLevel};
 
 pub const BLOCK_TABLE_OF_CONTENTS: BlockRule = BlockRule {
     name: "block-toc",
@@ -41,11 +41,39 @@ fn parse_fn<'r, 't>(
     parser.check_page_syntax()?;
     assert!(!flag_star, "Table of Contents doesn't allow star flag");
     assert!(!flag_score, "Table of Contents doesn't allow score flag");
-    assert_block_name(&BLOCK_TABLE_OF_CONTENTS, name);
+    check_block_name(parser, &BLOCK_TABLE_OF_CONTENTS, name)?;
+
+    let mut arguments = parser.get_head_map(&BLOCK_TABLE_OF_CONTENTS, in_head)?;
+    let min_level = get_heading_level(parser, &mut arguments, "min")?;
+    let max_level = get_heading_level(parser, &mut arguments, "max")?;
+
+    if let (Some(min_level), Some(max_level)) = (min_level, max_level) {
+        if min_level.value() > max_level.value() {
+            return Err(parser.make_err(ParseErrorKind::BlockMalformedArguments));
+        }
+    }
 
-    let arguments = parser.get_head_map(&BLOCK_TABLE_OF_CONTENTS, in_head)?;
     let attributes = arguments.to_attribute_map(parser.settings());
     let align = FloatAlignment::parse(name).map(|float| float.align);
-    let element = Element::TableOfContents { align, attributes };
+    let element = Element::TableOfContents {
+        align,
+        attributes,
+        min_level,
+        max_level,
+    };
     ok!(false; element)
 }
+
+fn get_heading_level<'t>(
+    parser: &Parser<'_, 't>,
+    arguments: &mut Arguments<'t>,
+    key: &'t str,
+) -> Result<Option<HeadingLevel>, ParseError> {
+    match arguments.get_value::<u8>(parser, key)? {
+        Some(value) => match HeadingLevel::try_from(value) {
+            Ok(level) => Ok(Some(level)),
+            Err(_) => Err(parser.make_err(ParseErrorKind::BlockMalformedArguments)),
+        },
+        None => Ok(None),
+    }
+}
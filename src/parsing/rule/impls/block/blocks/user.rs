@@ -38,7 +38,7 @@ fn parse_fn<'r, 't>(
 ) -> ParseResult<'r, 't, Elements<'t>> {
     debug!("Parsing user block (name '{name}', in-head {in_head})");
     assert!(!flag_score, "User doesn't allow score flag");
-    assert_block_name(&BLOCK_USER, name);
+    check_block_name(parser, &BLOCK_USER, name)?;
 
     let name =
         parser.get_head_value(&BLOCK_USER, in_head, |parser, value| match value {
@@ -23,7 +23,7 @@ use once_cell::sync::Lazy;
 use std::collections::HashMap;
 use unicase::UniCase;
 
-pub const BLOCK_RULES: [BlockRule; 60] = [
+pub const BLOCK_RULES: [BlockRule; 62] = [
     BLOCK_ALIGN_CENTER,
     BLOCK_ALIGN_JUSTIFY,
     BLOCK_ALIGN_LEFT,
@@ -53,6 +53,7 @@ pub const BLOCK_RULES: [BlockRule; 60] = [
     BLOCK_INCLUDE_ELEMENTS,
     BLOCK_INCLUDE_MESSY,
     BLOCK_INS,
+    BLOCK_INTERWIKI,
     BLOCK_INVISIBLE,
     BLOCK_ITALICS,
     BLOCK_LATER,
@@ -63,6 +64,7 @@ pub const BLOCK_RULES: [BlockRule; 60] = [
     BLOCK_MODULE,
     BLOCK_MONOSPACE,
     BLOCK_OL,
+    BLOCK_PAGINATION,
     BLOCK_PARAGRAPH,
     BLOCK_RADIO,
     BLOCK_RB,
@@ -99,6 +101,25 @@ pub fn get_block_rule_with_name(name: &str) -> Option<&'static BlockRule> {
     BLOCK_RULE_MAP.get(&name).copied()
 }
 
+/// Determines if the block with the given name accepts the score flag (`_`).
+///
+/// Returns `false` if there is no block with this name.
+pub fn block_accepts_score(name: &str) -> bool {
+    match get_block_rule_with_name(name) {
+        Some(block_rule) => block_rule.accepts_score,
+        None => false,
+    }
+}
+
+/// Returns the names of all blocks which accept the score flag (`_`).
+pub fn get_score_block_names() -> Vec<&'static str> {
+    BLOCK_RULES
+        .iter()
+        .filter(|block_rule| block_rule.accepts_score)
+        .flat_map(|block_rule| block_rule.accepts_names.iter().copied())
+        .collect()
+}
+
 fn build_block_rule_map(block_rules: &'static [BlockRule]) -> BlockRuleMap {
     let mut map = HashMap::new();
 
@@ -131,3 +152,89 @@ fn build_block_rule_map(block_rules: &'static [BlockRule]) -> BlockRuleMap {
 fn block_rule_map() {
     let _ = &*BLOCK_RULE_MAP;
 }
+
+#[test]
+fn block_rule_names() {
+    // Every accepted name must map back to the rule which accepts it,
+    // in any case, and with the score flag if the rule allows it.
+    for block_rule in &BLOCK_RULES {
+        for name in block_rule.accepts_names {
+            let names = [
+                str!(name),
+                name.to_ascii_uppercase(),
+                name.to_ascii_lowercase(),
+            ];
+
+            for name in names {
+                let found = get_block_rule_with_name(&name)
+                    .unwrap_or_else(|| panic!("No block rule found for name '{name}'"));
+
+                assert_eq!(
+                    found.name, block_rule.name,
+                    "Block name '{name}' maps to the wrong rule",
+                );
+
+                if block_rule.accepts_score {
+                    let found = get_block_rule_with_name(&format!("{name}_"))
+                        .unwrap_or_else(|| {
+                            panic!("No block rule found for name '{name}_'")
+                        });
+
+                    assert_eq!(
+                        found.name, block_rule.name,
+                        "Block name '{name}_' maps to the wrong rule",
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn score_blocks() {
+    assert!(block_accepts_score("div"));
+    assert!(block_accepts_score("SPAN"));
+    assert!(block_accepts_score("blockquote"));
+    assert!(block_accepts_score("b"));
+    assert!(!block_accepts_score("code"));
+    assert!(!block_accepts_score("nonexistent"));
+
+    let names = get_score_block_names();
+    assert!(names.contains(&"collapsible"));
+    assert!(names.contains(&"size"));
+    assert!(!names.contains(&"module"));
+
+    // Every name must agree with the lookup
+    for name in names {
+        assert!(block_accepts_score(name));
+    }
+}
+
+#[test]
+fn blocks_no_panic() {
+    use crate::data::PageInfo;
+    use crate::layout::Layout;
+    use crate::settings::{WikitextMode, WikitextSettings};
+
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+
+    // Every block, with every flag, with and without arguments and a body.
+    // Flags which a block doesn't accept must be rejected before its parse
+    // function is called, since it asserts that they are absent.
+    for block_rule in &BLOCK_RULES {
+        for &name in block_rule.accepts_names {
+            for (prefix, suffix) in [("", ""), ("*", ""), ("", "_"), ("*", "_")] {
+                for input in [
+                    format!("[[{prefix}{name}{suffix}]]"),
+                    format!("[[{prefix}{name}{suffix} x]]\nA\n[[/{name}]]"),
+                    format!("[[{prefix}{name}{suffix} a=\"b\" c]]A[[/{name}]]"),
+                    format!("[[{prefix}{name}{suffix}]][[{name}]]"),
+                ] {
+                    let tokens = crate::tokenize(&input);
+                    let _ = crate::parse(&tokens, &page_info, &settings);
+                }
+            }
+        }
+    }
+}
@@ -38,6 +38,7 @@ mod rule;
 pub mod blocks;
 
 pub use self::arguments::Arguments;
+pub use self::mapping::{block_accepts_score, get_score_block_names};
 pub use self::rule::{RULE_BLOCK, RULE_BLOCK_SKIP_NEWLINE, RULE_BLOCK_STAR};
 
 /// Define a rule for how to parse a block.
@@ -65,6 +66,10 @@ pub struct BlockRule {
     ///
     /// For instance, div can be invoked as both
     /// `[[div]]` and `[[div_]]`.
+    ///
+    /// Blocks which wrap their contents in paragraphs (such as `[[div]]`)
+    /// do not do so with this flag, and other container blocks
+    /// (such as `[[span]]`) strip leading and trailing newlines instead.
     accepts_score: bool,
 
     /// Whether this block optionally allows its head and tail to be separated by newlines.
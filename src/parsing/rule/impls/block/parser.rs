@@ -25,7 +25,7 @@ use crate::parsing::condition::ParseCondition;
 use crate::parsing::consume::consume;
 use crate::parsing::{
     gather_paragraphs, parse_string, ExtractedToken, ParseError, ParseErrorKind,
-    ParseResult, Parser, Token,
+    ParseResult, ParseSuggestion, Parser, Token,
 };
 use crate::tree::Element;
 use once_cell::sync::Lazy;
@@ -195,7 +195,9 @@ where
         debug!("Getting block body as text (rule {})", block_rule.name);
 
         // State variables for collecting span
-        let (start, end) = self.get_body_generic(block_rule, |_| Ok(()))?;
+        let (start, end) = self
+            .get_body_generic(block_rule, |_| Ok(()))
+            .map_err(|error| unclosed_block_error(error, block_rule))?;
         let slice = self.full_text().slice_partial(start, end);
         Ok(slice)
     }
@@ -211,11 +213,13 @@ where
             block_rule.name, as_paragraphs,
         );
 
-        if as_paragraphs {
+        let result = if as_paragraphs {
             self.get_body_elements_paragraphs(block_rule)
         } else {
             self.get_body_elements_no_paragraphs(block_rule)
-        }
+        };
+
+        result.map_err(|error| unclosed_block_error(error, block_rule))
     }
 
     fn get_body_elements_paragraphs(
@@ -451,3 +455,13 @@ where
         self.set_rule(block_rule.rule());
     }
 }
+
+/// Adds a suggestion to close the block, if the body ran until the end of input.
+fn unclosed_block_error(error: ParseError, block_rule: &BlockRule) -> ParseError {
+    if error.kind() != ParseErrorKind::EndOfInput {
+        return error;
+    }
+
+    let expected = format!("[[/{}]]", block_rule.accepts_names[0]);
+    error.with_suggestion(ParseSuggestion::UnclosedBlock { expected })
+}
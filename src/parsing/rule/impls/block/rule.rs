@@ -69,8 +69,10 @@ fn block_skip<'r, 't>(parser: &mut Parser<'r, 't>) -> ParseResult<'r, 't, Elemen
 
         // Get the block rule: if it accepts newlines, then we consume here
         match get_block_rule_with_name(name) {
-            Some(block_rule) => Ok(block_rule.accepts_newlines),
-            None => Ok(false),
+            Some(block_rule) if parser.rules().is_enabled(block_rule.name) => {
+                Ok(block_rule.accepts_newlines)
+            }
+            _ => Ok(false),
         }
     });
 
@@ -117,6 +119,12 @@ where
         None => return Err(parser.make_err(ParseErrorKind::NoSuchBlock)),
     };
 
+    // Check that this block hasn't been disabled
+    if !parser.rules().is_enabled(block.name) {
+        debug!("Block rule {} is disabled", block.name);
+        return Err(parser.make_err(ParseErrorKind::BlockDisabled));
+    }
+
     // Set block rule for better errors
     parser.set_block(block);
 
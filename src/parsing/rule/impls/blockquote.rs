@@ -39,6 +39,7 @@ fn try_consume_fn<'r, 't>(
     // Context variables
     let mut depths = Vec::new();
     let mut errors = Vec::new();
+    let mut attribution = None;
 
     // Produce a depth list with elements
     loop {
@@ -56,12 +57,39 @@ fn try_consume_fn<'r, 't>(
         parser.step()?;
         parser.get_optional_space()?; // allow whitespace after ">"
 
+        // Only the final line can be an attribution, so put back the previous one
+        if let Some(line) = attribution.take() {
+            depths.push(restore_attribution_line(line));
+        }
+
         // Check that the depth isn't obscenely deep, to avoid DOS attacks via stack overflow.
         if depth > MAX_BLOCKQUOTE_DEPTH {
             debug!("Native blockquote has a depth ({depth}) greater than the maximum ({MAX_BLOCKQUOTE_DEPTH})! Failing");
             return Err(parser.make_err(ParseErrorKind::BlockquoteDepthExceeded));
         }
 
+        // Check if this is an attribution line, such as "> -- Author"
+        //
+        // The dash is kept as it would otherwise be rendered, in case
+        // this line turns out not to be an attribution.
+        let attribution_prefix = match (parser.current(), parser.look_ahead(0)) {
+            (dash, Some(next))
+                if depth == 1
+                    && dash.token == Token::DoubleDash
+                    && next.token == Token::Whitespace =>
+            {
+                let dash = if parser.settings().typography.em_dash {
+                    "\u{2014}"
+                } else {
+                    dash.slice
+                };
+
+                parser.step_n(2)?;
+                Some((dash, next.slice))
+            }
+            _ => None,
+        };
+
         // Parse elements until we hit the end of the line
         let mut paragraph_safe = true;
         let mut elements = collect_consume(
@@ -77,6 +105,12 @@ fn try_consume_fn<'r, 't>(
         )?
         .chain(&mut errors, &mut paragraph_safe);
 
+        // Hold attribution lines until we know if they're last
+        if let Some((dash, space)) = attribution_prefix {
+            attribution = Some((dash, space, elements, paragraph_safe));
+            continue;
+        }
+
         // Add a line break for the end of the line
         elements.push(Element::LineBreak);
 
@@ -89,20 +123,56 @@ fn try_consume_fn<'r, 't>(
         depths.push((depth - 1, (), (elements, paragraph_safe)))
     }
 
+    // An attribution on its own is just an ordinary line
+    if depths.is_empty() {
+        if let Some(line) = attribution.take() {
+            depths.push(restore_attribution_line(line));
+        }
+    }
+
     // This blockquote has no rows, so the rule fails
     if depths.is_empty() {
         return Err(parser.make_err(ParseErrorKind::RuleFailed));
     }
 
     let depth_lists = process_depths((), depths);
-    let elements: Vec<Element> = depth_lists
+    let mut elements: Vec<Element> = depth_lists
         .into_iter()
         .map(|(_, depth_list)| build_blockquote_element(depth_list))
         .collect();
 
+    // Add the attribution to the outermost blockquote
+    if let Some((_, _, attribution_elements, _)) = attribution {
+        if let Some(Element::Container(container)) = elements.last_mut() {
+            container
+                .elements_mut()
+                .push(Element::Container(Container::new(
+                    ContainerType::BlockquoteAttribution,
+                    attribution_elements,
+                    AttributeMap::new(),
+                )));
+        }
+    }
+
     ok!(false; elements, errors)
 }
 
+/// Converts a held attribution line back into a regular blockquote line.
+fn restore_attribution_line<'t>(
+    (dash, space, mut elements, paragraph_safe): (
+        &'t str,
+        &'t str,
+        Vec<Element<'t>>,
+        bool,
+    ),
+) -> (usize, (), (Vec<Element<'t>>, bool)) {
+    // Re-add the dash and whitespace which were consumed
+    elements.splice(0..0, [text!(dash), text!(space)]);
+    elements.push(Element::LineBreak);
+
+    (0, (), (elements, paragraph_safe))
+}
+
 fn build_blockquote_element(list: DepthList<(), (Vec<Element>, bool)>) -> Element {
     let mut stack = ParagraphStack::new();
 
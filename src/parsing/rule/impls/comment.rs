@@ -31,6 +31,9 @@ fn try_consume_fn<'r, 't>(
 ) -> ParseResult<'r, 't, Elements<'t>> {
     debug!("Consuming tokens until end of comment");
 
+    // In case the comment is never closed, so the warning points at its start
+    let unclosed_error = parser.make_err(ParseErrorKind::UnclosedComment);
+
     check_step(parser, Token::LeftComment)?;
 
     loop {
@@ -50,10 +53,10 @@ fn try_consume_fn<'r, 't>(
                 return ok!(Elements::None);
             }
 
-            // Hit the end of the input, abort
+            // Hit the end of the input, close the comment with a warning
             Token::InputEnd => {
-                trace!("Reached end of input, aborting");
-                return Err(parser.make_err(ParseErrorKind::EndOfInput));
+                warn!("Reached end of input inside comment, closing it");
+                return ok!(Elements::None, vec![unclosed_error]);
             }
 
             // Consume any other token
@@ -27,8 +27,13 @@ pub const RULE_DASH: Rule = Rule {
 };
 
 fn try_consume_fn<'r, 't>(
-    _parser: &mut Parser<'r, 't>,
+    parser: &mut Parser<'r, 't>,
 ) -> ParseResult<'r, 't, Elements<'t>> {
+    if !parser.settings().typography.em_dash {
+        debug!("Em dashes are disabled, keeping token as text");
+        return ok!(text!(parser.current().slice));
+    }
+
     debug!("Consuming token to create an em dash");
 
     // — - EM DASH
@@ -19,6 +19,7 @@
  */
 
 use super::prelude::*;
+use super::RULE_LIST;
 use crate::parsing::{strip_whitespace, Token};
 use crate::tree::DefinitionListItem;
 
@@ -162,11 +163,26 @@ fn parse_item<'r, 't>(
     let should_break = match last.token {
         Token::ParagraphBreak | Token::InputEnd => true,
         Token::LineBreak => false,
-        _ => panic!("Invalid close token: {}", last.token.name()),
+        _ => {
+            error!("Invalid close token: {}", last.token.name());
+            return Err(parser.make_err(ParseErrorKind::RuleFailed));
+        }
     };
 
     strip_whitespace(&mut value_elements);
 
+    // A list starting on the next line is nested within the value
+    let should_break = if !should_break
+        && matches!(
+            parser.current().token,
+            Token::BulletItem | Token::NumberedItem,
+        ) {
+        parse_nested_list(parser, &mut value_elements)?
+            .chain(&mut errors, &mut _paragraph_safe)
+    } else {
+        should_break
+    };
+
     // Build and return
     let item = DefinitionListItem {
         key_string: cow!(key_string),
@@ -176,3 +192,31 @@ fn parse_item<'r, 't>(
 
     ok!(false; (item, should_break), errors)
 }
+
+fn parse_nested_list<'r, 't>(
+    parser: &mut Parser<'r, 't>,
+    value_elements: &mut Vec<Element<'t>>,
+) -> ParseResult<'r, 't, bool> {
+    trace!("Trying to parse a list nested in a definition list value");
+
+    let old_remaining = parser.remaining();
+    let (elements, errors, _) = match RULE_LIST.try_consume(parser) {
+        Ok(success) => success.into(),
+        Err(error) => {
+            warn!("Failed to parse nested list, ending definition list item: {error:?}");
+            return ok!(false; false);
+        }
+    };
+
+    value_elements.extend(elements);
+
+    // The list consumes the token ending its last line, which
+    // determines whether the definition list continues.
+    //
+    // Since the remaining slice excludes the current token,
+    // the last consumed token is the one before the new current token.
+    let consumed = old_remaining.len() - parser.remaining().len();
+    let should_break = old_remaining[consumed - 2].token != Token::LineBreak;
+
+    ok!(false; should_break, errors)
+}
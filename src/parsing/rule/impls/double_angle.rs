@@ -35,6 +35,12 @@ fn try_consume_fn<'r, 't>(
         current.token.name(),
     );
 
+    let is_guillemet = current.token == Token::LeftDoubleAngle || current.slice == ">>";
+    if is_guillemet && !parser.settings().typography.guillemets {
+        debug!("Guillemets are disabled, keeping token as text");
+        return ok!(text!(current.slice));
+    }
+
     match current.token {
         // « - LEFT-POINTING DOUBLE ANGLE QUOTATION MARK
         Token::LeftDoubleAngle => ok!(text!("\u{0ab}")),
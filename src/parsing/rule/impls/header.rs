@@ -73,7 +73,10 @@ fn try_consume_fn<'r, 't>(
         // We unwrap it so we can get the elements composing the name.
         let elements = match elements {
             Elements::Single(Element::Container(ref container)) => container.elements(),
-            _ => panic!("Collected heading produced a non-single non-container element"),
+            _ => {
+                error!("Collected heading produced a non-single non-container element");
+                return Err(parser.make_err(ParseErrorKind::RuleFailed));
+            }
         };
 
         // Create table of contents entry with the given level and name.
@@ -54,16 +54,8 @@ fn try_consume_fn<'r, 't>(
     )?;
 
     // Determine if this is an anchor link or fake link
-    let url = if url.is_empty() {
-        Cow::Borrowed("javascript:;")
-    } else {
-        // Make URL "#name", where 'name' is normalized.
-        let mut url = str!(url);
-        normalize(&mut url);
-        url.insert(0, '#');
-
-        Cow::Owned(url)
-    };
+    let name = url;
+    let url = anchor_url(name);
 
     // Gather label for link
     let label = collect_text(
@@ -82,12 +74,48 @@ fn try_consume_fn<'r, 't>(
     // Trim label
     let label = label.trim();
 
+    // If the label is empty, then it's the anchor name.
+    // If this is a fake link, then there's nothing to show, so it's removed.
+    let mut errors = Vec::new();
+    let label = if label.is_empty() {
+        warn!("Anchor link label is empty, using anchor name instead");
+        errors.push(parser.make_err(ParseErrorKind::LinkLabelEmpty));
+
+        if name.is_empty() {
+            return ok!(Elements::None, errors);
+        }
+
+        LinkLabel::Url(Some(cow!(name)))
+    } else {
+        LinkLabel::Text(cow!(label))
+    };
+
     // Build and return link element
-    ok!(Element::Link {
-        ltype: LinkType::Anchor,
-        link: LinkLocation::Url(url),
-        extra: None,
-        label: LinkLabel::Text(cow!(label)),
-        target: None,
-    })
+    ok!(
+        Element::Link {
+            ltype: LinkType::Anchor,
+            link: LinkLocation::Url(url),
+            extra: None,
+            label,
+            target: None,
+        },
+        errors,
+    )
+}
+
+/// Builds the URL for a link to the given anchor name.
+///
+/// The name is normalized, and if it is empty, then this is
+/// a fake link, which goes nowhere.
+pub fn anchor_url(name: &str) -> Cow<'static, str> {
+    if name.is_empty() {
+        Cow::Borrowed("javascript:;")
+    } else {
+        // Make URL "#name", where 'name' is normalized.
+        let mut url = str!(name);
+        normalize(&mut url);
+        url.insert(0, '#');
+
+        Cow::Owned(url)
+    }
 }
@@ -24,9 +24,11 @@
 //! This method allows any URL, either opening in a new tab or not.
 //! Its syntax is `[https://example.com/ Label text]`.
 
+use super::link_anchor::anchor_url;
 use super::prelude::*;
+use crate::settings::WikitextSettings;
 use crate::tree::{AnchorTarget, LinkLabel, LinkLocation, LinkType};
-use crate::url::is_url;
+use crate::url::is_url_with_settings;
 
 pub const RULE_LINK_SINGLE: Rule = Rule {
     name: "link-single",
@@ -81,10 +83,26 @@ fn try_consume_link<'r, 't>(
         None,
     )?;
 
-    // Return error if the resultant URL is not valid.
-    if !url_valid(url) {
+    // Determine what kind of link this is.
+    //
+    // Anchor and interwiki links are also accepted here, so that
+    // they can be opened in a new tab using "[*".
+    let (link, ltype) = if let Some(name) = url.strip_prefix('#') {
+        (LinkLocation::Url(anchor_url(name)), LinkType::Anchor)
+    } else if url.starts_with('!') {
+        match LinkLocation::parse_interwiki_with(
+            cow!(url),
+            &parser.interwiki(),
+            parser.settings(),
+        ) {
+            Some(result) => result,
+            None => return Err(parser.make_err(ParseErrorKind::InvalidUrl)),
+        }
+    } else if url_valid(url, parser.settings()) {
+        (LinkLocation::Url(cow!(url)), LinkType::Direct)
+    } else {
         return Err(parser.make_err(ParseErrorKind::InvalidUrl));
-    }
+    };
 
     trace!("Retrieved URL '{url}' for link, now fetching label");
 
@@ -105,20 +123,41 @@ fn try_consume_link<'r, 't>(
     // Trim label
     let label = label.trim();
 
+    // If the label is empty, then it mirrors the URL,
+    // unless this is a fake link, in which case there's nothing to show.
+    let mut errors = Vec::new();
+    let label = if label.is_empty() {
+        warn!("Link label is empty, using URL instead");
+        errors.push(parser.make_err(ParseErrorKind::LinkLabelEmpty));
+
+        match ltype {
+            LinkType::Anchor if url == "#" => return ok!(Elements::None, errors),
+            LinkType::Anchor => LinkLabel::Url(Some(cow!(&url[1..]))),
+            _ => LinkLabel::Url(None),
+        }
+    } else {
+        LinkLabel::Text(cow!(label))
+    };
+
     // Build link element
+    let extra = match ltype {
+        LinkType::Anchor => None,
+        _ => LinkLocation::parse_extra(cow!(url), parser.settings()),
+    };
+
     let element = Element::Link {
-        ltype: LinkType::Direct,
-        link: LinkLocation::Url(cow!(url)),
-        extra: LinkLocation::parse_extra(cow!(url)),
-        label: LinkLabel::Text(cow!(label)),
+        ltype,
+        link,
+        extra,
+        label,
         target,
     };
 
     // Return result
-    ok!(element)
+    ok!(element, errors)
 }
 
-fn url_valid(url: &str) -> bool {
+fn url_valid(url: &str, settings: &WikitextSettings) -> bool {
     // If url is an empty string
     if url.is_empty() {
         return false;
@@ -130,7 +169,7 @@ fn url_valid(url: &str) -> bool {
     }
 
     // If it's a URL
-    if is_url(url) {
+    if is_url_with_settings(url, settings) {
         return true;
     }
 
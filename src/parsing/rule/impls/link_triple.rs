@@ -117,8 +117,11 @@ fn build_same<'r, 't>(
     let label = strip_category(url).map(Cow::Borrowed);
 
     // Parse out link location
-    let (link, ltype) = match LinkLocation::parse_interwiki(cow!(url), parser.settings())
-    {
+    let (link, ltype) = match LinkLocation::parse_interwiki_with(
+        cow!(url),
+        &parser.interwiki(),
+        parser.settings(),
+    ) {
         Some(result) => result,
         None => return Err(parser.make_err(ParseErrorKind::RuleFailed)),
     };
@@ -127,7 +130,7 @@ fn build_same<'r, 't>(
     let element = Element::Link {
         ltype,
         link,
-        extra: LinkLocation::parse_extra(cow!(url)),
+        extra: LinkLocation::parse_extra(cow!(url), parser.settings()),
         label: LinkLabel::Url(label),
         target,
     };
@@ -162,26 +165,30 @@ fn build_separate<'r, 't>(
     // Trim label
     let label = label.trim();
 
-    // If label is empty, then it takes on the page's title
-    // Otherwise, use the label
-    let label = if label.is_empty() {
-        LinkLabel::Page
-    } else {
-        LinkLabel::Text(cow!(label))
-    };
-
     // Parse out link location
-    let (link, ltype) = match LinkLocation::parse_interwiki(cow!(url), parser.settings())
-    {
+    let (link, ltype) = match LinkLocation::parse_interwiki_with(
+        cow!(url),
+        &parser.interwiki(),
+        parser.settings(),
+    ) {
         Some(result) => result,
         None => return Err(parser.make_err(ParseErrorKind::RuleFailed)),
     };
 
+    // If label is empty, then it takes on the page's title,
+    // or for URLs, the URL itself
+    // Otherwise, use the label
+    let label = match (label.is_empty(), &link) {
+        (true, LinkLocation::Page(_)) => LinkLabel::Page,
+        (true, LinkLocation::Url(_)) => LinkLabel::Url(None),
+        (false, _) => LinkLabel::Text(cow!(label)),
+    };
+
     // Build link element
     let element = Element::Link {
         ltype,
         link,
-        extra: LinkLocation::parse_extra(cow!(url)),
+        extra: LinkLocation::parse_extra(cow!(url), parser.settings()),
         label,
         target,
     };
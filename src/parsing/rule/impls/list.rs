@@ -19,8 +19,8 @@
  */
 
 use super::prelude::*;
-use crate::parsing::{process_depths, DepthItem, DepthList};
-use crate::tree::{AttributeMap, ListItem, ListType};
+use crate::parsing::{process_depths, DepthItem, DepthList, ParserWrap};
+use crate::tree::{AcceptsPartial, AttributeMap, ListItem, ListType, PartialElement};
 
 const MAX_LIST_DEPTH: usize = 20;
 
@@ -44,6 +44,14 @@ fn try_consume_fn<'r, 't>(
     // We don't know the list type(s) yet, so just log that we're starting
     debug!("Parsing a list");
 
+    // If this list is directly within a [[ul]] or [[ol]] block,
+    // then its items become items of that list, rather than a sub-list.
+    let in_list_block = parser.accepts_partial() == AcceptsPartial::ListItem;
+
+    // Permit list items to be written as [[li]] blocks,
+    // so that they can have attributes.
+    let parser = &mut ParserWrap::new(parser, AcceptsPartial::ListItem);
+
     // Context variables
     let mut depths = Vec::new();
     let mut errors = Vec::new();
@@ -116,8 +124,10 @@ fn try_consume_fn<'r, 't>(
         )?
         .chain(&mut errors, &mut paragraph_safe);
 
+        let item = extract_list_item(parser, elements)?;
+
         // Append list line
-        depths.push((depth, list_type, elements));
+        depths.push((depth, list_type, item));
     }
 
     // This list has no rows, so the rule fails
@@ -126,29 +136,74 @@ fn try_consume_fn<'r, 't>(
     }
 
     let depth_lists = process_depths(ListType::Generic, depths);
-    let elements: Vec<Element> = depth_lists
-        .into_iter()
-        .map(|(ltype, depth_list)| build_list_element(ltype, depth_list))
-        .collect();
+    let elements: Vec<Element> = if in_list_block {
+        depth_lists
+            .into_iter()
+            .flat_map(|(_, depth_list)| build_list_items(depth_list))
+            .map(|item| Element::Partial(PartialElement::ListItem(item)))
+            .collect()
+    } else {
+        depth_lists
+            .into_iter()
+            .map(|(ltype, depth_list)| build_list_element(ltype, depth_list))
+            .collect()
+    };
 
     ok!(paragraph_safe; elements, errors)
 }
 
+/// Gets the contents of a list line, and the attributes of its item.
+///
+/// If the line is only a `[[li]]` block, then it is used as the item,
+/// the same as it would be within a `[[ul]]` or `[[ol]]` block.
+fn extract_list_item<'r, 't>(
+    parser: &Parser<'r, 't>,
+    elements: Vec<Element<'t>>,
+) -> Result<ListItemContents<'t>, ParseError> {
+    let has_partial = elements
+        .iter()
+        .any(|element| matches!(element, Element::Partial(_)));
+
+    if !has_partial {
+        return Ok((elements, AttributeMap::new()));
+    }
+
+    let mut item = None;
+    for element in elements {
+        match element {
+            Element::Partial(PartialElement::ListItem(ListItem::Elements {
+                elements,
+                attributes,
+            })) if item.is_none() => item = Some((elements, attributes)),
+            element if element.is_whitespace() => (),
+            _ => return Err(parser.make_err(ParseErrorKind::ListContainsNonItem)),
+        }
+    }
+
+    Ok(item.expect("No list item found despite partial"))
+}
+
+type ListItemContents<'t> = (Vec<Element<'t>>, AttributeMap<'t>);
+
+fn build_list_items(list: DepthList<ListType, ListItemContents>) -> Vec<ListItem> {
+    list.into_iter()
+        .map(|item| match item {
+            DepthItem::Item((elements, attributes)) => ListItem::Elements {
+                elements,
+                attributes,
+            },
+            DepthItem::List(ltype, list) => ListItem::SubList {
+                element: Box::new(build_list_element(ltype, list)),
+            },
+        })
+        .collect()
+}
+
 fn build_list_element(
     top_ltype: ListType,
-    list: DepthList<ListType, Vec<Element>>,
+    list: DepthList<ListType, ListItemContents>,
 ) -> Element {
-    let build_item = |item| match item {
-        DepthItem::Item(elements) => ListItem::Elements {
-            elements,
-            attributes: AttributeMap::new(),
-        },
-        DepthItem::List(ltype, list) => ListItem::SubList {
-            element: Box::new(build_list_element(ltype, list)),
-        },
-    };
-
-    let items = list.into_iter().map(build_item).collect();
+    let items = build_list_items(list);
     let attributes = AttributeMap::new();
 
     // Return the Element::List object
@@ -158,3 +213,45 @@ fn build_list_element(
         attributes,
     }
 }
+
+#[test]
+fn mixed_syntax() {
+    use crate::data::PageInfo;
+    use crate::layout::Layout;
+    use crate::settings::{WikitextMode, WikitextSettings};
+
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+
+    macro_rules! parse {
+        ($input:expr $(,)?) => {{
+            let tokens = crate::tokenize($input);
+            let (tree, errors) = crate::parse(&tokens, &page_info, &settings).into();
+            assert!(errors.is_empty(), "Errors produced: {errors:?}");
+            tree.elements
+                .iter()
+                .map(Element::to_owned)
+                .collect::<Vec<_>>()
+        }};
+    }
+
+    // Both syntaxes, and any mix of them, produce the same list
+    let expected =
+        parse!("[[ul]]\n[[li class=\"a\"]]Apple[[/li]]\n[[li]]Banana[[/li]]\n[[/ul]]");
+
+    assert_eq!(
+        parse!("* [[li class=\"a\"]]Apple[[/li]]\n* Banana"),
+        expected,
+        "Bullet list with [[li]] doesn't match list block",
+    );
+    assert_eq!(
+        parse!("[[ul]]\n[[li class=\"a\"]]Apple[[/li]]\n* Banana\n[[/ul]]"),
+        expected,
+        "List block with bullet items doesn't match list block",
+    );
+    assert_eq!(
+        parse!("[[ul]]\n* [[li class=\"a\"]]Apple[[/li]]\n* Banana\n[[/ul]]"),
+        expected,
+        "List block with bullet [[li]] items doesn't match list block",
+    );
+}
@@ -0,0 +1,176 @@
+/*
+ * parsing/rule/impls/mention.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Inline mentions of users, such as `@username`.
+//!
+//! This is only enabled if `enable_mentions` is set,
+//! since `@` is otherwise ordinary text.
+
+use super::prelude::*;
+
+pub const RULE_MENTION: Rule = Rule {
+    name: "mention",
+    position: LineRequirement::Any,
+    try_consume_fn,
+};
+
+fn try_consume_fn<'r, 't>(
+    parser: &mut Parser<'r, 't>,
+) -> ParseResult<'r, 't, Elements<'t>> {
+    debug!("Trying to create a user mention");
+
+    if !parser.settings().enable_mentions {
+        trace!("Mentions are disabled, failing rule");
+        return Err(parser.make_err(ParseErrorKind::RuleFailed));
+    }
+
+    let current = parser.current();
+    if current.slice != "@" {
+        return Err(parser.make_err(ParseErrorKind::RuleFailed));
+    }
+
+    // Don't match in the middle of a word, such as an email address.
+    if let Some(c) = parser.full_text().char_before(current) {
+        if c.is_alphanumeric() || c == '_' || c == '-' {
+            trace!("Mention is preceded by a word character, failing rule");
+            return Err(parser.make_err(ParseErrorKind::RuleFailed));
+        }
+    }
+
+    // The username is composed of letters, digits, hyphens, and underscores,
+    // but must begin and end with a letter or digit, so "@user-" is just "user".
+    let mut length = 0;
+    let mut offset = 0;
+
+    while let Some(token) = parser.look_ahead(offset) {
+        match (token.token, token.slice) {
+            (Token::Identifier, _) => {
+                offset += 1;
+                length = offset;
+            }
+            (Token::Underscore, _) | (Token::Other, "-") if offset > 0 => offset += 1,
+            _ => break,
+        }
+    }
+
+    if length == 0 {
+        trace!("No username after '@', failing rule");
+        return Err(parser.make_err(ParseErrorKind::RuleFailed));
+    }
+
+    let first = parser.look_ahead_err(0)?;
+    let last = parser.look_ahead_err(length - 1)?;
+    let name = parser.full_text().slice(first, last);
+
+    // Consume the '@' and the username
+    parser.step_n(length + 1)?;
+
+    ok!(Element::Mention { name: cow!(name) })
+}
+
+#[test]
+fn mention() {
+    use crate::data::PageInfo;
+    use crate::layout::Layout;
+    use crate::settings::{WikitextMode, WikitextSettings};
+    use crate::tree::Container;
+
+    let page_info = PageInfo::dummy();
+    let mut settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+
+    macro_rules! check {
+        ($input:expr, $expected:expr, $mentions:expr $(,)?) => {{
+            let tokens = crate::tokenize($input);
+            let (tree, errors) = crate::parse(&tokens, &page_info, &settings).into();
+            let mentions: Vec<&str> = $mentions;
+
+            assert_eq!(
+                tree.elements.first(),
+                Some(&Element::Container(Container::new(
+                    ContainerType::Paragraph,
+                    $expected,
+                    AttributeMap::new(),
+                ))),
+                "Elements do not match expected",
+            );
+            assert_eq!(tree.mentions, mentions, "Mentions do not match expected");
+            assert!(errors.is_empty(), "Unexpected errors: {errors:#?}");
+        }};
+    }
+
+    macro_rules! mention {
+        ($name:expr) => {
+            Element::Mention { name: cow!($name) }
+        };
+    }
+
+    settings.enable_mentions = false;
+    check!("@alice", vec![text!("@"), text!("alice")], vec![]);
+
+    settings.enable_mentions = true;
+    check!("@alice", vec![mention!("alice")], vec!["alice"]);
+    check!(
+        "Hi @alice-b_c, and @bob.",
+        vec![
+            text!("Hi"),
+            text!(" "),
+            mention!("alice-b_c"),
+            text!(","),
+            text!(" "),
+            text!("and"),
+            text!(" "),
+            mention!("bob"),
+            text!("."),
+        ],
+        vec!["alice-b_c", "bob"],
+    );
+    check!(
+        "@bob- @alice @bob",
+        vec![
+            mention!("bob"),
+            text!("-"),
+            text!(" "),
+            mention!("alice"),
+            text!(" "),
+            mention!("bob"),
+        ],
+        vec!["bob", "alice"],
+    );
+    check!(
+        "alice@example.com",
+        vec![Element::Email(cow!("alice@example.com"))],
+        vec![],
+    );
+    check!(
+        "alice@localhost",
+        vec![text!("alice"), text!("@"), text!("localhost")],
+        vec![],
+    );
+    check!(
+        "@ alice",
+        vec![text!("@"), text!(" "), text!("alice")],
+        vec![]
+    );
+    check!(
+        "@-alice",
+        vec![text!("@"), text!("-"), text!("alice")],
+        vec![]
+    );
+}
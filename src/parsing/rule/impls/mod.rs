@@ -54,10 +54,13 @@ mod link_single;
 mod link_triple;
 mod list;
 mod math;
+mod mention;
 mod monospace;
 mod null;
 mod page;
+mod page_variable;
 mod raw;
+mod ruby_shorthand;
 mod strikethrough;
 mod subscript;
 mod superscript;
@@ -70,7 +73,10 @@ mod variable;
 
 pub use self::anchor::RULE_ANCHOR;
 pub use self::bibcite::RULE_BIBCITE;
-pub use self::block::{RULE_BLOCK, RULE_BLOCK_SKIP_NEWLINE, RULE_BLOCK_STAR};
+pub use self::block::{
+    block_accepts_score, get_score_block_names, RULE_BLOCK, RULE_BLOCK_SKIP_NEWLINE,
+    RULE_BLOCK_STAR,
+};
 pub use self::blockquote::RULE_BLOCKQUOTE;
 pub use self::bold::RULE_BOLD;
 pub use self::center::RULE_CENTER;
@@ -93,10 +99,13 @@ pub use self::link_single::{RULE_LINK_SINGLE, RULE_LINK_SINGLE_NEW_TAB};
 pub use self::link_triple::{RULE_LINK_TRIPLE, RULE_LINK_TRIPLE_NEW_TAB};
 pub use self::list::RULE_LIST;
 pub use self::math::RULE_MATH;
+pub use self::mention::RULE_MENTION;
 pub use self::monospace::RULE_MONOSPACE;
 pub use self::null::RULE_NULL;
 pub use self::page::RULE_PAGE;
+pub use self::page_variable::RULE_PAGE_VARIABLE;
 pub use self::raw::RULE_RAW;
+pub use self::ruby_shorthand::RULE_RUBY_SHORTHAND;
 pub use self::strikethrough::RULE_STRIKETHROUGH;
 pub use self::subscript::RULE_SUBSCRIPT;
 pub use self::superscript::RULE_SUPERSCRIPT;
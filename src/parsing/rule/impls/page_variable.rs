@@ -0,0 +1,49 @@
+/*
+ * parsing/rule/impls/page_variable.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Page variables, such as `%%title%%`.
+//!
+//! These are only permitted if `enable_page_syntax` is set, since they
+//! refer to the page being rendered. They are resolved when rendering.
+
+use super::prelude::*;
+
+pub const RULE_PAGE_VARIABLE: Rule = Rule {
+    name: "page-variable",
+    position: LineRequirement::Any,
+    try_consume_fn,
+};
+
+fn try_consume_fn<'r, 't>(
+    parser: &mut Parser<'r, 't>,
+) -> ParseResult<'r, 't, Elements<'t>> {
+    debug!("Consuming token to create a page variable");
+
+    if !parser.settings().enable_page_syntax {
+        trace!("Page syntax is disabled, failing rule");
+        return Err(parser.make_err(ParseErrorKind::RuleFailed));
+    }
+
+    // Strip the surrounding "%%"s
+    let ExtractedToken { slice, .. } = parser.current();
+    let name = &slice[2..slice.len() - 2];
+
+    ok!(Element::PageVariable { name: cow!(name) })
+}
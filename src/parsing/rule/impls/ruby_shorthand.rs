@@ -0,0 +1,170 @@
+/*
+ * parsing/rule/impls/ruby_shorthand.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Inline shorthand for ruby annotations, such as `{振り仮名|ふりがな}`.
+//!
+//! This is only enabled if `enable_ruby_shorthand` is set,
+//! since braces are otherwise ordinary text.
+
+use super::prelude::*;
+use crate::tree::Container;
+
+pub const RULE_RUBY_SHORTHAND: Rule = Rule {
+    name: "ruby-shorthand",
+    position: LineRequirement::Any,
+    try_consume_fn,
+};
+
+fn try_consume_fn<'r, 't>(
+    parser: &mut Parser<'r, 't>,
+) -> ParseResult<'r, 't, Elements<'t>> {
+    debug!("Trying to create ruby shorthand");
+
+    if !parser.settings().enable_ruby_shorthand {
+        trace!("Ruby shorthand is disabled, failing rule");
+        return Err(parser.make_err(ParseErrorKind::RuleFailed));
+    }
+
+    check_step(parser, Token::LeftBrace)?;
+
+    // The pattern for ruby shorthand is:
+    // { [base text] | [annotation text] }
+
+    let base_text = collect_text(
+        parser,
+        RULE_RUBY_SHORTHAND,
+        &[ParseCondition::current(Token::Pipe)],
+        &[
+            ParseCondition::current(Token::RightBrace),
+            ParseCondition::current(Token::ParagraphBreak),
+            ParseCondition::current(Token::LineBreak),
+        ],
+        None,
+    )?
+    .trim();
+
+    let ruby_text = collect_text(
+        parser,
+        RULE_RUBY_SHORTHAND,
+        &[ParseCondition::current(Token::RightBrace)],
+        &[
+            ParseCondition::current(Token::Pipe),
+            ParseCondition::current(Token::ParagraphBreak),
+            ParseCondition::current(Token::LineBreak),
+        ],
+        None,
+    )?
+    .trim();
+
+    if base_text.is_empty() || ruby_text.is_empty() {
+        warn!("Ruby shorthand has empty base or annotation, failing rule");
+        return Err(parser.make_err(ParseErrorKind::RuleFailed));
+    }
+
+    // Build the same structure as [[rb]]
+    let ruby_text = Element::Container(Container::new(
+        ContainerType::RubyText,
+        vec![text!(ruby_text)],
+        AttributeMap::new(),
+    ));
+
+    let ruby = Element::Container(Container::new(
+        ContainerType::Ruby,
+        vec![text!(base_text), ruby_text],
+        AttributeMap::new(),
+    ));
+
+    ok!(ruby)
+}
+
+#[test]
+fn ruby_shorthand() {
+    use crate::data::PageInfo;
+    use crate::layout::Layout;
+    use crate::settings::{WikitextMode, WikitextSettings};
+
+    let page_info = PageInfo::dummy();
+    let mut settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+
+    macro_rules! check {
+        ($input:expr, $expected:expr $(,)?) => {{
+            let tokens = crate::tokenize($input);
+            let (tree, errors) = crate::parse(&tokens, &page_info, &settings).into();
+
+            assert_eq!(
+                tree.elements.first(),
+                Some(&Element::Container(Container::new(
+                    ContainerType::Paragraph,
+                    $expected,
+                    AttributeMap::new(),
+                ))),
+                "Elements do not match expected",
+            );
+            assert!(errors.is_empty(), "Unexpected errors: {errors:#?}");
+        }};
+    }
+
+    macro_rules! ruby {
+        ($base:expr, $ruby:expr $(,)?) => {
+            Element::Container(Container::new(
+                ContainerType::Ruby,
+                vec![
+                    text!($base),
+                    Element::Container(Container::new(
+                        ContainerType::RubyText,
+                        vec![text!($ruby)],
+                        AttributeMap::new(),
+                    )),
+                ],
+                AttributeMap::new(),
+            ))
+        };
+    }
+
+    settings.enable_ruby_shorthand = false;
+    check!(
+        "{語|go}",
+        vec![text!("{"), text!("語"), text!("|"), text!("go"), text!("}")],
+    );
+
+    settings.enable_ruby_shorthand = true;
+    check!("{語|go}", vec![ruby!("語", "go")]);
+    check!(
+        "{振り仮名|ふりがな}を使う",
+        vec![
+            ruby!("振り仮名", "ふりがな"),
+            text!("を"),
+            text!("使"),
+            text!("う"),
+        ],
+    );
+    check!(
+        "A { Big Cheese | Horace } B",
+        vec![
+            text!("A"),
+            text!(" "),
+            ruby!("Big Cheese", "Horace"),
+            text!(" "),
+            text!("B"),
+        ],
+    );
+    check!("{}", vec![text!("{"), text!("}")]);
+    check!("{a}", vec![text!("{"), text!("a"), text!("}")]);
+}
@@ -89,7 +89,10 @@ fn try_consume_fn<'r, 't>(
                         elements: mem::take(&mut elements),
                         header,
                         column_span,
+                        row_span: NonZeroU32::MIN,
                         align,
+                        valign: None,
+                        width: None,
                         attributes: AttributeMap::new(),
                     })
                 };
@@ -218,7 +221,7 @@ fn parse_cell_start(parser: &mut Parser) -> Result<Option<TableCellStart>, Parse
             // Style cases, terminal
             Token::TableColumnTitle => {
                 increase_span!();
-                break (None, true);
+                break (parse_title_alignment(parser)?, true);
             }
             Token::TableColumnLeft => {
                 increase_span!();
@@ -244,8 +247,11 @@ fn parse_cell_start(parser: &mut Parser) -> Result<Option<TableCellStart>, Parse
         }
     };
 
-    let column_span =
-        NonZeroU32::new(span).expect("Cell start exited without column span");
+    // Every terminal case above has increased the span
+    let column_span = match NonZeroU32::new(span) {
+        Some(span) => span,
+        None => return Ok(None),
+    };
 
     Ok(Some(TableCellStart {
         align,
@@ -253,3 +259,20 @@ fn parse_cell_start(parser: &mut Parser) -> Result<Option<TableCellStart>, Parse
         column_span,
     }))
 }
+
+/// Parse out the alignment of a title cell, if any.
+///
+/// Unlike regular cells, these do not have their own tokens,
+/// so `||~<`, `||~=`, and `||~>` are handled here.
+fn parse_title_alignment(parser: &mut Parser) -> Result<Option<Alignment>, ParseError> {
+    let current = parser.current();
+    let align = match (current.token, current.slice) {
+        (Token::Other, "<") => Alignment::Left,
+        (Token::Equals, "=") => Alignment::Center,
+        (Token::Quote, ">") => Alignment::Right,
+        _ => return Ok(None),
+    };
+
+    parser.step()?;
+    Ok(Some(align))
+}
@@ -22,7 +22,8 @@ use super::prelude::*;
 use once_cell::sync::Lazy;
 use regex::Regex;
 
-static VARIABLE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{\$(.+)\}").unwrap());
+static VARIABLE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{\$([^|}]+)(?:\|(.*))?\}").unwrap());
 
 pub const RULE_VARIABLE: Rule = Rule {
     name: "variable",
@@ -37,12 +38,15 @@ fn try_consume_fn<'r, 't>(
 
     let ExtractedToken { slice, .. } = parser.current();
 
-    let variable = VARIABLE_REGEX
+    let captures = VARIABLE_REGEX
         .captures(slice)
-        .expect("Variable regex didn't match")
-        .get(1)
-        .expect("Capture group not found")
-        .as_str();
+        .expect("Variable regex didn't match");
 
-    ok!(Element::Variable(cow!(variable)))
+    let name = captures.get(1).expect("Capture group not found").as_str();
+    let default = captures.get(2).map(|mtch| cow!(mtch.as_str()));
+
+    ok!(Element::Variable {
+        name: cow!(name),
+        default,
+    })
 }
@@ -54,6 +54,8 @@ pub static RULE_MAP: Lazy<EnumMap<Token, Vec<Rule>>> = Lazy::new(|| {
         Token::ClearFloatLeft => vec![RULE_CLEAR_FLOAT],
         Token::ClearFloatRight => vec![RULE_CLEAR_FLOAT],
         Token::Pipe => vec![RULE_TEXT],
+        Token::LeftBrace => vec![RULE_RUBY_SHORTHAND, RULE_TEXT],
+        Token::RightBrace => vec![RULE_TEXT],
         Token::Equals => vec![RULE_CENTER, RULE_TEXT],
         Token::Colon => vec![RULE_DEFINITION_LIST, RULE_TEXT],
         Token::Underscore => vec![RULE_UNDERSCORE_LINE_BREAK, RULE_TEXT],
@@ -95,6 +97,7 @@ pub static RULE_MAP: Lazy<EnumMap<Token, Vec<Rule>>> = Lazy::new(|| {
         Token::Email => vec![RULE_EMAIL],
         Token::Url => vec![RULE_URL],
         Token::Variable => vec![RULE_VARIABLE, RULE_TEXT],
+        Token::PageVariable => vec![RULE_PAGE_VARIABLE, RULE_TEXT],
         Token::String => vec![RULE_TEXT],
 
         // Input boundaries
@@ -108,7 +111,7 @@ pub static RULE_MAP: Lazy<EnumMap<Token, Vec<Rule>>> = Lazy::new(|| {
         Token::RightComment => vec![],
 
         // Fallback
-        Token::Other => vec![RULE_TEXT],
+        Token::Other => vec![RULE_MENTION, RULE_TEXT],
     }
 });
 
@@ -23,10 +23,12 @@ use crate::parsing::Parser;
 use std::fmt::{self, Debug};
 
 mod mapping;
+mod set;
 
 pub mod impls;
 
 pub use self::mapping::get_rules_for_token;
+pub use self::set::RuleSet;
 
 /// Defines a rule that can possibly match tokens and return an `Element`.
 #[derive(Copy, Clone)]
@@ -55,6 +57,7 @@ impl Rule {
         parser: &mut Parser<'r, 't>,
     ) -> ParseResult<'r, 't, Elements<'t>> {
         debug!("Trying to consume for parse rule {}", self.name);
+        enter_span!("rule", name = self.name);
 
         // Check that the line position matches what the rule wants.
         match self.position {
@@ -67,8 +70,10 @@ impl Rule {
         }
 
         // Fork parser and try running the rule.
+        let timer = parser.start_rule_timer();
         let mut sub_parser = parser.clone_with_rule(self);
         let result = (self.try_consume_fn)(&mut sub_parser);
+        parser.record_rule(self, result.is_ok(), timer);
 
         if let Ok(ref output) = result {
             // First, ensure there aren't any partial elements in the result.
@@ -0,0 +1,126 @@
+/*
+ * parsing/rule/set.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::{get_rules_for_token, Rule};
+use crate::parsing::token::ExtractedToken;
+use crate::settings::WikitextSettings;
+use std::collections::HashSet;
+
+/// The set of rules which are permitted while parsing.
+///
+/// By default all rules are enabled, but specific ones can be disabled
+/// via the `disabled_rules` setting, for instance to prohibit raw HTML
+/// or iframes on a particular site. Both regular rules (such as `url`)
+/// and block rules (such as `block-html`) are referred to by name.
+///
+/// A disabled rule is never attempted, so its syntax is parsed as though
+/// the rule did not exist, which usually means falling back to text.
+#[derive(Debug, Clone, Default)]
+pub struct RuleSet {
+    disabled: HashSet<String>,
+}
+
+impl RuleSet {
+    pub fn from_settings(settings: &WikitextSettings) -> Self {
+        let disabled = settings
+            .disabled_rules
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+
+        RuleSet { disabled }
+    }
+
+    /// Whether the rule or block rule with the given name may be used.
+    #[inline]
+    pub fn is_enabled(&self, name: &str) -> bool {
+        !self.disabled.contains(name)
+    }
+
+    /// Gets the enabled rules the given token could possibly correspond with.
+    ///
+    /// See [`get_rules_for_token()`], which this filters.
+    pub fn rules_for_token<'s>(
+        &'s self,
+        current: &ExtractedToken,
+    ) -> impl Iterator<Item = Rule> + 's {
+        get_rules_for_token(current)
+            .iter()
+            .copied()
+            .filter(|rule| self.is_enabled(rule.name()))
+    }
+}
+
+#[test]
+fn rule_set() {
+    use crate::data::PageInfo;
+    use crate::layout::Layout;
+    use crate::parsing::ParseErrorKind;
+    use crate::settings::WikitextMode;
+    use crate::tree::Element;
+
+    let page_info = PageInfo::dummy();
+    let mut settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let tokens =
+        crate::tokenize("[[html]]\n<b>Apple</b>\n[[/html]]\nhttps://example.com/");
+
+    macro_rules! parse {
+        () => {
+            crate::parse(&tokens, &page_info, &settings).into()
+        };
+    }
+
+    fn has_element(elements: &[Element], f: fn(&Element) -> bool) -> bool {
+        elements.iter().any(|element| match element {
+            Element::Container(container) => has_element(container.elements(), f),
+            _ => f(element),
+        })
+    }
+
+    let is_html = |element: &Element| matches!(element, Element::Html { .. });
+    let is_link = |element: &Element| matches!(element, Element::Link { .. });
+
+    // Default, all enabled
+    let (tree, errors): (_, Vec<_>) = parse!();
+    assert!(RuleSet::from_settings(&settings).is_enabled("block-html"));
+    assert!(errors.is_empty(), "Errors produced with all rules enabled");
+    assert!(
+        has_element(&tree.elements, is_html),
+        "HTML block not produced"
+    );
+    assert!(
+        has_element(&tree.elements, is_link),
+        "URL link not produced"
+    );
+
+    // With rules disabled
+    settings.disabled_rules = vec![cow!("block-html"), cow!("url")];
+    let (tree, errors): (_, Vec<_>) = parse!();
+    assert!(!RuleSet::from_settings(&settings).is_enabled("block-html"));
+    assert!(!has_element(&tree.elements, is_html), "HTML block produced");
+    assert!(!has_element(&tree.elements, is_link), "URL link produced");
+    assert!(tree.html_blocks.is_empty());
+    assert!(
+        errors
+            .iter()
+            .any(|error| error.kind() == ParseErrorKind::BlockDisabled),
+        "No disabled block error produced",
+    );
+}
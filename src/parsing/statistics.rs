@@ -0,0 +1,167 @@
+/*
+ * parsing/statistics.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use cfg_if::cfg_if;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
+/// Statistics about a parse, for finding wikitext which is slow to parse.
+///
+/// These are only collected if `collect_stats` is enabled in the settings.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct ParseStatistics {
+    /// Statistics for each rule which was attempted, by rule name.
+    pub rules: BTreeMap<String, RuleStatistics>,
+
+    /// The number of tokens in the input.
+    pub token_count: usize,
+
+    /// The number of steps the parser made over tokens.
+    ///
+    /// Since rules which fail are backtracked, tokens may be stepped
+    /// over more than once. A value much larger than `token_count`
+    /// indicates a lot of backtracking.
+    pub token_steps: usize,
+
+    /// The deepest level of rule recursion reached.
+    pub max_depth: usize,
+}
+
+/// Statistics for a single rule, as recorded in [`ParseStatistics`].
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct RuleStatistics {
+    /// How many times this rule was attempted.
+    pub invocations: usize,
+
+    /// How many of those attempts succeeded.
+    pub successes: usize,
+
+    /// The total time spent in this rule, including any rules nested within it.
+    ///
+    /// There is no clock available on WebAssembly, so this is always zero there.
+    pub time: Duration,
+}
+
+impl ParseStatistics {
+    #[inline]
+    pub fn new(token_count: usize) -> Self {
+        ParseStatistics {
+            token_count,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn add_rule(&mut self, name: &str, success: bool, time: Duration) {
+        if !self.rules.contains_key(name) {
+            self.rules.insert(str!(name), RuleStatistics::default());
+        }
+
+        let rule = self
+            .rules
+            .get_mut(name)
+            .expect("Rule statistics not inserted");
+        rule.invocations += 1;
+        rule.time += time;
+
+        if success {
+            rule.successes += 1;
+        }
+    }
+
+    #[inline]
+    pub(crate) fn add_step(&mut self) {
+        self.token_steps += 1;
+    }
+
+    #[inline]
+    pub(crate) fn add_depth(&mut self, depth: usize) {
+        self.max_depth = self.max_depth.max(depth);
+    }
+}
+
+/// Measures how long a rule takes, if a clock is available.
+#[derive(Debug, Copy, Clone)]
+pub struct RuleTimer {
+    #[cfg(not(target_arch = "wasm32"))]
+    start: Instant,
+}
+
+impl RuleTimer {
+    #[inline]
+    pub fn start() -> Self {
+        RuleTimer {
+            #[cfg(not(target_arch = "wasm32"))]
+            start: Instant::now(),
+        }
+    }
+
+    #[inline]
+    pub fn elapsed(self) -> Duration {
+        cfg_if! {
+            if #[cfg(not(target_arch = "wasm32"))] {
+                self.start.elapsed()
+            } else {
+                Duration::ZERO
+            }
+        }
+    }
+}
+
+#[test]
+fn statistics() {
+    use crate::data::PageInfo;
+    use crate::layout::Layout;
+    use crate::settings::{WikitextMode, WikitextSettings};
+
+    let page_info = PageInfo::dummy();
+    let mut settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let tokens = crate::tokenize("**Apple //banana//** cherry");
+
+    // Not collected by default
+    let outcome = crate::parse(&tokens, &page_info, &settings);
+    assert_eq!(outcome.statistics(), None);
+
+    settings.collect_stats = true;
+    let outcome = crate::parse(&tokens, &page_info, &settings);
+    let statistics = outcome.statistics().expect("No statistics collected");
+
+    assert_eq!(statistics.token_count, tokens.tokens().len());
+    assert!(
+        statistics.token_steps >= statistics.token_count - 1,
+        "Fewer token steps than tokens",
+    );
+    assert!(
+        statistics.max_depth >= 3,
+        "Nested rules not reflected in depth",
+    );
+
+    let bold = &statistics.rules["bold"];
+    assert_eq!(bold.invocations, 1);
+    assert_eq!(bold.successes, 1);
+
+    let italics = &statistics.rules["italics"];
+    assert_eq!(italics.invocations, 1);
+    assert_eq!(italics.successes, 1);
+}
@@ -55,9 +55,7 @@ impl ExtractedToken<'_> {
         let ExtractedToken { token, slice, span } = self.clone();
 
         // Map indices to UTF-16
-        let start = map.get_index(span.start);
-        let end = map.get_index(span.end);
-        let span = start..end;
+        let span = map.get_range(span);
 
         // Output new ExtractedToken
         ExtractedToken { token, slice, span }
@@ -67,7 +65,7 @@ impl ExtractedToken<'_> {
 /// Enum that represents the type of a parsed token. For a struct with additional context
 /// surrounding the positioning and content of the token, see [`ExtractedToken`].
 #[derive(
-    Serialize, Deserialize, Enum, IntoStaticStr, Debug, Copy, Clone, PartialEq, Eq,
+    Serialize, Deserialize, Enum, IntoStaticStr, Debug, Copy, Clone, Hash, PartialEq, Eq,
 )]
 #[serde(rename_all = "kebab-case")]
 pub enum Token {
@@ -94,6 +92,8 @@ pub enum Token {
     ClearFloatLeft,
     ClearFloatRight,
     Pipe,
+    LeftBrace,
+    RightBrace,
     Equals,
     Colon,
     Underscore,
@@ -151,6 +151,7 @@ pub enum Token {
     Email,
     Url,
     Variable,
+    PageVariable,
     String,
 
     //
@@ -172,7 +173,7 @@ impl Token {
     /// # Errors
     /// Returns an error if something goes wrong with the parsing process. This will result in the
     /// only [`Token`] being a raw text containing all of the input.
-    pub(crate) fn extract_all(text: &str) -> Vec<ExtractedToken> {
+    pub(crate) fn extract_all(text: &str) -> Vec<ExtractedToken<'_>> {
         debug!("Running lexer on input");
 
         match TokenLexer::parse(Rule::document, text) {
@@ -248,6 +249,8 @@ impl Token {
             Rule::clear_float_left => Token::ClearFloatLeft,
             Rule::clear_float_right => Token::ClearFloatRight,
             Rule::pipe => Token::Pipe,
+            Rule::left_brace => Token::LeftBrace,
+            Rule::right_brace => Token::RightBrace,
             Rule::colon => Token::Colon,
             Rule::underscore => Token::Underscore,
             Rule::equals => Token::Equals,
@@ -292,6 +295,7 @@ impl Token {
             Rule::email => Token::Email,
             Rule::url => Token::Url,
             Rule::variable => Token::Variable,
+            Rule::page_variable => Token::PageVariable,
             Rule::string => Token::String,
 
             // Other
@@ -299,7 +303,25 @@ impl Token {
             Rule::EOI => Token::InputEnd,
 
             // Invalid
-            Rule::char | Rule::document | Rule::token => {
+            Rule::char
+            | Rule::document
+            | Rule::email_label
+            | Rule::email_local
+            | Rule::email_local_char
+            | Rule::failed_email
+            | Rule::token
+            | Rule::token_head
+            | Rule::token_tail
+            | Rule::token_no_string
+            | Rule::token_no_variable
+            | Rule::token_no_string_variable
+            | Rule::unterminated_string
+            | Rule::unterminated_string_no_variable
+            | Rule::unterminated_string_start
+            | Rule::unterminated_variable
+            | Rule::unterminated_variable_no_string
+            | Rule::unterminated_variable_start
+            | Rule::variable_default => {
                 panic!("Received invalid pest rule: {rule:?}")
             }
         }
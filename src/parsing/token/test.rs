@@ -232,4 +232,116 @@ fn tokens() {
             },
         ],
     );
+
+    test!(
+        "\"a\\\"b {$x|c\\\"\n\"d\"",
+        vec![
+            ExtractedToken {
+                token: Token::Other,
+                slice: "\"",
+                span: 0..1,
+            },
+            ExtractedToken {
+                token: Token::Identifier,
+                slice: "a",
+                span: 1..2,
+            },
+            ExtractedToken {
+                token: Token::Other,
+                slice: "\\",
+                span: 2..3,
+            },
+            ExtractedToken {
+                token: Token::Other,
+                slice: "\"",
+                span: 3..4,
+            },
+            ExtractedToken {
+                token: Token::Identifier,
+                slice: "b",
+                span: 4..5,
+            },
+            ExtractedToken {
+                token: Token::Whitespace,
+                slice: " ",
+                span: 5..6,
+            },
+            ExtractedToken {
+                token: Token::LeftBrace,
+                slice: "{",
+                span: 6..7,
+            },
+            ExtractedToken {
+                token: Token::Other,
+                slice: "$",
+                span: 7..8,
+            },
+            ExtractedToken {
+                token: Token::Identifier,
+                slice: "x",
+                span: 8..9,
+            },
+            ExtractedToken {
+                token: Token::Pipe,
+                slice: "|",
+                span: 9..10,
+            },
+            ExtractedToken {
+                token: Token::Identifier,
+                slice: "c",
+                span: 10..11,
+            },
+            ExtractedToken {
+                token: Token::Other,
+                slice: "\\",
+                span: 11..12,
+            },
+            ExtractedToken {
+                token: Token::Other,
+                slice: "\"",
+                span: 12..13,
+            },
+            ExtractedToken {
+                token: Token::LineBreak,
+                slice: "\n",
+                span: 13..14,
+            },
+            ExtractedToken {
+                token: Token::String,
+                slice: "\"d\"",
+                span: 14..17,
+            },
+        ],
+    );
+
+    test!(
+        "a.b--]c@d.io",
+        vec![
+            ExtractedToken {
+                token: Token::Identifier,
+                slice: "a",
+                span: 0..1,
+            },
+            ExtractedToken {
+                token: Token::Other,
+                slice: ".",
+                span: 1..2,
+            },
+            ExtractedToken {
+                token: Token::Identifier,
+                slice: "b",
+                span: 2..3,
+            },
+            ExtractedToken {
+                token: Token::RightComment,
+                slice: "--]",
+                span: 3..6,
+            },
+            ExtractedToken {
+                token: Token::Email,
+                slice: "c@d.io",
+                span: 6..12,
+            },
+        ],
+    );
 }
@@ -27,11 +27,14 @@ pub mod whitespace;
 #[cfg(test)]
 mod test;
 
+use crate::budget::{BudgetExceeded, WorkBudget};
+use crate::data::PageInfo;
+use crate::settings::WikitextSettings;
 use regex::Regex;
 
 /// Helper struct to easily perform string replacements.
 #[derive(Debug)]
-pub enum Replacer {
+pub enum Replacer<'a> {
     /// Replaces any text matching the "repl" group,
     /// (or the entire regular expression if "repl" does not exist)
     /// with the static string.
@@ -53,12 +56,12 @@ pub enum Replacer {
     /// Then input string `[% wikidork %]` would become `<(wikidork)>`.
     RegexSurround {
         regex: Regex,
-        begin: &'static str,
-        end: &'static str,
+        begin: &'a str,
+        end: &'a str,
     },
 }
 
-impl Replacer {
+impl Replacer<'_> {
     /// Replaces the text in the manner defined by its enum, using the buffer as a temporary space
     /// to copy to.
     fn replace(&self, text: &mut String, buffer: &mut String) {
@@ -139,15 +142,43 @@ impl Replacer {
 /// * Convert tabs to four spaces
 /// * Wikidot typography transformations
 ///
+/// Only the typographic substitutions enabled in the `typography` settings
+/// are performed. Unless the settings give a typography profile, quotes use
+/// the conventions of the page's language.
+///
 /// This call always succeeds. The return value designates where issues occurred
 /// to allow programmatic determination of where things were not as expected.
-pub fn preprocess(text: &mut String) {
-    info!("Beginning preprocessing of text ({} bytes)", text.len());
+pub fn preprocess(text: &mut String, page_info: &PageInfo, settings: &WikitextSettings) {
+    info!(
+        "Beginning preprocessing of text ({} bytes, language {})",
+        text.len(),
+        page_info.language,
+    );
+    enter_span!("preprocess", bytes = text.len());
+
+    // Apply any settings overlays for this page's category
+    let settings = settings.resolve(page_info);
+
     whitespace::substitute(text);
-    typography::substitute(text);
+    typography::substitute_with_settings(text, &settings.typography, &page_info.language);
     debug!("Finished preprocessing of text ({} bytes)", text.len());
 }
 
+/// Runs the preprocessor, spending from the given work budget.
+///
+/// If the budget is exceeded, the text is left unmodified.
+/// See [`WorkBudget`] for more information.
+pub fn preprocess_with_budget(
+    text: &mut String,
+    page_info: &PageInfo,
+    settings: &WikitextSettings,
+    budget: &WorkBudget,
+) -> Result<(), BudgetExceeded> {
+    budget.spend(text.len())?;
+    preprocess(text, page_info, settings);
+    Ok(())
+}
+
 #[test]
 fn fn_type() {
     type SubstituteFn = fn(&mut String);
@@ -19,6 +19,9 @@
  */
 
 use super::preprocess;
+use crate::data::PageInfo;
+use crate::layout::Layout;
+use crate::settings::{WikitextMode, WikitextSettings};
 use proptest::prelude::*;
 
 pub fn test_substitution<F>(filter_name: &str, mut substitute: F, tests: &[(&str, &str)])
@@ -76,7 +79,14 @@ const PREFILTER_TEST_CASES: [(&str, &str); 10] = [
 
 #[test]
 fn prefilter() {
-    test_substitution("prefilter", |text| preprocess(text), &PREFILTER_TEST_CASES);
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+
+    test_substitution(
+        "prefilter",
+        |text| preprocess(text, &page_info, &settings),
+        &PREFILTER_TEST_CASES,
+    );
 }
 
 proptest! {
@@ -84,7 +94,9 @@ proptest! {
 
     #[test]
     fn prefilter_prop(mut s in ".*") {
-        crate::preprocess(&mut s);
+        let page_info = PageInfo::dummy();
+        let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+        crate::preprocess(&mut s, &page_info, &settings);
 
         const INVALID_SUBSTRINGS: [&str; 7] = [
             "...",
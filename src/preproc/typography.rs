@@ -28,36 +28,23 @@
 //! * ,, .. '' to fancy lowered double quotes
 //! * ... to an ellipsis
 //!
+//! The characters used for quotes depend on the typography profile,
+//! which is selected by locale. See [`TypographyProfile`].
+//! Each substitution can also be disabled, see [`TypographySettings`].
+//!
 //! Em dash conversion was originally implemented here, however
 //! it was moved to the parser to prevent typography from converting
 //! the `--` in `[!--` and `--]` into em dashes.
 
 use super::Replacer;
+use crate::settings::TypographySettings;
 use once_cell::sync::Lazy;
 use regex::Regex;
+use std::borrow::Cow;
 
-// ‘ - LEFT SINGLE QUOTATION MARK
-// ’ - RIGHT SINGLE QUOTATION MARK
-static SINGLE_QUOTES: Lazy<Replacer> = Lazy::new(|| Replacer::RegexSurround {
-    regex: Regex::new(r"`(.*?)'").unwrap(),
-    begin: "\u{2018}",
-    end: "\u{2019}",
-});
-
-// “ - LEFT DOUBLE QUOTATION MARK
-// ” - RIGHT DOUBLE QUOTATION MARK
-static DOUBLE_QUOTES: Lazy<Replacer> = Lazy::new(|| Replacer::RegexSurround {
-    regex: Regex::new(r"``(.*?)''").unwrap(),
-    begin: "\u{201c}",
-    end: "\u{201d}",
-});
-
-// „ - DOUBLE LOW-9 QUOTATION MARK
-static LOW_DOUBLE_QUOTES: Lazy<Replacer> = Lazy::new(|| Replacer::RegexSurround {
-    regex: Regex::new(r",,(.*?)''").unwrap(),
-    begin: "\u{201e}",
-    end: "\u{201d}",
-});
+static SINGLE_QUOTES: Lazy<Regex> = Lazy::new(|| Regex::new(r"`(.*?)'").unwrap());
+static DOUBLE_QUOTES: Lazy<Regex> = Lazy::new(|| Regex::new(r"``(.*?)''").unwrap());
+static LOW_DOUBLE_QUOTES: Lazy<Regex> = Lazy::new(|| Regex::new(r",,(.*?)''").unwrap());
 
 // … - HORIZONTAL ELLIPSIS
 static HORIZONTAL_ELLIPSIS: Lazy<Replacer> = Lazy::new(|| Replacer::RegexReplace {
@@ -65,8 +52,122 @@ static HORIZONTAL_ELLIPSIS: Lazy<Replacer> = Lazy::new(|| Replacer::RegexReplace
     replacement: "\u{2026}",
 });
 
+/// The set of characters used for typographic substitutions in a locale.
+///
+/// Each pair of strings is the opening and closing text
+/// which replaces the respective quote syntax.
+#[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct TypographyProfile {
+    /// What `` `` .. '' `` is replaced with.
+    pub double_quotes: (Cow<'static, str>, Cow<'static, str>),
+
+    /// What `` ,, .. '' `` is replaced with.
+    pub low_double_quotes: (Cow<'static, str>, Cow<'static, str>),
+
+    /// What `` ` .. ' `` is replaced with.
+    pub single_quotes: (Cow<'static, str>, Cow<'static, str>),
+}
+
+impl TypographyProfile {
+    /// English typography, as performed by Wikidot. This is the default.
+    pub const ENGLISH: Self = TypographyProfile {
+        double_quotes: (cow!("\u{201c}"), cow!("\u{201d}")), // “ ”
+        low_double_quotes: (cow!("\u{201e}"), cow!("\u{201d}")), // „ ”
+        single_quotes: (cow!("\u{2018}"), cow!("\u{2019}")), // ‘ ’
+    };
+
+    /// German typography, using low-high quotes.
+    pub const GERMAN: Self = TypographyProfile {
+        double_quotes: (cow!("\u{201e}"), cow!("\u{201c}")), // „ “
+        low_double_quotes: (cow!("\u{201e}"), cow!("\u{201c}")), // „ “
+        single_quotes: (cow!("\u{201a}"), cow!("\u{2018}")), // ‚ ‘
+    };
+
+    /// French typography, using guillemets padded with no-break spaces.
+    pub const FRENCH: Self = TypographyProfile {
+        double_quotes: (cow!("\u{ab}\u{a0}"), cow!("\u{a0}\u{bb}")), // « »
+        low_double_quotes: (cow!("\u{ab}\u{a0}"), cow!("\u{a0}\u{bb}")), // « »
+        single_quotes: (cow!("\u{2039}\u{a0}"), cow!("\u{a0}\u{203a}")), // ‹ ›
+    };
+
+    /// Russian typography, using unpadded guillemets.
+    pub const RUSSIAN: Self = TypographyProfile {
+        double_quotes: (cow!("\u{ab}"), cow!("\u{bb}")), // « »
+        low_double_quotes: (cow!("\u{201e}"), cow!("\u{201c}")), // „ “
+        single_quotes: (cow!("\u{201e}"), cow!("\u{201c}")), // „ “
+    };
+
+    /// Gets the profile to use for the given locale, such as `fr` or `de-AT`.
+    ///
+    /// Only the primary language subtag is considered. If there is no
+    /// profile for that language, then [`TypographyProfile::ENGLISH`] is returned.
+    pub fn for_locale(locale: &str) -> &'static TypographyProfile {
+        let language = locale.split(['-', '_']).next().unwrap_or(locale);
+
+        for (languages, profile) in &PROFILES {
+            if languages
+                .iter()
+                .any(|name| name.eq_ignore_ascii_case(language))
+            {
+                return profile;
+            }
+        }
+
+        &TypographyProfile::ENGLISH
+    }
+}
+
+impl Default for TypographyProfile {
+    #[inline]
+    fn default() -> Self {
+        TypographyProfile::ENGLISH
+    }
+}
+
+/// The locale profiles, listed by language subtag.
+///
+/// To add support for a new locale, add an entry here.
+static PROFILES: [(&[&str], TypographyProfile); 4] = [
+    (&["en"], TypographyProfile::ENGLISH),
+    (&["de"], TypographyProfile::GERMAN),
+    (&["fr"], TypographyProfile::FRENCH),
+    (&["ru", "uk", "be"], TypographyProfile::RUSSIAN),
+];
+
 /// Performs all typographic substitutions in-place in the given text
 pub fn substitute(text: &mut String) {
+    substitute_with_profile(text, &TypographyProfile::ENGLISH);
+}
+
+/// Performs all typographic substitutions in-place, using the given profile.
+pub fn substitute_with_profile(text: &mut String, profile: &TypographyProfile) {
+    substitute_inner(text, profile, &TypographySettings::default());
+}
+
+/// Performs the typographic substitutions enabled in the given settings, in-place.
+///
+/// Quotes use the profile from the settings, or if there is none, the profile
+/// for the given locale. Only the quote and ellipsis settings apply here,
+/// the others are used by the parser.
+pub fn substitute_with_settings(
+    text: &mut String,
+    settings: &TypographySettings,
+    locale: &str,
+) {
+    let profile = match settings.profile {
+        Some(ref profile) => profile,
+        None => TypographyProfile::for_locale(locale),
+    };
+
+    substitute_inner(text, profile, settings);
+}
+
+fn substitute_inner(
+    text: &mut String,
+    profile: &TypographyProfile,
+    settings: &TypographySettings,
+) {
     let mut buffer = String::new();
     debug!("Performing typography substitutions");
 
@@ -76,13 +177,33 @@ pub fn substitute(text: &mut String) {
         };
     }
 
+    macro_rules! surround {
+        ($regex:expr, $quotes:expr) => {{
+            let (begin, end) = $quotes;
+            let replacer = Replacer::RegexSurround {
+                regex: Regex::clone(&$regex),
+                begin: begin.as_ref(),
+                end: end.as_ref(),
+            };
+
+            replace!(replacer);
+        }};
+    }
+
     // Quotes
-    replace!(DOUBLE_QUOTES);
-    replace!(LOW_DOUBLE_QUOTES);
-    replace!(SINGLE_QUOTES);
+    if settings.double_quotes {
+        surround!(DOUBLE_QUOTES, &profile.double_quotes);
+        surround!(LOW_DOUBLE_QUOTES, &profile.low_double_quotes);
+    }
+
+    if settings.single_quotes {
+        surround!(SINGLE_QUOTES, &profile.single_quotes);
+    }
 
     // Miscellaneous
-    replace!(HORIZONTAL_ELLIPSIS);
+    if settings.ellipsis {
+        replace!(HORIZONTAL_ELLIPSIS);
+    }
 }
 
 #[cfg(test)]
@@ -147,3 +268,65 @@ fn test_substitute() {
 
     test_substitution("typography", substitute, &TEST_CASES);
 }
+
+#[test]
+fn test_substitute_locales() {
+    use super::test::test_substitution;
+
+    test_substitution(
+        "typography (de)",
+        |text| substitute_with_profile(text, TypographyProfile::for_locale("de-AT")),
+        &[(
+            "Er sagte: ``Das ist `gut'.'' ,,Ja...''",
+            "Er sagte: „Das ist ‚gut‘.“ „Ja…“",
+        )],
+    );
+
+    test_substitution(
+        "typography (fr)",
+        |text| substitute_with_profile(text, TypographyProfile::for_locale("FR")),
+        &[(
+            "Il a dit : ``Bonjour !''",
+            "Il a dit : «\u{a0}Bonjour !\u{a0}»",
+        )],
+    );
+
+    assert_eq!(
+        TypographyProfile::for_locale("ja"),
+        &TypographyProfile::ENGLISH,
+    );
+    assert_eq!(
+        TypographyProfile::for_locale("uk_UA"),
+        &TypographyProfile::RUSSIAN,
+    );
+}
+
+#[test]
+fn test_substitute_settings() {
+    use super::test::test_substitution;
+
+    let settings = TypographySettings {
+        single_quotes: false,
+        ellipsis: false,
+        profile: Some(TypographyProfile::FRENCH),
+        ..TypographySettings::default()
+    };
+
+    test_substitution(
+        "typography (settings)",
+        |text| substitute_with_settings(text, &settings, "de"),
+        &[("``Bonjour `toi'...''", "«\u{a0}Bonjour `toi'...\u{a0}»")],
+    );
+
+    test_substitution(
+        "typography (settings, locale)",
+        |text| substitute_with_settings(text, &TypographySettings::default(), "de"),
+        &[("``Hallo `du'...''", "„Hallo ‚du‘…“")],
+    );
+
+    test_substitution(
+        "typography (none)",
+        |text| substitute_with_settings(text, &TypographySettings::none(), "en"),
+        &[("``Hello `you'...''", "``Hello `you'...''")],
+    );
+}
@@ -0,0 +1,234 @@
+/*
+ * python.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Python bindings, for calling ftml from Python.
+//!
+//! These are built as a native module named `ftml`, for instance using
+//! [`maturin`](https://www.maturin.rs/) with `--features python`.
+//! Structured values, such as settings and syntax trees, are passed as
+//! dictionaries, using the same serialization as the rest of the crate.
+//! They are converted through JSON, using Python's `json` module.
+//!
+//! ```python
+//! import ftml
+//!
+//! page_info = {"page": "scp-xxxx", "category": None, ...}
+//! text = ftml.preprocess("**Hello**, world...", page_info)
+//! result = ftml.parse(text, page_info)
+//! html = ftml.render_html(result["syntax-tree"], page_info)
+//! ```
+//!
+//! Each function takes an optional `settings` dictionary. If it is omitted,
+//! the settings for [`WikitextMode::Page`] in the Wikidot layout are used.
+
+use crate::data::PageInfo;
+use crate::info;
+use crate::layout::Layout;
+use crate::parsing::{ExtractedToken, ParseError};
+use crate::render::html::HtmlRender;
+use crate::render::text::TextRender;
+use crate::render::Render;
+use crate::settings::{WikitextMode, WikitextSettings};
+use crate::tree::SyntaxTree;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+struct ParseOutput<'a, 't> {
+    syntax_tree: &'a SyntaxTree<'t>,
+    errors: &'a [ParseError],
+}
+
+/// Returns the version string of this library.
+#[pyfunction]
+fn version() -> String {
+    info::VERSION.clone()
+}
+
+/// Performs the typography and whitespace substitutions on the given wikitext.
+#[pyfunction]
+#[pyo3(signature = (text, page_info, settings = None))]
+fn preprocess(
+    text: String,
+    page_info: &Bound<'_, PyAny>,
+    settings: Option<&Bound<'_, PyAny>>,
+) -> PyResult<String> {
+    let page_info: PageInfo = from_python(page_info)?;
+    let settings = read_settings(settings)?;
+    let mut text = text;
+    crate::preprocess(&mut text, &page_info, &settings);
+    Ok(text)
+}
+
+/// Tokenizes the given wikitext, returning a list of tokens.
+///
+/// Each token is a dictionary with `token`, `slice`, and `span` keys.
+#[pyfunction]
+fn tokenize<'py>(py: Python<'py>, text: &str) -> PyResult<Bound<'py, PyAny>> {
+    let tokenization = crate::tokenize(text);
+    let tokens: &[ExtractedToken] = tokenization.tokens();
+    to_python(py, tokens)
+}
+
+/// Tokenizes and parses the given wikitext, which should already be preprocessed.
+///
+/// Returns a dictionary with `syntax-tree` and `errors` keys.
+#[pyfunction]
+#[pyo3(signature = (text, page_info, settings = None))]
+fn parse<'py>(
+    py: Python<'py>,
+    text: &str,
+    page_info: &Bound<'py, PyAny>,
+    settings: Option<&Bound<'py, PyAny>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let page_info: PageInfo = from_python(page_info)?;
+    let settings = read_settings(settings)?;
+
+    let tokens = crate::tokenize(text);
+    let (tree, errors) = crate::parse_infallible(&tokens, &page_info, &settings).into();
+    let output = ParseOutput {
+        syntax_tree: &tree,
+        errors: &errors,
+    };
+
+    to_python(py, &output)
+}
+
+/// Renders the given syntax tree, as produced by `parse()`, to HTML.
+///
+/// Returns a dictionary with the HTML body, styles, and metadata.
+#[pyfunction]
+#[pyo3(signature = (syntax_tree, page_info, settings = None))]
+fn render_html<'py>(
+    py: Python<'py>,
+    syntax_tree: &Bound<'py, PyAny>,
+    page_info: &Bound<'py, PyAny>,
+    settings: Option<&Bound<'py, PyAny>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let tree: SyntaxTree = from_python(syntax_tree)?;
+    let page_info: PageInfo = from_python(page_info)?;
+    let settings = read_settings(settings)?;
+
+    let output = HtmlRender.render(&tree, &page_info, &settings);
+    to_python(py, &output)
+}
+
+/// Renders the given syntax tree, as produced by `parse()`, to plain text.
+#[pyfunction]
+#[pyo3(signature = (syntax_tree, page_info, settings = None))]
+fn render_text(
+    syntax_tree: &Bound<'_, PyAny>,
+    page_info: &Bound<'_, PyAny>,
+    settings: Option<&Bound<'_, PyAny>>,
+) -> PyResult<String> {
+    let tree: SyntaxTree = from_python(syntax_tree)?;
+    let page_info: PageInfo = from_python(page_info)?;
+    let settings = read_settings(settings)?;
+
+    Ok(TextRender.render(&tree, &page_info, &settings))
+}
+
+#[pymodule]
+fn ftml(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(version, module)?)?;
+    module.add_function(wrap_pyfunction!(preprocess, module)?)?;
+    module.add_function(wrap_pyfunction!(tokenize, module)?)?;
+    module.add_function(wrap_pyfunction!(parse, module)?)?;
+    module.add_function(wrap_pyfunction!(render_html, module)?)?;
+    module.add_function(wrap_pyfunction!(render_text, module)?)?;
+    Ok(())
+}
+
+// Helpers
+
+fn read_settings(settings: Option<&Bound<'_, PyAny>>) -> PyResult<WikitextSettings> {
+    match settings {
+        Some(settings) => from_python(settings),
+        None => Ok(WikitextSettings::from_mode(
+            WikitextMode::Page,
+            Layout::Wikidot,
+        )),
+    }
+}
+
+/// Converts a value into Python objects, through its JSON serialization.
+fn to_python<'py, T>(py: Python<'py>, value: &T) -> PyResult<Bound<'py, PyAny>>
+where
+    T: Serialize + ?Sized,
+{
+    let json = serde_json::to_string(value)
+        .map_err(|error| PyValueError::new_err(error.to_string()))?;
+
+    py.import("json")?.call_method1("loads", (json,))
+}
+
+/// Converts Python objects into a value, through its JSON serialization.
+fn from_python<T: DeserializeOwned>(value: &Bound<'_, PyAny>) -> PyResult<T> {
+    let json: String = value
+        .py()
+        .import("json")?
+        .call_method1("dumps", (value,))?
+        .extract()?;
+
+    serde_json::from_str(&json).map_err(|error| PyValueError::new_err(error.to_string()))
+}
+
+#[test]
+fn python() {
+    use pyo3::types::PyDict;
+
+    Python::initialize();
+    Python::attach(|py| {
+        let page_info = to_python(py, &PageInfo::dummy()).unwrap();
+
+        // Preprocess and tokenize
+        let text = preprocess(str!("**Apple** //banana//..."), &page_info, None).unwrap();
+        assert_eq!(text, "**Apple** //banana//…");
+
+        let tokens = tokenize(py, "**Apple**").unwrap();
+        assert_eq!(tokens.len().unwrap(), 5);
+
+        // Parse
+        let output = parse(py, &text, &page_info, None).unwrap();
+        let output = output.cast::<PyDict>().unwrap();
+        let tree = output.get_item("syntax-tree").unwrap().unwrap();
+        let errors = output.get_item("errors").unwrap().unwrap();
+        assert_eq!(errors.len().unwrap(), 0);
+
+        // Render
+        let output = render_html(py, &tree, &page_info, None).unwrap();
+        let body: String = output.get_item("body").unwrap().extract().unwrap();
+        assert_eq!(
+            body,
+            "<wj-body class=\"wj-body\"><p><strong>Apple</strong> <em>banana</em>…</p></wj-body>",
+        );
+
+        let output = render_text(&tree, &page_info, None).unwrap();
+        assert_eq!(output, "Apple banana…");
+
+        // Invalid input
+        let settings = PyDict::new(py);
+        settings.set_item("mode", "not-a-mode").unwrap();
+        assert!(parse(py, &text, &page_info, Some(&settings)).is_err());
+    });
+}
@@ -0,0 +1,266 @@
+/*
+ * render/context.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use crate::data::PageInfo;
+use crate::settings::WikitextSettings;
+use crate::tree::{
+    Bibliography, BibliographyList, CitationStyle, Element, SyntaxTree, VariableScopes,
+};
+use std::num::NonZeroUsize;
+
+/// State common to all renderers, independent of the output format.
+///
+/// This holds the parts of the syntax tree which are looked up by reference
+/// while rendering, such as footnotes and bibliographies, the variable scopes
+/// of included pages, and the counters used to number elements in order.
+///
+/// The built-in renderers each wrap one of these, and it is intended for
+/// use by renderers outside this crate too, so that they number footnotes
+/// and equations the same way without reimplementing this bookkeeping.
+/// Create one per render with [`from_tree()`](Self::from_tree), then call
+/// the `next_*_index()` methods as each such element is encountered.
+#[derive(Debug)]
+pub struct RenderContext<'i, 'e, 't>
+where
+    'e: 't,
+{
+    info: &'i PageInfo<'i>,
+    settings: &'e WikitextSettings,
+
+    //
+    // Included page scopes
+    //
+    variables: VariableScopes,
+
+    //
+    // Fields from syntax tree
+    //
+    table_of_contents: &'e [Element<'t>],
+    heading_anchors: &'e [String],
+    footnotes: &'e [Vec<Element<'t>>],
+    bibliographies: &'e BibliographyList<'t>,
+
+    //
+    // Counters for numbering elements
+    //
+    code_snippet_index: NonZeroUsize,
+    table_of_contents_index: usize,
+    equation_index: NonZeroUsize,
+    footnote_index: NonZeroUsize,
+}
+
+impl<'i, 'e, 't> RenderContext<'i, 'e, 't> {
+    /// Creates a context from the individual fields of a syntax tree.
+    ///
+    /// This is useful when only rendering some elements, in which case
+    /// empty slices can be passed for any fields which are not needed.
+    pub fn new(
+        info: &'i PageInfo<'i>,
+        settings: &'e WikitextSettings,
+        (table_of_contents, heading_anchors): (&'e [Element<'t>], &'e [String]),
+        footnotes: &'e [Vec<Element<'t>>],
+        bibliographies: &'e BibliographyList<'t>,
+    ) -> Self {
+        RenderContext {
+            info,
+            settings,
+            variables: VariableScopes::new(),
+            table_of_contents,
+            heading_anchors,
+            footnotes,
+            bibliographies,
+            code_snippet_index: NonZeroUsize::MIN,
+            table_of_contents_index: 0,
+            equation_index: NonZeroUsize::MIN,
+            footnote_index: NonZeroUsize::MIN,
+        }
+    }
+
+    /// Creates a context for rendering the given syntax tree.
+    #[inline]
+    pub fn from_tree(
+        info: &'i PageInfo<'i>,
+        settings: &'e WikitextSettings,
+        tree: &'e SyntaxTree<'t>,
+    ) -> Self {
+        Self::new(
+            info,
+            settings,
+            (&tree.table_of_contents, &tree.heading_anchors),
+            &tree.footnotes,
+            &tree.bibliographies,
+        )
+    }
+
+    // Field access
+    #[inline]
+    pub fn info(&self) -> &'i PageInfo<'i> {
+        self.info
+    }
+
+    #[inline]
+    pub fn settings(&self) -> &'e WikitextSettings {
+        self.settings
+    }
+
+    #[inline]
+    pub fn language(&self) -> &'i str {
+        &self.info.language
+    }
+
+    /// The variables set by `[[include]]` arguments and similar.
+    ///
+    /// Renderers push a scope when entering an included page and pop it
+    /// when leaving, so that variable lookups resolve to the innermost value.
+    #[inline]
+    pub fn variables(&self) -> &VariableScopes {
+        &self.variables
+    }
+
+    #[inline]
+    pub fn variables_mut(&mut self) -> &mut VariableScopes {
+        &mut self.variables
+    }
+
+    #[inline]
+    pub fn table_of_contents(&self) -> &'e [Element<'t>] {
+        self.table_of_contents
+    }
+
+    #[inline]
+    pub fn heading_anchors(&self) -> &'e [String] {
+        self.heading_anchors
+    }
+
+    #[inline]
+    pub fn footnotes(&self) -> &'e [Vec<Element<'t>>] {
+        self.footnotes
+    }
+
+    /// Gets the contents of the footnote with the given one-based index.
+    #[inline]
+    pub fn get_footnote(&self, index_one: NonZeroUsize) -> Option<&'e [Element<'t>]> {
+        self.footnotes
+            .get(usize::from(index_one) - 1)
+            .map(|elements| elements.as_slice())
+    }
+
+    #[inline]
+    pub fn get_bibliography(&self, index: usize) -> Option<&'e Bibliography<'t>> {
+        self.bibliographies.get_bibliography(index)
+    }
+
+    /// Looks up a bibliography entry by label.
+    ///
+    /// Returns the index of the entry, its contents, and the citation style
+    /// of the bibliography it is in.
+    pub fn get_bibliography_ref(
+        &self,
+        label: &str,
+    ) -> Option<(usize, &'e [Element<'t>], CitationStyle)> {
+        self.bibliographies.get_reference(label)
+    }
+
+    // Counters
+    pub fn next_code_snippet_index(&mut self) -> NonZeroUsize {
+        let index = self.code_snippet_index;
+        self.code_snippet_index = index.saturating_add(1);
+        index
+    }
+
+    /// Gets the index of the next heading which appears in the table of contents.
+    pub fn next_table_of_contents_index(&mut self) -> usize {
+        let index = self.table_of_contents_index;
+        self.table_of_contents_index += 1;
+        index
+    }
+
+    pub fn next_equation_index(&mut self) -> NonZeroUsize {
+        let index = self.equation_index;
+        self.equation_index = index.saturating_add(1);
+        index
+    }
+
+    /// Gets the index of the next footnote reference.
+    ///
+    /// Footnotes are numbered in the order they are referenced, which is
+    /// the same order as in [`footnotes()`](Self::footnotes).
+    pub fn next_footnote_index(&mut self) -> NonZeroUsize {
+        let index = self.footnote_index;
+        self.footnote_index = index.saturating_add(1);
+        index
+    }
+
+    /// Exchanges the code snippet and equation counters with another context.
+    ///
+    /// This is used to continue the numbering within a separately rendered tree.
+    #[cfg(feature = "html")]
+    pub(crate) fn swap_shared_indices(&mut self, other: &mut RenderContext) {
+        std::mem::swap(&mut self.code_snippet_index, &mut other.code_snippet_index);
+        std::mem::swap(&mut self.equation_index, &mut other.equation_index);
+    }
+
+    /// Sets the starting values of the index counters.
+    ///
+    /// This is used when rendering only part of a tree in this context,
+    /// so that the indices match those of a render of the whole tree.
+    #[cfg(feature = "parallel")]
+    pub(crate) fn set_indices(
+        &mut self,
+        code_snippet_index: NonZeroUsize,
+        table_of_contents_index: usize,
+        equation_index: NonZeroUsize,
+        footnote_index: NonZeroUsize,
+    ) {
+        self.code_snippet_index = code_snippet_index;
+        self.table_of_contents_index = table_of_contents_index;
+        self.equation_index = equation_index;
+        self.footnote_index = footnote_index;
+    }
+}
+
+#[test]
+fn counters() {
+    use crate::layout::Layout;
+    use crate::settings::WikitextMode;
+
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let tree = SyntaxTree::default();
+    let mut ctx = RenderContext::from_tree(&page_info, &settings, &tree);
+
+    assert_eq!(ctx.next_footnote_index().get(), 1);
+    assert_eq!(ctx.next_footnote_index().get(), 2);
+    assert_eq!(ctx.next_equation_index().get(), 1);
+    assert_eq!(ctx.next_code_snippet_index().get(), 1);
+    assert_eq!(ctx.next_table_of_contents_index(), 0);
+    assert_eq!(ctx.next_table_of_contents_index(), 1);
+    assert_eq!(ctx.get_footnote(NonZeroUsize::MIN), None);
+    assert_eq!(ctx.get_bibliography_ref("missing"), None);
+
+    let tokens = crate::tokenize("A[[footnote]]B[[/footnote]]");
+    let (tree, _) = crate::parse(&tokens, &page_info, &settings).into();
+    let mut ctx = RenderContext::from_tree(&page_info, &settings, &tree);
+    let index = ctx.next_footnote_index();
+
+    assert_eq!(ctx.get_footnote(index), Some(tree.footnotes[0].as_slice()));
+    let index = ctx.next_footnote_index();
+    assert_eq!(ctx.get_footnote(index), None);
+}
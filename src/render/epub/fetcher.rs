@@ -0,0 +1,49 @@
+/*
+ * render/epub/fetcher.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+/// An image which has been retrieved for embedding into an exported document.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct FetchedImage {
+    /// The MIME type of the image, e.g. `image/png`.
+    pub mime_type: String,
+
+    /// The raw contents of the image file.
+    pub data: Vec<u8>,
+}
+
+/// A trait which retrieves images so they can be embedded into an exported document.
+///
+/// Images which cannot be fetched are left as links to their original URL.
+pub trait ImageFetcher {
+    /// Retrieves the image at the given URL, if possible.
+    fn fetch_image(&self, url: &str) -> Option<FetchedImage>;
+}
+
+/// An [`ImageFetcher`] which does not fetch any images.
+#[derive(Debug)]
+pub struct NullImageFetcher;
+
+impl ImageFetcher for NullImageFetcher {
+    #[inline]
+    fn fetch_image(&self, _url: &str) -> Option<FetchedImage> {
+        None
+    }
+}
@@ -0,0 +1,200 @@
+/*
+ * render/epub/mod.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Renderer producing self-contained XHTML documents.
+//!
+//! The output is a complete XHTML document, with all styles inlined into the
+//! document head and images embedded as `data:` URIs where they can be fetched.
+//! This makes it suitable for packaging pages into EPUB files or offline archives.
+
+#[cfg(test)]
+mod test;
+
+mod fetcher;
+
+#[cfg(test)]
+use super::prelude;
+
+pub use self::fetcher::{FetchedImage, ImageFetcher, NullImageFetcher};
+
+use super::html::{escape, process_css, HtmlHooks, HtmlMetaType, HtmlRender};
+use super::{Handle, Render};
+use crate::data::PageInfo;
+use crate::settings::WikitextSettings;
+use crate::tree::visit::{walk_element_mut, VisitorMut};
+use crate::tree::{Element, ImageSource, SyntaxTree};
+use base64_simd::Base64;
+use std::borrow::Cow;
+
+#[derive(Debug)]
+pub struct EpubRender<F> {
+    fetcher: F,
+}
+
+impl<F: ImageFetcher> EpubRender<F> {
+    #[inline]
+    pub fn new(fetcher: F) -> Self {
+        EpubRender { fetcher }
+    }
+
+    #[inline]
+    pub fn fetcher(&self) -> &F {
+        &self.fetcher
+    }
+
+    /// Renders the document, using the given [`HtmlHooks`] for the body.
+    ///
+    /// See [`HtmlRender::render_with_hooks()`] for more information.
+    pub fn render_with_hooks(
+        &self,
+        tree: &SyntaxTree,
+        page_info: &PageInfo,
+        settings: &WikitextSettings,
+        hooks: HtmlHooks,
+    ) -> String {
+        enter_span!(
+            "render",
+            format = "epub",
+            site = %page_info.site,
+            page = %page_info.page,
+        );
+
+        info!(
+            "Rendering XHTML document (site {}, page {})",
+            page_info.site.as_ref(),
+            page_info.page.as_ref(),
+        );
+
+        // Pull out styles and embed images
+        let mut tree = tree.clone();
+        let mut exporter = Exporter {
+            fetcher: &self.fetcher,
+            page_info,
+            settings,
+            styles: Vec::new(),
+        };
+
+        exporter.visit_syntax_tree_mut(&mut tree);
+        let styles = exporter.styles;
+
+        // Render body and build full document
+        let output = HtmlRender.render_xhtml(&tree, page_info, settings, hooks);
+        let mut document = String::with_capacity(output.body.len() + 512);
+
+        document.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        document.push_str("<!DOCTYPE html>\n");
+        document.push_str("<html xmlns=\"http://www.w3.org/1999/xhtml\" lang=\"");
+        escape(&mut document, &page_info.language);
+        document.push_str("\" xml:lang=\"");
+        escape(&mut document, &page_info.language);
+        document.push_str("\">\n<head>\n<meta charset=\"UTF-8\" />\n<title>");
+        escape(&mut document, &page_info.title);
+        document.push_str("</title>\n");
+
+        for meta in &output.meta {
+            // The content type is specified by the document itself
+            if meta.tag_type != HtmlMetaType::HttpEquiv {
+                meta.render(&mut document);
+                document.push('\n');
+            }
+        }
+
+        for css in styles {
+            if let Some(css) = process_css(&css, settings) {
+                // Wrap in CDATA, in case the stylesheet has characters
+                // which are not permitted in XML text, such as '<' or '&'.
+                document.push_str("<style>/*<![CDATA[*/\n");
+                document.push_str(&css.replace("]]>", "]]\\>"));
+                document.push_str("\n/*]]>*/</style>\n");
+            }
+        }
+
+        document.push_str("</head>\n<body>\n");
+        document.push_str(&output.body);
+        document.push_str("\n</body>\n</html>\n");
+        document
+    }
+}
+
+impl<F: ImageFetcher> Render for EpubRender<F> {
+    type Output = String;
+
+    #[inline]
+    fn render(
+        &self,
+        tree: &SyntaxTree,
+        page_info: &PageInfo,
+        settings: &WikitextSettings,
+    ) -> String {
+        self.render_with_hooks(tree, page_info, settings, HtmlHooks::default())
+    }
+}
+
+#[derive(Debug)]
+struct Exporter<'a, F> {
+    fetcher: &'a F,
+    page_info: &'a PageInfo<'a>,
+    settings: &'a WikitextSettings,
+    styles: Vec<String>,
+}
+
+impl<F: ImageFetcher> Exporter<'_, F> {
+    fn embed_image(&self, source: &mut ImageSource) {
+        let url = match Handle.get_image_link(source, self.page_info, self.settings) {
+            Some(url) if !url.starts_with("data:") => url,
+            _ => return,
+        };
+
+        match self.fetcher.fetch_image(&url) {
+            Some(FetchedImage { mime_type, data }) => {
+                debug!("Embedding image '{url}' ({} bytes)", data.len());
+
+                let encoded = Base64::STANDARD.encode_to_boxed_str(&data);
+                let data_uri = format!("data:{mime_type};base64,{encoded}");
+                *source = ImageSource::Url(Cow::Owned(data_uri));
+            }
+            None => warn!("Unable to fetch image '{url}', leaving as link"),
+        }
+    }
+}
+
+impl<'t, F: ImageFetcher> VisitorMut<'t> for Exporter<'_, F> {
+    fn visit_elements_mut(&mut self, elements: &mut Vec<Element<'t>>) {
+        elements.retain(|element| match element {
+            Element::Style(css) => {
+                self.styles.push(str!(css));
+                false
+            }
+            _ => true,
+        });
+
+        for element in elements {
+            self.visit_element_mut(element);
+        }
+    }
+
+    fn visit_element_mut(&mut self, element: &mut Element<'t>) {
+        if let Element::Image { source, .. } = element {
+            self.embed_image(source);
+        }
+
+        walk_element_mut(self, element);
+    }
+}
@@ -0,0 +1,71 @@
+/*
+ * render/epub/test.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::prelude::*;
+use super::{EpubRender, FetchedImage, ImageFetcher};
+
+#[derive(Debug)]
+struct TestFetcher;
+
+impl ImageFetcher for TestFetcher {
+    fn fetch_image(&self, url: &str) -> Option<FetchedImage> {
+        if url == "https://example.com/cat.png" {
+            Some(FetchedImage {
+                mime_type: str!("image/png"),
+                data: b"meow".to_vec(),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+#[test]
+fn epub() {
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let mut text = str!(
+        "[[image https://example.com/cat.png]] [[image https://example.com/dog.png]]\n\n----\n\n[[module CSS]]\n.a > .b { color: red; }\n[[/module]]"
+    );
+    crate::preprocess(&mut text, &page_info, &settings);
+
+    let tokens = crate::tokenize(&text);
+    let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+    let document = EpubRender::new(TestFetcher).render(&tree, &page_info, &settings);
+
+    assert!(document.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n"));
+    assert!(document.contains("<html xmlns=\"http://www.w3.org/1999/xhtml\""));
+    assert!(document.contains(&format!("<title>{}</title>", page_info.title)));
+
+    // Styles are moved into the head
+    let (head, body) = document.split_once("</head>").unwrap();
+    assert!(head.contains("<style>/*<![CDATA[*/"));
+    assert!(head.contains(".a>.b"));
+    assert!(!body.contains("<style>"));
+    assert!(!head.contains("http-equiv"));
+
+    // Fetched images are embedded, others are left as links
+    assert!(body.contains("src=\"data:image/png;base64,bWVvdw==\""));
+    assert!(body.contains("src=\"https://example.com/dog.png\""));
+
+    // Void elements are self-closed
+    assert!(body.contains("<hr />"));
+    assert!(!body.contains("<hr>"));
+}
@@ -0,0 +1,227 @@
+/*
+ * render/error.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Errors for syntax trees which cannot be rendered faithfully.
+//!
+//! The parser never produces such trees, but since [`SyntaxTree`] can be
+//! deserialized, a stored or handcrafted tree may be inconsistent. Renderers
+//! do not panic on these, instead skipping the offending part of the tree.
+//! [`validate_tree()`] can be used to detect these cases ahead of time,
+//! and is what [`Render::try_render()`](super::Render::try_render) uses.
+
+use super::count_footnotes;
+use crate::tree::visit::{walk_element, Visitor};
+use crate::tree::{Element, LinkLabel, LinkLocation, SyntaxTree};
+use std::error::Error;
+use std::fmt::{self, Display};
+
+/// Error returned when a syntax tree is inconsistent and cannot be rendered.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case", tag = "type", content = "data")]
+pub enum RenderError {
+    /// The tree contains a partial element, which is only valid during parsing.
+    PartialElement,
+
+    /// The tree has more footnote references than footnotes.
+    MissingFootnotes { references: usize, footnotes: usize },
+
+    /// A bibliography block refers to a bibliography which does not exist.
+    MissingBibliography { index: usize, bibliographies: usize },
+
+    /// A link uses the page title as its label, but points to a URL.
+    PageLabelForUrl,
+}
+
+impl Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RenderError::PartialElement => {
+                write!(f, "partial element encountered during rendering")
+            }
+            RenderError::MissingFootnotes {
+                references,
+                footnotes,
+            } => write!(
+                f,
+                "{references} footnote references found, but only {footnotes} footnotes",
+            ),
+            RenderError::MissingBibliography {
+                index,
+                bibliographies,
+            } => write!(
+                f,
+                "bibliography index {index} out of bounds (only {bibliographies} bibliographies)",
+            ),
+            RenderError::PageLabelForUrl => {
+                write!(f, "link requests page title label for a URL")
+            }
+        }
+    }
+}
+
+impl Error for RenderError {}
+
+/// Checks that a syntax tree can be rendered faithfully.
+///
+/// Returns the first problem found, if any. See [`RenderError`]
+/// for the conditions which are checked.
+pub fn validate_tree(tree: &SyntaxTree) -> Result<(), RenderError> {
+    #[derive(Debug)]
+    struct Validator {
+        bibliographies: usize,
+        error: Option<RenderError>,
+    }
+
+    impl<'t> Visitor<'t> for Validator {
+        fn visit_element(&mut self, element: &Element<'t>) {
+            if self.error.is_some() {
+                return;
+            }
+
+            match element {
+                Element::Partial(_) => {
+                    self.error = Some(RenderError::PartialElement);
+                    return;
+                }
+                Element::BibliographyBlock { index, .. }
+                    if *index >= self.bibliographies =>
+                {
+                    self.error = Some(RenderError::MissingBibliography {
+                        index: *index,
+                        bibliographies: self.bibliographies,
+                    });
+                    return;
+                }
+                Element::Link {
+                    link: LinkLocation::Url(_),
+                    label: LinkLabel::Page,
+                    ..
+                } => {
+                    self.error = Some(RenderError::PageLabelForUrl);
+                    return;
+                }
+                _ => (),
+            }
+
+            walk_element(self, element);
+        }
+    }
+
+    let mut validator = Validator {
+        bibliographies: tree.bibliographies.slice().len(),
+        error: None,
+    };
+
+    validator.visit_syntax_tree(tree);
+    if let Some(error) = validator.error {
+        return Err(error);
+    }
+
+    // Footnote references within footnotes would also be rendered,
+    // so they are counted too.
+    let references = count_footnotes(&tree.elements)
+        + tree
+            .footnotes
+            .iter()
+            .map(|contents| count_footnotes(contents))
+            .sum::<usize>();
+
+    if references > tree.footnotes.len() {
+        return Err(RenderError::MissingFootnotes {
+            references,
+            footnotes: tree.footnotes.len(),
+        });
+    }
+
+    Ok(())
+}
+
+#[test]
+fn validate() {
+    use super::text::TextRender;
+    use super::Render;
+    use crate::data::PageInfo;
+    use crate::layout::Layout;
+    use crate::settings::{WikitextMode, WikitextSettings};
+    use crate::tree::{ListItem, PartialElement};
+
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+
+    macro_rules! check {
+        ($elements:expr, $footnotes:expr, $expected:expr $(,)?) => {{
+            let tree = SyntaxTree {
+                elements: $elements,
+                footnotes: $footnotes,
+                ..SyntaxTree::default()
+            };
+
+            assert_eq!(
+                validate_tree(&tree),
+                $expected,
+                "Tree validation result doesn't match expected",
+            );
+
+            // Rendering an invalid tree must not panic
+            let _ = TextRender.render(&tree, &page_info, &settings);
+
+            assert_eq!(
+                TextRender
+                    .try_render(&tree, &page_info, &settings)
+                    .map(|_| ()),
+                $expected,
+                "Render result doesn't match expected",
+            );
+        }};
+    }
+
+    check!(vec![text!("Apple")], vec![], Ok(()));
+    check!(vec![Element::Footnote], vec![vec![text!("Banana")]], Ok(()),);
+    check!(
+        vec![Element::Footnote, Element::Footnote],
+        vec![vec![text!("Banana")]],
+        Err(RenderError::MissingFootnotes {
+            references: 2,
+            footnotes: 1,
+        }),
+    );
+    check!(
+        vec![Element::Partial(PartialElement::ListItem(
+            ListItem::Elements {
+                elements: vec![],
+                attributes: Default::default(),
+            },
+        ))],
+        vec![],
+        Err(RenderError::PartialElement),
+    );
+    check!(
+        vec![Element::BibliographyBlock {
+            index: 0,
+            title: None,
+            hide: false,
+        }],
+        vec![],
+        Err(RenderError::MissingBibliography {
+            index: 0,
+            bibliographies: 0,
+        }),
+    );
+}
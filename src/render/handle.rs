@@ -20,20 +20,28 @@
 
 use crate::data::{PageInfo, UserInfo};
 use crate::settings::WikitextSettings;
-use crate::tree::{ImageSource, LinkLabel, LinkLocation, Module};
-use crate::url::BuildSiteUrl;
+use crate::tree::{ImageSource, LinkLabel, LinkLocation};
+use crate::url::{BuildSiteUrl, SiteLocation};
 use std::borrow::Cow;
 use std::num::NonZeroUsize;
 use wikidot_normalize::normalize;
 
+#[cfg(feature = "html")]
+use crate::{render::html::TrustedHtml, tree::Module};
+
 #[derive(Debug)]
 pub struct Handle;
 
 impl Handle {
-    pub fn render_module(&self, buffer: &mut String, module: &Module) {
+    #[cfg(feature = "html")]
+    pub fn render_module(&self, module: &Module) -> TrustedHtml {
         // Modules only render to HTML
         debug!("Rendering module '{}'", module.name());
-        str_write!(buffer, "<p>TODO: module {}</p>", module.name());
+
+        let mut html = TrustedHtml::from_trusted("<p>TODO: module ");
+        html.push_escaped(module.name());
+        html.push_trusted("</p>");
+        html
     }
 
     pub fn get_page_title(&self, _site: &str, _page: &str) -> Option<String> {
@@ -87,10 +95,7 @@ impl Handle {
             ImageSource::File3 { site, page, file } => (site, page, file),
         };
 
-        // TODO: emit url
-        Some(Cow::Owned(format!(
-            "https://{site}.wjfiles.com/local--files/{page}/{file}",
-        )))
+        Some(Cow::Owned(self.build_file_url(site, page, file)))
     }
 
     pub fn get_link_label<F>(
@@ -104,15 +109,16 @@ impl Handle {
     {
         let page_title;
         let label_text = match *label {
-            LinkLabel::Text(ref text) => text,
+            LinkLabel::Text(ref text) if !label.is_blank() => text,
             LinkLabel::Url(Some(ref text)) => text,
-            LinkLabel::Url(None) => match link {
+            LinkLabel::Text(_) | LinkLabel::Url(None) => match link {
                 LinkLocation::Url(url) => url,
                 LinkLocation::Page(page_ref) => page_ref.page(),
             },
             LinkLabel::Page => match link {
-                LinkLocation::Url(_) => {
-                    panic!("Requested link label of page for a URL");
+                LinkLocation::Url(url) => {
+                    error!("Requested link label of page for a URL, using URL");
+                    url.as_ref()
                 }
                 LinkLocation::Page(page_ref) => {
                     let (site, page) = page_ref.fields_or(site);
@@ -142,10 +148,15 @@ impl Handle {
             "table-of-contents" => "Table of Contents",
             "footnote" => "Footnote",
             "footnote-block-title" => "Footnotes",
+            "footnote-backlink" => "Back to reference",
             "bibliography-reference" => "Reference",
             "bibliography-block-title" => "Bibliography",
             "bibliography-cite-not-found" => "Bibliography item not found",
             "image-context-bad" => "No images in this context",
+            "link-list-title" => "Links",
+            "external-link" => "external link",
+            "pagination-previous" => "« previous",
+            "pagination-next" => "next »",
             _ => {
                 error!("Unknown message requested (key {message})");
                 "?"
@@ -187,4 +198,20 @@ impl BuildSiteUrl for Handle {
         // TODO
         format!("https://{site}.wikijump.com/{path}")
     }
+
+    fn site_location(&self, _site: &str) -> SiteLocation {
+        // For testing
+        #[cfg(test)]
+        if _site == "external" {
+            return SiteLocation::External;
+        }
+
+        // TODO
+        SiteLocation::SameFarm
+    }
+
+    fn build_file_url(&self, site: &str, page: &str, file: &str) -> String {
+        // TODO: emit url
+        format!("https://{site}.wjfiles.com/local--files/{page}/{file}")
+    }
 }
@@ -20,6 +20,7 @@
 
 use super::attributes::AddedAttributes;
 use super::context::HtmlContext;
+use super::minify::{style_preserves_whitespace, PRESERVE_WHITESPACE_TAGS};
 use super::render::ItemRender;
 use std::collections::HashSet;
 
@@ -37,6 +38,13 @@ const SOLO_HTML_TAGS: [&str; 14] = [
     "source", "track", "wbr",
 ];
 
+/// Custom elements which contain block content.
+///
+/// AMP does not permit custom elements, so these are replaced with `<div>`
+/// rather than `<span>` when rendering for it.
+pub const BLOCK_CUSTOM_ELEMENTS: [&str; 4] =
+    ["wj-body", "wj-code", "wj-source-span", "wj-tabs"];
+
 // Main struct
 
 #[derive(Debug)]
@@ -66,11 +74,42 @@ where
     }
 
     /// Create a new custom element. Tag must start with `wj-`.
+    ///
+    /// For AMP, a plain element is created in its place.
     #[inline]
     pub fn element(self, tag: &'t str) -> HtmlBuilderTag<'c, 'i, 'h, 'e, 't> {
         debug_assert!(tag.starts_with("wj-"));
 
-        self.tag(tag)
+        if !self.ctx.amp() {
+            self.tag(tag)
+        } else if BLOCK_CUSTOM_ELEMENTS.contains(&tag) {
+            self.tag("div")
+        } else {
+            self.tag("span")
+        }
+    }
+
+    /// Create a new image element, or `<amp-img>` for AMP.
+    pub fn img(self) -> HtmlBuilderTag<'c, 'i, 'h, 'e, 't> {
+        if self.ctx.amp() {
+            let mut tag = self.tag("amp-img");
+            tag.attr_single("layout", &["flex-item"]);
+            tag
+        } else {
+            self.tag("img")
+        }
+    }
+
+    /// Create a new iframe element, or `<amp-iframe>` for AMP.
+    pub fn iframe(self) -> HtmlBuilderTag<'c, 'i, 'h, 'e, 't> {
+        if self.ctx.amp() {
+            let mut tag = self.tag("amp-iframe");
+            tag.attr_single("layout", &["flex-item"]);
+            tag.attr_single("sandbox", &["allow-scripts allow-same-origin"]);
+            tag
+        } else {
+            self.tag("iframe")
+        }
     }
 
     #[inline]
@@ -111,8 +150,6 @@ where
     tag_method!(dl);
     tag_method!(dt);
     tag_method!(hr);
-    tag_method!(iframe);
-    tag_method!(img);
     tag_method!(input);
     tag_method!(li);
     tag_method!(ol);
@@ -131,7 +168,7 @@ where
 
     #[inline]
     pub fn text(&mut self, text: &str) {
-        self.ctx.push_escaped(text);
+        self.ctx.push_text(text);
     }
 }
 
@@ -146,6 +183,8 @@ where
     tag: &'t str,
     in_tag: bool,
     in_contents: bool,
+    keep_whitespace: bool,
+    preserve_whitespace: bool,
 }
 
 impl<'c, 'i, 'h, 'e, 't> HtmlBuilderTag<'c, 'i, 'h, 'e, 't> {
@@ -158,9 +197,20 @@ impl<'c, 'i, 'h, 'e, 't> HtmlBuilderTag<'c, 'i, 'h, 'e, 't> {
             tag,
             in_tag: true,
             in_contents: false,
+            keep_whitespace: false,
+            preserve_whitespace: false,
         }
     }
 
+    /// Emits the text within this element exactly as given, even when minifying.
+    ///
+    /// This is already done for tags such as `<pre>`, and for elements
+    /// whose inline style is `white-space: pre` or similar.
+    pub fn keep_whitespace(&mut self) -> &mut Self {
+        self.keep_whitespace = true;
+        self
+    }
+
     fn attr_key(&mut self, key: &str, has_value: bool) {
         debug_assert!(is_alphanumeric(key));
         debug_assert!(self.in_tag);
@@ -170,6 +220,9 @@ impl<'c, 'i, 'h, 'e, 't> HtmlBuilderTag<'c, 'i, 'h, 'e, 't> {
 
         if has_value {
             self.ctx.push_raw('=');
+        } else if self.ctx.xhtml() {
+            // XML does not permit attribute minimization
+            self.ctx.push_raw_str("=\"\"");
         }
     }
 
@@ -184,6 +237,36 @@ impl<'c, 'i, 'h, 'e, 't> HtmlBuilderTag<'c, 'i, 'h, 'e, 't> {
     }
 
     pub fn attr_single(&mut self, key: &str, value_parts: &[&str]) -> &mut Self {
+        // Allow the host to rewrite any emitted URLs
+        if key == "href" || key == "src" {
+            let url = value_parts.concat();
+            let url = self.ctx.url_rewriter().rewrite_url(&url);
+            return self.attr_single_raw(key, &[&url]);
+        }
+
+        self.attr_single_raw(key, value_parts)
+    }
+
+    fn attr_single_raw(&mut self, key: &str, value_parts: &[&str]) -> &mut Self {
+        // Text in elements styled like <pre> must not be minified
+        if key == "style" && style_preserves_whitespace(&value_parts.concat()) {
+            self.keep_whitespace = true;
+        }
+
+        if self.ctx.amp() {
+            // AMP forbids event handlers, and !important in inline styles
+            if key.starts_with("on") {
+                return self;
+            }
+
+            if key == "style" {
+                let value = value_parts.concat().replace("!important", "");
+                self.attr_key(key, true);
+                self.attr_value(&[&value]);
+                return self;
+            }
+        }
+
         // If value_parts is empty, then we just give the key.
         //
         // For instance, ("checked", &[]) in input produces
@@ -222,7 +305,6 @@ impl<'c, 'i, 'h, 'e, 't> HtmlBuilderTag<'c, 'i, 'h, 'e, 't> {
         }
 
         let mut merged = HashSet::new();
-        let mut merged_value = Vec::new();
 
         // Merge any attributes in common.
         if let Some(attribute_map) = attributes.map {
@@ -232,9 +314,15 @@ impl<'c, 'i, 'h, 'e, 't> HtmlBuilderTag<'c, 'i, 'h, 'e, 't> {
                 if let Some(map_value) = attribute_map.get(&cow!(key)) {
                     // Merge keys by prepending value_parts before
                     // the attribute map value.
-
-                    merged_value.clear();
-                    merged_value.extend(value_parts);
+                    //
+                    // Only the renderer's classes are prefixed, not the user's.
+                    let class_list = self.class_list(key, value_parts);
+                    let mut merged_value = Vec::new();
+
+                    match class_list {
+                        Some(ref class_list) => merged_value.push(class_list.as_str()),
+                        None => merged_value.extend(value_parts),
+                    }
                     merged_value.push(" ");
                     merged_value.push(map_value);
 
@@ -247,7 +335,10 @@ impl<'c, 'i, 'h, 'e, 't> HtmlBuilderTag<'c, 'i, 'h, 'e, 't> {
         // Add attributes from renderer.
         for (key, value_parts) in filter_entries(&attributes) {
             if !merged.contains(key) {
-                self.attr_single(key, value_parts);
+                match self.class_list(key, value_parts) {
+                    Some(class_list) => self.attr_single(key, &[&class_list]),
+                    None => self.attr_single(key, value_parts),
+                };
             }
         }
 
@@ -263,6 +354,15 @@ impl<'c, 'i, 'h, 'e, 't> HtmlBuilderTag<'c, 'i, 'h, 'e, 't> {
         self
     }
 
+    /// Applies the configured prefix to the renderer's classes, if this is the `class` attribute.
+    fn class_list(&self, key: &str, value_parts: &[&str]) -> Option<String> {
+        if key == "class" {
+            self.ctx.class_list(value_parts)
+        } else {
+            None
+        }
+    }
+
     fn content_start(&mut self) {
         if self.in_tag {
             self.ctx.push_raw('>');
@@ -271,6 +371,11 @@ impl<'c, 'i, 'h, 'e, 't> HtmlBuilderTag<'c, 'i, 'h, 'e, 't> {
 
         assert!(!self.in_contents, "Already in tag contents");
         self.in_contents = true;
+
+        if self.keep_whitespace || PRESERVE_WHITESPACE_TAGS.contains(&self.tag) {
+            self.ctx.preserve_whitespace_start();
+            self.preserve_whitespace = true;
+        }
     }
 
     #[inline]
@@ -294,11 +399,25 @@ impl<'c, 'i, 'h, 'e, 't> HtmlBuilderTag<'c, 'i, 'h, 'e, 't> {
 
 impl Drop for HtmlBuilderTag<'_, '_, '_, '_, '_> {
     fn drop(&mut self) {
+        let close_tag = should_close_tag(self.tag);
+
         if self.in_tag && !self.in_contents {
-            self.ctx.push_raw('>');
+            if !close_tag && self.ctx.xhtml() {
+                self.ctx.push_raw_str(" />");
+            } else {
+                self.ctx.push_raw('>');
+            }
+        }
+
+        if self.preserve_whitespace {
+            self.ctx.preserve_whitespace_end();
         }
 
-        if should_close_tag(self.tag) {
+        if close_tag {
+            if self.in_contents {
+                self.ctx.minify_before_close(self.tag);
+            }
+
             self.ctx.push_raw_str("</");
             self.ctx.push_raw_str(self.tag);
             self.ctx.push_raw('>');
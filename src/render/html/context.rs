@@ -19,26 +19,58 @@
  */
 
 use super::builder::HtmlBuilder;
+use super::email_rewriter::EmailRewriter;
 use super::escape::escape;
+use super::hooks::HtmlHooks;
+use super::image_srcset::ImageSrcsetProvider;
+use super::layout_class::ElementClass;
+use super::math_image::MathImageProvider;
 use super::meta::{HtmlMeta, HtmlMetaType};
+use super::minify::{push_minified, trim_before_close};
+use super::module::ModuleRenderer;
 use super::output::HtmlOutput;
+use super::page_variables::PageVariableProvider;
+use super::pagination::PaginationProvider;
 use super::random::Random;
+use super::trusted::TrustedHtml;
+use super::url_rewriter::UrlRewriter;
+use crate::budget::WorkBudget;
 use crate::data::PageRef;
-use crate::data::{Backlinks, PageInfo};
+use crate::data::{Backlinks, ContentReport, DeadLink, PageInfo};
 use crate::info;
 use crate::layout::Layout;
 use crate::next_index::{NextIndex, TableOfContentsIndex};
-use crate::render::Handle;
+use crate::parsing::ParseError;
+use crate::render::{Handle, RenderContext};
 use crate::settings::WikitextSettings;
 use crate::tree::{
-    Bibliography, BibliographyList, Element, LinkLocation, VariableScopes,
+    Bibliography, BibliographyList, CitationStyle, Element, ImageSource, LinkLocation,
+    LinkType, SyntaxTree, VariableScopes,
 };
-use crate::url::is_url;
+use crate::url::{BuildSiteUrl, SiteLocation};
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::fmt::{self, Write};
+use std::mem;
 use std::num::NonZeroUsize;
 
+/// How deeply expanded modules may contain further expanded modules.
+const MAX_MODULE_DEPTH: usize = 10;
+
+/// Which flavor of HTML is being produced.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum HtmlProfile {
+    /// Regular HTML, as used for pages.
+    #[default]
+    Html,
+
+    /// Well-formed XML, as used by EPUB.
+    Xhtml,
+
+    /// The subset of HTML permitted by AMP.
+    Amp,
+}
+
 #[derive(Debug)]
 pub struct HtmlContext<'i, 'h, 'e, 't>
 where
@@ -47,22 +79,19 @@ where
     body: String,
     meta: Vec<HtmlMeta>,
     backlinks: Backlinks<'static>,
-    info: &'i PageInfo<'i>,
+    content_report: ContentReport<'static>,
+    render: RenderContext<'i, 'e, 't>,
     handle: &'h Handle,
-    settings: &'e WikitextSettings,
+    module_renderer: Option<&'h dyn ModuleRenderer>,
+    math_images: &'h dyn MathImageProvider,
+    image_srcset: &'h dyn ImageSrcsetProvider,
+    pagination: &'h dyn PaginationProvider,
+    page_variables: &'h dyn PageVariableProvider,
+    url_rewriter: &'h dyn UrlRewriter,
+    email_rewriter: &'h dyn EmailRewriter,
+    budget: Option<&'h WorkBudget>,
     random: Random,
-
-    //
-    // Included page scopes
-    //
-    variables: VariableScopes,
-
-    //
-    // Fields from syntax tree
-    //
-    table_of_contents: &'e [Element<'t>],
-    footnotes: &'e [Vec<Element<'t>>],
-    bibliographies: &'e BibliographyList<'t>,
+    profile: HtmlProfile,
 
     //
     // Cached data
@@ -72,10 +101,11 @@ where
     //
     // Other fields to track
     //
-    code_snippet_index: NonZeroUsize,
-    table_of_contents_index: usize,
-    equation_index: NonZeroUsize,
-    footnote_index: NonZeroUsize,
+    table_of_contents_block_index: usize,
+    collapsible_index: NonZeroUsize,
+    footnote_refs: Vec<FootnoteRefs>,
+    module_depth: usize,
+    preserve_whitespace: usize,
 }
 
 impl<'i, 'h, 'e, 't> HtmlContext<'i, 'h, 'e, 't> {
@@ -84,7 +114,7 @@ impl<'i, 'h, 'e, 't> HtmlContext<'i, 'h, 'e, 't> {
         info: &'i PageInfo<'i>,
         handle: &'h Handle,
         settings: &'e WikitextSettings,
-        table_of_contents: &'e [Element<'t>],
+        (table_of_contents, heading_anchors): (&'e [Element<'t>], &'e [String]),
         footnotes: &'e [Vec<Element<'t>>],
         bibliographies: &'e BibliographyList<'t>,
         wikitext_len: usize,
@@ -112,19 +142,31 @@ impl<'i, 'h, 'e, 't> HtmlContext<'i, 'h, 'e, 't> {
             body: String::with_capacity(capacity),
             meta: Self::initial_metadata(info, settings.layout),
             backlinks: Backlinks::new(),
-            info,
+            content_report: ContentReport::new(),
+            render: RenderContext::new(
+                info,
+                settings,
+                (table_of_contents, heading_anchors),
+                footnotes,
+                bibliographies,
+            ),
             handle,
-            settings,
+            module_renderer: None,
+            math_images: handle,
+            image_srcset: handle,
+            pagination: handle,
+            page_variables: handle,
+            url_rewriter: handle,
+            email_rewriter: handle,
+            budget: None,
             random: Random::default(),
-            variables: VariableScopes::new(),
-            table_of_contents,
-            footnotes,
-            bibliographies,
+            profile: HtmlProfile::default(),
             pages_exists: HashMap::new(),
-            code_snippet_index: NonZeroUsize::new(1).unwrap(),
-            table_of_contents_index: 0,
-            equation_index: NonZeroUsize::new(1).unwrap(),
-            footnote_index: NonZeroUsize::new(1).unwrap(),
+            table_of_contents_block_index: 0,
+            collapsible_index: NonZeroUsize::MIN,
+            footnote_refs: Vec::new(),
+            module_depth: 0,
+            preserve_whitespace: 0,
         }
     }
 
@@ -165,13 +207,55 @@ impl<'i, 'h, 'e, 't> HtmlContext<'i, 'h, 'e, 't> {
 
     // Field access
     #[inline]
-    pub fn info(&self) -> &PageInfo<'i> {
-        self.info
+    pub fn render_context(&self) -> &RenderContext<'i, 'e, 't> {
+        &self.render
+    }
+
+    #[inline]
+    pub fn render_context_mut(&mut self) -> &mut RenderContext<'i, 'e, 't> {
+        &mut self.render
+    }
+
+    #[inline]
+    pub fn info(&self) -> &'i PageInfo<'i> {
+        self.render.info()
     }
 
     #[inline]
     pub fn settings(&self) -> &WikitextSettings {
-        self.settings
+        self.render.settings()
+    }
+
+    /// Gets the class for this element in the current layout.
+    #[inline]
+    pub fn layout_class(&self, element: ElementClass) -> &'static str {
+        element.get(self.settings().layout)
+    }
+
+    /// Gets the name of a generated class, applying the configured prefix.
+    ///
+    /// If [`WikitextSettings::class_prefix`] is set, then it replaces
+    /// the `wj-` at the start of the class. Other classes are unchanged.
+    pub fn class_name<'a>(&self, class: &'a str) -> Cow<'a, str> {
+        match (&self.settings().class_prefix, class.strip_prefix("wj-")) {
+            (Some(prefix), Some(rest)) => Cow::Owned(format!("{prefix}{rest}")),
+            _ => Cow::Borrowed(class),
+        }
+    }
+
+    /// Applies the configured prefix to each of a space-separated list of generated classes.
+    ///
+    /// Returns `None` if there is no prefix configured, as then the classes are unchanged.
+    pub fn class_list(&self, value_parts: &[&str]) -> Option<String> {
+        self.settings().class_prefix.as_ref()?;
+
+        let value = value_parts.concat();
+        let classes = value
+            .split(' ')
+            .map(|class| self.class_name(class))
+            .collect::<Vec<_>>();
+
+        Some(classes.join(" "))
     }
 
     #[inline]
@@ -179,115 +263,458 @@ impl<'i, 'h, 'e, 't> HtmlContext<'i, 'h, 'e, 't> {
         self.handle
     }
 
+    #[inline]
+    pub fn module_renderer(&self) -> Option<&'h dyn ModuleRenderer> {
+        self.module_renderer
+    }
+
+    #[inline]
+    pub fn set_module_renderer(&mut self, module_renderer: &'h dyn ModuleRenderer) {
+        self.module_renderer = Some(module_renderer);
+    }
+
+    #[inline]
+    pub fn math_images(&self) -> &'h dyn MathImageProvider {
+        self.math_images
+    }
+
+    #[inline]
+    pub fn set_math_image_provider(&mut self, math_images: &'h dyn MathImageProvider) {
+        self.math_images = math_images;
+    }
+
+    #[inline]
+    pub fn image_srcset(&self) -> &'h dyn ImageSrcsetProvider {
+        self.image_srcset
+    }
+
+    #[inline]
+    pub fn set_image_srcset_provider(
+        &mut self,
+        image_srcset: &'h dyn ImageSrcsetProvider,
+    ) {
+        self.image_srcset = image_srcset;
+    }
+
+    #[inline]
+    pub fn pagination(&self) -> &'h dyn PaginationProvider {
+        self.pagination
+    }
+
+    #[inline]
+    pub fn set_pagination_provider(&mut self, pagination: &'h dyn PaginationProvider) {
+        self.pagination = pagination;
+    }
+
+    #[inline]
+    pub fn page_variables(&self) -> &'h dyn PageVariableProvider {
+        self.page_variables
+    }
+
+    #[inline]
+    pub fn set_page_variable_provider(
+        &mut self,
+        page_variables: &'h dyn PageVariableProvider,
+    ) {
+        self.page_variables = page_variables;
+    }
+
+    #[inline]
+    pub fn url_rewriter(&self) -> &'h dyn UrlRewriter {
+        self.url_rewriter
+    }
+
+    #[inline]
+    pub fn set_url_rewriter(&mut self, url_rewriter: &'h dyn UrlRewriter) {
+        self.url_rewriter = url_rewriter;
+    }
+
+    #[inline]
+    pub fn email_rewriter(&self) -> &'h dyn EmailRewriter {
+        self.email_rewriter
+    }
+
+    #[inline]
+    pub fn set_email_rewriter(&mut self, email_rewriter: &'h dyn EmailRewriter) {
+        self.email_rewriter = email_rewriter;
+    }
+
+    /// Gets the work budget to spend from, if any.
+    #[inline]
+    pub fn budget(&self) -> Option<&'h WorkBudget> {
+        self.budget
+    }
+
+    #[inline]
+    pub fn set_budget(&mut self, budget: &'h WorkBudget) {
+        self.budget = Some(budget);
+    }
+
+    /// Sets each hook which is provided, keeping the defaults for the rest.
+    pub fn set_hooks(&mut self, hooks: HtmlHooks<'h>) {
+        if let Some(modules) = hooks.modules {
+            self.set_module_renderer(modules);
+        }
+
+        if let Some(math_images) = hooks.math_images {
+            self.set_math_image_provider(math_images);
+        }
+
+        if let Some(image_srcset) = hooks.image_srcset {
+            self.set_image_srcset_provider(image_srcset);
+        }
+
+        if let Some(pagination) = hooks.pagination {
+            self.set_pagination_provider(pagination);
+        }
+
+        if let Some(page_variables) = hooks.page_variables {
+            self.set_page_variable_provider(page_variables);
+        }
+
+        if let Some(url_rewriter) = hooks.url_rewriter {
+            self.set_url_rewriter(url_rewriter);
+        }
+
+        if let Some(email_rewriter) = hooks.email_rewriter {
+            self.set_email_rewriter(email_rewriter);
+        }
+
+        if let Some(budget) = hooks.budget {
+            self.set_budget(budget);
+        }
+    }
+
     #[inline]
     pub fn random(&mut self) -> &mut Random {
         &mut self.random
     }
 
+    /// Whether output should be well-formed XHTML rather than HTML.
+    #[inline]
+    pub fn xhtml(&self) -> bool {
+        self.profile == HtmlProfile::Xhtml
+    }
+
+    /// Whether output should be restricted to markup valid in AMP.
+    #[inline]
+    pub fn amp(&self) -> bool {
+        self.profile == HtmlProfile::Amp
+    }
+
+    #[inline]
+    pub fn set_profile(&mut self, profile: HtmlProfile) {
+        self.profile = profile;
+    }
+
     #[inline]
     pub fn language(&self) -> &str {
-        &self.info.language
+        self.render.language()
     }
 
     #[inline]
     pub fn variables(&self) -> &VariableScopes {
-        &self.variables
+        self.render.variables()
     }
 
     #[inline]
     pub fn variables_mut(&mut self) -> &mut VariableScopes {
-        &mut self.variables
+        self.render.variables_mut()
     }
 
     #[inline]
     pub fn table_of_contents(&self) -> &'e [Element<'t>] {
-        self.table_of_contents
+        self.render.table_of_contents()
+    }
+
+    #[inline]
+    pub fn heading_anchors(&self) -> &'e [String] {
+        self.render.heading_anchors()
     }
 
     #[inline]
     pub fn footnotes(&self) -> &'e [Vec<Element<'t>>] {
-        self.footnotes
+        self.render.footnotes()
     }
 
     #[inline]
-    pub fn get_bibliography(&self, index: usize) -> &'e Bibliography<'t> {
-        self.bibliographies.get_bibliography(index)
+    pub fn get_bibliography(&self, index: usize) -> Option<&'e Bibliography<'t>> {
+        self.render.get_bibliography(index)
     }
 
+    #[inline]
     pub fn get_bibliography_ref(
         &self,
         label: &str,
-    ) -> Option<(usize, &'e [Element<'t>])> {
-        self.bibliographies.get_reference(label)
+    ) -> Option<(usize, &'e [Element<'t>], CitationStyle)> {
+        self.render.get_bibliography_ref(label)
+    }
+
+    /// Enters an expanded module, returning `false` if they are nested too deeply.
+    pub fn enter_module(&mut self) -> bool {
+        if self.module_depth >= MAX_MODULE_DEPTH {
+            return false;
+        }
+
+        self.module_depth += 1;
+        true
     }
 
+    #[inline]
+    pub fn exit_module(&mut self) {
+        self.module_depth -= 1;
+    }
+
+    /// Renders a separately parsed tree in place, such as the wikitext of an expanded module.
+    ///
+    /// The tree is rendered in its own context, so that its footnotes, bibliographies,
+    /// and table of contents are used rather than those of the page. The hooks and
+    /// the code snippet, equation, and collapsible indices are shared with this context.
+    pub fn render_tree<F>(
+        &mut self,
+        tree: &SyntaxTree,
+        settings: &WikitextSettings,
+        render: F,
+    ) where
+        F: FnOnce(&mut HtmlContext),
+    {
+        let mut ctx = HtmlContext::new(
+            self.info(),
+            self.handle,
+            settings,
+            (&tree.table_of_contents, &tree.heading_anchors),
+            &tree.footnotes,
+            &tree.bibliographies,
+            tree.wikitext_len,
+        );
+
+        ctx.module_renderer = self.module_renderer;
+        ctx.math_images = self.math_images;
+        ctx.image_srcset = self.image_srcset;
+        ctx.pagination = self.pagination;
+        ctx.page_variables = self.page_variables;
+        ctx.url_rewriter = self.url_rewriter;
+        ctx.email_rewriter = self.email_rewriter;
+        ctx.budget = self.budget;
+        ctx.profile = self.profile;
+        ctx.module_depth = self.module_depth;
+        ctx.preserve_whitespace = self.preserve_whitespace;
+
+        // Continue the page's numbering within the tree, then take it back
+        self.swap_shared_indices(&mut ctx);
+        render(&mut ctx);
+        self.swap_shared_indices(&mut ctx);
+
+        self.body.push_str(&ctx.body);
+        self.backlinks.append(&mut ctx.backlinks);
+        self.content_report.append(&mut ctx.content_report);
+    }
+
+    fn swap_shared_indices(&mut self, other: &mut HtmlContext) {
+        self.render.swap_shared_indices(&mut other.render);
+        mem::swap(
+            &mut self.table_of_contents_block_index,
+            &mut other.table_of_contents_block_index,
+        );
+        mem::swap(&mut self.collapsible_index, &mut other.collapsible_index);
+    }
+
+    #[inline]
     pub fn next_code_snippet_index(&mut self) -> NonZeroUsize {
-        let index = self.code_snippet_index;
-        self.code_snippet_index = NonZeroUsize::new(index.get() + 1).unwrap();
-        index
+        self.render.next_code_snippet_index()
     }
 
+    #[inline]
     pub fn next_table_of_contents_index(&mut self) -> usize {
-        let index = self.table_of_contents_index;
-        self.table_of_contents_index += 1;
+        self.render.next_table_of_contents_index()
+    }
+
+    /// Gets the index of the next `[[toc]]` block.
+    ///
+    /// Only the first block gets the regular IDs, later ones have
+    /// this index appended so that the IDs remain unique.
+    pub fn next_table_of_contents_block_index(&mut self) -> usize {
+        let index = self.table_of_contents_block_index;
+        self.table_of_contents_block_index += 1;
         index
     }
 
-    pub fn next_equation_index(&mut self) -> NonZeroUsize {
-        let index = self.equation_index;
-        self.equation_index = NonZeroUsize::new(index.get() + 1).unwrap();
+    /// Gets the index of the next collapsible.
+    ///
+    /// Unlike the randomly-generated IDs, this is the same between renders
+    /// of the page, so it can be used to identify a particular collapsible.
+    pub fn next_collapsible_index(&mut self) -> NonZeroUsize {
+        let index = self.collapsible_index;
+        self.collapsible_index = index.saturating_add(1);
         index
     }
 
+    #[inline]
+    pub fn next_equation_index(&mut self) -> NonZeroUsize {
+        self.render.next_equation_index()
+    }
+
+    #[inline]
     pub fn next_footnote_index(&mut self) -> NonZeroUsize {
-        let index = self.footnote_index;
-        self.footnote_index = NonZeroUsize::new(index.get() + 1).unwrap();
-        index
+        self.render.next_footnote_index()
+    }
+
+    /// Sets the starting values of the index counters.
+    ///
+    /// This is used when rendering only part of a tree in this context,
+    /// so that the indices match those of a render of the whole tree.
+    #[cfg(feature = "parallel")]
+    pub fn set_indices(
+        &mut self,
+        code_snippet_index: NonZeroUsize,
+        table_of_contents_index: usize,
+        table_of_contents_block_index: usize,
+        collapsible_index: NonZeroUsize,
+        equation_index: NonZeroUsize,
+        footnote_index: NonZeroUsize,
+    ) {
+        self.render.set_indices(
+            code_snippet_index,
+            table_of_contents_index,
+            equation_index,
+            footnote_index,
+        );
+        self.table_of_contents_block_index = table_of_contents_block_index;
+        self.collapsible_index = collapsible_index;
+    }
+
+    /// Records a reference to the given footnote, returning its HTML ID.
+    ///
+    /// With `use_true_ids`, the first reference to a footnote gets
+    /// `wj-footnote-ref-{index}`, and later ones have a counter appended
+    /// so that the IDs remain unique. Otherwise each ID is randomly generated.
+    pub fn add_footnote_ref(&mut self, index_one: NonZeroUsize) -> String {
+        let index = usize::from(index_one) - 1;
+        if self.footnote_refs.len() <= index {
+            self.footnote_refs
+                .resize_with(index + 1, FootnoteRefs::default);
+        }
+
+        // Use the ID already given to the footnote block, if any
+        let refs = &self.footnote_refs[index];
+        let id = match refs.ids.get(refs.used) {
+            Some(id) => id.clone(),
+            None => {
+                let id = self.footnote_ref_id(index_one, refs.ids.len());
+                self.footnote_refs[index].ids.push(id.clone());
+                id
+            }
+        };
+
+        self.footnote_refs[index].used += 1;
+        id
+    }
+
+    /// Gets the HTML IDs of the references to the given footnote.
+    ///
+    /// If none have been rendered yet, such as when the footnote block
+    /// comes before them, then the ID of the first reference is chosen now.
+    pub fn footnote_refs(&mut self, index_one: NonZeroUsize) -> Vec<String> {
+        let index = usize::from(index_one) - 1;
+        if self.footnote_refs.len() <= index {
+            self.footnote_refs
+                .resize_with(index + 1, FootnoteRefs::default);
+        }
+
+        if self.footnote_refs[index].ids.is_empty() {
+            let id = self.footnote_ref_id(index_one, 0);
+            self.footnote_refs[index].ids.push(id);
+        }
+
+        self.footnote_refs[index].ids.clone()
+    }
+
+    /// Takes the HTML IDs of the footnote references rendered in this context.
+    ///
+    /// This is used to combine the references from separately rendered parts
+    /// of a tree, see [`HtmlContext::set_footnote_refs()`].
+    #[cfg(feature = "parallel")]
+    pub fn take_footnote_refs(&mut self) -> Vec<Vec<String>> {
+        self.footnote_refs
+            .drain(..)
+            .map(|mut refs| {
+                refs.ids.truncate(refs.used);
+                refs.ids
+            })
+            .collect()
+    }
+
+    /// Sets the HTML IDs of footnote references rendered elsewhere.
+    ///
+    /// The footnote block then links back to these, and any further references
+    /// are numbered after them. For footnotes which have no references yet,
+    /// the ID from `first_refs` is used for the first one.
+    #[cfg(feature = "parallel")]
+    pub fn set_footnote_refs(
+        &mut self,
+        mut footnote_refs: Vec<Vec<String>>,
+        first_refs: &[String],
+    ) {
+        if footnote_refs.len() < first_refs.len() {
+            footnote_refs.resize_with(first_refs.len(), Vec::new);
+        }
+
+        self.footnote_refs = footnote_refs
+            .into_iter()
+            .enumerate()
+            .map(|(index, ids)| match first_refs.get(index) {
+                Some(id) if ids.is_empty() => FootnoteRefs {
+                    ids: vec![id.clone()],
+                    used: 0,
+                },
+                _ => FootnoteRefs {
+                    used: ids.len(),
+                    ids,
+                },
+            })
+            .collect();
+    }
+
+    fn footnote_ref_id(&mut self, index_one: NonZeroUsize, count: usize) -> String {
+        // Footnotes within expanded modules are numbered separately from the page's,
+        // so their references can't use the same IDs
+        if !self.settings().use_true_ids || self.module_depth > 0 {
+            return self.random.generate_html_id();
+        }
+
+        match count {
+            0 => format!("wj-footnote-ref-{index_one}"),
+            _ => format!("wj-footnote-ref-{index_one}-{}", count + 1),
+        }
     }
 
     #[inline]
     pub fn get_footnote(&self, index_one: NonZeroUsize) -> Option<&'e [Element<'t>]> {
-        self.footnotes
-            .get(usize::from(index_one) - 1)
-            .map(|elements| elements.as_slice())
+        self.render.get_footnote(index_one)
     }
 
     // Backlinks
     #[inline]
-    pub fn add_link(&mut self, link: &LinkLocation) {
-        // TODO: set to internal link if domain matches site
-        // See https://scuttle.atlassian.net/browse/WJ-24
+    pub fn add_link(
+        &mut self,
+        link: &LinkLocation,
+        extra: Option<&str>,
+        ltype: LinkType,
+    ) {
+        self.backlinks
+            .add_link(link, extra, ltype, self.render.settings(), self.handle);
+    }
 
-        match link {
-            LinkLocation::Page(page) => {
-                self.backlinks.internal_links.push(page.to_owned());
-            }
-            LinkLocation::Url(link) => {
-                let mut link: &str = link;
-
-                if link == "javascript:;" {
-                    return;
-                }
-
-                // Also support [ links pointing to local pages.
-                // e.g. [/scp-001 SCP-001] in addition to [[[SCP-001]]].
-                if link.starts_with('/') {
-                    link = &link[1..];
-                }
-
-                if is_url(link) {
-                    let link = Cow::Owned(str!(link));
-                    self.backlinks.external_links.push(link);
-                } else {
-                    let page_ref = PageRef::page_only(cow!(link));
-                    self.backlinks.internal_links.push(page_ref.to_owned());
-                }
-            }
+    /// Whether the page is on a site outside of this farm.
+    pub fn is_external_page(&self, page_ref: &PageRef) -> bool {
+        match page_ref.site() {
+            Some(site) => self.handle.site_location(site) == SiteLocation::External,
+            None => false,
         }
     }
 
     pub fn page_exists(&mut self, page_ref: &PageRef) -> bool {
-        let (site, page) = page_ref.fields_or(&self.info.site);
+        let (site, page) = page_ref.fields_or(&self.render.info().site);
 
         // Get from cache, or fetch and add
         match self.pages_exists.get(page_ref) {
@@ -300,16 +727,50 @@ impl<'i, 'h, 'e, 't> HtmlContext<'i, 'h, 'e, 't> {
         }
     }
 
+    /// Adds backlinks gathered by another context, such as from rendering in parallel.
+    #[cfg(feature = "parallel")]
+    #[inline]
+    pub fn append_backlinks(&mut self, backlinks: &mut Backlinks<'static>) {
+        self.backlinks.append(backlinks);
+    }
+
+    // Content report
+    pub fn add_dead_link(&mut self, page: &PageRef, ltype: LinkType) {
+        self.content_report.dead_links.push(DeadLink {
+            page: page.to_owned(),
+            ltype,
+        });
+    }
+
+    pub fn add_missing_include(&mut self, page: &PageRef) {
+        self.content_report.missing_includes.push(page.to_owned());
+    }
+
+    pub fn add_blocked_image(&mut self, source: &ImageSource) {
+        self.content_report.blocked_images.push(source.to_owned());
+    }
+
+    pub fn add_module_errors(&mut self, errors: Vec<ParseError>) {
+        self.content_report.module_errors.extend(errors);
+    }
+
+    /// Adds a content report gathered by another context, such as from rendering in parallel.
+    #[cfg(feature = "parallel")]
+    #[inline]
+    pub fn append_content_report(&mut self, report: &mut ContentReport<'static>) {
+        self.content_report.append(report);
+    }
+
     // TODO
     #[allow(dead_code)]
     #[inline]
-    pub fn add_include(&mut self, page: PageRef) {
+    pub fn add_include(&mut self, page: &PageRef) {
         self.backlinks.included_pages.push(page.to_owned());
     }
 
     // Buffer management
     #[inline]
-    pub fn buffer(&mut self) -> &mut String {
+    fn buffer(&mut self) -> &mut String {
         &mut self.body
     }
 
@@ -328,12 +789,62 @@ impl<'i, 'h, 'e, 't> HtmlContext<'i, 'h, 'e, 't> {
         escape(self.buffer(), s);
     }
 
+    /// Appends text content, escaping it.
+    ///
+    /// If `minify_html` is enabled, whitespace is collapsed
+    /// unless within an element which preserves it, such as `<pre>`.
+    pub fn push_text(&mut self, s: &str) {
+        if self.minify_html() {
+            push_minified(self.buffer(), s);
+        } else {
+            escape(self.buffer(), s);
+        }
+    }
+
+    #[inline]
+    pub fn preserve_whitespace_start(&mut self) {
+        self.preserve_whitespace += 1;
+    }
+
+    #[inline]
+    pub fn preserve_whitespace_end(&mut self) {
+        self.preserve_whitespace -= 1;
+    }
+
+    /// Removes insignificant whitespace before a closing tag, if minifying.
+    pub fn minify_before_close(&mut self, tag: &str) {
+        if self.minify_html() {
+            trim_before_close(self.buffer(), tag);
+        }
+    }
+
+    #[inline]
+    fn minify_html(&self) -> bool {
+        self.settings().minify_html && self.preserve_whitespace == 0
+    }
+
+    /// Appends HTML from the host, such as an expanded module.
+    #[inline]
+    pub fn push_trusted(&mut self, html: &TrustedHtml) {
+        self.buffer().push_str(html.as_str());
+    }
+
     #[inline]
     pub fn html(&mut self) -> HtmlBuilder<'_, 'i, 'h, 'e, 't> {
         HtmlBuilder::new(self)
     }
 }
 
+/// The HTML IDs of the references to a footnote.
+///
+/// Any past `used` were chosen by the footnote block before
+/// the references themselves were rendered.
+#[derive(Debug, Default, Clone)]
+struct FootnoteRefs {
+    ids: Vec<String>,
+    used: usize,
+}
+
 impl<'i, 'h, 'e, 't> From<HtmlContext<'i, 'h, 'e, 't>> for HtmlOutput {
     #[inline]
     fn from(ctx: HtmlContext<'i, 'h, 'e, 't>) -> HtmlOutput {
@@ -341,6 +852,7 @@ impl<'i, 'h, 'e, 't> From<HtmlContext<'i, 'h, 'e, 't>> for HtmlOutput {
             body,
             meta,
             backlinks,
+            content_report,
             ..
         } = ctx;
 
@@ -348,6 +860,7 @@ impl<'i, 'h, 'e, 't> From<HtmlContext<'i, 'h, 'e, 't>> for HtmlOutput {
             body,
             meta,
             backlinks,
+            content_report,
         }
     }
 }
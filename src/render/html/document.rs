@@ -0,0 +1,117 @@
+/*
+ * render/html/document.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Rendering of complete, standalone HTML documents.
+
+use super::{escape, process_css, HtmlHooks, HtmlMetaType, HtmlRender};
+use crate::data::PageInfo;
+use crate::settings::WikitextSettings;
+use crate::tree::visit::{walk_element_mut, VisitorMut};
+use crate::tree::{Element, SyntaxTree};
+
+impl HtmlRender {
+    /// Renders the tree as a complete HTML document.
+    ///
+    /// Rather than a fragment, this produces a document with a doctype, and
+    /// a head containing the title, the collected `<meta>` tags, and the
+    /// page's stylesheets. This is suitable for exports, previews within
+    /// sandboxed iframes, or emails, where there is no surrounding page.
+    ///
+    /// Styles from `[[style]]` blocks are moved into the document head,
+    /// except for those within `[[iftags]]` conditions which are evaluated
+    /// when rendering, since those only apply if the condition is met.
+    pub fn render_document(
+        &self,
+        tree: &SyntaxTree,
+        page_info: &PageInfo,
+        settings: &WikitextSettings,
+        hooks: HtmlHooks,
+    ) -> String {
+        info!(
+            "Rendering HTML document (site {}, page {})",
+            page_info.site.as_ref(),
+            page_info.page.as_ref(),
+        );
+
+        // Pull out styles
+        let mut tree = tree.clone();
+        let mut collector = StyleCollector { styles: Vec::new() };
+        collector.visit_syntax_tree_mut(&mut tree);
+
+        // Render body and build full document
+        let output = self.render_with_hooks(&tree, page_info, settings, hooks);
+        let mut document = String::with_capacity(output.body.len() + 512);
+
+        document.push_str("<!DOCTYPE html>\n<html lang=\"");
+        escape(&mut document, &page_info.language);
+        document.push_str("\">\n<head>\n<meta charset=\"utf-8\" />\n<title>");
+        escape(&mut document, &page_info.title);
+        document.push_str("</title>\n");
+
+        for meta in &output.meta {
+            // The content type is specified by the charset above
+            if meta.tag_type != HtmlMetaType::HttpEquiv {
+                meta.render(&mut document);
+                document.push('\n');
+            }
+        }
+
+        for css in collector.styles {
+            if let Some(css) = process_css(&css, settings) {
+                document.push_str("<style>\n");
+                document.push_str(&css);
+                document.push_str("\n</style>\n");
+            }
+        }
+
+        document.push_str("</head>\n<body>\n");
+        document.push_str(&output.body);
+        document.push_str("\n</body>\n</html>\n");
+        document
+    }
+}
+
+#[derive(Debug)]
+struct StyleCollector {
+    styles: Vec<String>,
+}
+
+impl<'t> VisitorMut<'t> for StyleCollector {
+    fn visit_elements_mut(&mut self, elements: &mut Vec<Element<'t>>) {
+        elements.retain(|element| match element {
+            Element::Style(css) => {
+                self.styles.push(str!(css));
+                false
+            }
+            _ => true,
+        });
+
+        for element in elements {
+            self.visit_element_mut(element);
+        }
+    }
+
+    fn visit_element_mut(&mut self, element: &mut Element<'t>) {
+        // Conditional styles are left in place, see render_document()
+        if !matches!(element, Element::Conditional { .. }) {
+            walk_element_mut(self, element);
+        }
+    }
+}
@@ -19,30 +19,37 @@
  */
 
 use super::prelude::*;
-use crate::tree::Bibliography;
+use crate::tree::{Bibliography, CitationStyle};
 
 pub fn render_bibcite(ctx: &mut HtmlContext, label: &str, brackets: bool) {
     debug!("Rendering bibliography citation (label {label}, brackets {brackets})");
 
     match ctx.get_bibliography_ref(label) {
         // Valid bibliography reference, render it
-        Some((index, contents)) => {
+        Some((index, contents, style)) => {
+            // The marker, and what surrounds it, depends on the citation style
+            let (marker, open, close) = match style {
+                CitationStyle::Numeric => (str!(index), '[', ']'),
+                CitationStyle::AuthorYear => (str!(label), '(', ')'),
+            };
+
             // TODO make this into a locale template string
             let reference_string = ctx
                 .handle()
                 .get_message(ctx.language(), "bibliography-reference");
-            let label = format!("{reference_string} {index}.");
+            let label = format!("{reference_string} {marker}.");
 
             // TODO: For now, copied from footnotes
+            let class = ctx.layout_class(ElementClass::BibliographyRef);
             ctx.html()
                 .span()
-                .attr(attr!("class" => "wj-bibliography-ref"))
+                .attr(attr!("class" => class))
                 .inner(|ctx| {
                     let id = str!(index);
 
                     // Bibliography marker that is hoverable
                     if brackets {
-                        ctx.push_raw('[');
+                        ctx.push_raw(open);
                     }
 
                     ctx.html()
@@ -53,10 +60,10 @@ pub fn render_bibcite(ctx: &mut HtmlContext, label: &str, brackets: bool) {
                             "aria-label" => &label,
                             "data-id" => &id,
                         ))
-                        .contents(&id);
+                        .contents(&marker);
 
                     if brackets {
-                        ctx.push_raw(']');
+                        ctx.push_raw(close);
                     }
 
                     // Tooltip shown on hover.
@@ -92,9 +99,10 @@ pub fn render_bibcite(ctx: &mut HtmlContext, label: &str, brackets: bool) {
                 .handle()
                 .get_message(ctx.language(), "bibliography-cite-not-found");
 
+            let class = ctx.layout_class(ElementClass::ErrorInline);
             ctx.html()
                 .span()
-                .attr(attr!("class" => "wj-error-inline"))
+                .attr(attr!("class" => class))
                 .contents(message);
         }
     }
@@ -107,8 +115,9 @@ pub fn render_bibliography(
     bibliography: &Bibliography,
 ) {
     debug!(
-        "Rendering bibliography block (title {}, items {})",
+        "Rendering bibliography block (title {}, style {}, items {})",
         title.unwrap_or("<default>"),
+        bibliography.style().name(),
         bibliography.slice().len(),
     );
 
@@ -124,46 +133,45 @@ pub fn render_bibliography(
         }
     };
 
-    ctx.html()
-        .div()
-        .attr(attr!("class" => "wj-bibliography bibitems"))
-        .inner(|ctx| {
+    let class = ctx.layout_class(ElementClass::Bibliography);
+    ctx.html().div().attr(attr!("class" => class)).inner(|ctx| {
+        ctx.html()
+            .div()
+            .attr(attr!("class" => "wj-bibliography-title title"))
+            .contents(title);
+
+        let mut id = String::new();
+        for (entry_index, (label, elements)) in bibliography.slice().iter().enumerate() {
+            // Convert to 1-indexing
+            let bibliography_index = bibliography_index + 1;
+            let entry_index = entry_index + 1;
+
+            // Produce HTML ID
+            id.clear();
+            str_write!(
+                id,
+                "wj-bibliography-item-{}-{} bibitem-{}-{}",
+                bibliography_index,
+                entry_index,
+                bibliography_index,
+                entry_index,
+            );
+
+            // Make bibliography row
             ctx.html()
                 .div()
-                .attr(attr!("class" => "wj-bibliography-title title"))
-                .contents(title);
-
-            let mut id = String::new();
-            for (entry_index, (_, elements)) in bibliography.slice().iter().enumerate() {
-                // Convert to 1-indexing
-                let bibliography_index = bibliography_index + 1;
-                let entry_index = entry_index + 1;
-
-                // Produce HTML ID
-                id.clear();
-                str_write!(
-                    id,
-                    "wj-bibliography-item-{}-{} bibitem-{}-{}",
-                    bibliography_index,
-                    entry_index,
-                    bibliography_index,
-                    entry_index,
-                );
-
-                // Make bibliography row
-                ctx.html()
-                    .div()
-                    .attr(attr!("class" => "wj-bibliography-item bibitem", "id" => &id))
-                    .inner(|ctx| {
-                        // Number and clickable anchor
-                        ctx.html()
-                            .element("wj-bibliography-item-marker")
-                            .attr(attr!(
-                                "class" => "wj-bibliography-item-marker",
-                                "type" => "button",
-                                "role" => "link",
-                            ))
-                            .inner(|ctx| {
+                .attr(attr!("class" => "wj-bibliography-item bibitem", "id" => &id))
+                .inner(|ctx| {
+                    // Number and clickable anchor
+                    ctx.html()
+                        .element("wj-bibliography-item-marker")
+                        .attr(attr!(
+                            "class" => "wj-bibliography-item-marker",
+                            "type" => "button",
+                            "role" => "link",
+                        ))
+                        .inner(|ctx| match bibliography.style() {
+                            CitationStyle::Numeric => {
                                 str_write!(ctx, "{entry_index}");
 
                                 // Period after entry number. Has special class to permit styling.
@@ -171,10 +179,12 @@ pub fn render_bibliography(
                                     .span()
                                     .attr(attr!("class" => "wj-bibliography-sep"))
                                     .contents(".");
-                            });
+                            }
+                            CitationStyle::AuthorYear => ctx.push_text(label),
+                        });
 
-                        render_elements(ctx, elements);
-                    });
-            }
-        });
+                    render_elements(ctx, elements);
+                });
+        }
+    });
 }
@@ -19,7 +19,9 @@
  */
 
 use super::prelude::*;
-use crate::tree::{AttributeMap, Element};
+use super::render_element;
+use crate::tree::{AttributeMap, ContainerType, Element, HeadingLevel};
+use std::ops::Range;
 
 #[derive(Debug, Copy, Clone)]
 pub struct Collapsible<'a> {
@@ -30,10 +32,12 @@ pub struct Collapsible<'a> {
     hide_text: Option<&'a str>,
     show_top: bool,
     show_bottom: bool,
+    remember: bool,
 }
 
 impl<'a> Collapsible<'a> {
     #[inline]
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         elements: &'a [Element<'a>],
         attributes: &'a AttributeMap<'a>,
@@ -42,6 +46,7 @@ impl<'a> Collapsible<'a> {
         hide_text: Option<&'a str>,
         show_top: bool,
         show_bottom: bool,
+        remember: bool,
     ) -> Self {
         Collapsible {
             elements,
@@ -51,6 +56,7 @@ impl<'a> Collapsible<'a> {
             hide_text,
             show_top,
             show_bottom,
+            remember,
         }
     }
 }
@@ -64,16 +70,18 @@ pub fn render_collapsible(ctx: &mut HtmlContext, collapsible: Collapsible) {
         hide_text,
         show_top,
         show_bottom,
+        remember,
     } = collapsible;
 
     debug!(
-        "Rendering collapsible (elements length {}, start-open {}, show-text {}, hide-text {}, show-top {}, show-bottom {})",
+        "Rendering collapsible (elements length {}, start-open {}, show-text {}, hide-text {}, show-top {}, show-bottom {}, remember {})",
         elements.len(),
         start_open,
         show_text.unwrap_or("<default>"),
         hide_text.unwrap_or("<default>"),
         show_top,
         show_bottom,
+        remember,
     );
 
     let show_text = show_text
@@ -82,13 +90,23 @@ pub fn render_collapsible(ctx: &mut HtmlContext, collapsible: Collapsible) {
     let hide_text = hide_text
         .unwrap_or_else(|| ctx.handle().get_message(ctx.language(), "collapsible-hide"));
 
+    // Used so the buttons can reference the content they toggle
+    let content_id = ctx.random().generate_html_id();
+    let expanded = if start_open { "true" } else { "false" };
+
+    // Used by the frontend to identify this collapsible across renders,
+    // such as to remember its state
+    let collapsible_id = ctx.next_collapsible_index().to_string();
+
     ctx.html()
         .details()
         .attr(attr!(
             "class" => "wj-collapsible",
+            "data-collapsible-id" => &collapsible_id,
             "open"; if start_open,
             "data-show-top"; if show_top,
-            "data-show-bottom"; if show_bottom;;
+            "data-show-bottom"; if show_bottom,
+            "data-remember"; if remember;;
             attributes,
         ))
         .inner(|ctx| {
@@ -97,6 +115,8 @@ pub fn render_collapsible(ctx: &mut HtmlContext, collapsible: Collapsible) {
                 .summary()
                 .attr(attr!(
                     "class" => "wj-collapsible-button wj-collapsible-button-top",
+                    "aria-controls" => &content_id,
+                    "aria-expanded" => expanded,
                 ))
                 .inner(|ctx| {
                     // Block is folded text
@@ -115,15 +135,26 @@ pub fn render_collapsible(ctx: &mut HtmlContext, collapsible: Collapsible) {
             // Content block
             ctx.html()
                 .div()
-                .attr(attr!("class" => "wj-collapsible-content"))
+                .attr(attr!(
+                    "class" => "wj-collapsible-content",
+                    "id" => &content_id,
+                ))
                 .contents(elements);
 
             // Bottom open/close button
+            //
+            // This is not a <summary>, so it needs to be made focusable
+            // and activatable by the frontend. It is only visible while
+            // the collapsible is open, so it is always expanded.
             if show_bottom {
                 ctx.html()
                     .element("wj-collapsible-button-bottom")
                     .attr(attr!(
                         "class" => "wj-collapsible-button wj-collapsible-button-bottom",
+                        "role" => "button",
+                        "tabindex" => "0",
+                        "aria-controls" => &content_id,
+                        "aria-expanded" => "true",
                     ))
                     .inner(|ctx| {
                         // Block is unfolded text
@@ -135,3 +166,108 @@ pub fn render_collapsible(ctx: &mut HtmlContext, collapsible: Collapsible) {
             }
         });
 }
+
+/// Splits a list of elements into the ranges which are rendered together.
+///
+/// A heading with a collapsible section is grouped with the elements in
+/// its section, which extends until the next heading of the same or a higher
+/// level. Every other element is in a range of its own.
+///
+/// Each range should be rendered using [`render_section()`]. This is shared
+/// by all the ways of rendering a list of elements, so that they group
+/// sections the same way, even when each range is rendered separately.
+pub fn section_ranges(elements: &[Element]) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut index = 0;
+
+    while index < elements.len() {
+        let start = index;
+        index += 1;
+
+        // Collapsible headings take the rest of their section with them
+        if let Some(level) = collapsible_heading_level(&elements[start]) {
+            index += elements[index..]
+                .iter()
+                .position(|element| ends_section(element, level))
+                .unwrap_or(elements.len() - index);
+        }
+
+        ranges.push(start..index);
+    }
+
+    ranges
+}
+
+/// Renders one range of elements produced by [`section_ranges()`].
+pub fn render_section(ctx: &mut HtmlContext, elements: &[Element]) {
+    match elements.split_first() {
+        Some((heading, section)) if collapsible_heading_level(heading).is_some() => {
+            render_collapsible_section(ctx, heading, section);
+        }
+        _ => {
+            for element in elements {
+                render_element(ctx, element);
+            }
+        }
+    }
+}
+
+/// Returns the level of this heading, if it has a collapsible section.
+fn collapsible_heading_level(element: &Element) -> Option<HeadingLevel> {
+    match element {
+        Element::Container(container) => match container.ctype() {
+            ContainerType::Header(heading) if heading.collapsible => Some(heading.level),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Whether this element ends the section of a heading with the given level.
+fn ends_section(element: &Element, level: HeadingLevel) -> bool {
+    match element {
+        Element::Container(container) => match container.ctype() {
+            ContainerType::Header(heading) => heading.level.value() <= level.value(),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn render_collapsible_section(
+    ctx: &mut HtmlContext,
+    heading: &Element,
+    section: &[Element],
+) {
+    debug!(
+        "Rendering collapsible section (elements length {})",
+        section.len(),
+    );
+
+    let content_id = ctx.random().generate_html_id();
+
+    ctx.html()
+        .details()
+        .attr(attr!(
+            "class" => "wj-collapsible-section",
+            "open",
+        ))
+        .inner(|ctx| {
+            // The heading itself toggles the section
+            ctx.html()
+                .summary()
+                .attr(attr!(
+                    "class" => "wj-collapsible-section-heading",
+                    "aria-controls" => &content_id,
+                ))
+                .contents(heading);
+
+            ctx.html()
+                .div()
+                .attr(attr!(
+                    "class" => "wj-collapsible-section-content",
+                    "id" => &content_id,
+                ))
+                .contents(section);
+        });
+}
@@ -0,0 +1,59 @@
+/*
+ * render/html/element/conditional.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::prelude::*;
+use crate::parsing::ElementCondition;
+use crate::render::count_footnotes;
+
+pub fn render_conditional(
+    ctx: &mut HtmlContext,
+    conditions: &[ElementCondition],
+    elements: &[Element],
+) {
+    debug!(
+        "Rendering conditional (conditions length {}, elements length {})",
+        conditions.len(),
+        elements.len(),
+    );
+
+    if ElementCondition::check(conditions, &ctx.info().tags) {
+        render_elements(ctx, elements);
+    } else {
+        // The footnotes were still collected during parsing,
+        // so skip their indices to keep later references correct.
+        for _ in 0..count_footnotes(elements) {
+            ctx.next_footnote_index();
+        }
+    }
+}
+
+/// Determines if these elements consist only of conditionals which are not shown.
+///
+/// If so, the paragraph wrapping them should be omitted, as it would
+/// have been if the conditions had been checked during parsing.
+pub fn only_hidden_conditionals(ctx: &HtmlContext, elements: &[Element]) -> bool {
+    !elements.is_empty()
+        && elements.iter().all(|element| match element {
+            Element::Conditional { conditions, .. } => {
+                !ElementCondition::check(conditions, &ctx.info().tags)
+            }
+            _ => false,
+        })
+}
@@ -18,6 +18,7 @@
  * along with this program. If not, see <http://www.gnu.org/licenses/>.
  */
 
+use super::conditional::only_hidden_conditionals;
 use super::prelude::*;
 use crate::tree::{Container, ContainerType, HtmlTag};
 
@@ -34,6 +35,31 @@ pub fn render_container(ctx: &mut HtmlContext, container: &Container) {
             ctx.html().rp().contents(")");
         }
 
+        // Wrap blockquotes with an attribution line in a <figure>
+        ContainerType::Blockquote => match split_attribution(container) {
+            Some((elements, attribution)) => {
+                ctx.html()
+                    .tag("figure")
+                    .attr(attr!("class" => "wj-blockquote-figure"))
+                    .inner(|ctx| {
+                        ctx.html()
+                            .tag("blockquote")
+                            .attr(attr!(;; container.attributes()))
+                            .contents(elements);
+
+                        render_container_internal(ctx, attribution);
+                    });
+            }
+            None => render_container_internal(ctx, container),
+        },
+
+        // Omit paragraphs whose contents are all hidden
+        ContainerType::Paragraph
+            if only_hidden_conditionals(ctx, container.elements()) =>
+        {
+            debug!("Skipping paragraph with only hidden conditionals");
+        }
+
         // Render normally
         _ => render_container_internal(ctx, container),
     }
@@ -41,7 +67,8 @@ pub fn render_container(ctx: &mut HtmlContext, container: &Container) {
 
 pub fn render_container_internal(ctx: &mut HtmlContext, container: &Container) {
     // Get HTML tag type for this type of container
-    let tag_spec = container.ctype().html_tag(ctx);
+    let heading_anchors = ctx.heading_anchors();
+    let tag_spec = container.ctype().html_tag(ctx, heading_anchors);
 
     // Get correct ID, based on the render setting
     let random_id = choose_id(ctx, &tag_spec);
@@ -80,6 +107,20 @@ pub fn render_color(ctx: &mut HtmlContext, color: &str, elements: &[Element]) {
         .contents(elements);
 }
 
+/// Separates a blockquote's trailing attribution, if it has one.
+fn split_attribution<'a, 't>(
+    container: &'a Container<'t>,
+) -> Option<(&'a [Element<'t>], &'a Container<'t>)> {
+    match container.elements().split_last() {
+        Some((Element::Container(attribution), elements))
+            if attribution.ctype() == ContainerType::BlockquoteAttribution =>
+        {
+            Some((elements, attribution))
+        }
+        _ => None,
+    }
+}
+
 fn choose_id(ctx: &mut HtmlContext, tag_spec: &HtmlTag) -> Option<String> {
     // If we're in a situation where we want a randomly generated ID
     if matches!(tag_spec, HtmlTag::TagAndId { .. }) && !ctx.settings().use_true_ids {
@@ -52,10 +52,11 @@ pub fn render_date(
     };
 
     // Build HTML elements
+    let class = ctx.layout_class(ElementClass::Date);
     ctx.html()
         .span()
         .attr(attr!(
-            "class" => "wj-date" space hover_class,
+            "class" => class space hover_class,
             "data-timestamp" => &timestamp,
             "data-delta" => &delta,
         ))
@@ -19,6 +19,7 @@
  */
 
 use super::prelude::*;
+use crate::settings::EmbedProviderSettings;
 use crate::tree::Embed;
 
 pub fn render_embed(ctx: &mut HtmlContext, embed: &Embed) {
@@ -28,34 +29,46 @@ pub fn render_embed(ctx: &mut HtmlContext, embed: &Embed) {
         embed.direct_url(),
     );
 
+    // Malformed embeds could point anywhere on the provider's site, so omit them
+    if !embed.is_valid() {
+        warn!("Embed '{}' has an invalid ID, skipping", embed.name());
+        return;
+    }
+
+    // Embeds from providers which aren't permitted are linked to instead
+    let provider = match ctx.settings().embed.provider(embed.provider()) {
+        Some(provider) => provider.clone(),
+        None => {
+            debug!("Embed provider '{}' not permitted", embed.provider());
+
+            let url = embed.direct_url();
+            ctx.html().a().attr(attr!("href" => &url)).contents(&url);
+            return;
+        }
+    };
+
+    let class = ctx.layout_class(ElementClass::Embed);
     ctx.html()
         .div()
         .attr(attr!(
-            "class" => "wj-embed",
+            "class" => class,
         ))
         .inner(|ctx| match embed {
             Embed::Youtube { video_id } => {
                 let url = format!("https://www.youtube.com/embed/{video_id}");
-
-                ctx.html().iframe().attr(attr!(
-                    "src" => &url,
-                    "frameborder" => "0",
-                    "allow" => "accelerometer; autoplay; "
-                               "clipboard-write; encrypted-media; "
-                               "gyroscope; picture-in-picture",
-                    "allowfullscreen",
-                ));
+                render_iframe(ctx, &url, &provider);
             }
 
             Embed::Vimeo { video_id } => {
                 let url = format!("https://player.vimeo.com/video/{video_id}");
+                render_iframe(ctx, &url, &provider);
+            }
 
-                ctx.html().iframe().attr(attr!(
-                    "src" => &url,
-                    "frameborder" => "0",
-                    "allow" => "autoplay; fullscreen; picture-inpicture",
-                    "allowfullscreen",
-                ));
+            // Scripts aren't permitted in AMP, so link to the page instead
+            Embed::GithubGist { .. } | Embed::GitlabSnippet { .. } if ctx.amp() => {
+                let url = embed.direct_url();
+
+                ctx.html().a().attr(attr!("href" => &url)).contents(&url);
             }
 
             Embed::GithubGist { username, hash } => {
@@ -71,3 +84,16 @@ pub fn render_embed(ctx: &mut HtmlContext, embed: &Embed) {
             }
         });
 }
+
+fn render_iframe(ctx: &mut HtmlContext, url: &str, provider: &EmbedProviderSettings) {
+    let lazy_load = ctx.settings().iframe.lazy_load;
+
+    ctx.html().iframe().attr(attr!(
+        "src" => url,
+        "frameborder" => "0",
+        "loading" => "lazy"; if lazy_load,
+        "sandbox" => provider.sandbox.as_deref().unwrap_or(""); if provider.sandbox.is_some(),
+        "allow" => provider.allow.as_deref().unwrap_or(""); if provider.allow.is_some(),
+        "allowfullscreen",
+    ));
+}
@@ -19,6 +19,7 @@
  */
 
 use super::prelude::*;
+use std::num::NonZeroUsize;
 
 pub fn render_footnote(ctx: &mut HtmlContext) {
     debug!("Rendering footnote reference");
@@ -30,18 +31,30 @@ pub fn render_footnote(ctx: &mut HtmlContext) {
     let footnote_string = ctx.handle().get_message(ctx.language(), "footnote");
     let label = format!("{footnote_string} {index}.");
 
-    let contents = ctx
-        .get_footnote(index)
-        .expect("Footnote index out of bounds from gathered footnote list");
+    let contents = match ctx.get_footnote(index) {
+        Some(contents) => contents,
+        None => {
+            error!("Footnote index {index} out of bounds from gathered footnote list, skipping");
+            return;
+        }
+    };
 
+    let ref_id = if ctx.settings().footnote_backlinks {
+        Some(ctx.add_footnote_ref(index))
+    } else {
+        None
+    };
+
+    let class = ctx.layout_class(ElementClass::FootnoteRef);
     ctx.html()
         .span()
-        .attr(attr!("class" => "wj-footnote-ref"))
+        .attr(attr!("class" => class))
         .inner(|ctx| {
             // Footnote marker that is hoverable
             ctx.html()
                 .element("wj-footnote-ref-marker")
                 .attr(attr!(
+                    "id" => ref_id.as_deref().unwrap_or(""); if ref_id.is_some(),
                     "class" => "wj-footnote-ref-marker",
                     "role" => "link",
                     "aria-label" => &label,
@@ -93,54 +106,75 @@ pub fn render_footnote_block(ctx: &mut HtmlContext, title: Option<&str>) {
         }
     };
 
-    ctx.html()
-        .div()
-        .attr(attr!("class" => "wj-footnote-list"))
-        .inner(|ctx| {
-            ctx.html()
-                .div()
-                .attr(attr!("class" => "wj-title"))
-                .contents(title);
-
-            ctx.html().ol().inner(|ctx| {
-                // TODO make this into a footnote helper method
-                for (index, contents) in ctx.footnotes().iter().enumerate() {
-                    let index = index + 1;
-                    let id = &format!("{index}");
-
-                    // Build actual footnote item
-                    ctx.html()
-                        .li()
-                        .attr(attr!(
-                            "class" => "wj-footnote-list-item",
-                            "data-id" => id,
-                        ))
-                        .inner(|ctx| {
-                            // Number and clickable anchor
-                            ctx.html()
-                                .element("wj-footnote-list-item-marker")
-                                .attr(attr!(
-                                    "class" => "wj-footnote-list-item-marker",
-                                    "type" => "button",
-                                    "role" => "link",
-                                ))
-                                .inner(|ctx| {
-                                    str_write!(ctx, "{index}");
-
-                                    // Period after entry number. Has special class to permit styling.
-                                    ctx.html()
-                                        .span()
-                                        .attr(attr!("class" => "wj-footnote-sep"))
-                                        .contents(".");
-                                });
-
-                            // Footnote contents
-                            ctx.html()
-                                .span()
-                                .attr(attr!("class" => "wj-footnote-list-item-contents"))
-                                .contents(contents);
-                        });
-                }
-            });
+    let class = ctx.layout_class(ElementClass::FootnoteList);
+    ctx.html().div().attr(attr!("class" => class)).inner(|ctx| {
+        ctx.html()
+            .div()
+            .attr(attr!("class" => "wj-title"))
+            .contents(title);
+
+        ctx.html().ol().inner(|ctx| {
+            // TODO make this into a footnote helper method
+            for (index, contents) in ctx.footnotes().iter().enumerate() {
+                let index = index + 1;
+                let id = &format!("{index}");
+
+                // Build actual footnote item
+                ctx.html()
+                    .li()
+                    .attr(attr!(
+                        "class" => "wj-footnote-list-item",
+                        "data-id" => id,
+                    ))
+                    .inner(|ctx| {
+                        // Number and clickable anchor
+                        ctx.html()
+                            .element("wj-footnote-list-item-marker")
+                            .attr(attr!(
+                                "class" => "wj-footnote-list-item-marker",
+                                "type" => "button",
+                                "role" => "link",
+                            ))
+                            .inner(|ctx| {
+                                str_write!(ctx, "{index}");
+
+                                // Period after entry number. Has special class to permit styling.
+                                ctx.html()
+                                    .span()
+                                    .attr(attr!("class" => "wj-footnote-sep"))
+                                    .contents(".");
+                            });
+
+                        // Footnote contents
+                        ctx.html()
+                            .span()
+                            .attr(attr!("class" => "wj-footnote-list-item-contents"))
+                            .contents(contents);
+
+                        // Links back to each reference
+                        if ctx.settings().footnote_backlinks {
+                            render_footnote_backlinks(ctx, index);
+                        }
+                    });
+            }
         });
+    });
+}
+
+fn render_footnote_backlinks(ctx: &mut HtmlContext, index: usize) {
+    let index = NonZeroUsize::new(index).expect("Footnote index is zero");
+    let label = ctx
+        .handle()
+        .get_message(ctx.language(), "footnote-backlink");
+
+    for ref_id in ctx.footnote_refs(index) {
+        ctx.html()
+            .a()
+            .attr(attr!(
+                "href" => "#" ref_id.as_str(),
+                "class" => "wj-footnote-backlink",
+                "aria-label" => label,
+            ))
+            .contents("↩");
+    }
 }
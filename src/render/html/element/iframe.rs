@@ -19,25 +19,77 @@
  */
 
 use super::prelude::*;
-use crate::tree::AttributeMap;
+use crate::tree::{AttributeMap, Dimension};
+
+/// The type of message sent by a resizable `[[html]]` block to report its height.
+///
+/// If an `[[html]]` block has `resize="auto"`, then its iframe has `data-resize="auto"`,
+/// and `data-resize-message` with this value. The hosted page is expected to call
+/// `window.parent.postMessage({ type: HTML_RESIZE_MESSAGE, height }, "*")`
+/// whenever its content height changes, and the frontend to set the height of the
+/// iframe whose `contentWindow` is the message's `source` accordingly.
+pub const HTML_RESIZE_MESSAGE: &str = "wj-html-resize";
 
 pub fn render_iframe(ctx: &mut HtmlContext, url: &str, attributes: &AttributeMap) {
     debug!("Rendering iframe block (url '{url}')");
 
+    let settings = &ctx.settings().iframe;
+    let lazy_load = settings.lazy_load;
+    let sandbox = settings.sandbox.clone();
+    let aspect_ratio = aspect_ratio_style(attributes);
+
     ctx.html().iframe().attr(attr!(
         "src" => url,
-        "crossorigin";;
+        "crossorigin",
+        "loading" => "lazy"; if lazy_load,
+        "sandbox" => sandbox.as_deref().unwrap_or(""); if sandbox.is_some(),
+        "style" => aspect_ratio.as_deref().unwrap_or(""); if aspect_ratio.is_some();;
         attributes
     ));
 }
 
-pub fn render_html(ctx: &mut HtmlContext, contents: &str) {
-    debug!("Rendering html block (submitting to remote for iframe)");
+/// Builds the CSS which keeps a frame's proportions when it is resized.
+///
+/// This is only possible if both `width` and `height` are given in pixels,
+/// such as `width="560" height="315"`. The frame's height then follows its
+/// width, so it scales down on narrow screens instead of being cropped.
+fn aspect_ratio_style(attributes: &AttributeMap) -> Option<String> {
+    fn parse_pixels(value: &str) -> Option<u32> {
+        let value = value.trim();
+        let value = value.strip_suffix("px").unwrap_or(value);
+
+        value.parse().ok().filter(|&pixels| pixels > 0)
+    }
+
+    let attributes = attributes.get();
+    let width = parse_pixels(attributes.get("width")?)?;
+    let height = parse_pixels(attributes.get("height")?)?;
+
+    Some(format!("aspect-ratio: {width} / {height}; height: auto;"))
+}
+
+pub fn render_html(
+    ctx: &mut HtmlContext,
+    contents: &str,
+    width: Option<Dimension>,
+    height: Option<Dimension>,
+    resize: bool,
+) {
+    debug!("Rendering html block (submitting to remote for iframe, resize {resize})");
+
+    // The size is left for the frontend to apply,
+    // since with resizing the height is only the initial one.
+    let width = width.map(|width| width.to_string());
+    let height = height.map(|height| height.to_string());
 
     // Submit HTML to be hosted on wjfiles, then get back its URL for the iframe.
     let iframe_url = ctx.handle().post_html(ctx.info(), contents);
     ctx.html().iframe().attr(attr!(
         "src" => &iframe_url,
         "crossorigin",
+        "data-width" => width.as_deref().unwrap_or(""); if width.is_some(),
+        "data-height" => height.as_deref().unwrap_or(""); if height.is_some(),
+        "data-resize" => "auto"; if resize,
+        "data-resize-message" => HTML_RESIZE_MESSAGE; if resize,
     ));
 }
@@ -19,18 +19,21 @@
  */
 
 use super::prelude::*;
+use crate::render::html::ImageResolution;
 use crate::tree::{AttributeMap, FloatAlignment, ImageSource, LinkLocation};
-use crate::url::normalize_link;
+use crate::url::normalize_link_with_settings;
 
 pub fn render_image(
     ctx: &mut HtmlContext,
     source: &ImageSource,
     link: &Option<LinkLocation>,
+    link_extra: Option<&str>,
     alignment: Option<FloatAlignment>,
+    lazy: bool,
     attributes: &AttributeMap,
 ) {
     debug!(
-        "Rendering image element (source '{}', link {}, alignment {}, float {})",
+        "Rendering image element (source '{}', link {}, alignment {}, float {}, lazy {})",
         source.name(),
         match link {
             Some(link) => format!("{link:?}"),
@@ -44,6 +47,7 @@ pub fn render_image(
             Some(image) => image.float,
             None => false,
         },
+        lazy,
     );
 
     let source_url = ctx
@@ -52,18 +56,43 @@ pub fn render_image(
 
     match source_url {
         // Found URL
-        Some(url) => render_image_element(ctx, &url, link, alignment, attributes),
+        Some(url) => {
+            let srcset = ctx.image_srcset().get_image_srcset(source, &url);
+            let link_url = link.as_ref().map(|link| {
+                let mut link_url =
+                    normalize_link_with_settings(link, ctx.handle(), ctx.settings());
+                if let Some(extra) = link_extra {
+                    link_url.to_mut().push_str(extra);
+                }
+                link_url
+            });
+
+            render_image_element(
+                ctx,
+                &url,
+                &srcset,
+                link_url.as_deref(),
+                alignment,
+                lazy,
+                attributes,
+            );
+        }
 
         // Missing or error
-        None => render_image_missing(ctx),
+        None => {
+            ctx.add_blocked_image(source);
+            render_image_missing(ctx);
+        }
     }
 }
 
 fn render_image_element(
     ctx: &mut HtmlContext,
     url: &str,
-    link: &Option<LinkLocation>,
+    srcset: &[ImageResolution],
+    link_url: Option<&str>,
     alignment: Option<FloatAlignment>,
+    lazy: bool,
     attributes: &AttributeMap,
 ) {
     trace!("Found URL, rendering image (value '{url}')");
@@ -73,27 +102,38 @@ fn render_image_element(
         None => ("", ""),
     };
 
+    let srcset = srcset
+        .iter()
+        .map(|ImageResolution { url, descriptor }| format!("{url} {descriptor}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let class = ctx.layout_class(ElementClass::Image);
     ctx.html()
         .div()
         .attr(attr!(
-            "class" => "wj-image-container" space align_class,
+            "class" => class space align_class,
         ))
         .inner(|ctx| {
             let build_image = |ctx: &mut HtmlContext| {
+                // AMP images are always loaded lazily
+                let lazy = lazy && !ctx.amp();
+
                 ctx.html().img().attr(attr!(
                     "class" => "wj-image",
                     "src" => url,
+                    "srcset" => &srcset; if !srcset.is_empty(),
+                    "loading" => "lazy"; if lazy,
                     "crossorigin";;
                     attributes
                 ));
             };
 
-            match link {
-                Some(link) => {
-                    let url = normalize_link(link, ctx.handle());
+            match link_url {
+                Some(link_url) => {
                     ctx.html()
                         .a()
-                        .attr(attr!("href" => &url))
+                        .attr(attr!("href" => link_url))
                         .inner(build_image);
                 }
                 None => build_image(ctx),
@@ -108,8 +148,9 @@ fn render_image_missing(ctx: &mut HtmlContext) {
         .handle()
         .get_message(ctx.language(), "image-context-bad");
 
+    let class = ctx.layout_class(ElementClass::ErrorBlock);
     ctx.html()
         .div()
-        .attr(attr!("class" => "wj-error-block"))
+        .attr(attr!("class" => class))
         .contents(message);
 }
@@ -29,12 +29,18 @@ pub fn render_include(
     elements: &[Element],
 ) {
     debug!("Rendering include (location {location:?})");
+    ctx.add_include(location);
+
+    if !ctx.page_exists(location) {
+        ctx.add_missing_include(location);
+    }
+
     ctx.variables_mut().push_scope(variables);
     render_elements(ctx, elements);
     ctx.variables_mut().pop_scope();
 }
 
-pub fn render_variable(ctx: &mut HtmlContext, name: &str) {
+pub fn render_variable(ctx: &mut HtmlContext, name: &str, default: Option<&str>) {
     let value = ctx.variables().get(name);
     debug!(
         "Rendering variable (name '{}', value '{}'",
@@ -43,15 +49,37 @@ pub fn render_variable(ctx: &mut HtmlContext, name: &str) {
     );
 
     // Write to a separate buffer since we can't borrow &mut for buffer and & for variables.
-    let value = match value {
+    let value = match (value, default) {
         // Value exists, substitute normally.
-        Some(value) => str!(value),
+        (Some(value), _) => str!(value),
+
+        // Value is absent, but there is a default.
+        (None, Some(default)) => str!(default),
 
         // Value is absent, leave as original value.
         // Variables are {$name}, so just write that back.
-        None => format!("{{${name}}}"),
+        (None, None) => format!("{{${name}}}"),
     };
 
     // Append the formatted string
-    ctx.push_escaped(&value);
+    ctx.push_text(&value);
+}
+
+pub fn render_page_variable(ctx: &mut HtmlContext, name: &str) {
+    let value = match ctx.info().variable(name) {
+        Some(value) => Some(value.into_owned()),
+        None => ctx.page_variables().get_page_variable(ctx.info(), name),
+    };
+
+    debug!(
+        "Rendering page variable (name '{}', value '{}')",
+        name,
+        value.as_deref().unwrap_or("<none>"),
+    );
+
+    // If there is no value, leave as the original text
+    match value {
+        Some(value) => ctx.push_text(&value),
+        None => ctx.push_text(&format!("%%{name}%%")),
+    }
 }
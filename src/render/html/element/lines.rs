@@ -0,0 +1,49 @@
+/*
+ * render/html/element/lines.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::prelude::*;
+use crate::layout::Layout;
+use crate::settings::LineBreakStyle;
+use std::num::NonZeroU32;
+
+pub fn render_line_breaks(ctx: &mut HtmlContext, amount: NonZeroU32) {
+    let amount = amount.get();
+    debug!("Rendering {amount} line breaks");
+
+    let settings = ctx.settings();
+    match (settings.layout, settings.line_break_style) {
+        (Layout::Wikijump, LineBreakStyle::Margin) => {
+            // The element ends the current line itself,
+            // so the gap is one less than the number of lines.
+            let class = ctx.layout_class(ElementClass::LineBreaks);
+            let style = format!("display: block; height: {}lh;", amount - 1);
+
+            ctx.html().span().attr(attr!(
+                "class" => class,
+                "style" => &style,
+            ));
+        }
+        _ => {
+            for _ in 0..amount {
+                ctx.html().br();
+            }
+        }
+    }
+}
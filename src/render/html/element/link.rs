@@ -22,7 +22,7 @@ use super::prelude::*;
 use crate::tree::{
     AnchorTarget, AttributeMap, Element, LinkLabel, LinkLocation, LinkType,
 };
-use crate::url::normalize_link;
+use crate::url::{is_external_url, normalize_link_with_settings};
 
 pub fn render_anchor(
     ctx: &mut HtmlContext,
@@ -37,10 +37,11 @@ pub fn render_anchor(
         None => "",
     };
 
+    let class = ctx.layout_class(ElementClass::Anchor);
     ctx.html()
         .a()
         .attr(attr!(
-            "class" => "wj-anchor",
+            "class" => class,
             "target" => target_value; if target.is_some();;
             attributes,
         ))
@@ -59,24 +60,34 @@ pub fn render_link(
     let handle = ctx.handle();
 
     // Add to backlinks
-    ctx.add_link(link);
+    ctx.add_link(link, extra, ltype);
 
-    let url = normalize_link(link, ctx.handle());
+    let url = normalize_link_with_settings(link, ctx.handle(), ctx.settings());
 
     let target_value = match target {
         Some(target) => target.html_attr(),
         None => "",
     };
 
+    let site = ctx.info().site.as_ref().to_string();
+    let mut external = false;
     let css_class = match link {
         LinkLocation::Url(url) if url == "javascript:;" => "wj-link-anchor",
         LinkLocation::Url(url) if url.starts_with('#') => "wj-link-anchor",
-        LinkLocation::Url(url) if url.starts_with('/') => "wj-link-internal",
-        LinkLocation::Url(_) => "wj-link-external",
+        LinkLocation::Url(url) if is_external_url(url, &site, handle) => {
+            external = true;
+            "wj-link-external"
+        }
+        LinkLocation::Url(_) => "wj-link-internal",
+        LinkLocation::Page(page) if ctx.is_external_page(page) => {
+            external = true;
+            "wj-link-external"
+        }
         LinkLocation::Page(page) => {
             if ctx.page_exists(page) {
                 "wj-link-internal"
             } else {
+                ctx.add_dead_link(page, ltype);
                 "wj-link-internal wj-link-missing"
             }
         }
@@ -88,17 +99,37 @@ pub fn render_link(
         ""
     };
 
-    let site = ctx.info().site.as_ref().to_string();
+    // Attributes for external links, as set for this mode
+    let external_links = &ctx.settings().external_links;
+    let rel = external_links.rel.clone().filter(|_| external);
+    let referrer_policy = external_links.referrer_policy.clone().filter(|_| external);
+    let marker = external_links.marker.clone().filter(|_| external);
+
+    let class = ctx.layout_class(ElementClass::Link);
     let mut tag = ctx.html().a();
     tag.attr(attr!(
         "href" => &url extra.unwrap_or(""),
         "target" => target_value; if target.is_some(),
-        "class" => "wj-link " css_class interwiki_class,
+        "class" => class " " css_class interwiki_class,
+        "rel" => rel.as_deref().unwrap_or(""); if rel.is_some(),
+        "referrerpolicy" => referrer_policy.as_deref().unwrap_or(""); if referrer_policy.is_some(),
         "data-link-type" => ltype.name(),
     ));
 
-    // Add <a> internals, i.e. the link name
+    // Add <a> internals, i.e. the link name and external marker
     handle.get_link_label(&site, link, label, |label| {
-        tag.contents(label);
+        tag.inner(|ctx| {
+            ctx.push_text(label);
+
+            if let Some(ref marker) = marker {
+                let marker_label =
+                    ctx.handle().get_message(ctx.language(), "external-link");
+
+                ctx.html().span().attr(attr!(
+                    "class" => marker,
+                    "aria-label" => marker_label,
+                ));
+            }
+        });
     });
 }
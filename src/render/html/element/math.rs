@@ -19,7 +19,10 @@
  */
 
 use super::prelude::*;
+use crate::render::html::{MathDisplay, MathImage};
+use crate::tree::MathEnvironment;
 use cfg_if::cfg_if;
+use std::borrow::Cow;
 use std::num::NonZeroUsize;
 
 cfg_if! {
@@ -35,21 +38,34 @@ cfg_if! {
     }
 }
 
-pub fn render_math_block(ctx: &mut HtmlContext, name: Option<&str>, latex_source: &str) {
+pub fn render_math_block(
+    ctx: &mut HtmlContext,
+    name: Option<&str>,
+    latex_source: &str,
+    env: Option<MathEnvironment>,
+) {
     debug!(
-        "Rendering math block (name '{}', source '{}')",
+        "Rendering math block (name '{}', env '{}', source '{}')",
         name.unwrap_or("<none>"),
+        env.map(MathEnvironment::name).unwrap_or("<none>"),
         latex_source,
     );
 
     let index = ctx.next_equation_index();
 
-    render_latex(ctx, name, Some(index), latex_source, DisplayStyle::Block);
+    render_latex(
+        ctx,
+        name,
+        Some(index),
+        latex_source,
+        env,
+        DisplayStyle::Block,
+    );
 }
 
 pub fn render_math_inline(ctx: &mut HtmlContext, latex_source: &str) {
     debug!("Rendering math inline (source '{latex_source}'");
-    render_latex(ctx, None, None, latex_source, DisplayStyle::Inline);
+    render_latex(ctx, None, None, latex_source, None, DisplayStyle::Inline);
 }
 
 fn render_latex(
@@ -57,20 +73,33 @@ fn render_latex(
     name: Option<&str>,
     index: Option<NonZeroUsize>,
     latex_source: &str,
+    env: Option<MathEnvironment>,
     display: DisplayStyle,
 ) {
+    let (html_tag, wj_type, error_element) = match display {
+        DisplayStyle::Block => ("div", "wj-math-block", ElementClass::ErrorBlock),
+        DisplayStyle::Inline => ("span", "wj-math-inline", ElementClass::ErrorInline),
+    };
+
     // error_type is unused if MathML is disabled
-    let (html_tag, wj_type, _error_type) = match display {
-        DisplayStyle::Block => ("div", "wj-math-block", "wj-error-block"),
-        DisplayStyle::Inline => ("span", "wj-math-inline", "wj-error-inline"),
+    let class = ctx.layout_class(ElementClass::Math);
+    let _error_type = ctx.layout_class(error_element);
+
+    // Image of the math, if the host provides one
+    let math_display = match display {
+        DisplayStyle::Block => MathDisplay::Block,
+        DisplayStyle::Inline => MathDisplay::Inline,
     };
 
+    let math_image = ctx.math_images().get_math_image(latex_source, math_display);
+
     // Outer container
     ctx.html()
         .tag(html_tag)
         .attr(attr!(
-            "class" => "wj-math " wj_type,
+            "class" => class " " wj_type,
             "data-name" => name.unwrap_or(""); if name.is_some(),
+            "data-env" => env.map(MathEnvironment::name).unwrap_or(""); if env.is_some(),
         ))
         .inner(|ctx| {
             // Add equation index
@@ -109,10 +138,35 @@ fn render_latex(
                 ))
                 .contents(latex_source);
 
-            // Add generated MathML
+            // Add image, if present, in place of MathML
+            if let Some(MathImage { url, alt }) = &math_image {
+                debug!("Using provided image for LaTeX");
+
+                ctx.html().img().attr(attr!(
+                    "class" => "wj-math-image",
+                    "src" => url,
+                    "alt" => alt.as_deref().unwrap_or(latex_source),
+                ));
+            }
+
+            // AMP doesn't permit MathML, so use its component for LaTeX instead
+            if ctx.amp() && math_image.is_none() {
+                render_amp_mathml(ctx, latex_source, env, display);
+            }
+
+            // Otherwise, add generated MathML
             cfg_if! {
                 if #[cfg(feature = "mathml")] {
-                    match latex_to_mathml(latex_source, display) {
+                    if ctx.amp() || math_image.is_some() {
+                        return;
+                    }
+
+                    let result = match env {
+                        Some(env) => latex_to_mathml(&env.wrap_source(latex_source), display),
+                        None => latex_to_mathml(latex_source, display),
+                    };
+
+                    match result {
                         Ok(mathml) => {
                             debug!("Processed LaTeX -> MathML");
 
@@ -137,12 +191,37 @@ fn render_latex(
         });
 }
 
+fn render_amp_mathml(
+    ctx: &mut HtmlContext,
+    latex_source: &str,
+    env: Option<MathEnvironment>,
+    display: DisplayStyle,
+) {
+    let latex_source = match env {
+        Some(env) => Cow::Owned(env.wrap_source(latex_source)),
+        None => Cow::Borrowed(latex_source),
+    };
+
+    let (formula, inline) = match display {
+        DisplayStyle::Block => (format!("\\[{latex_source}\\]"), false),
+        DisplayStyle::Inline => (format!("\\({latex_source}\\)"), true),
+    };
+
+    ctx.html().tag("amp-mathml").attr(attr!(
+        "class" => "wj-math-ml",
+        "layout" => "container",
+        "data-formula" => &formula,
+        "inline"; if inline,
+    ));
+}
+
 pub fn render_equation_reference(ctx: &mut HtmlContext, name: &str) {
     debug!("Rendering equation reference (name '{name}')");
 
+    let class = ctx.layout_class(ElementClass::EquationRef);
     ctx.html()
         .span()
-        .attr(attr!("class" => "wj-equation-ref"))
+        .attr(attr!("class" => class))
         .inner(|ctx| {
             // Equation marker that is hoverable
             ctx.html()
@@ -22,6 +22,7 @@
 
 mod bibliography;
 mod collapsible;
+mod conditional;
 mod container;
 mod date;
 mod definition_list;
@@ -31,9 +32,12 @@ mod iframe;
 mod image;
 mod include;
 mod input;
+mod lines;
 mod link;
 mod list;
 mod math;
+mod module;
+mod pagination;
 mod style;
 mod table;
 mod tabs;
@@ -44,25 +48,33 @@ mod user;
 mod prelude {
     pub use super::super::attributes::AddedAttributes;
     pub use super::super::context::HtmlContext;
+    pub use super::super::layout_class::ElementClass;
     pub use super::super::random::Random;
     pub use super::{render_element, render_elements};
     pub use crate::tree::Element;
 }
 
 use self::bibliography::{render_bibcite, render_bibliography};
-use self::collapsible::{render_collapsible, Collapsible};
+pub use self::collapsible::section_ranges;
+use self::collapsible::{render_collapsible, render_section, Collapsible};
+use self::conditional::render_conditional;
 use self::container::{render_color, render_container};
 use self::date::render_date;
 use self::definition_list::render_definition_list;
 use self::embed::render_embed;
 use self::footnotes::{render_footnote, render_footnote_block};
+pub use self::iframe::HTML_RESIZE_MESSAGE;
 use self::iframe::{render_html, render_iframe};
 use self::image::render_image;
-use self::include::{render_include, render_variable};
+use self::include::{render_include, render_page_variable, render_variable};
 use self::input::{render_checkbox, render_radio_button};
+use self::lines::render_line_breaks;
 use self::link::{render_anchor, render_link};
 use self::list::render_list;
 use self::math::{render_equation_reference, render_math_block, render_math_inline};
+use self::module::render_module;
+use self::pagination::render_pagination;
+pub use self::style::process_css;
 use self::style::render_style;
 use self::table::render_table;
 use self::tabs::render_tabview;
@@ -70,15 +82,50 @@ use self::text::{render_code, render_email, render_wikitext_raw};
 use self::toc::render_table_of_contents;
 use self::user::render_user;
 use super::attributes::AddedAttributes;
+use super::layout_class::ElementClass;
 use super::HtmlContext;
 use crate::tree::Element;
 use ref_map::*;
+use std::ops::Range;
 
 pub fn render_elements(ctx: &mut HtmlContext, elements: &[Element]) {
     debug!("Rendering elements (length {})", elements.len());
 
-    for element in elements {
-        render_element(ctx, element);
+    for range in section_ranges(elements) {
+        render_section(ctx, &elements[range]);
+    }
+}
+
+/// Renders elements, wrapping each in a tag noting its source span.
+///
+/// The spans list is parallel to the elements list. Any element without
+/// a corresponding span is rendered as normal. A collapsible heading section
+/// is wrapped as a whole, with a span covering the heading and its contents.
+pub fn render_elements_with_spans(
+    ctx: &mut HtmlContext,
+    elements: &[Element],
+    spans: &[Range<usize>],
+) {
+    debug!(
+        "Rendering elements with source spans (length {}, spans {})",
+        elements.len(),
+        spans.len(),
+    );
+
+    for range in section_ranges(elements) {
+        let section = &elements[range.clone()];
+
+        match (spans.get(range.start), spans.get(range.end - 1)) {
+            (Some(first), Some(last)) => {
+                let value = format!("{}..{}", first.start, last.end);
+
+                ctx.html()
+                    .element("wj-source-span")
+                    .attr(attr!("data-ftml-span" => &value))
+                    .inner(|ctx| render_section(ctx, section));
+            }
+            _ => render_section(ctx, section),
+        }
     }
 }
 
@@ -93,10 +140,13 @@ pub fn render_element(ctx: &mut HtmlContext, element: &Element) {
 
     match element {
         Element::Container(container) => render_container(ctx, container),
-        Element::Module(module) => ctx.handle().render_module(ctx.buffer(), module),
-        Element::Text(text) => ctx.push_escaped(text),
+        Element::Module(module) => render_module(ctx, module),
+        Element::Text(text) => ctx.push_text(text),
         Element::Raw(text) => render_wikitext_raw(ctx, text),
-        Element::Variable(name) => render_variable(ctx, name),
+        Element::Variable { name, default } => {
+            render_variable(ctx, name, ref_cow!(default))
+        }
+        Element::PageVariable { name } => render_page_variable(ctx, name),
         Element::Email(email) => render_email(ctx, email),
         Element::Table(table) => render_table(ctx, table),
         Element::TabView(tabs) => render_tabview(ctx, tabs),
@@ -118,9 +168,19 @@ pub fn render_element(ctx: &mut HtmlContext, element: &Element) {
         Element::Image {
             source,
             link,
+            link_extra,
             alignment,
+            lazy,
+            attributes,
+        } => render_image(
+            ctx,
+            source,
+            link,
+            ref_cow!(link_extra),
+            *alignment,
+            *lazy,
             attributes,
-        } => render_image(ctx, source, link, *alignment, attributes),
+        ),
         Element::List {
             ltype,
             items,
@@ -144,6 +204,7 @@ pub fn render_element(ctx: &mut HtmlContext, element: &Element) {
             hide_text,
             show_top,
             show_bottom,
+            remember,
         } => render_collapsible(
             ctx,
             Collapsible::new(
@@ -154,11 +215,16 @@ pub fn render_element(ctx: &mut HtmlContext, element: &Element) {
                 ref_cow!(hide_text),
                 *show_top,
                 *show_bottom,
+                *remember,
             ),
         ),
-        Element::TableOfContents { align, attributes } => {
-            render_table_of_contents(ctx, *align, attributes)
-        }
+        Element::TableOfContents {
+            align,
+            attributes,
+            min_level,
+            max_level,
+        } => render_table_of_contents(ctx, *align, attributes, (*min_level, *max_level)),
+        Element::Pagination { attributes } => render_pagination(ctx, attributes),
         Element::Footnote => render_footnote(ctx),
         Element::FootnoteBlock { title, hide } => {
             if !(*hide || ctx.footnotes().is_empty()) {
@@ -171,11 +237,16 @@ pub fn render_element(ctx: &mut HtmlContext, element: &Element) {
         Element::BibliographyBlock { index, title, hide } => {
             if !hide {
                 let title = title.ref_map(|s| s.as_ref());
-                let bibliography = ctx.get_bibliography(*index);
-                render_bibliography(ctx, title, *index, bibliography);
+                match ctx.get_bibliography(*index) {
+                    Some(bibliography) => {
+                        render_bibliography(ctx, title, *index, bibliography)
+                    }
+                    None => error!("Bibliography index {index} out of bounds, skipping"),
+                }
             }
         }
         Element::User { name, show_avatar } => render_user(ctx, name, *show_avatar),
+        Element::Mention { name } => render_user(ctx, name, false),
         Element::Date {
             value,
             format,
@@ -185,13 +256,20 @@ pub fn render_element(ctx: &mut HtmlContext, element: &Element) {
         Element::Code { contents, language } => {
             render_code(ctx, ref_cow!(language), contents)
         }
-        Element::Math { name, latex_source } => {
-            render_math_block(ctx, ref_cow!(name), latex_source)
-        }
+        Element::Math {
+            name,
+            latex_source,
+            env,
+        } => render_math_block(ctx, ref_cow!(name), latex_source, *env),
         Element::MathInline { latex_source } => render_math_inline(ctx, latex_source),
         Element::EquationReference(name) => render_equation_reference(ctx, name),
         Element::Embed(embed) => render_embed(ctx, embed),
-        Element::Html { contents } => render_html(ctx, contents),
+        Element::Html {
+            contents,
+            width,
+            height,
+            resize,
+        } => render_html(ctx, contents, *width, *height, *resize),
         Element::Iframe { url, attributes } => render_iframe(ctx, url, attributes),
         Element::Include {
             variables,
@@ -199,25 +277,27 @@ pub fn render_element(ctx: &mut HtmlContext, element: &Element) {
             elements,
             ..
         } => render_include(ctx, location, variables, elements),
+        Element::Conditional {
+            conditions,
+            elements,
+            ..
+        } => render_conditional(ctx, conditions, elements),
         Element::Style(css) => render_style(ctx, css),
         Element::LineBreak => {
             ctx.html().br();
         }
-        Element::LineBreaks(amount) => {
-            let amount = amount.get();
-
-            for _ in 0..amount {
-                ctx.html().br();
-            }
-        }
+        Element::LineBreaks(amount) => render_line_breaks(ctx, *amount),
         Element::ClearFloat(clear_float) => {
+            let class = ctx.layout_class(ElementClass::ClearFloat);
             ctx.html().div().attr(attr!(
-                "class" => "wj-clear-float " clear_float.html_class(),
+                "class" => class " " clear_float.html_class(),
             ));
         }
         Element::HorizontalRule => {
             ctx.html().hr();
         }
-        Element::Partial(_) => panic!("Encountered partial element during parsing"),
+        Element::Partial(_) => {
+            error!("Encountered partial element during rendering, skipping");
+        }
     }
 }
@@ -0,0 +1,107 @@
+/*
+ * render/html/element/module.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::prelude::*;
+use crate::budget::{render_cost, BudgetExceeded, WorkBudget};
+use crate::render::html::module::{expand_template, ModuleExpansion};
+use crate::settings::{FootnoteBlockPlacement, WikitextSettings};
+use crate::tree::Module;
+
+pub fn render_module(ctx: &mut HtmlContext, module: &Module) {
+    let expansion = ctx
+        .module_renderer()
+        .and_then(|renderer| renderer.render_module(module, ctx.info()));
+
+    let expansion = match expansion {
+        Some(expansion) => expansion,
+        None => {
+            let html = ctx.handle().render_module(module);
+            ctx.push_trusted(&html);
+            return;
+        }
+    };
+
+    debug!("Rendering expanded module '{}'", module.name());
+
+    if !ctx.enter_module() {
+        warn!("Expanded modules are nested too deeply, not rendering");
+        return;
+    }
+
+    match expansion {
+        ModuleExpansion::Html(html) => ctx.push_trusted(&html),
+        ModuleExpansion::Wikitext(wikitext) => render_wikitext(ctx, wikitext),
+        ModuleExpansion::Template(pages) => {
+            let template = match module {
+                Module::ListPages { body, .. } => body,
+                _ => "",
+            };
+
+            let mut wikitext = String::new();
+            for variables in &pages {
+                wikitext.push_str(&expand_template(template, variables));
+            }
+
+            render_wikitext(ctx, wikitext);
+        }
+    }
+
+    ctx.exit_module();
+}
+
+fn render_wikitext(ctx: &mut HtmlContext, mut wikitext: String) {
+    // Fragments of the page shouldn't each get their own footnote block
+    let settings = WikitextSettings {
+        footnote_block: FootnoteBlockPlacement::None,
+        ..ctx.settings().clone()
+    };
+
+    let unlimited;
+    let budget = match ctx.budget() {
+        Some(budget) => budget,
+        None => {
+            unlimited = WorkBudget::unlimited();
+            &unlimited
+        }
+    };
+
+    if render_parsed(ctx, &mut wikitext, &settings, budget).is_err() {
+        warn!("Work budget exceeded by expanded module, not rendering");
+    }
+}
+
+fn render_parsed(
+    ctx: &mut HtmlContext,
+    wikitext: &mut String,
+    settings: &WikitextSettings,
+    budget: &WorkBudget,
+) -> Result<(), BudgetExceeded> {
+    budget.spend(wikitext.len())?;
+    crate::preprocess(wikitext, ctx.info(), settings);
+
+    let tokens = crate::tokenize_with_budget(wikitext, budget)?;
+    let (tree, errors) =
+        crate::parse_with_budget(&tokens, ctx.info(), settings, budget).into();
+    budget.spend(render_cost(&tree))?;
+
+    ctx.add_module_errors(errors);
+    ctx.render_tree(&tree, settings, |ctx| render_elements(ctx, &tree.elements));
+    Ok(())
+}
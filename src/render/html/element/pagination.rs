@@ -0,0 +1,65 @@
+/*
+ * render/html/element/pagination.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::prelude::*;
+use crate::data::PageRef;
+use crate::tree::{AttributeMap, LinkLocation};
+use crate::url::normalize_link_with_settings;
+
+pub fn render_pagination(ctx: &mut HtmlContext, attributes: &AttributeMap) {
+    debug!("Rendering pagination block");
+
+    let neighbors = ctx.pagination().get_page_neighbors(ctx.info());
+    if neighbors.is_empty() {
+        debug!("No adjacent pages, skipping pagination");
+        return;
+    }
+
+    let class = ctx.layout_class(ElementClass::Pagination);
+    ctx.html()
+        .tag("nav")
+        .attr(attr!("class" => class;; attributes))
+        .inner(|ctx| {
+            if let Some(page) = &neighbors.previous {
+                render_page_link(ctx, page, "previous", "prev");
+            }
+
+            if let Some(page) = &neighbors.next {
+                render_page_link(ctx, page, "next", "next");
+            }
+        });
+}
+
+fn render_page_link(ctx: &mut HtmlContext, page: &PageRef, direction: &str, rel: &str) {
+    let link = LinkLocation::Page(page.clone());
+    let url = normalize_link_with_settings(&link, ctx.handle(), ctx.settings());
+    let label = ctx
+        .handle()
+        .get_message(ctx.language(), &format!("pagination-{direction}"));
+
+    ctx.html()
+        .a()
+        .attr(attr!(
+            "href" => &url,
+            "class" => "wj-pagination-" direction,
+            "rel" => rel,
+        ))
+        .contents(label);
+}
@@ -19,10 +19,66 @@
  */
 
 use super::prelude::*;
+use crate::settings::{CssSettings, WikitextSettings};
+use once_cell::sync::Lazy;
+use parcel_css::declaration::DeclarationBlock;
+use parcel_css::properties::position::Position;
+use parcel_css::properties::{Property, PropertyId};
+use parcel_css::rules::style::StyleRule;
+use parcel_css::rules::{CssRule, CssRuleList};
 use parcel_css::stylesheet::{ParserOptions, PrinterOptions, StyleSheet};
+use parcel_selectors::parser::{Combinator, Component, Selector};
+use regex::Regex;
+
+static CSS_CLASS_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^-?[A-Za-z_][A-Za-z0-9_-]*$").unwrap());
 
 pub fn render_style(ctx: &mut HtmlContext, input_css: &str) {
-    let minify = ctx.settings().minify_css;
+    // AMP only permits stylesheets in the document head
+    if ctx.amp() {
+        debug!("Skipping style block for AMP");
+        return;
+    }
+
+    if let Some(output_css) = process_css(input_css, ctx.settings()) {
+        ctx.html().style().inner(|ctx| {
+            // SAFETY: The resultant CSS cannot contain HTML-escaping elements,
+            //         as those are invalid and would not be retained during
+            //         the parcel_css parsing process.
+            ctx.push_raw_str(&output_css);
+        });
+    }
+}
+
+/// Parses and re-prints the given CSS, returning `None` if it cannot be outputted.
+///
+/// Any transformations requested in the CSS settings are applied here.
+pub fn process_css(input_css: &str, settings: &WikitextSettings) -> Option<String> {
+    let minify = settings.minify_css;
+    let css_settings = &settings.css;
+
+    // Build the scope selector, if any.
+    //
+    // The selector module of parcel_css is private, so we get the class component
+    // by parsing a stylesheet containing only it, and then take it from that rule.
+    let scope_css = match css_settings.scope_class {
+        Some(ref class) if CSS_CLASS_REGEX.is_match(class.as_str()) => {
+            format!(".{class} {{}}")
+        }
+        Some(ref class) => {
+            warn!("Invalid CSS scope class '{class}', not outputting stylesheet");
+            return None;
+        }
+        None => String::new(),
+    };
+
+    let scope_stylesheet = StyleSheet::parse(&scope_css, ParserOptions::default())
+        .expect("Unable to parse scope class stylesheet");
+
+    let scope = match scope_stylesheet.rules.0.first() {
+        Some(CssRule::Style(rule)) => Some(rule),
+        _ => None,
+    };
 
     let parser_options = ParserOptions {
         error_recovery: true,
@@ -35,24 +91,97 @@ pub fn render_style(ctx: &mut HtmlContext, input_css: &str) {
     };
 
     debug!("Parsing input CSS ({} bytes)", input_css.len());
-    let stylesheet = StyleSheet::parse(input_css, parser_options)
+    let mut stylesheet = StyleSheet::parse(input_css, parser_options)
         .expect("Produced error with recovery enabled");
 
-    trace!("Rendering CSS into HTML (minify: {minify})");
-    let output_css = match stylesheet.to_css(print_options) {
-        Ok(output) => output.code,
+    transform_rules(&mut stylesheet.rules, css_settings, scope);
+
+    trace!("Rendering CSS (minify: {minify})");
+    match stylesheet.to_css(print_options) {
+        Ok(output) => Some(output.code),
         Err(error) => {
             error!("Problem outputting CSS from stylesheet: {error}");
             trace!("Input CSS:\n{input_css}");
             trace!("Parsed stylesheet:\n{stylesheet:#?}");
-            return;
+            None
         }
-    };
+    }
+}
+
+fn transform_rules<'i>(
+    rules: &mut CssRuleList<'i>,
+    settings: &CssSettings,
+    scope: Option<&StyleRule<'i>>,
+) {
+    if settings.strip_imports {
+        rules.0.retain(|rule| !matches!(rule, CssRule::Import(_)));
+    }
+
+    for rule in &mut rules.0 {
+        match rule {
+            CssRule::Style(rule) => {
+                if let Some(scope) = scope {
+                    scope_selectors(rule, scope);
+                }
+
+                // Nested rules are relative to this one, so they are already scoped.
+                transform_rules(&mut rule.rules, settings, None);
+                transform_declarations(&mut rule.declarations, settings);
+            }
+            CssRule::Nesting(rule) => {
+                transform_rules(&mut rule.style.rules, settings, None);
+                transform_declarations(&mut rule.style.declarations, settings);
+            }
+            CssRule::Media(rule) => transform_rules(&mut rule.rules, settings, scope),
+            CssRule::Supports(rule) => transform_rules(&mut rule.rules, settings, scope),
+            CssRule::LayerBlock(rule) => {
+                transform_rules(&mut rule.rules, settings, scope)
+            }
+            CssRule::Container(rule) => transform_rules(&mut rule.rules, settings, scope),
+            CssRule::MozDocument(rule) => {
+                transform_rules(&mut rule.rules, settings, scope)
+            }
+            _ => (),
+        }
+    }
+}
+
+fn transform_declarations(declarations: &mut DeclarationBlock, settings: &CssSettings) {
+    fn is_fixed_position(property: &Property) -> bool {
+        match property {
+            Property::Position(position) => *position == Position::Fixed,
+
+            // Values we cannot interpret (such as "var(--x)") could also be fixed
+            Property::Unparsed(property) => property.property_id == PropertyId::Position,
+            _ => false,
+        }
+    }
+
+    if settings.forbid_fixed_position {
+        declarations
+            .declarations
+            .retain(|property| !is_fixed_position(property));
+
+        declarations
+            .important_declarations
+            .retain(|property| !is_fixed_position(property));
+    }
+}
+
+fn scope_selectors<'i>(rule: &mut StyleRule<'i>, scope: &StyleRule<'i>) {
+    let scope_component = scope
+        .selectors
+        .0
+        .first()
+        .and_then(|selector| selector.iter_raw_match_order().next())
+        .expect("Scope stylesheet has no class selector");
 
-    ctx.html().style().inner(|ctx| {
-        // SAFETY: The resultant CSS cannot contain HTML-escaping elements,
-        //         as those are invalid and would not be retained during
-        //         the parcel_css parsing process.
-        ctx.push_raw_str(&output_css);
-    });
+    for selector in &mut rule.selectors.0 {
+        let mut components = vec![
+            scope_component.clone(),
+            Component::Combinator(Combinator::Descendant),
+        ];
+        components.extend(selector.iter_raw_parse_order_from(0).cloned());
+        *selector = Selector::from_vec2(components);
+    }
 }
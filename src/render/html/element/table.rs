@@ -26,7 +26,9 @@ pub fn render_table(ctx: &mut HtmlContext, table: &Table) {
     debug!("Rendering table");
 
     let mut column_span_buf = String::new();
-    let value_one = NonZeroU32::new(1).unwrap();
+    let mut row_span_buf = String::new();
+    let mut style_buf = String::new();
+    let value_one = NonZeroU32::MIN;
 
     // Full table
     ctx.html()
@@ -53,6 +55,29 @@ pub fn render_table(ctx: &mut HtmlContext, table: &Table) {
                                     str_write!(column_span_buf, "{}", cell.column_span);
                                 }
 
+                                if cell.row_span > value_one {
+                                    row_span_buf.clear();
+                                    str_write!(row_span_buf, "{}", cell.row_span);
+                                }
+
+                                style_buf.clear();
+
+                                if let Some(valign) = cell.valign {
+                                    str_write!(
+                                        style_buf,
+                                        "vertical-align: {};",
+                                        valign.name()
+                                    );
+                                }
+
+                                if let Some(width) = cell.width {
+                                    if !style_buf.is_empty() {
+                                        style_buf.push(' ');
+                                    }
+
+                                    str_write!(style_buf, "width: {width};");
+                                }
+
                                 ctx.html()
                                     .table_cell(cell.header)
                                     .attr(attr!(
@@ -60,9 +85,17 @@ pub fn render_table(ctx: &mut HtmlContext, table: &Table) {
                                         "colspan" => &column_span_buf;
                                             if cell.column_span > value_one,
 
+                                        // Add row span if not default (1)
+                                        "rowspan" => &row_span_buf;
+                                            if cell.row_span > value_one,
+
                                         // Add alignment if specified
                                         "class" => align_class;
-                                            if cell.align.is_some();;
+                                            if cell.align.is_some(),
+
+                                        // Add vertical alignment and width if specified
+                                        "style" => &style_buf;
+                                            if !style_buf.is_empty();;
 
                                         &cell.attributes,
                                     ))
@@ -30,10 +30,11 @@ pub fn render_tabview(ctx: &mut HtmlContext, tabs: &[Tab]) {
     let tab_ids = generate_ids(ctx.random(), tabs.len());
 
     // Entire tab view
+    let class = ctx.layout_class(ElementClass::Tabs);
     ctx.html()
         .element("wj-tabs")
         .attr(attr!(
-            "class" => "wj-tabs",
+            "class" => class,
         ))
         .inner(|ctx| {
             // Tab buttons
@@ -93,8 +94,7 @@ pub fn render_tabview(ctx: &mut HtmlContext, tabs: &[Tab]) {
 }
 
 fn generate_ids(random: &mut Random, len: usize) -> Vec<String> {
-    iter::repeat(())
-        .take(len)
+    iter::repeat_n((), len)
         .map(|_| random.generate_html_id())
         .collect()
 }
@@ -19,15 +19,18 @@
  */
 
 use super::prelude::*;
+use crate::settings::EmailObfuscation;
 
 pub fn render_wikitext_raw(ctx: &mut HtmlContext, text: &str) {
     debug!("Escaping raw string '{text}'");
 
+    let class = ctx.layout_class(ElementClass::Raw);
     ctx.html()
         .span()
         .attr(attr!(
-            "class" => "wj-raw",
+            "class" => class,
         ))
+        .keep_whitespace()
         .contents(text);
 }
 
@@ -37,10 +40,45 @@ pub fn render_email(ctx: &mut HtmlContext, email: &str) {
     // Since our usecase doesn't typically have emails as real,
     // but rather as fictional elements, we're just rendering as text.
 
-    ctx.html()
-        .span()
-        .attr(attr!("class" => "wj-email"))
-        .contents(email);
+    let class = ctx.layout_class(ElementClass::Email);
+    match ctx.settings().email_obfuscation {
+        EmailObfuscation::None => {
+            ctx.html()
+                .span()
+                .attr(attr!("class" => class))
+                .contents(email);
+        }
+        EmailObfuscation::Entities => {
+            let mut encoded = String::with_capacity(email.len() * 6);
+            for ch in email.chars() {
+                str_write!(encoded, "&#{};", u32::from(ch));
+            }
+
+            ctx.html()
+                .span()
+                .attr(attr!("class" => class))
+                .inner(|ctx| ctx.push_raw_str(&encoded));
+        }
+        EmailObfuscation::Reversed => {
+            let reversed = email.chars().rev().collect::<String>();
+
+            ctx.html()
+                .span()
+                .attr(attr!(
+                    "class" => class,
+                    "style" => "unicode-bidi: bidi-override; direction: rtl;",
+                ))
+                .contents(&reversed);
+        }
+        EmailObfuscation::Rewriter => {
+            let html = ctx.email_rewriter().rewrite_email(email);
+
+            ctx.html()
+                .span()
+                .attr(attr!("class" => class))
+                .contents(&html);
+        }
+    }
 }
 
 pub fn render_code(ctx: &mut HtmlContext, language: Option<&str>, contents: &str) {
@@ -52,7 +90,11 @@ pub fn render_code(ctx: &mut HtmlContext, language: Option<&str>, contents: &str
     ctx.handle().post_code(index, contents);
 
     let class = {
-        let mut class = format!("wj-code wj-language-{}", language.unwrap_or("none"));
+        let mut class = format!(
+            "{} wj-language-{}",
+            ctx.layout_class(ElementClass::Code),
+            language.unwrap_or("none"),
+        );
         class.make_ascii_lowercase();
         class
     };
@@ -97,7 +139,29 @@ pub fn render_code(ctx: &mut HtmlContext, language: Option<&str>, contents: &str
 
             // Code block containing highlighted contents
             ctx.html().pre().inner(|ctx| {
-                ctx.html().code().contents(contents);
+                ctx.html()
+                    .code()
+                    .inner(|ctx| render_code_contents(ctx, language, contents));
             });
         });
 }
+
+// language is unused if syntax highlighting is disabled
+fn render_code_contents(ctx: &mut HtmlContext, _language: Option<&str>, contents: &str) {
+    cfg_if! {
+        if #[cfg(feature = "syntax-highlight")] {
+            use super::super::highlight::highlight_code;
+
+            if ctx.settings().syntax_highlighting {
+                let class_prefix = ctx.settings().class_prefix.as_deref();
+                if let Some(html) = _language.and_then(|language| highlight_code(language, contents, class_prefix)) {
+                    debug!("Highlighted code block contents");
+                    ctx.push_raw_str(&html);
+                    return;
+                }
+            }
+        }
+    }
+
+    ctx.html().text(contents);
+}
@@ -19,16 +19,29 @@
  */
 
 use super::prelude::*;
-use crate::tree::{Alignment, AttributeMap, FloatAlignment};
+use crate::tree::{Alignment, AttributeMap, FloatAlignment, HeadingLevel, ListItem};
 
 pub fn render_table_of_contents(
     ctx: &mut HtmlContext,
     align: Option<Alignment>,
     attributes: &AttributeMap,
+    (min_level, max_level): (Option<HeadingLevel>, Option<HeadingLevel>),
 ) {
     debug!("Creating table of contents");
     let use_true_ids = ctx.settings().use_true_ids;
 
+    // Additional blocks get distinct IDs, so the page's IDs remain unique
+    let suffix = match ctx.next_table_of_contents_block_index() {
+        0 => String::new(),
+        index => {
+            warn!("Found additional table of contents block (index {index})");
+            format!("-{index}")
+        }
+    };
+    let toc_id = format!("wj-toc{suffix}");
+    let action_bar_id = format!("wj-toc-action-bar{suffix}");
+    let list_id = format!("wj-toc-list{suffix}");
+
     let class_value = match align {
         None => "",
         Some(align) => {
@@ -40,7 +53,7 @@ pub fn render_table_of_contents(
     ctx.html()
         .div()
         .attr(attr!(
-            "id" => "wj-toc"; if use_true_ids,
+            "id" => &toc_id; if use_true_ids,
             "class" => class_value; if align.is_some();;
             attributes
         ))
@@ -48,7 +61,7 @@ pub fn render_table_of_contents(
             // TOC buttons
             ctx.html()
                 .div()
-                .attr(attr!("id" => "wj-toc-action-bar"; if use_true_ids))
+                .attr(attr!("id" => &action_bar_id; if use_true_ids))
                 .inner(|ctx| {
                     // TODO button
                     ctx.html().a().attr(attr!(
@@ -69,10 +82,99 @@ pub fn render_table_of_contents(
 
             // TOC List
             let table_of_contents = ctx.table_of_contents();
+            let mut list = ctx.html().div();
+            list.attr(attr!("id" => &list_id; if use_true_ids));
 
-            ctx.html()
-                .div()
-                .attr(attr!("id" => "wj-toc-list"; if use_true_ids))
-                .contents(table_of_contents);
+            if min_level.is_none() && max_level.is_none() {
+                list.contents(table_of_contents);
+            } else {
+                let min_level = min_level.map(HeadingLevel::value).unwrap_or(1);
+                let max_level = max_level.map(HeadingLevel::value).unwrap_or(6);
+                let table_of_contents =
+                    filter_table_of_contents(table_of_contents, min_level, max_level);
+
+                list.contents(&table_of_contents);
+            }
         });
 }
+
+/// Limits the table of contents to headings within the given levels.
+///
+/// Entries above the minimum level are removed, with their sub-entries
+/// taking their place. Entries below the maximum level are removed entirely.
+fn filter_table_of_contents<'t>(
+    elements: &[Element<'t>],
+    min_level: u8,
+    max_level: u8,
+) -> Vec<Element<'t>> {
+    elements
+        .iter()
+        .filter_map(|element| match element {
+            Element::List {
+                ltype,
+                items,
+                attributes,
+            } => {
+                let items = filter_list_items(items, 1, min_level, max_level);
+
+                if items.is_empty() {
+                    None
+                } else {
+                    Some(Element::List {
+                        ltype: *ltype,
+                        items,
+                        attributes: attributes.clone(),
+                    })
+                }
+            }
+            _ => Some(element.clone()),
+        })
+        .collect()
+}
+
+fn filter_list_items<'t>(
+    items: &[ListItem<'t>],
+    level: u8,
+    min_level: u8,
+    max_level: u8,
+) -> Vec<ListItem<'t>> {
+    let in_range = (min_level..=max_level).contains(&level);
+    let mut filtered = Vec::new();
+
+    for item in items {
+        match item {
+            ListItem::SubList { element } => {
+                if let Element::List {
+                    ltype,
+                    items,
+                    attributes,
+                } = element.as_ref()
+                {
+                    let items = filter_list_items(items, level + 1, min_level, max_level);
+
+                    if level < min_level {
+                        // This level is hidden, so the sub-entries move up
+                        filtered.extend(items);
+                    } else if !items.is_empty() {
+                        filtered.push(ListItem::SubList {
+                            element: Box::new(Element::List {
+                                ltype: *ltype,
+                                items,
+                                attributes: attributes.clone(),
+                            }),
+                        });
+                    }
+                } else if in_range {
+                    filtered.push(item.clone());
+                }
+            }
+            ListItem::Elements { .. } => {
+                if in_range {
+                    filtered.push(item.clone());
+                }
+            }
+        }
+    }
+
+    filtered
+}
@@ -23,9 +23,12 @@ use super::prelude::*;
 pub fn render_user(ctx: &mut HtmlContext, name: &str, show_avatar: bool) {
     debug!("Rendering user block (name '{name}', show-avatar {show_avatar})");
 
+    let class = ctx.layout_class(ElementClass::UserInfo);
+    let error_class = ctx.layout_class(ElementClass::ErrorInline);
+
     ctx.html()
         .span()
-        .attr(attr!("class" => "wj-user-info"))
+        .attr(attr!("class" => class))
         .inner(|ctx| match ctx.handle().get_user_info(name) {
             Some(info) => {
                 trace!(
@@ -69,7 +72,7 @@ pub fn render_user(ctx: &mut HtmlContext, name: &str, show_avatar: bool) {
 
                 ctx.html()
                     .span()
-                    .attr(attr!("class" => "wj-error-inline"))
+                    .attr(attr!("class" => error_class))
                     .inner(|ctx| {
                         if show_avatar {
                             // Karma SVG
@@ -0,0 +1,48 @@
+/*
+ * render/html/email_rewriter.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Extension point for rendering email addresses.
+
+use super::TrustedHtml;
+use crate::render::Handle;
+use std::fmt::Debug;
+
+/// A trait which library users can implement to render email addresses.
+///
+/// This is used when [`WikitextSettings::email_obfuscation`] is
+/// [`EmailObfuscation::Rewriter`]. It allows the host to substitute its own
+/// markup, for instance a link to a contact form or a script which assembles
+/// the address. The result is placed inside the email element's `<span>`.
+///
+/// By default, the address is rendered as escaped text.
+///
+/// [`WikitextSettings::email_obfuscation`]: crate::settings::WikitextSettings::email_obfuscation
+/// [`EmailObfuscation::Rewriter`]: crate::settings::EmailObfuscation::Rewriter
+pub trait EmailRewriter: Debug + Send + Sync {
+    /// Returns the HTML to emit in place of the given email address.
+    fn rewrite_email(&self, email: &str) -> TrustedHtml;
+}
+
+impl EmailRewriter for Handle {
+    #[inline]
+    fn rewrite_email(&self, email: &str) -> TrustedHtml {
+        TrustedHtml::escaped(email)
+    }
+}
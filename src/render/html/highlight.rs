@@ -0,0 +1,155 @@
+/*
+ * render/html/highlight.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Syntax highlighting for code blocks, via `syntect`.
+//!
+//! Highlighted output uses CSS classes rather than inline styles,
+//! all prefixed with `wj-hl-`, or `hl-` after the configured class prefix.
+//! The stylesheet for a particular theme can be generated with
+//! [`highlight_theme_css()`].
+
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+use syntect::highlighting::ThemeSet;
+use syntect::html::{css_for_theme_with_class_style, ClassStyle, ClassedHTMLGenerator};
+use syntect::parsing::SyntaxSet;
+use syntect::util::LinesWithEndings;
+
+const DEFAULT_CLASS_STYLE: ClassStyle = ClassStyle::SpacedPrefixed { prefix: "wj-hl-" };
+
+static SYNTAX_SET: Lazy<SyntaxSet> = Lazy::new(SyntaxSet::load_defaults_newlines);
+static THEME_SET: Lazy<ThemeSet> = Lazy::new(ThemeSet::load_defaults);
+
+/// The class style for each class prefix which has been used.
+///
+/// `syntect` requires the prefix to be `'static`, so each distinct
+/// prefix is leaked once and then reused for later renders.
+static CLASS_STYLES: Lazy<Mutex<HashMap<String, ClassStyle>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Gets the class style for the given class prefix, which replaces `wj-`.
+///
+/// See [`WikitextSettings::class_prefix`](crate::settings::WikitextSettings::class_prefix).
+fn class_style(class_prefix: Option<&str>) -> ClassStyle {
+    let class_prefix = match class_prefix {
+        Some(class_prefix) => class_prefix,
+        None => return DEFAULT_CLASS_STYLE,
+    };
+
+    let mut class_styles = CLASS_STYLES.lock().unwrap_or_else(PoisonError::into_inner);
+    *class_styles.entry(str!(class_prefix)).or_insert_with(|| {
+        let prefix = format!("{class_prefix}hl-").into_boxed_str();
+        ClassStyle::SpacedPrefixed {
+            prefix: Box::leak(prefix),
+        }
+    })
+}
+
+/// Produces highlighted HTML for the given code, if the language is known.
+///
+/// The language is matched against the syntax name or file extension,
+/// e.g. `rust` or `rs`. If no syntax is found, `None` is returned
+/// and the caller should emit the contents without highlighting.
+pub fn highlight_code(
+    language: &str,
+    contents: &str,
+    class_prefix: Option<&str>,
+) -> Option<String> {
+    let syntax = SYNTAX_SET.find_syntax_by_token(language)?;
+    let mut generator = ClassedHTMLGenerator::new_with_class_style(
+        syntax,
+        &SYNTAX_SET,
+        class_style(class_prefix),
+    );
+
+    for line in LinesWithEndings::from(contents) {
+        if let Err(error) = generator.parse_html_for_line_which_includes_newline(line) {
+            warn!("Unable to highlight code block (language {language}): {error}");
+            return None;
+        }
+    }
+
+    Some(generator.finalize())
+}
+
+/// Generates the CSS stylesheet for one of the built-in highlighting themes.
+///
+/// The class prefix should be the same as the one in the settings used
+/// to render, see [`WikitextSettings::class_prefix`].
+///
+/// See [`highlight_theme_names()`] for the list of available themes.
+/// Returns `None` if there is no theme with this name.
+///
+/// [`WikitextSettings::class_prefix`]: crate::settings::WikitextSettings::class_prefix
+pub fn highlight_theme_css(
+    theme_name: &str,
+    class_prefix: Option<&str>,
+) -> Option<String> {
+    let theme = THEME_SET.themes.get(theme_name)?;
+
+    match css_for_theme_with_class_style(theme, class_style(class_prefix)) {
+        Ok(css) => Some(css),
+        Err(error) => {
+            warn!("Unable to generate CSS for theme {theme_name}: {error}");
+            None
+        }
+    }
+}
+
+/// Lists the names of all built-in highlighting themes.
+pub fn highlight_theme_names() -> impl Iterator<Item = &'static str> {
+    THEME_SET.themes.keys().map(|name| name.as_str())
+}
+
+#[test]
+fn highlight() {
+    let html =
+        highlight_code("rust", "fn main() {}\n", None).expect("Rust syntax not found");
+    assert!(html.contains("wj-hl-"), "Highlighted output has no classes");
+    assert!(html.contains("main"), "Highlighted output missing contents");
+
+    let html = highlight_code("rust", "fn main() {}\n", Some("ftml-"))
+        .expect("Rust syntax not found");
+    assert!(
+        html.contains("ftml-hl-"),
+        "Highlighted output has no prefixed classes"
+    );
+    assert!(
+        !html.contains("wj-"),
+        "Highlighted output has unprefixed classes"
+    );
+
+    assert!(highlight_code("not-a-language", "abc", None).is_none());
+
+    for name in highlight_theme_names() {
+        let css = highlight_theme_css(name, None).expect("Unable to generate theme CSS");
+        assert!(css.contains(".wj-hl-"), "Theme CSS has no prefixed classes");
+
+        let css = highlight_theme_css(name, Some("ftml-"))
+            .expect("Unable to generate theme CSS");
+        assert!(
+            css.contains(".ftml-hl-"),
+            "Theme CSS has no prefixed classes"
+        );
+    }
+
+    assert!(highlight_theme_css("not-a-theme", None).is_none());
+}
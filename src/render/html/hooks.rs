@@ -0,0 +1,61 @@
+/*
+ * render/html/hooks.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::{
+    EmailRewriter, ImageSrcsetProvider, MathImageProvider, ModuleRenderer,
+    PageVariableProvider, PaginationProvider, UrlRewriter,
+};
+use crate::budget::WorkBudget;
+
+/// Extension points which library users can provide to the HTML renderer.
+///
+/// Any hook which is not set uses the default behavior.
+/// See [`HtmlRender::render_with_hooks()`].
+///
+/// [`HtmlRender::render_with_hooks()`]: super::HtmlRender::render_with_hooks
+#[derive(Debug, Copy, Clone, Default)]
+pub struct HtmlHooks<'h> {
+    /// Expands modules, see [`ModuleRenderer`].
+    pub modules: Option<&'h dyn ModuleRenderer>,
+
+    /// Renders math as images, see [`MathImageProvider`].
+    pub math_images: Option<&'h dyn MathImageProvider>,
+
+    /// Provides alternate image resolutions, see [`ImageSrcsetProvider`].
+    pub image_srcset: Option<&'h dyn ImageSrcsetProvider>,
+
+    /// Provides the pages linked by `[[pagination]]`, see [`PaginationProvider`].
+    pub pagination: Option<&'h dyn PaginationProvider>,
+
+    /// Resolves page variables such as `%%created_by%%`, see [`PageVariableProvider`].
+    pub page_variables: Option<&'h dyn PageVariableProvider>,
+
+    /// Rewrites emitted `href` and `src` values, see [`UrlRewriter`].
+    pub url_rewriter: Option<&'h dyn UrlRewriter>,
+
+    /// Renders email addresses, see [`EmailRewriter`].
+    pub email_rewriter: Option<&'h dyn EmailRewriter>,
+
+    /// The work budget to spend from when parsing the wikitext of expanded modules.
+    ///
+    /// Once it is exhausted, further expanded modules are not rendered.
+    /// This is set by [`Render::render_with_budget()`](crate::render::Render::render_with_budget).
+    pub budget: Option<&'h WorkBudget>,
+}
@@ -0,0 +1,62 @@
+/*
+ * render/html/image_srcset.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Extension point for responsive images.
+
+use crate::render::Handle;
+use crate::tree::ImageSource;
+use std::fmt::Debug;
+
+/// An alternate resolution of an image, used as an entry in `srcset`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct ImageResolution {
+    /// The URL of the image at this resolution.
+    pub url: String,
+
+    /// The width or pixel density descriptor, such as `800w` or `2x`.
+    pub descriptor: String,
+}
+
+/// A trait which library users can implement to provide responsive images.
+///
+/// This allows serving alternate resolutions of an image, such as from a
+/// thumbnailing service, which the browser can choose between based on
+/// the viewport. If any resolutions are returned, they are emitted
+/// as the `srcset` of the image.
+///
+/// By default, no resolutions are provided.
+pub trait ImageSrcsetProvider: Debug + Send + Sync {
+    /// Returns the alternate resolutions for the given image.
+    ///
+    /// The `url` is the resolved link to the image, which is what `src` is set to.
+    fn get_image_srcset(&self, source: &ImageSource, url: &str) -> Vec<ImageResolution>;
+}
+
+impl ImageSrcsetProvider for Handle {
+    #[inline]
+    fn get_image_srcset(
+        &self,
+        _source: &ImageSource,
+        _url: &str,
+    ) -> Vec<ImageResolution> {
+        Vec::new()
+    }
+}
@@ -0,0 +1,145 @@
+/*
+ * render/html/layout_class.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Table of the classes given to each rendered element, per layout.
+//!
+//! Only the outermost class of each element is listed here. Classes for the
+//! parts within an element (such as `wj-collapsible-button`) are derived from it.
+//! Keeping these in one table means the layouts can be compared at a glance,
+//! rather than searching through each render function for differences.
+//!
+//! At present both layouts use the same classes.
+
+use crate::layout::Layout;
+use enum_map::{Enum, EnumMap};
+use once_cell::sync::Lazy;
+
+/// An element kind whose class depends on the layout.
+#[derive(Enum, Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum ElementClass {
+    Body,
+    Anchor,
+    Link,
+    Email,
+    Raw,
+    Code,
+    Collapsible,
+    Tabs,
+    Embed,
+    Image,
+    Date,
+    UserInfo,
+    ClearFloat,
+    LineBreaks,
+    Math,
+    EquationRef,
+    FootnoteRef,
+    FootnoteList,
+    BibliographyRef,
+    Bibliography,
+    ErrorBlock,
+    Pagination,
+    ErrorInline,
+}
+
+impl ElementClass {
+    /// Gets the class for this element in the given layout.
+    #[inline]
+    pub fn get(self, layout: Layout) -> &'static str {
+        LAYOUT_CLASSES[self].get(layout)
+    }
+}
+
+/// The class of an element in each layout.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct LayoutClasses {
+    pub wikidot: &'static str,
+    pub wikijump: &'static str,
+}
+
+impl LayoutClasses {
+    #[inline]
+    pub fn get(self, layout: Layout) -> &'static str {
+        match layout {
+            Layout::Wikidot => self.wikidot,
+            Layout::Wikijump => self.wikijump,
+        }
+    }
+}
+
+macro_rules! classes {
+    ($wikidot:expr, $wikijump:expr $(,)?) => {
+        LayoutClasses {
+            wikidot: $wikidot,
+            wikijump: $wikijump,
+        }
+    };
+}
+
+/// Mapping of all element kinds to their classes in each layout.
+pub static LAYOUT_CLASSES: Lazy<EnumMap<ElementClass, LayoutClasses>> = Lazy::new(|| {
+    enum_map! {
+        ElementClass::Body => classes!("wj-body", "wj-body"),
+        ElementClass::Anchor => classes!("wj-anchor", "wj-anchor"),
+        ElementClass::Link => classes!("wj-link", "wj-link"),
+        ElementClass::Email => classes!("wj-email", "wj-email"),
+        ElementClass::Raw => classes!("wj-raw", "wj-raw"),
+        ElementClass::Code => classes!("wj-code", "wj-code"),
+        ElementClass::Collapsible => classes!("wj-collapsible", "wj-collapsible"),
+        ElementClass::Tabs => classes!("wj-tabs", "wj-tabs"),
+        ElementClass::Embed => classes!("wj-embed", "wj-embed"),
+        ElementClass::Image => classes!("wj-image-container", "wj-image-container"),
+        ElementClass::Date => classes!("wj-date", "wj-date"),
+        ElementClass::UserInfo => classes!("wj-user-info", "wj-user-info"),
+        ElementClass::ClearFloat => classes!("wj-clear-float", "wj-clear-float"),
+        ElementClass::LineBreaks => classes!("wj-line-breaks", "wj-line-breaks"),
+        ElementClass::Math => classes!("wj-math", "wj-math"),
+        ElementClass::EquationRef => classes!("wj-equation-ref", "wj-equation-ref"),
+        ElementClass::FootnoteRef => classes!("wj-footnote-ref", "wj-footnote-ref"),
+        ElementClass::FootnoteList => classes!("wj-footnote-list", "wj-footnote-list"),
+        ElementClass::BibliographyRef => classes!("wj-bibliography-ref", "wj-bibliography-ref"),
+        ElementClass::Bibliography => classes!("wj-bibliography bibitems", "wj-bibliography bibitems"),
+        ElementClass::ErrorBlock => classes!("wj-error-block", "wj-error-block"),
+        ElementClass::Pagination => classes!("wj-pagination pager", "wj-pagination"),
+        ElementClass::ErrorInline => classes!("wj-error-inline", "wj-error-inline"),
+    }
+});
+
+#[test]
+fn layout_classes() {
+    use std::collections::HashSet;
+
+    for layout in [Layout::Wikidot, Layout::Wikijump] {
+        let mut seen = HashSet::new();
+
+        for (element, classes) in LAYOUT_CLASSES.iter() {
+            let class = classes.get(layout);
+
+            assert!(
+                !class.trim().is_empty(),
+                "Class for {element:?} in {layout:?} layout is empty",
+            );
+            assert!(
+                seen.insert(class),
+                "Class for {element:?} in {layout:?} layout is not unique",
+            );
+        }
+    }
+}
@@ -0,0 +1,73 @@
+/*
+ * render/html/math_image.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Extension point for rendering math as images.
+
+use crate::render::Handle;
+use std::fmt::Debug;
+
+/// Whether a math expression is displayed as a block or inline.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum MathDisplay {
+    Block,
+    Inline,
+}
+
+/// An image of a rendered math expression.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct MathImage {
+    /// The URL of the image, such as from an external LaTeX to SVG service.
+    pub url: String,
+
+    /// The alternate text of the image.
+    ///
+    /// If absent, the LaTeX source is used.
+    pub alt: Option<String>,
+}
+
+/// A trait which library users can implement to render math as images.
+///
+/// This allows integrating an external service to render LaTeX,
+/// without ftml needing to depend on a renderer. If an image is returned,
+/// it is used in place of MathML, which is useful when the `mathml` feature
+/// is disabled, or when targeting readers without MathML support.
+///
+/// By default, no images are provided.
+pub trait MathImageProvider: Debug + Send + Sync {
+    /// Returns an image for the given LaTeX source, or `None` to render normally.
+    fn get_math_image(
+        &self,
+        latex_source: &str,
+        display: MathDisplay,
+    ) -> Option<MathImage>;
+}
+
+impl MathImageProvider for Handle {
+    #[inline]
+    fn get_math_image(
+        &self,
+        _latex_source: &str,
+        _display: MathDisplay,
+    ) -> Option<MathImage> {
+        None
+    }
+}
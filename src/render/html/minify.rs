@@ -0,0 +1,221 @@
+/*
+ * render/html/minify.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Whitespace collapsing for the `minify_html` setting.
+//!
+//! Browsers already render any run of whitespace in text as a single space,
+//! and ignore whitespace adjacent to block-level boundaries, so removing it
+//! here does not change how the page appears.
+
+use super::builder::BLOCK_CUSTOM_ELEMENTS;
+use super::escape::escape;
+
+/// Tags whose contents must be emitted exactly as given.
+pub const PRESERVE_WHITESPACE_TAGS: [&str; 5] =
+    ["code", "pre", "script", "style", "textarea"];
+
+/// Values of the CSS `white-space` property which keep whitespace as written.
+const PRESERVE_WHITESPACE_VALUES: [&str; 4] =
+    ["pre", "pre-wrap", "pre-line", "break-spaces"];
+
+/// Tags where whitespace directly inside or around them is not rendered.
+const BLOCK_TAGS: [&str; 34] = [
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "body",
+    "br",
+    "dd",
+    "details",
+    "div",
+    "dl",
+    "dt",
+    "figcaption",
+    "figure",
+    "footer",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "li",
+    "ol",
+    "p",
+    "section",
+    "summary",
+    "table",
+    "tbody",
+    "td",
+    "th",
+    "thead",
+    "tr",
+    "ul",
+];
+
+#[inline]
+pub fn is_block_tag(tag: &str) -> bool {
+    BLOCK_TAGS.contains(&tag) || BLOCK_CUSTOM_ELEMENTS.contains(&tag)
+}
+
+/// Escapes the text into the buffer, collapsing runs of whitespace.
+///
+/// Whitespace is dropped entirely if the buffer already ends with
+/// whitespace or a block-level tag.
+pub fn push_minified(buffer: &mut String, s: &str) {
+    let mut pending_space = false;
+
+    for (i, word) in s.split(is_whitespace).enumerate() {
+        if i > 0 {
+            pending_space = true;
+        }
+
+        if word.is_empty() {
+            continue;
+        }
+
+        if pending_space {
+            push_space(buffer);
+            pending_space = false;
+        }
+
+        escape(buffer, word);
+    }
+
+    if pending_space {
+        push_space(buffer);
+    }
+}
+
+/// Whether an inline style keeps whitespace as written, such as `white-space: pre`.
+///
+/// Text within such elements must not be collapsed.
+pub fn style_preserves_whitespace(style: &str) -> bool {
+    style.split(';').any(|declaration| {
+        let (property, value) = match declaration.split_once(':') {
+            Some(parts) => parts,
+            None => return false,
+        };
+
+        let value = value.trim().trim_end_matches("!important").trim_end();
+        property.trim().eq_ignore_ascii_case("white-space")
+            && PRESERVE_WHITESPACE_VALUES
+                .iter()
+                .any(|preserve| value.eq_ignore_ascii_case(preserve))
+    })
+}
+
+/// Removes a trailing space before a block-level tag is closed.
+pub fn trim_before_close(buffer: &mut String, tag: &str) {
+    if is_block_tag(tag) && buffer.ends_with(' ') {
+        buffer.pop();
+    }
+}
+
+fn push_space(buffer: &mut String) {
+    if !buffer.is_empty()
+        && !buffer.ends_with(is_whitespace)
+        && !ends_with_block_tag(buffer)
+    {
+        buffer.push(' ');
+    }
+}
+
+fn ends_with_block_tag(buffer: &str) -> bool {
+    if !buffer.ends_with('>') {
+        return false;
+    }
+
+    // Attribute values are escaped, so the last '<' starts the final tag.
+    let start = match buffer.rfind('<') {
+        Some(index) => index + 1,
+        None => return false,
+    };
+
+    let tag = buffer[start..].trim_start_matches('/');
+    let end = tag
+        .find(|c: char| !c.is_ascii_alphanumeric() && c != '-')
+        .unwrap_or(tag.len());
+
+    is_block_tag(&tag[..end])
+}
+
+#[inline]
+fn is_whitespace(c: char) -> bool {
+    // Non-breaking spaces and the like are significant, so only
+    // collapse what HTML itself treats as whitespace.
+    matches!(c, ' ' | '\t' | '\n' | '\r' | '\x0c')
+}
+
+#[test]
+fn minify() {
+    macro_rules! check {
+        ($prefix:expr, $input:expr, $expected:expr $(,)?) => {{
+            let mut buffer = String::from($prefix);
+            push_minified(&mut buffer, $input);
+            assert_eq!(buffer, $expected, "Minified text doesn't match expected");
+        }};
+    }
+
+    check!("", "apple", "apple");
+    check!("", "  apple  ", "apple ");
+    check!("x", "apple \n\t banana", "xapple banana");
+    check!("x", "  apple", "x apple");
+    check!("x ", "  apple", "x apple");
+    check!("x", "   ", "x ");
+    check!("x ", "   ", "x ");
+    check!("<div>", "  apple", "<div>apple");
+    check!("</p>", " \n ", "</p>");
+    check!("<br>", " apple", "<br>apple");
+    check!("<span>", " apple", "<span> apple");
+    check!(
+        "<wj-body class=\"wj-body\">",
+        "\napple",
+        "<wj-body class=\"wj-body\">apple"
+    );
+    check!("", "a < b\u{a0} c", "a &lt; b\u{a0} c");
+
+    let mut buffer = String::from("<p>apple ");
+    trim_before_close(&mut buffer, "p");
+    assert_eq!(buffer, "<p>apple");
+
+    let mut buffer = String::from("<span>apple ");
+    trim_before_close(&mut buffer, "span");
+    assert_eq!(buffer, "<span>apple ");
+}
+
+#[test]
+fn preserve_styles() {
+    assert!(style_preserves_whitespace("white-space: pre"));
+    assert!(style_preserves_whitespace(
+        "color: red; white-space:pre-wrap;"
+    ));
+    assert!(style_preserves_whitespace(
+        "WHITE-SPACE: Pre-Line !important"
+    ));
+    assert!(style_preserves_whitespace("white-space: break-spaces"));
+    assert!(!style_preserves_whitespace(""));
+    assert!(!style_preserves_whitespace("white-space: normal"));
+    assert!(!style_preserves_whitespace("white-space: nowrap"));
+    assert!(!style_preserves_whitespace("font-family: pre"));
+}
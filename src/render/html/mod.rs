@@ -25,38 +25,189 @@ mod test;
 mod attributes;
 mod builder;
 mod context;
+mod document;
 mod element;
+mod email_rewriter;
 mod escape;
+#[cfg(feature = "syntax-highlight")]
+mod highlight;
+mod hooks;
+mod image_srcset;
+mod layout_class;
+mod math_image;
 mod meta;
+mod minify;
+mod module;
 mod output;
+mod page_variables;
+mod pagination;
+#[cfg(feature = "parallel")]
+mod parallel;
 mod random;
 mod render;
+mod trusted;
+mod url_rewriter;
 
+pub use self::email_rewriter::EmailRewriter;
+#[cfg(feature = "syntax-highlight")]
+pub use self::highlight::{highlight_theme_css, highlight_theme_names};
+pub use self::hooks::HtmlHooks;
+pub use self::image_srcset::{ImageResolution, ImageSrcsetProvider};
+pub use self::layout_class::{ElementClass, LayoutClasses, LAYOUT_CLASSES};
+pub use self::math_image::{MathDisplay, MathImage, MathImageProvider};
 pub use self::meta::{HtmlMeta, HtmlMetaType};
+pub use self::module::{expand_template, ModuleExpansion, ModuleRenderer};
 pub use self::output::HtmlOutput;
+pub use self::page_variables::PageVariableProvider;
+pub use self::pagination::{PageNeighbors, PaginationProvider};
+pub use self::trusted::TrustedHtml;
+pub use self::url_rewriter::UrlRewriter;
 
 #[cfg(test)]
 use super::prelude;
 
 use self::attributes::AddedAttributes;
-use self::context::HtmlContext;
+use self::context::{HtmlContext, HtmlProfile};
+pub(crate) use self::element::process_css;
+pub use self::element::HTML_RESIZE_MESSAGE;
+use self::element::{render_elements, render_elements_with_spans};
+pub(crate) use self::escape::escape;
+use crate::budget::{render_cost, BudgetExceeded, WorkBudget};
 use crate::data::PageInfo;
 use crate::render::{Handle, Render};
 use crate::settings::WikitextSettings;
-use crate::tree::SyntaxTree;
+use crate::tree::{BibliographyList, Element, SyntaxTree};
 
 #[derive(Debug)]
 pub struct HtmlRender;
 
-impl Render for HtmlRender {
-    type Output = HtmlOutput;
+impl HtmlRender {
+    /// Renders the tree as XHTML, that is, as well-formed XML.
+    ///
+    /// Void elements are self-closed and attributes always have values.
+    pub(crate) fn render_xhtml(
+        &self,
+        tree: &SyntaxTree,
+        page_info: &PageInfo,
+        settings: &WikitextSettings,
+        hooks: HtmlHooks,
+    ) -> HtmlOutput {
+        self.render_inner(tree, page_info, settings, HtmlProfile::Xhtml, hooks)
+    }
 
-    fn render(
+    /// Renders the tree as HTML which is valid under [AMP](https://amp.dev/).
+    ///
+    /// Images and iframes use their AMP components, math is rendered using
+    /// `amp-mathml`, and custom elements are replaced with plain ones.
+    /// Anything which requires scripts, such as embedded gists, is replaced
+    /// by a link, and `[[style]]` blocks are omitted since AMP only permits
+    /// stylesheets in the document head.
+    ///
+    /// The given [`HtmlHooks`] are used as in [`render_with_hooks()`](Self::render_with_hooks).
+    pub fn render_amp(
         &self,
         tree: &SyntaxTree,
         page_info: &PageInfo,
         settings: &WikitextSettings,
+        hooks: HtmlHooks,
     ) -> HtmlOutput {
+        self.render_inner(tree, page_info, settings, HtmlProfile::Amp, hooks)
+    }
+
+    /// Renders only the given elements, rather than a whole syntax tree.
+    ///
+    /// This is useful for rendering fragments, such as a single footnote
+    /// or a preview of one block. Unlike [`render()`](Render::render), the
+    /// output is not wrapped in a `wj-body` element.
+    ///
+    /// Since there is no tree, the table of contents, footnote blocks,
+    /// and bibliography citations within these elements render as empty.
+    /// The given [`HtmlHooks`] are used as in [`render_with_hooks()`](Self::render_with_hooks).
+    pub fn render_partial(
+        &self,
+        elements: &[Element],
+        page_info: &PageInfo,
+        settings: &WikitextSettings,
+        hooks: HtmlHooks,
+        wikitext_len: usize,
+    ) -> HtmlOutput {
+        enter_span!("render", format = "html", partial = true);
+
+        // Apply any settings overlays for this page's category
+        let settings = settings.resolve(page_info);
+        let settings = settings.as_ref();
+        debug!(
+            "Rendering partial HTML (site {}, page {}, category {})",
+            page_info.site.as_ref(),
+            page_info.page.as_ref(),
+            match &page_info.category {
+                Some(category) => category.as_ref(),
+                None => "_default",
+            },
+        );
+
+        let bibliographies = BibliographyList::new();
+        let mut ctx = HtmlContext::new(
+            page_info,
+            &Handle,
+            settings,
+            (&[], &[]),
+            &[],
+            &bibliographies,
+            wikitext_len,
+        );
+        ctx.set_hooks(hooks);
+
+        render_elements(&mut ctx, elements);
+        ctx.into()
+    }
+
+    /// Renders the tree, expanding modules using the given [`ModuleRenderer`].
+    pub fn render_with_modules(
+        &self,
+        tree: &SyntaxTree,
+        page_info: &PageInfo,
+        settings: &WikitextSettings,
+        modules: &dyn ModuleRenderer,
+    ) -> HtmlOutput {
+        let hooks = HtmlHooks {
+            modules: Some(modules),
+            ..Default::default()
+        };
+
+        self.render_with_hooks(tree, page_info, settings, hooks)
+    }
+
+    /// Renders the tree, using the given [`HtmlHooks`] as extension points.
+    pub fn render_with_hooks(
+        &self,
+        tree: &SyntaxTree,
+        page_info: &PageInfo,
+        settings: &WikitextSettings,
+        hooks: HtmlHooks,
+    ) -> HtmlOutput {
+        self.render_inner(tree, page_info, settings, HtmlProfile::Html, hooks)
+    }
+
+    fn render_inner(
+        &self,
+        tree: &SyntaxTree,
+        page_info: &PageInfo,
+        settings: &WikitextSettings,
+        profile: HtmlProfile,
+        hooks: HtmlHooks,
+    ) -> HtmlOutput {
+        enter_span!(
+            "render",
+            format = "html",
+            site = %page_info.site,
+            page = %page_info.page,
+        );
+
+        // Apply any settings overlays for this page's category
+        let settings = settings.resolve(page_info);
+        let settings = settings.as_ref();
+
         info!(
             "Rendering HTML (site {}, page {}, category {})",
             page_info.site.as_ref(),
@@ -71,19 +222,84 @@ impl Render for HtmlRender {
             page_info,
             &Handle,
             settings,
-            &tree.table_of_contents,
+            (&tree.table_of_contents, &tree.heading_anchors),
             &tree.footnotes,
             &tree.bibliographies,
             tree.wikitext_len,
         );
+        ctx.set_profile(profile);
+
+        ctx.set_hooks(hooks);
 
         // Crawl through elements and generate HTML
+        let source_spans = settings.source_spans;
+        let (space, scope_class) = match settings.css.scope_class {
+            Some(ref class) => (" ", class.as_str()),
+            None => ("", ""),
+        };
+
+        let class = ctx.layout_class(ElementClass::Body);
         ctx.html()
             .element("wj-body")
-            .attr(attr!("class" => "wj-body"))
-            .contents(&tree.elements);
+            .attr(attr!("class" => class space scope_class))
+            .inner(|ctx| {
+                if source_spans {
+                    render_elements_with_spans(ctx, &tree.elements, &tree.element_spans);
+                } else {
+                    render_elements(ctx, &tree.elements);
+                }
+            });
 
         // Build and return HtmlOutput
         ctx.into()
     }
 }
+
+impl Render for HtmlRender {
+    type Output = HtmlOutput;
+
+    #[inline]
+    fn render(
+        &self,
+        tree: &SyntaxTree,
+        page_info: &PageInfo,
+        settings: &WikitextSettings,
+    ) -> HtmlOutput {
+        self.render_inner(
+            tree,
+            page_info,
+            settings,
+            HtmlProfile::Html,
+            HtmlHooks::default(),
+        )
+    }
+
+    /// Render an abstract syntax tree, spending from the given work budget.
+    ///
+    /// Besides one unit per element in the tree, the wikitext of any
+    /// expanded modules is charged as it is parsed and rendered.
+    fn render_with_budget(
+        &self,
+        tree: &SyntaxTree,
+        page_info: &PageInfo,
+        settings: &WikitextSettings,
+        budget: &WorkBudget,
+    ) -> Result<HtmlOutput, BudgetExceeded> {
+        budget.spend(render_cost(tree))?;
+
+        let hooks = HtmlHooks {
+            budget: Some(budget),
+            ..Default::default()
+        };
+        let output =
+            self.render_inner(tree, page_info, settings, HtmlProfile::Html, hooks);
+
+        if budget.is_exhausted() {
+            return Err(BudgetExceeded {
+                limit: budget.limit(),
+            });
+        }
+
+        Ok(output)
+    }
+}
@@ -0,0 +1,95 @@
+/*
+ * render/html/module.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Extension point for expanding modules during HTML rendering.
+
+use super::TrustedHtml;
+use crate::data::PageInfo;
+use crate::tree::Module;
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+/// A trait which library users can implement to expand modules server-side.
+///
+/// It is registered using [`HtmlRender::render_with_modules()`], and is
+/// consulted whenever a `[[module]]` is rendered. If it declines to expand
+/// a module, then the default module rendering is used.
+///
+/// [`HtmlRender::render_with_modules()`]: super::HtmlRender::render_with_modules
+pub trait ModuleRenderer: Debug + Send + Sync {
+    /// Expands the given module, or returns `None` to use the default rendering.
+    fn render_module(
+        &self,
+        module: &Module,
+        page_info: &PageInfo,
+    ) -> Option<ModuleExpansion>;
+}
+
+/// The result of expanding a module with a [`ModuleRenderer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModuleExpansion {
+    /// HTML from the host, which is inserted without any escaping.
+    Html(TrustedHtml),
+
+    /// Wikitext, which is parsed and rendered in place of the module.
+    Wikitext(String),
+
+    /// Sets of template variables, for modules with a body template.
+    ///
+    /// The module's body is expanded once per set of variables (see
+    /// [`expand_template()`]), then the result is parsed and rendered
+    /// in place of the module. For instance, `ListPages` would produce
+    /// one set of variables per page.
+    Template(Vec<HashMap<String, String>>),
+}
+
+/// Replaces `%%name%%`-style variables in a module body template.
+///
+/// Variables which are not present in the map are left as-is.
+pub fn expand_template(template: &str, variables: &HashMap<String, String>) -> String {
+    static TEMPLATE_VARIABLE_REGEX: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"%%([^%\s]+)%%").unwrap());
+
+    TEMPLATE_VARIABLE_REGEX
+        .replace_all(template, |captures: &Captures| {
+            match variables.get(&captures[1]) {
+                Some(value) => value.clone(),
+                None => str!(&captures[0]),
+            }
+        })
+        .into_owned()
+}
+
+#[test]
+fn template() {
+    let variables = hashmap! {
+        str!("title") => str!("SCP-001"),
+        str!("name") => str!("scp-001"),
+    };
+
+    assert_eq!(
+        expand_template("* [[[%%name%%|%%title%%]]] (%%rating%%)", &variables),
+        "* [[[scp-001|SCP-001]]] (%%rating%%)",
+    );
+    assert_eq!(expand_template("100%% %%", &variables), "100%% %%");
+    assert_eq!(expand_template("", &variables), "");
+}
@@ -19,11 +19,12 @@
  */
 
 use super::meta::HtmlMeta;
-use crate::data::Backlinks;
+use crate::data::{Backlinks, ContentReport};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct HtmlOutput {
     pub body: String,
     pub meta: Vec<HtmlMeta>,
     pub backlinks: Backlinks<'static>,
+    pub content_report: ContentReport<'static>,
 }
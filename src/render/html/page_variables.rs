@@ -0,0 +1,45 @@
+/*
+ * render/html/page_variables.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Extension point for resolving page variables.
+
+use crate::data::PageInfo;
+use crate::render::Handle;
+use std::fmt::Debug;
+
+/// A trait which library users can implement to provide page variables.
+///
+/// Page variables such as `%%title%%` are first resolved from the [`PageInfo`]
+/// (see [`PageInfo::variable()`]). Any others, such as `%%created_by%%` or
+/// `%%updated_at%%`, are requested from this provider, since they depend on
+/// information about the page which only the host has.
+///
+/// By default, no other variables are provided, so they are rendered as-is.
+pub trait PageVariableProvider: Debug + Send + Sync {
+    /// Returns the value of the given page variable, or `None` if it is unknown.
+    fn get_page_variable(&self, info: &PageInfo, name: &str) -> Option<String>;
+}
+
+impl PageVariableProvider for Handle {
+    #[inline]
+    fn get_page_variable(&self, _info: &PageInfo, _name: &str) -> Option<String> {
+        None
+    }
+}
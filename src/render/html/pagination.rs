@@ -0,0 +1,63 @@
+/*
+ * render/html/pagination.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Extension point for pagination links.
+
+use crate::data::{PageInfo, PageRef};
+use crate::render::Handle;
+use std::fmt::Debug;
+
+/// The pages adjacent to the current one, as linked by `[[pagination]]`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct PageNeighbors<'a> {
+    /// The page before this one, if any.
+    pub previous: Option<PageRef<'a>>,
+
+    /// The page after this one, if any.
+    pub next: Option<PageRef<'a>>,
+}
+
+impl PageNeighbors<'_> {
+    /// Whether there are no adjacent pages.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.previous.is_none() && self.next.is_none()
+    }
+}
+
+/// A trait which library users can implement to provide pagination.
+///
+/// This determines which pages the `[[pagination]]` block links to,
+/// such as the previous and next entries in a series. It is only
+/// consulted when a page contains that block.
+///
+/// By default, no adjacent pages are provided, so nothing is rendered.
+pub trait PaginationProvider: Debug + Send + Sync {
+    /// Returns the pages before and after the given one.
+    fn get_page_neighbors(&self, info: &PageInfo) -> PageNeighbors<'static>;
+}
+
+impl PaginationProvider for Handle {
+    #[inline]
+    fn get_page_neighbors(&self, _info: &PageInfo) -> PageNeighbors<'static> {
+        PageNeighbors::default()
+    }
+}
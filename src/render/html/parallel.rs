@@ -0,0 +1,364 @@
+/*
+ * render/html/parallel.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Parallel rendering of top-level elements.
+//!
+//! Each top-level element is rendered in its own [`HtmlContext`], and the
+//! results are concatenated in order. Since some index counters (such as
+//! footnotes or table of contents entries) are shared across the whole page,
+//! a sequential pre-pass determines their values at the start of each element.
+//!
+//! Note that randomly-generated IDs will differ from those of a sequential render.
+
+use super::attributes::AddedAttributes;
+use super::context::HtmlContext;
+use super::element::{render_elements, render_elements_with_spans, section_ranges};
+use super::{ElementClass, HtmlHooks, HtmlOutput, HtmlRender};
+use crate::data::PageInfo;
+use crate::parsing::ElementCondition;
+use crate::render::count_footnotes;
+use crate::render::Handle;
+use crate::settings::WikitextSettings;
+use crate::tree::visit::{walk_container, walk_element, Visitor};
+use crate::tree::{BibliographyList, Container, ContainerType, Element, SyntaxTree};
+use rayon::prelude::*;
+use std::num::NonZeroUsize;
+use std::ops::Range;
+
+impl HtmlRender {
+    /// Renders the tree to HTML, processing top-level elements in parallel.
+    ///
+    /// The output is the same as [`Render::render()`](crate::render::Render::render),
+    /// except for any randomly-generated IDs.
+    /// This is intended for very large pages, where the overhead of
+    /// separately rendering each element is worth it.
+    ///
+    /// The given [`HtmlHooks`] are used in the same way as in
+    /// [`render_with_hooks()`](HtmlRender::render_with_hooks),
+    /// and so may be called from several threads at once.
+    pub fn render_parallel(
+        &self,
+        tree: &SyntaxTree,
+        page_info: &PageInfo,
+        settings: &WikitextSettings,
+        hooks: HtmlHooks,
+    ) -> HtmlOutput {
+        enter_span!(
+            "render",
+            format = "html",
+            site = %page_info.site,
+            page = %page_info.page,
+            parallel = true,
+        );
+
+        // Apply any settings overlays for this page's category
+        let settings = settings.resolve(page_info);
+        let settings = settings.as_ref();
+
+        info!(
+            "Rendering HTML in parallel (site {}, page {}, {} top-level elements)",
+            page_info.site.as_ref(),
+            page_info.page.as_ref(),
+            tree.elements.len(),
+        );
+
+        // Determine the counter values at the start of each element
+        let indices = starting_indices(tree, page_info);
+
+        // Context which the results are combined into
+        let mut ctx = HtmlContext::new(
+            page_info,
+            &Handle,
+            settings,
+            (&tree.table_of_contents, &tree.heading_anchors),
+            &tree.footnotes,
+            &tree.bibliographies,
+            tree.wikitext_len,
+        );
+        ctx.set_hooks(hooks);
+
+        // Choose the ID of the first reference to each footnote ahead of time,
+        // so that the parts agree on them
+        let first_refs: Vec<String> = if settings.footnote_backlinks {
+            (1..=tree.footnotes.len())
+                .filter_map(NonZeroUsize::new)
+                .map(|index| ctx.footnote_refs(index).swap_remove(0))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        // Render each top-level element separately,
+        // except for collapsible sections, which are rendered together.
+        let render_range = |range: Range<usize>, footnote_refs: Vec<Vec<String>>| {
+            let indices = indices[range.start];
+            let mut ctx = HtmlContext::new(
+                page_info,
+                &Handle,
+                settings,
+                (&tree.table_of_contents, &tree.heading_anchors),
+                &tree.footnotes,
+                &tree.bibliographies,
+                0,
+            );
+
+            ctx.set_hooks(hooks);
+            ctx.set_indices(
+                indices.code_snippet,
+                indices.table_of_contents,
+                indices.table_of_contents_block,
+                indices.collapsible,
+                indices.equation,
+                indices.footnote,
+            );
+
+            let seeded: Vec<usize> = footnote_refs.iter().map(Vec::len).collect();
+            ctx.set_footnote_refs(footnote_refs, &first_refs);
+
+            let elements = &tree.elements[range.clone()];
+            if settings.source_spans {
+                let spans = tree.element_spans.get(range).unwrap_or(&[]);
+                render_elements_with_spans(&mut ctx, elements, spans);
+            } else {
+                render_elements(&mut ctx, elements);
+            }
+
+            // Only return the footnote references rendered here
+            let mut footnote_refs = ctx.take_footnote_refs();
+            for (refs, &count) in footnote_refs.iter_mut().zip(&seeded) {
+                refs.drain(..count);
+            }
+
+            (HtmlOutput::from(ctx), footnote_refs)
+        };
+
+        // Parts with a footnote block are rendered last, so that its
+        // back-links include the footnote references from the rest of the page.
+        let (block_ranges, other_ranges): (Vec<_>, Vec<_>) =
+            section_ranges(&tree.elements)
+                .into_iter()
+                .enumerate()
+                .partition(|(_, range)| {
+                    has_footnote_block(&tree.elements[range.clone()])
+                });
+
+        let mut parts = other_ranges
+            .into_par_iter()
+            .map(|(part, range)| {
+                let (output, footnote_refs) = render_range(range, Vec::new());
+                (part, output, footnote_refs)
+            })
+            .collect::<Vec<_>>();
+
+        for (part, range) in block_ranges {
+            let mut footnote_refs: Vec<Vec<String>> = Vec::new();
+            for (_, _, refs) in &parts {
+                if footnote_refs.len() < refs.len() {
+                    footnote_refs.resize_with(refs.len(), Vec::new);
+                }
+
+                for (merged, refs) in footnote_refs.iter_mut().zip(refs) {
+                    merged.extend_from_slice(refs);
+                }
+            }
+
+            let (output, footnote_refs) = render_range(range, footnote_refs);
+            let position = parts.partition_point(|(other, _, _)| *other < part);
+            parts.insert(position, (part, output, footnote_refs));
+        }
+
+        let mut outputs: Vec<HtmlOutput> =
+            parts.into_iter().map(|(_, output, _)| output).collect();
+
+        // Combine the results
+        let (space, scope_class) = match settings.css.scope_class {
+            Some(ref class) => (" ", class.as_str()),
+            None => ("", ""),
+        };
+
+        let class = ctx.layout_class(ElementClass::Body);
+        ctx.html()
+            .element("wj-body")
+            .attr(attr!("class" => class space scope_class))
+            .inner(|ctx| {
+                for output in &outputs {
+                    ctx.push_raw_str(&output.body);
+                }
+            });
+
+        for output in &mut outputs {
+            ctx.append_backlinks(&mut output.backlinks);
+            ctx.append_content_report(&mut output.content_report);
+        }
+
+        ctx.into()
+    }
+}
+
+/// The values of the index counters at a particular point in the tree.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Indices {
+    code_snippet: NonZeroUsize,
+    table_of_contents: usize,
+    table_of_contents_block: usize,
+    collapsible: NonZeroUsize,
+    equation: NonZeroUsize,
+    footnote: NonZeroUsize,
+}
+
+impl Default for Indices {
+    fn default() -> Self {
+        let one = NonZeroUsize::MIN;
+
+        Indices {
+            code_snippet: one,
+            table_of_contents: 0,
+            table_of_contents_block: 0,
+            collapsible: one,
+            equation: one,
+            footnote: one,
+        }
+    }
+}
+
+/// Determines if these elements contain a footnote block which will be rendered.
+fn has_footnote_block(elements: &[Element]) -> bool {
+    #[derive(Debug, Default)]
+    struct Finder {
+        found: bool,
+    }
+
+    impl<'t> Visitor<'t> for Finder {
+        fn visit_element(&mut self, element: &Element<'t>) {
+            match element {
+                Element::FootnoteBlock { hide: false, .. } => self.found = true,
+                _ => walk_element(self, element),
+            }
+        }
+    }
+
+    let mut finder = Finder::default();
+    finder.visit_elements(elements);
+    finder.found
+}
+
+fn starting_indices(tree: &SyntaxTree, page_info: &PageInfo) -> Vec<Indices> {
+    let mut counter = IndexCounter {
+        page_info,
+        table_of_contents: &tree.table_of_contents,
+        footnotes: &tree.footnotes,
+        bibliographies: &tree.bibliographies,
+        indices: Indices::default(),
+    };
+
+    tree.elements
+        .iter()
+        .map(|element| {
+            let indices = counter.indices;
+            counter.visit_element(element);
+            indices
+        })
+        .collect()
+}
+
+/// Visitor which increments counters the same way that rendering does.
+///
+/// This must mirror the calls to `HtmlContext::next_*_index()`, including for
+/// elements which are rendered elsewhere, such as footnote contents.
+#[derive(Debug)]
+struct IndexCounter<'e, 't> {
+    page_info: &'e PageInfo<'e>,
+    table_of_contents: &'e [Element<'t>],
+    footnotes: &'e [Vec<Element<'t>>],
+    bibliographies: &'e BibliographyList<'t>,
+    indices: Indices,
+}
+
+impl<'t> Visitor<'t> for IndexCounter<'_, 't> {
+    fn visit_element(&mut self, element: &Element<'t>) {
+        fn increment(value: &mut NonZeroUsize) {
+            *value = value.saturating_add(1);
+        }
+
+        match element {
+            Element::Code { .. } => increment(&mut self.indices.code_snippet),
+            Element::Math { .. } => increment(&mut self.indices.equation),
+            Element::Collapsible { .. } => increment(&mut self.indices.collapsible),
+            Element::Footnote => {
+                // Footnote contents are rendered in the tooltip
+                let index = usize::from(self.indices.footnote) - 1;
+                increment(&mut self.indices.footnote);
+
+                if let Some(contents) = self.footnotes.get(index) {
+                    self.visit_elements(contents);
+                }
+            }
+            Element::FootnoteBlock { hide: false, .. } => {
+                let footnotes = self.footnotes;
+                for contents in footnotes {
+                    self.visit_elements(contents);
+                }
+            }
+            Element::BibliographyCite { label, .. } => {
+                if let Some((_, contents, _)) = self.bibliographies.get_reference(label) {
+                    self.visit_elements(contents);
+                }
+            }
+            Element::BibliographyBlock {
+                index, hide: false, ..
+            } => {
+                if let Some(bibliography) = self.bibliographies.get_bibliography(*index) {
+                    self.visit_bibliography(bibliography);
+                }
+            }
+            Element::TableOfContents { .. } => {
+                self.indices.table_of_contents_block += 1;
+
+                let table_of_contents = self.table_of_contents;
+                self.visit_elements(table_of_contents);
+            }
+            Element::Conditional {
+                conditions,
+                elements,
+                ..
+            } if !ElementCondition::check(conditions, &self.page_info.tags) => {
+                // Hidden contents are not rendered, but still use footnote indices
+                for _ in 0..count_footnotes(elements) {
+                    increment(&mut self.indices.footnote);
+                }
+
+                return;
+            }
+            _ => (),
+        }
+
+        walk_element(self, element);
+    }
+
+    fn visit_container(&mut self, container: &Container<'t>) {
+        if let ContainerType::Header(heading) = container.ctype() {
+            if heading.has_toc {
+                self.indices.table_of_contents += 1;
+            }
+        }
+
+        walk_container(self, container);
+    }
+}
@@ -20,6 +20,7 @@
 
 use super::context::HtmlContext;
 use super::element::{render_element, render_elements};
+use super::trusted::TrustedHtml;
 use crate::tree::Element;
 use std::borrow::Cow;
 
@@ -30,28 +31,35 @@ pub trait ItemRender {
 impl ItemRender for &'_ str {
     #[inline]
     fn render(&self, ctx: &mut HtmlContext) {
-        ctx.push_escaped(self);
+        ctx.push_text(self);
     }
 }
 
 impl ItemRender for &'_ Cow<'_, str> {
     #[inline]
     fn render(&self, ctx: &mut HtmlContext) {
-        ctx.push_escaped(self);
+        ctx.push_text(self);
     }
 }
 
 impl ItemRender for String {
     #[inline]
     fn render(&self, ctx: &mut HtmlContext) {
-        ctx.push_escaped(self);
+        ctx.push_text(self);
     }
 }
 
 impl ItemRender for &'_ String {
     #[inline]
     fn render(&self, ctx: &mut HtmlContext) {
-        ctx.push_escaped(self);
+        ctx.push_text(self);
+    }
+}
+
+impl ItemRender for &'_ TrustedHtml {
+    #[inline]
+    fn render(&self, ctx: &mut HtmlContext) {
+        ctx.push_trusted(self);
     }
 }
 
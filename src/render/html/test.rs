@@ -21,17 +21,17 @@
 use super::prelude::*;
 use super::HtmlRender;
 use crate::layout::Layout;
-use crate::tree::BibliographyList;
+use crate::tree::{BibliographyList, Embed};
 
 #[test]
 fn html() {
     let page_info = PageInfo::dummy();
     let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
     let result = SyntaxTree::from_element_result(
-        vec![],
-        vec![],
         (vec![], vec![]),
         vec![],
+        (vec![], vec![]),
+        (vec![], vec![]),
         vec![],
         BibliographyList::new(),
         0,
@@ -39,3 +39,1442 @@ fn html() {
     let (tree, _) = result.into();
     let _output = HtmlRender.render(&tree, &page_info, &settings);
 }
+
+#[test]
+fn backlinks() {
+    use crate::data::{ExternalLink, InternalLink, PageRef};
+    use crate::tree::LinkType;
+
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let mut text = str!(
+        "[[[scp-001#toc0|SCP-001]]] [[[:other:scp-002]]] [/scp-003/comments SCP-003] [[[!wikipedia:Apple]]] [https://example.com/ Example] [[[:external:scp-004#top|SCP-004]]]"
+    );
+    crate::preprocess(&mut text, &page_info, &settings);
+
+    let tokens = crate::tokenize(&text);
+    let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+    let output = HtmlRender.render(&tree, &page_info, &settings);
+
+    assert_eq!(
+        output.backlinks.internal_links,
+        vec![
+            InternalLink {
+                page: PageRef::page_only(cow!("scp-001")),
+                extra: Some(cow!("#toc0")),
+                ltype: LinkType::Page,
+            },
+            InternalLink {
+                page: PageRef::page_and_site(cow!("other"), cow!("scp-002")),
+                extra: None,
+                ltype: LinkType::Page,
+            },
+            InternalLink {
+                page: PageRef::page_only(cow!("scp-003")),
+                extra: Some(cow!("/comments")),
+                ltype: LinkType::Direct,
+            },
+        ],
+    );
+
+    assert_eq!(
+        output.backlinks.external_links,
+        vec![
+            ExternalLink {
+                url: cow!("https://wikipedia.org/wiki/Apple"),
+                ltype: LinkType::Interwiki,
+            },
+            ExternalLink {
+                url: cow!("https://example.com/"),
+                ltype: LinkType::Direct,
+            },
+            ExternalLink {
+                url: cow!("https://external.wikijump.com/scp-004#top"),
+                ltype: LinkType::Page,
+            },
+        ],
+    );
+
+    // Sites outside of the farm are linked as external
+    assert!(output.body.contains(
+        "<a href=\"https://external.wikijump.com/scp-004#top\" class=\"wj-link wj-link-external\"",
+    ));
+}
+
+#[test]
+fn content_report() {
+    use crate::data::{DeadLink, PageRef};
+    use crate::tree::{ImageSource, LinkType};
+
+    let page_info = PageInfo::dummy();
+    let mut settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    settings.allow_local_paths = false;
+
+    let text = "[[[missing]]] [[[scp-001]]] [[[missing|Again]]]\n\n[[image page/file.png]]\n\n[[image https://example.com/a.png]]\n\n[[include-elements missing]]";
+    let tokens = crate::tokenize(text);
+    let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+    let output = HtmlRender.render(&tree, &page_info, &settings);
+    let report = output.content_report;
+
+    let dead_link = DeadLink {
+        page: PageRef::page_only(cow!("missing")),
+        ltype: LinkType::Page,
+    };
+
+    assert!(!report.is_empty(), "Content report is empty");
+    assert_eq!(
+        report.dead_links,
+        vec![dead_link.clone(), dead_link],
+        "Dead links don't match expected",
+    );
+    assert_eq!(
+        report.missing_includes,
+        vec![PageRef::page_only(cow!("missing"))],
+        "Missing includes don't match expected",
+    );
+    assert_eq!(
+        report.blocked_images,
+        vec![ImageSource::File2 {
+            page: cow!("page"),
+            file: cow!("file.png"),
+        }],
+        "Blocked images don't match expected",
+    );
+
+    // A healthy page has an empty report
+    let tokens = crate::tokenize("[[[scp-001]]]");
+    let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+    let output = HtmlRender.render(&tree, &page_info, &settings);
+    assert!(
+        output.content_report.is_empty(),
+        "Content report isn't empty"
+    );
+}
+
+#[test]
+#[cfg(feature = "syntax-highlight")]
+fn syntax_highlight() {
+    let page_info = PageInfo::dummy();
+    let mut settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let text = "[[code type=\"rust\"]]\nfn main() {}\n[[/code]]";
+
+    macro_rules! render {
+        () => {{
+            let tokens = crate::tokenize(text);
+            let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+            HtmlRender.render(&tree, &page_info, &settings).body
+        }};
+    }
+
+    settings.syntax_highlighting = false;
+    let output = render!();
+    assert!(
+        !output.contains("wj-hl-"),
+        "Code block was highlighted when disabled",
+    );
+
+    settings.syntax_highlighting = true;
+    let output = render!();
+    assert!(
+        output.contains("wj-hl-"),
+        "Code block was not highlighted when enabled",
+    );
+}
+
+#[test]
+fn source_spans() {
+    let page_info = PageInfo::dummy();
+    let mut settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let text = "apple **banana**\n\n[[div]]\ncherry\n[[/div]]";
+
+    macro_rules! render {
+        ($text:expr) => {{
+            let tokens = crate::tokenize($text);
+            let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+            let output = HtmlRender.render(&tree, &page_info, &settings).body;
+            (tree.element_spans, output)
+        }};
+    }
+
+    settings.source_spans = false;
+    let (spans, output) = render!(text);
+    assert!(spans.is_empty(), "Spans recorded when disabled");
+    assert!(
+        !output.contains("data-ftml-span"),
+        "Spans rendered when disabled",
+    );
+
+    settings.source_spans = true;
+    let (spans, output) = render!(text);
+    assert_eq!(spans, vec![0..16, 18..41], "Recorded spans do not match",);
+    assert_eq!(&text[0..16], "apple **banana**");
+    assert_eq!(&text[18..41], "[[div]]\ncherry\n[[/div]]");
+    assert!(
+        output.starts_with(
+            "<wj-body class=\"wj-body\"><wj-source-span data-ftml-span=\"0..16\"><p>",
+        ),
+        "First element not annotated with source span: {output}",
+    );
+    assert!(
+        output.contains("<wj-source-span data-ftml-span=\"18..41\"><div>"),
+        "Second element not annotated with source span: {output}",
+    );
+
+    // Collapsible sections are annotated as a whole
+    let text = "+- Apple\nbanana\n\ncherry\n\n+ Durian";
+    let (spans, output) = render!(text);
+    assert_eq!(spans.len(), 4, "Recorded spans do not match");
+    assert!(
+        output.starts_with(concat!(
+            "<wj-body class=\"wj-body\"><wj-source-span data-ftml-span=\"0..23\">",
+            "<details class=\"wj-collapsible-section\" open>",
+        )),
+        "Collapsible section not annotated with source span: {output}",
+    );
+    assert!(
+        output.contains("cherry</p></div></details></wj-source-span>"),
+        "Collapsible section does not contain its contents: {output}",
+    );
+}
+
+#[test]
+#[cfg(feature = "parallel")]
+fn parallel() {
+    use super::HtmlHooks;
+
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let mut text = str!(
+        "\
+[[toc]]
+
++ Apple
+
+Some text[[footnote]]A footnote with [[code]]code[[/code]][[/footnote]].
+
+[[code]]
+fn main() {}
+[[/code]]
+
+++- Banana
+
+[[math]]
+x^2
+[[/math]]
+
+* [[[some-page]]]
+* [https://example.com/ Example] ((bibcite cherry))
+
+[[footnote]]Another footnote[[/footnote]]
+
+[[bibliography]]
+: cherry : A [[code]]citation[[/code]]
+[[/bibliography]]
+
++++ Cherry
+[[footnoteblock]]
+
+[[f>toc]]"
+    );
+    crate::preprocess(&mut text, &page_info, &settings);
+
+    let tokens = crate::tokenize(&text);
+    let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+
+    let sequential = HtmlRender.render(&tree, &page_info, &settings);
+    let parallel =
+        HtmlRender.render_parallel(&tree, &page_info, &settings, HtmlHooks::default());
+
+    assert_eq!(
+        parallel.body, sequential.body,
+        "Parallel HTML output does not match sequential",
+    );
+    assert_eq!(
+        parallel.backlinks, sequential.backlinks,
+        "Parallel backlinks do not match sequential",
+    );
+}
+
+#[test]
+#[cfg(feature = "parallel")]
+fn parallel_footnote_backlinks() {
+    use super::context::HtmlContext;
+    use super::HtmlHooks;
+    use crate::render::Handle;
+    use std::num::NonZeroUsize;
+
+    let page_info = PageInfo::dummy();
+    let text = "\
+Apple[[footnote]]First[[/footnote]]
+
+[[footnoteblock]]
+
+Banana[[footnote]]Second[[/footnote]]
+
++- Section
+
+Cherry[[footnote]]Third[[/footnote]] and durian[[footnote]]Fourth[[/footnote]]
+
+[[div]]
+Eggplant[[footnote]]Fifth[[/footnote]]
+[[footnoteblock]]
+Fig[[footnote]]Sixth[[/footnote]]
+[[/div]]
+
+Grape[[footnote]]Seventh[[/footnote]]";
+
+    let tokens = crate::tokenize(text);
+
+    // With true IDs, the output is the same
+    let settings = WikitextSettings {
+        footnote_backlinks: true,
+        ..WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot)
+    };
+    let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+    let sequential = HtmlRender.render(&tree, &page_info, &settings);
+    let parallel =
+        HtmlRender.render_parallel(&tree, &page_info, &settings, HtmlHooks::default());
+
+    assert_eq!(
+        parallel.body, sequential.body,
+        "Parallel HTML output does not match sequential",
+    );
+    assert!(
+        parallel
+            .body
+            .contains("<a href=\"#wj-footnote-ref-7\" class=\"wj-footnote-backlink\""),
+        "Footnote block doesn't link to later reference:\n{}",
+        parallel.body,
+    );
+
+    // Otherwise, the randomly-generated IDs still match up
+    let settings = WikitextSettings {
+        footnote_backlinks: true,
+        ..WikitextSettings::from_mode(WikitextMode::ForumPost, Layout::Wikidot)
+    };
+    let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+    let parallel =
+        HtmlRender.render_parallel(&tree, &page_info, &settings, HtmlHooks::default());
+
+    let ids: Vec<&str> = parallel
+        .body
+        .split("<wj-footnote-ref-marker id=\"")
+        .skip(1)
+        .filter_map(|rest| rest.split('"').next())
+        .collect();
+    let backlinks: Vec<&str> = parallel
+        .body
+        .split("<a href=\"#")
+        .skip(1)
+        .filter_map(|rest| rest.split('"').next())
+        .collect();
+
+    assert_eq!(ids.len(), 7, "Wrong number of footnote references");
+    assert_eq!(
+        backlinks,
+        [ids.as_slice(), ids.as_slice()].concat(),
+        "Footnote blocks don't link back to each reference:\n{}",
+        parallel.body,
+    );
+
+    // Repeated references rendered elsewhere are numbered after those already there
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let tree = SyntaxTree::default();
+    let mut ctx = HtmlContext::new(
+        &page_info,
+        &Handle,
+        &settings,
+        (&tree.table_of_contents, &tree.heading_anchors),
+        &tree.footnotes,
+        &tree.bibliographies,
+        0,
+    );
+
+    let index = NonZeroUsize::new(2).unwrap();
+    ctx.set_footnote_refs(
+        vec![
+            vec![],
+            vec![str!("wj-footnote-ref-2"), str!("wj-footnote-ref-2-2")],
+        ],
+        &[str!("wj-footnote-ref-1"), str!("wj-footnote-ref-2")],
+    );
+    assert_eq!(ctx.add_footnote_ref(index), "wj-footnote-ref-2-3");
+    assert_eq!(
+        ctx.footnote_refs(index),
+        vec![
+            "wj-footnote-ref-2",
+            "wj-footnote-ref-2-2",
+            "wj-footnote-ref-2-3"
+        ],
+    );
+    assert_eq!(
+        ctx.take_footnote_refs(),
+        vec![
+            vec![],
+            vec![
+                "wj-footnote-ref-2",
+                "wj-footnote-ref-2-2",
+                "wj-footnote-ref-2-3"
+            ],
+        ],
+    );
+}
+
+#[test]
+fn modules() {
+    use super::{ModuleExpansion, ModuleRenderer, TrustedHtml};
+    use crate::tree::Module;
+
+    #[derive(Debug)]
+    struct TestModules;
+
+    impl ModuleRenderer for TestModules {
+        fn render_module(
+            &self,
+            module: &Module,
+            _page_info: &PageInfo,
+        ) -> Option<ModuleExpansion> {
+            match module {
+                Module::ListPages { .. } => Some(ModuleExpansion::Template(vec![
+                    hashmap! {
+                        str!("name") => str!("scp-001"),
+                        str!("title") => str!("SCP-001"),
+                    },
+                    hashmap! {
+                        str!("name") => str!("scp-002"),
+                        str!("title") => str!("The \"Living\" Room"),
+                    },
+                ])),
+                Module::Rate => Some(ModuleExpansion::Html(TrustedHtml::from_trusted(
+                    "<b>+5</b>",
+                ))),
+                _ => None,
+            }
+        }
+    }
+
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let mut text = str!(
+        "[[module ListPages category=\"scp\"]]\n* [[[%%name%%|%%title%%]]]\n[[/module]]\n[[module Rate]]\n[[module Backlinks]]\nA[[footnote]]B[[/footnote]]"
+    );
+    crate::preprocess(&mut text, &page_info, &settings);
+
+    let tokens = crate::tokenize(&text);
+    let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+    let output =
+        HtmlRender.render_with_modules(&tree, &page_info, &settings, &TestModules);
+
+    assert!(output.body.contains(">SCP-001</a>"));
+    assert!(output.body.contains(">The &quot;Living&quot; Room</a>"));
+    assert!(output.body.contains("<b>+5</b>"));
+    assert!(output.body.contains("TODO: module Backlinks"));
+    assert_eq!(output.body.matches("class=\"wj-footnote-list\"").count(), 1);
+    assert_eq!(output.backlinks.internal_links.len(), 2);
+}
+
+#[test]
+fn module_footnotes() {
+    use super::{HtmlHooks, ModuleExpansion, ModuleRenderer};
+    use crate::budget::WorkBudget;
+    use crate::tree::Module;
+
+    #[derive(Debug)]
+    struct TestModules;
+
+    impl ModuleRenderer for TestModules {
+        fn render_module(
+            &self,
+            module: &Module,
+            _page_info: &PageInfo,
+        ) -> Option<ModuleExpansion> {
+            match module {
+                Module::Rate => Some(ModuleExpansion::Wikitext(str!(
+                    "Inside[[footnote]]MODULE NOTE[[/footnote]]"
+                ))),
+                Module::Backlinks { .. } => {
+                    Some(ModuleExpansion::Wikitext(str!("[[span]]Unclosed")))
+                }
+                _ => None,
+            }
+        }
+    }
+
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let mut text = str!(
+        "A[[footnote]]PAGE NOTE[[/footnote]]\n\n[[module Rate]]\n\n[[module Backlinks]]"
+    );
+    crate::preprocess(&mut text, &page_info, &settings);
+
+    let tokens = crate::tokenize(&text);
+    let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+    let output =
+        HtmlRender.render_with_modules(&tree, &page_info, &settings, &TestModules);
+
+    // The module's footnote uses its own list, and the page's is unaffected
+    assert!(output.body.contains("MODULE NOTE"));
+    assert_eq!(output.body.matches("PAGE NOTE").count(), 2);
+    assert_eq!(output.body.matches("class=\"wj-footnote-list\"").count(), 1);
+
+    // Errors in the module's wikitext are reported
+    assert!(!output.content_report.module_errors.is_empty());
+
+    // Parsing the module's wikitext is charged to the budget
+    let budget = WorkBudget::new(10);
+    let hooks = HtmlHooks {
+        modules: Some(&TestModules),
+        budget: Some(&budget),
+        ..Default::default()
+    };
+    let output = HtmlRender.render_with_hooks(&tree, &page_info, &settings, hooks);
+    assert!(!output.body.contains("MODULE NOTE"));
+    assert!(budget.is_exhausted());
+}
+
+#[test]
+fn math_images() {
+    use super::{HtmlHooks, MathDisplay, MathImage, MathImageProvider};
+
+    #[derive(Debug)]
+    struct TestMathImages;
+
+    impl MathImageProvider for TestMathImages {
+        fn get_math_image(
+            &self,
+            latex_source: &str,
+            display: MathDisplay,
+        ) -> Option<MathImage> {
+            match display {
+                MathDisplay::Block => Some(MathImage {
+                    url: format!("https://math.example.com/{latex_source}.svg"),
+                    alt: None,
+                }),
+                MathDisplay::Inline => Some(MathImage {
+                    url: str!("https://math.example.com/inline.svg"),
+                    alt: Some(str!("inline math")),
+                }),
+            }
+        }
+    }
+
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let mut text = str!("[[math]]\nx^2\n[[/math]]\n\nInline [[$ y $]] math");
+    crate::preprocess(&mut text, &page_info, &settings);
+
+    let tokens = crate::tokenize(&text);
+    let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+    let hooks = HtmlHooks {
+        math_images: Some(&TestMathImages),
+        ..Default::default()
+    };
+    let output = HtmlRender.render_with_hooks(&tree, &page_info, &settings, hooks);
+
+    assert!(output.body.contains(
+        "<img class=\"wj-math-image\" src=\"https://math.example.com/x^2.svg\" alt=\"x^2\">",
+    ));
+    assert!(output.body.contains(
+        "<img class=\"wj-math-image\" src=\"https://math.example.com/inline.svg\" alt=\"inline math\">",
+    ));
+    assert!(!output.body.contains("wj-math-ml"));
+    assert!(output.body.contains("wj-equation-number"));
+}
+
+#[test]
+fn image_srcset() {
+    use super::{HtmlHooks, ImageResolution, ImageSrcsetProvider};
+    use crate::tree::ImageSource;
+
+    #[derive(Debug)]
+    struct TestImageSrcset;
+
+    impl ImageSrcsetProvider for TestImageSrcset {
+        fn get_image_srcset(
+            &self,
+            _source: &ImageSource,
+            url: &str,
+        ) -> Vec<ImageResolution> {
+            vec![
+                ImageResolution {
+                    url: format!("{url}?w=400"),
+                    descriptor: str!("400w"),
+                },
+                ImageResolution {
+                    url: format!("{url}?w=800"),
+                    descriptor: str!("800w"),
+                },
+            ]
+        }
+    }
+
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let text = r#"[[image https://example.com/scp.png lazy="true"]]"#;
+    let tokens = crate::tokenize(text);
+    let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+
+    // Default, no alternate resolutions
+    let output = HtmlRender.render(&tree, &page_info, &settings);
+    assert!(output.body.contains(
+        "<img class=\"wj-image\" src=\"https://example.com/scp.png\" loading=\"lazy\" crossorigin>",
+    ));
+
+    // With provider
+    let hooks = HtmlHooks {
+        image_srcset: Some(&TestImageSrcset),
+        ..Default::default()
+    };
+    let output = HtmlRender.render_with_hooks(&tree, &page_info, &settings, hooks);
+    assert!(output.body.contains(
+        "srcset=\"https://example.com/scp.png?w=400 400w, https://example.com/scp.png?w=800 800w\"",
+    ));
+}
+
+#[test]
+fn pagination() {
+    use super::{HtmlHooks, PageNeighbors, PaginationProvider};
+    use crate::data::PageRef;
+
+    #[derive(Debug)]
+    struct TestPagination;
+
+    impl PaginationProvider for TestPagination {
+        fn get_page_neighbors(&self, _info: &PageInfo) -> PageNeighbors<'static> {
+            PageNeighbors {
+                previous: Some(PageRef::page_only(cow!("scp-001"))),
+                next: Some(PageRef::page_only(cow!("scp-003"))),
+            }
+        }
+    }
+
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let text = r#"[[pagination class="series"]]"#;
+    let tokens = crate::tokenize(text);
+    let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+
+    // Default, no adjacent pages
+    let output = HtmlRender.render(&tree, &page_info, &settings);
+    assert!(!output.body.contains("wj-pagination"));
+
+    // With provider
+    let hooks = HtmlHooks {
+        pagination: Some(&TestPagination),
+        ..Default::default()
+    };
+    let output = HtmlRender.render_with_hooks(&tree, &page_info, &settings, hooks);
+    assert!(output.body.contains(concat!(
+        "<nav class=\"wj-pagination pager series\">",
+        "<a href=\"/scp-001\" class=\"wj-pagination-previous\" rel=\"prev\">« previous</a>",
+        "<a href=\"/scp-003\" class=\"wj-pagination-next\" rel=\"next\">next »</a>",
+        "</nav>",
+    )));
+}
+
+#[test]
+fn url_rewriter() {
+    use super::{HtmlHooks, UrlRewriter};
+    use std::borrow::Cow;
+
+    #[derive(Debug)]
+    struct TestUrlRewriter;
+
+    impl UrlRewriter for TestUrlRewriter {
+        fn rewrite_url<'a>(&self, url: &'a str) -> Cow<'a, str> {
+            if url.starts_with('/') {
+                Cow::Owned(format!("/en{url}"))
+            } else if let Some(path) = url.strip_prefix("https://files.example.com/") {
+                Cow::Owned(format!("https://cdn.example.com/{path}"))
+            } else {
+                Cow::Borrowed(url)
+            }
+        }
+    }
+
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let text = "[[[scp-001]]] [https://example.com/ Example] [[image https://files.example.com/scp.png]]";
+    let tokens = crate::tokenize(text);
+    let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+
+    // Default, unchanged
+    let output = HtmlRender.render(&tree, &page_info, &settings);
+    assert!(output.body.contains("href=\"/scp-001\""));
+    assert!(output
+        .body
+        .contains("src=\"https://files.example.com/scp.png\""));
+
+    // With rewriter
+    let hooks = HtmlHooks {
+        url_rewriter: Some(&TestUrlRewriter),
+        ..Default::default()
+    };
+    let output = HtmlRender.render_with_hooks(&tree, &page_info, &settings, hooks);
+    assert!(output.body.contains("href=\"/en/scp-001\""));
+    assert!(output.body.contains("href=\"https://example.com/\""));
+    assert!(output
+        .body
+        .contains("src=\"https://cdn.example.com/scp.png\""));
+
+    // With rewriter, in other render paths
+    let output = HtmlRender.render_amp(&tree, &page_info, &settings, hooks);
+    assert!(output.body.contains("href=\"/en/scp-001\""));
+
+    let output =
+        HtmlRender.render_partial(&tree.elements, &page_info, &settings, hooks, 0);
+    assert!(output.body.contains("href=\"/en/scp-001\""));
+
+    let output = HtmlRender.render_xhtml(&tree, &page_info, &settings, hooks);
+    assert!(output.body.contains("href=\"/en/scp-001\""));
+
+    // With rewriter, in parallel
+    #[cfg(feature = "parallel")]
+    {
+        let output = HtmlRender.render_parallel(&tree, &page_info, &settings, hooks);
+        assert!(output.body.contains("href=\"/en/scp-001\""));
+    }
+}
+
+#[test]
+fn page_variables() {
+    use super::{HtmlHooks, PageVariableProvider};
+
+    #[derive(Debug)]
+    struct TestPageVariables;
+
+    impl PageVariableProvider for TestPageVariables {
+        fn get_page_variable(&self, _info: &PageInfo, name: &str) -> Option<String> {
+            match name {
+                "created_by" => Some(str!("<Alice>")),
+                "title" => Some(str!("Overridden")),
+                _ => None,
+            }
+        }
+    }
+
+    let page_info = PageInfo::dummy();
+    let mut settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let text = "%%title%% by %%created_by%%";
+    let tokens = crate::tokenize(text);
+    let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+
+    // Default, only variables from the page info
+    let output = HtmlRender.render(&tree, &page_info, &settings);
+    assert!(output
+        .body
+        .contains("<p>A page for the age by %%created_by%%</p>"));
+
+    // With provider, page info still takes precedence
+    let hooks = HtmlHooks {
+        page_variables: Some(&TestPageVariables),
+        ..Default::default()
+    };
+    let output = HtmlRender.render_with_hooks(&tree, &page_info, &settings, hooks);
+    assert!(output
+        .body
+        .contains("<p>A page for the age by &lt;Alice&gt;</p>"));
+
+    // Disabled outside of pages
+    settings.enable_page_syntax = false;
+    let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+    let output = HtmlRender.render_with_hooks(&tree, &page_info, &settings, hooks);
+    assert!(output.body.contains("<p>%%title%% by %%created_by%%</p>"));
+}
+
+#[test]
+fn line_breaks() {
+    use crate::parsing::ParseErrorKind;
+    use crate::settings::LineBreakStyle;
+
+    let page_info = PageInfo::dummy();
+
+    macro_rules! check {
+        ($input:expr, $settings:expr, $expected:expr, $errors:expr $(,)?) => {{
+            let settings = $settings;
+            let tokens = crate::tokenize($input);
+            let (tree, errors) = crate::parse(&tokens, &page_info, &settings).into();
+            let error_kinds: Vec<_> = errors.iter().map(|error| error.kind()).collect();
+            let output = HtmlRender.render(&tree, &page_info, &settings);
+
+            assert_eq!(
+                output.body,
+                format!("<wj-body class=\"wj-body\"><p>{}</p></wj-body>", $expected),
+                "HTML output doesn't match expected",
+            );
+            assert_eq!(error_kinds, $errors, "Parse errors don't match expected");
+        }};
+    }
+
+    let wikidot = WikitextSettings {
+        max_line_breaks: 3,
+        line_break_style: LineBreakStyle::Margin,
+        ..WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot)
+    };
+    let wikijump = WikitextSettings {
+        layout: Layout::Wikijump,
+        ..wikidot.clone()
+    };
+
+    // Margins are only used in the Wikijump layout
+    check!("[[lines 2]]", wikidot.clone(), "<br><br>", vec![]);
+    check!(
+        "[[lines 2]]",
+        wikijump.clone(),
+        "<span class=\"wj-line-breaks\" style=\"display: block; height: 1lh;\"></span>",
+        vec![],
+    );
+
+    // Excessive values are reduced to the maximum
+    check!(
+        "[[lines 10000]]",
+        wikidot,
+        "<br><br><br>",
+        vec![ParseErrorKind::TooManyLineBreaks],
+    );
+    check!(
+        "[[lines 10000]]",
+        wikijump,
+        "<span class=\"wj-line-breaks\" style=\"display: block; height: 2lh;\"></span>",
+        vec![ParseErrorKind::TooManyLineBreaks],
+    );
+}
+
+#[test]
+fn render_document() {
+    use super::HtmlHooks;
+
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let tree = SyntaxTree {
+        elements: vec![
+            Element::Style(cow!(".apple { color: red; }")),
+            text!("Apple"),
+        ],
+        ..SyntaxTree::default()
+    };
+
+    let document =
+        HtmlRender.render_document(&tree, &page_info, &settings, HtmlHooks::default());
+
+    assert!(document.starts_with("<!DOCTYPE html>\n<html lang=\"default\">\n<head>\n"));
+    assert!(document.contains("<title>A page for the age</title>"));
+    assert!(document.contains("<meta name=\"generator\" content=\"ftml "));
+    assert!(!document.contains("http-equiv"));
+    assert!(document.contains("<style>\n.apple{color:red}\n</style>\n</head>"));
+    assert!(document.ends_with(
+        "<body>\n<wj-body class=\"wj-body\">Apple</wj-body>\n</body>\n</html>\n",
+    ));
+}
+
+#[test]
+fn css_processing() {
+    use super::process_css;
+    use crate::settings::CssSettings;
+
+    let mut settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    settings.minify_css = true;
+
+    macro_rules! check {
+        ($css:expr, $expected:expr $(,)?) => {{
+            let actual = process_css($css, &settings);
+            assert_eq!(
+                actual.as_deref(),
+                $expected,
+                "Processed CSS doesn't match expected",
+            );
+        }};
+    }
+
+    let input = r#"@import url("https://example.com/style.css");
+.fruit, #apple > span { color: red; position: fixed }
+@media (max-width: 600px) { .banana { position: fixed !important; top: 0 } }"#;
+
+    // No changes by default
+    check!(
+        input,
+        Some(concat!(
+            r#"@import "https://example.com/style.css";"#,
+            ".fruit,#apple>span{color:red;position:fixed}",
+            "@media (max-width:600px){.banana{top:0;position:fixed!important}}",
+        )),
+    );
+
+    settings.css = CssSettings {
+        scope_class: Some(str!("page-scp-001")),
+        strip_imports: true,
+        forbid_fixed_position: true,
+    };
+
+    check!(
+        input,
+        Some(concat!(
+            ".page-scp-001 .fruit,.page-scp-001 #apple>span{color:red}",
+            "@media (max-width:600px){.page-scp-001 .banana{top:0}}",
+        )),
+    );
+
+    // Invalid scope classes output nothing, rather than unscoped CSS
+    settings.css.scope_class = Some(str!("bad class{"));
+    check!(input, None);
+
+    // The scope class is also added to the page body
+    settings.css.scope_class = Some(str!("page-scp-001"));
+    let page_info = PageInfo::dummy();
+    let tokens = crate::tokenize("Hello");
+    let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+    let output = HtmlRender.render(&tree, &page_info, &settings);
+    assert!(output
+        .body
+        .starts_with("<wj-body class=\"wj-body page-scp-001\">"));
+}
+
+#[test]
+fn render_partial() {
+    use super::HtmlHooks;
+
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let tokens = crate::tokenize("**Apple** [[[banana]]]");
+    let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+
+    // Only the paragraph's contents, without the tree or body wrapper
+    let elements = match &tree.elements[0] {
+        Element::Container(container) => container.elements(),
+        element => panic!("Unexpected element: {element:?}"),
+    };
+
+    let output = HtmlRender.render_partial(
+        elements,
+        &page_info,
+        &settings,
+        HtmlHooks::default(),
+        0,
+    );
+    assert!(
+        !output.body.contains("wj-body"),
+        "Partial render contains body wrapper",
+    );
+    assert!(
+        output.body.starts_with("<strong>Apple</strong> <a "),
+        "Partial render doesn't match expected: {}",
+        output.body,
+    );
+    assert_eq!(
+        output.backlinks.internal_links.len(),
+        1,
+        "Partial render doesn't record backlinks",
+    );
+}
+
+#[test]
+fn render_amp() {
+    use super::HtmlHooks;
+
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let input = concat!(
+        "[[image https://example.com/apple.png style=\"color: red !important\"]]\n",
+        "[[math]]\nx^2\n[[/math]]\n",
+        "[[module CSS]]\n.banana { color: yellow; }\n[[/module]]\n",
+        "Cherry[[footnote]]Durian[[/footnote]]",
+    );
+    let tokens = crate::tokenize(input);
+    let (mut tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+
+    // Embeds which use scripts can't be written in wikitext yet
+    tree.elements.push(Element::Embed(Embed::GithubGist {
+        username: cow!("user"),
+        hash: cow!("abc"),
+    }));
+
+    let output =
+        HtmlRender.render_amp(&tree, &page_info, &settings, HtmlHooks::default());
+    let body = &output.body;
+
+    for forbidden in [
+        "<img",
+        "<iframe",
+        "<script",
+        "<style",
+        "<wj-",
+        "<math",
+        "!important",
+    ] {
+        assert!(
+            !body.contains(forbidden),
+            "AMP render contains forbidden markup '{forbidden}': {body}",
+        );
+    }
+
+    for expected in [
+        "<div class=\"wj-body",
+        "<amp-img layout=\"flex-item\" class=\"wj-image\"",
+        "</amp-img>",
+        "<amp-mathml class=\"wj-math-ml\" layout=\"container\" data-formula=\"\\[x^2\\]\"",
+        "<a href=\"https://gist.github.com/user/abc\">",
+    ] {
+        assert!(
+            body.contains(expected),
+            "AMP render doesn't contain '{expected}': {body}",
+        );
+    }
+}
+
+#[test]
+fn email_obfuscation() {
+    use super::{EmailRewriter, HtmlHooks, TrustedHtml};
+    use crate::settings::EmailObfuscation;
+
+    let page_info = PageInfo::dummy();
+    let tree = SyntaxTree {
+        elements: vec![Element::Email(cow!("a@b.cd"))],
+        ..SyntaxTree::default()
+    };
+
+    macro_rules! check {
+        ($obfuscation:expr, $expected:expr $(,)?) => {{
+            let settings = WikitextSettings {
+                email_obfuscation: $obfuscation,
+                ..WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot)
+            };
+            let output = HtmlRender.render(&tree, &page_info, &settings);
+
+            assert_eq!(
+                output.body,
+                format!("<wj-body class=\"wj-body\">{}</wj-body>", $expected),
+                "HTML output doesn't match expected",
+            );
+        }};
+    }
+
+    check!(
+        EmailObfuscation::None,
+        "<span class=\"wj-email\">a@b.cd</span>",
+    );
+    check!(
+        EmailObfuscation::Entities,
+        "<span class=\"wj-email\">&#97;&#64;&#98;&#46;&#99;&#100;</span>",
+    );
+    check!(
+        EmailObfuscation::Reversed,
+        "<span class=\"wj-email\" style=\"unicode-bidi: bidi-override; direction: rtl;\">dc.b@a</span>",
+    );
+    check!(
+        EmailObfuscation::Rewriter,
+        "<span class=\"wj-email\">a@b.cd</span>",
+    );
+
+    // With a rewriter hook
+    #[derive(Debug)]
+    struct TestEmailRewriter;
+
+    impl EmailRewriter for TestEmailRewriter {
+        fn rewrite_email(&self, email: &str) -> TrustedHtml {
+            let mut html = TrustedHtml::from_trusted("<a href=\"/contact?to=");
+            html.push_escaped(&email.replace('@', "+"));
+            html.push_trusted("\">Contact</a>");
+            html
+        }
+    }
+
+    let settings = WikitextSettings {
+        email_obfuscation: EmailObfuscation::Rewriter,
+        ..WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot)
+    };
+    let hooks = HtmlHooks {
+        email_rewriter: Some(&TestEmailRewriter),
+        ..Default::default()
+    };
+    let output = HtmlRender.render_with_hooks(&tree, &page_info, &settings, hooks);
+    assert_eq!(
+        output.body,
+        "<wj-body class=\"wj-body\"><span class=\"wj-email\"><a href=\"/contact?to=a+b.cd\">Contact</a></span></wj-body>",
+    );
+}
+
+#[test]
+fn external_links() {
+    let page_info = PageInfo::dummy();
+
+    macro_rules! check {
+        ($mode:expr, $input:expr, $expected:expr $(,)?) => {{
+            let settings = WikitextSettings::from_mode($mode, Layout::Wikidot);
+            let tokens = crate::tokenize($input);
+            let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+            let output = HtmlRender.render(&tree, &page_info, &settings);
+
+            assert!(
+                output.body.contains($expected),
+                "HTML output doesn't contain expected link:\n{}",
+                output.body,
+            );
+        }};
+    }
+
+    check!(
+        WikitextMode::Page,
+        "[https://example.com/ Example]",
+        "<a href=\"https://example.com/\" class=\"wj-link wj-link-external\" data-link-type=\"direct\">",
+    );
+    check!(
+        WikitextMode::ForumPost,
+        "[https://example.com/ Example]",
+        "<a href=\"https://example.com/\" class=\"wj-link wj-link-external\" rel=\"nofollow noopener\" data-link-type=\"direct\">",
+    );
+    check!(
+        WikitextMode::ForumPost,
+        "[/some-page Example]",
+        "<a href=\"/some-page\" class=\"wj-link wj-link-internal\" data-link-type=\"direct\">",
+    );
+    check!(
+        WikitextMode::ForumPost,
+        "[#anchor Example]",
+        "<a href=\"#anchor\" class=\"wj-link wj-link-anchor\" data-link-type=\"anchor\">",
+    );
+
+    // Links to this site are not external, even if absolute
+    check!(
+        WikitextMode::ForumPost,
+        "[https://sandbox.wikijump.com/some-page Example]",
+        "<a href=\"https://sandbox.wikijump.com/some-page\" class=\"wj-link wj-link-internal\" data-link-type=\"direct\">",
+    );
+
+    // External link marker
+    let mut settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    settings.external_links.marker = Some(str!("wj-icon-external"));
+
+    let tokens = crate::tokenize("[https://example.com/ Example] [/some-page Page]");
+    let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+    let output = HtmlRender.render(&tree, &page_info, &settings);
+
+    assert!(
+        output.body.contains(
+            "data-link-type=\"direct\">Example<span class=\"wj-icon-external\" aria-label=\"external link\"></span></a>",
+        ),
+        "HTML output doesn't contain external link marker:\n{}",
+        output.body,
+    );
+    assert_eq!(
+        output.body.matches("wj-icon-external").count(),
+        1,
+        "External link marker added to internal link:\n{}",
+        output.body,
+    );
+}
+
+#[test]
+fn footnote_backlinks() {
+    use super::context::HtmlContext;
+    use crate::render::Handle;
+    use std::num::NonZeroUsize;
+
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings {
+        footnote_backlinks: true,
+        ..WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikijump)
+    };
+
+    macro_rules! check {
+        ($input:expr, $expected:expr $(,)?) => {{
+            let tokens = crate::tokenize($input);
+            let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+            let output = HtmlRender.render(&tree, &page_info, &settings);
+
+            for expected in $expected {
+                assert!(
+                    output.body.contains(expected),
+                    "HTML output doesn't contain {expected:?}:\n{}",
+                    output.body,
+                );
+            }
+        }};
+    }
+
+    check!(
+        "A[[footnote]]B[[/footnote]] C[[footnote]]D[[/footnote]]",
+        [
+            "id=\"wj-footnote-ref-1\"",
+            "id=\"wj-footnote-ref-2\"",
+            "<a href=\"#wj-footnote-ref-1\" class=\"wj-footnote-backlink\" aria-label=\"Back to reference\">↩</a>",
+            "<a href=\"#wj-footnote-ref-2\" class=\"wj-footnote-backlink\" aria-label=\"Back to reference\">↩</a>",
+        ],
+    );
+
+    // Footnote block before its references
+    check!(
+        "[[footnoteblock]]\n\nA[[footnote]]B[[/footnote]]",
+        [
+            "id=\"wj-footnote-ref-1\"",
+            "<a href=\"#wj-footnote-ref-1\" class=\"wj-footnote-backlink\"",
+        ],
+    );
+
+    // Multiple references to the same footnote
+    let tree = SyntaxTree::default();
+    let mut ctx = HtmlContext::new(
+        &page_info,
+        &Handle,
+        &settings,
+        (&tree.table_of_contents, &tree.heading_anchors),
+        &tree.footnotes,
+        &tree.bibliographies,
+        0,
+    );
+
+    let index = NonZeroUsize::new(3).unwrap();
+    assert_eq!(ctx.footnote_refs(index), vec!["wj-footnote-ref-3"]);
+    assert_eq!(ctx.add_footnote_ref(index), "wj-footnote-ref-3");
+    assert_eq!(ctx.add_footnote_ref(index), "wj-footnote-ref-3-2");
+    assert_eq!(
+        ctx.footnote_refs(index),
+        vec!["wj-footnote-ref-3", "wj-footnote-ref-3-2"],
+    );
+
+    // Without true IDs, since several renders may be on the same page
+    let settings = WikitextSettings {
+        footnote_backlinks: true,
+        ..WikitextSettings::from_mode(WikitextMode::ForumPost, Layout::Wikijump)
+    };
+    assert!(!settings.use_true_ids);
+
+    for input in [
+        "A[[footnote]]B[[/footnote]]\n\n[[footnoteblock]]",
+        "[[footnoteblock]]\n\nA[[footnote]]B[[/footnote]]",
+    ] {
+        let tokens = crate::tokenize(input);
+        let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+        let output = HtmlRender.render(&tree, &page_info, &settings);
+
+        assert!(
+            !output.body.contains("wj-footnote-ref-1"),
+            "HTML output contains true footnote reference ID:\n{}",
+            output.body,
+        );
+
+        // The backlink goes to the reference, wherever the block is
+        let id = output
+            .body
+            .split("<wj-footnote-ref-marker id=\"")
+            .nth(1)
+            .and_then(|rest| rest.split('"').next())
+            .expect("No footnote reference ID");
+
+        assert!(
+            id.starts_with("wj-id-"),
+            "Unexpected footnote reference ID {id}"
+        );
+        assert!(
+            output
+                .body
+                .contains(&format!("<a href=\"#{id}\" class=\"wj-footnote-backlink\"")),
+            "HTML output doesn't contain backlink to {id}:\n{}",
+            output.body,
+        );
+    }
+}
+
+#[test]
+fn embed_providers() {
+    use crate::settings::{EmbedProviderSettings, EmbedSettings};
+
+    let page_info = PageInfo::dummy();
+    let input = "[[embed youtube video=\"dQw4w9WgXcQ\"]]";
+
+    macro_rules! check {
+        ($settings:expr, $expected:expr $(,)?) => {{
+            let settings = $settings;
+            let tokens = crate::tokenize(input);
+            let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+            let output = HtmlRender.render(&tree, &page_info, &settings);
+
+            assert!(
+                output.body.contains($expected),
+                "HTML output doesn't contain expected embed:\n{}",
+                output.body,
+            );
+        }};
+    }
+
+    let page_settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+
+    // Custom attributes
+    check!(
+        WikitextSettings {
+            embed: EmbedSettings {
+                providers: vec![EmbedProviderSettings {
+                    name: str!("youtube"),
+                    sandbox: Some(str!("allow-scripts")),
+                    allow: None,
+                }],
+            },
+            ..page_settings.clone()
+        },
+        "<iframe src=\"https://www.youtube.com/embed/dQw4w9WgXcQ\" frameborder=\"0\" loading=\"lazy\" sandbox=\"allow-scripts\" allowfullscreen>",
+    );
+
+    // Not permitted when parsing
+    check!(
+        WikitextSettings {
+            embed: EmbedSettings::none(),
+            ..page_settings.clone()
+        },
+        "<p>[[embed youtube video=&quot;dQw4w9WgXcQ&quot;]]</p>",
+    );
+
+    // Not permitted when rendering
+    let tokens = crate::tokenize(input);
+    let (tree, _errors) = crate::parse(&tokens, &page_info, &page_settings).into();
+    let settings = WikitextSettings {
+        embed: EmbedSettings::none(),
+        ..page_settings.clone()
+    };
+    let output = HtmlRender.render(&tree, &page_info, &settings);
+    assert_eq!(
+        output.body,
+        "<wj-body class=\"wj-body\"><a href=\"https://youtu.be/dQw4w9WgXcQ\">https://youtu.be/dQw4w9WgXcQ</a></wj-body>",
+    );
+
+    // Invalid IDs, as could come from a deserialized tree
+    let tree = SyntaxTree {
+        elements: vec![Element::Embed(Embed::Youtube {
+            video_id: cow!("../../evil"),
+        })],
+        ..SyntaxTree::default()
+    };
+    let output = HtmlRender.render(&tree, &page_info, &page_settings);
+    assert_eq!(output.body, "<wj-body class=\"wj-body\"></wj-body>");
+}
+
+#[test]
+fn iframe_settings() {
+    use crate::settings::IframeSettings;
+
+    let page_info = PageInfo::dummy();
+    let input = "[[iframe https://example.com/ width=\"100%\" height=\"300\"]]";
+
+    macro_rules! check {
+        ($iframe:expr, $expected:expr $(,)?) => {{
+            let settings = WikitextSettings {
+                iframe: $iframe,
+                ..WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot)
+            };
+            let tokens = crate::tokenize(input);
+            let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+            let output = HtmlRender.render(&tree, &page_info, &settings);
+
+            assert_eq!(
+                output.body,
+                concat!("<wj-body class=\"wj-body\">", $expected, "</wj-body>"),
+                "HTML output doesn't match expected iframe",
+            );
+        }};
+    }
+
+    // No aspect ratio, since the width isn't in pixels
+    check!(
+        IframeSettings::default(),
+        "<iframe src=\"https://example.com/\" crossorigin loading=\"lazy\" height=\"300\" width=\"100%\"></iframe>",
+    );
+    check!(
+        IframeSettings {
+            lazy_load: false,
+            sandbox: Some(str!("allow-scripts")),
+        },
+        "<iframe src=\"https://example.com/\" crossorigin sandbox=\"allow-scripts\" height=\"300\" width=\"100%\"></iframe>",
+    );
+}
+
+#[test]
+fn html_block_size() {
+    use crate::parsing::ParseErrorKind;
+
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let render = |input: &str| {
+        let tokens = crate::tokenize(input);
+        let (tree, errors) = crate::parse(&tokens, &page_info, &settings).into();
+        let output = HtmlRender.render(&tree, &page_info, &settings);
+        (output.body, errors)
+    };
+
+    let (body, errors) =
+        render("[[html width=\"100%\" height=\"auto\"]]\n<p>Hello</p>\n[[/html]]");
+    assert!(errors.is_empty(), "Errors produced: {errors:#?}");
+    assert_eq!(
+        body,
+        "<wj-body class=\"wj-body\"><iframe src=\"https://example.com/\" crossorigin data-width=\"100%\" data-height=\"auto\"></iframe></wj-body>",
+        "HTML block size not rendered as data attributes",
+    );
+
+    // Invalid sizes are rejected when parsing
+    let (body, errors) =
+        render("[[html width=\"abc\" height=\"-1\"]]\n<p>Hello</p>\n[[/html]]");
+    assert!(
+        !body.contains("<iframe"),
+        "HTML block with invalid size rendered"
+    );
+    assert_eq!(errors[0].kind(), ParseErrorKind::BlockMalformedArguments);
+}
+
+#[test]
+fn minify_html() {
+    let page_info = PageInfo::dummy();
+    let input = "[[div]]\n  Apple   **banana**\tcherry  \n[[/div]]\n\n[[code]]\nfn  main() {\n    ()\n}\n[[/code]]\n\n@@Durian   egg@@ [[span style=\"white-space: pre\"]]Fig   grape[[/span]] [[span style=\"color: red\"]]Kiwi   lemon[[/span]]";
+
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let tokens = crate::tokenize(input);
+    let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+
+    let output = HtmlRender.render(&tree, &page_info, &settings);
+    let regular = output.body;
+
+    let settings = WikitextSettings {
+        minify_html: true,
+        ..settings.clone()
+    };
+    let output = HtmlRender.render(&tree, &page_info, &settings);
+    let minified = output.body;
+
+    assert!(regular.contains("<p>  Apple   <strong>banana</strong>\tcherry  </p>"));
+    assert!(minified.contains("<div><p>Apple <strong>banana</strong> cherry</p></div>"));
+    assert!(minified.contains("<pre><code>fn  main() {\n    ()\n}</code></pre>"));
+    assert!(minified.contains("<span class=\"wj-raw\">Durian   egg</span>"));
+    assert!(minified.contains("<span style=\"white-space: pre\">Fig   grape</span>"));
+    assert!(minified.contains("<span style=\"color: red\">Kiwi lemon</span>"));
+    assert!(minified.len() < regular.len());
+}
+
+#[test]
+fn class_prefix() {
+    let page_info = PageInfo::dummy();
+    let input = "[[span class=\"wj-user\"]]A[[/span]] [https://example.com/ B]\n\n[[collapsible class=\"wj-apple banana\"]]\nC\n[[/collapsible]]";
+
+    let settings = WikitextSettings {
+        class_prefix: Some(str!("ftml-")),
+        ..WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot)
+    };
+    let tokens = crate::tokenize(input);
+    let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+    let output = HtmlRender.render(&tree, &page_info, &settings);
+
+    for expected in [
+        "<wj-body class=\"ftml-body\">",
+        "<span class=\"wj-user\">A</span>",
+        "class=\"ftml-link ftml-link-external\"",
+        "<details class=\"ftml-collapsible wj-apple banana\"",
+        "<span class=\"ftml-collapsible-show-text\">",
+    ] {
+        assert!(
+            output.body.contains(expected),
+            "HTML output doesn't contain {expected:?}:\n{}",
+            output.body,
+        );
+    }
+
+    assert!(
+        !output.body.contains("wj-link"),
+        "HTML output has unprefixed classes:\n{}",
+        output.body,
+    );
+}
@@ -0,0 +1,104 @@
+/*
+ * render/html/trusted.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Marker type for HTML which is inserted into the output as-is.
+
+use super::escape::escape;
+use std::fmt::{self, Display};
+
+/// A fragment of HTML which is inserted into rendered output without escaping.
+///
+/// This is the only way for the host, such as a [`ModuleRenderer`], to add
+/// its own markup to the output. Since the contents are not checked, a value
+/// must be explicitly created using [`TrustedHtml::from_trusted()`], which
+/// makes it clear at the call site that the string is known to be safe.
+/// Data from users should instead be added using [`TrustedHtml::escaped()`],
+/// or [`push_escaped()`](TrustedHtml::push_escaped) when building a fragment.
+///
+/// [`ModuleRenderer`]: super::ModuleRenderer
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq)]
+pub struct TrustedHtml(String);
+
+impl TrustedHtml {
+    #[inline]
+    pub fn new() -> Self {
+        TrustedHtml::default()
+    }
+
+    /// Marks the given HTML as trusted.
+    ///
+    /// The caller is responsible for ensuring this is safe to insert into
+    /// the page. It must never contain unescaped data provided by users.
+    #[inline]
+    pub fn from_trusted<S: Into<String>>(html: S) -> Self {
+        TrustedHtml(html.into())
+    }
+
+    /// Creates a fragment containing the given text, escaped as HTML.
+    pub fn escaped(text: &str) -> Self {
+        let mut html = TrustedHtml::new();
+        html.push_escaped(text);
+        html
+    }
+
+    /// Appends trusted HTML to this fragment.
+    ///
+    /// The same requirements as [`from_trusted()`](TrustedHtml::from_trusted) apply.
+    #[inline]
+    pub fn push_trusted(&mut self, html: &str) {
+        self.0.push_str(html);
+    }
+
+    /// Appends text to this fragment, escaped as HTML.
+    #[inline]
+    pub fn push_escaped(&mut self, text: &str) {
+        escape(&mut self.0, text);
+    }
+
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl Display for TrustedHtml {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[test]
+fn trusted_html() {
+    let mut html = TrustedHtml::from_trusted("<b>");
+    html.push_escaped("<script>alert('apple')</script>");
+    html.push_trusted("</b>");
+
+    assert_eq!(
+        html.as_str(),
+        "<b>&lt;script&gt;alert(&#39;apple&#39;)&lt;/script&gt;</b>",
+    );
+    assert_eq!(TrustedHtml::escaped("a & b").into_inner(), "a &amp; b");
+}
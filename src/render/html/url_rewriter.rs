@@ -0,0 +1,46 @@
+/*
+ * render/html/url_rewriter.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Extension point for rewriting emitted URLs.
+
+use crate::render::Handle;
+use std::borrow::Cow;
+use std::fmt::Debug;
+
+/// A trait which library users can implement to rewrite URLs in the output.
+///
+/// This is called for the value of every `href` and `src` attribute emitted,
+/// including those provided by the user, such as in `[[a]]` or `[[iframe]]`.
+/// It allows adjusting relative links, for instance to add a site prefix,
+/// a language subpath, or to serve files from a CDN host, without needing
+/// to post-process the HTML.
+///
+/// By default, URLs are left unchanged.
+pub trait UrlRewriter: Debug + Send + Sync {
+    /// Returns the URL to emit in place of the given one.
+    fn rewrite_url<'a>(&self, url: &'a str) -> Cow<'a, str>;
+}
+
+impl UrlRewriter for Handle {
+    #[inline]
+    fn rewrite_url<'a>(&self, url: &'a str) -> Cow<'a, str> {
+        Cow::Borrowed(url)
+    }
+}
@@ -0,0 +1,241 @@
+/*
+ * render/index/mod.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! A renderer producing structured output for search indexing.
+//!
+//! Rather than a single string, this collects the parts of a page which a
+//! search engine would index separately, such as its headings and links.
+//! The body text is the same as produced by [`TextRender`].
+
+use super::prelude::*;
+use super::text::TextRender;
+use crate::tree::visit::{walk_element, Visitor};
+use crate::tree::{LinkLocation, LinkType};
+
+#[derive(Debug)]
+pub struct IndexRender;
+
+impl Render for IndexRender {
+    type Output = IndexOutput;
+
+    fn render(
+        &self,
+        tree: &SyntaxTree,
+        page_info: &PageInfo,
+        settings: &WikitextSettings,
+    ) -> IndexOutput {
+        enter_span!(
+            "render",
+            format = "index",
+            site = %page_info.site,
+            page = %page_info.page,
+        );
+
+        info!(
+            "Rendering search index (site {}, page {}, category {})",
+            page_info.site.as_ref(),
+            page_info.page.as_ref(),
+            match &page_info.category {
+                Some(category) => category.as_ref(),
+                None => "_default",
+            },
+        );
+
+        let body = TextRender.render(tree, page_info, settings);
+        let headings = tree
+            .headings()
+            .into_iter()
+            .map(|heading| IndexHeading {
+                level: heading.level.value(),
+                text: TextRender.render_partial(
+                    &heading.elements,
+                    page_info,
+                    settings,
+                    0,
+                ),
+            })
+            .collect();
+
+        // The table of contents only repeats the headings, so it is skipped.
+        let mut collector = Collector::default();
+        collector.visit_elements(&tree.elements);
+        for footnote in &tree.footnotes {
+            collector.visit_elements(footnote);
+        }
+
+        IndexOutput {
+            body,
+            headings,
+            links: collector.links,
+            image_alts: collector.image_alts,
+            metadata: IndexMetadata {
+                title: page_info.title.to_string(),
+                alt_title: page_info.alt_title.as_ref().map(|title| title.to_string()),
+                category: page_info
+                    .category
+                    .as_ref()
+                    .map(|category| category.to_string()),
+                tags: page_info.tags.iter().map(|tag| tag.to_string()).collect(),
+                language: page_info.language.to_string(),
+            },
+        }
+    }
+}
+
+/// The output of [`IndexRender`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct IndexOutput {
+    /// The text of the page, as produced by [`TextRender`].
+    pub body: String,
+
+    /// All the headings on the page, in order.
+    pub headings: Vec<IndexHeading>,
+
+    /// The targets of all links on the page, in order.
+    ///
+    /// Links to anchors on the same page are not included.
+    pub links: Vec<IndexLink>,
+
+    /// The alt text of all images on the page which have it, in order.
+    pub image_alts: Vec<String>,
+
+    /// Information about the page itself, rather than its contents.
+    pub metadata: IndexMetadata,
+}
+
+/// A heading, as listed in [`IndexOutput`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct IndexHeading {
+    /// The depth of this heading, from 1 to 6.
+    pub level: u8,
+
+    /// The text of this heading.
+    pub text: String,
+}
+
+/// A link target, as listed in [`IndexOutput`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct IndexLink {
+    /// The page or URL being linked to.
+    ///
+    /// Pages are written as `site:category:page` or `page`,
+    /// depending on what was specified in the link.
+    pub target: String,
+
+    /// What kind of link syntax produced this entry.
+    #[serde(rename = "type")]
+    pub ltype: LinkType,
+}
+
+/// Page metadata, as listed in [`IndexOutput`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct IndexMetadata {
+    pub title: String,
+    pub alt_title: Option<String>,
+    pub category: Option<String>,
+    pub tags: Vec<String>,
+    pub language: String,
+}
+
+#[derive(Debug, Default)]
+struct Collector {
+    links: Vec<IndexLink>,
+    image_alts: Vec<String>,
+}
+
+impl<'t> Visitor<'t> for Collector {
+    fn visit_element(&mut self, element: &Element<'t>) {
+        match element {
+            Element::Link { ltype, link, .. } if *ltype != LinkType::Anchor => {
+                let target = match link {
+                    LinkLocation::Page(page_ref) => page_ref.to_string(),
+                    LinkLocation::Url(url) => url.to_string(),
+                };
+
+                self.links.push(IndexLink {
+                    target,
+                    ltype: *ltype,
+                });
+            }
+            Element::Image { attributes, .. } => {
+                if let Some(alt) = attributes.get().get("alt") {
+                    if !alt.trim().is_empty() {
+                        self.image_alts.push(alt.to_string());
+                    }
+                }
+            }
+            _ => (),
+        }
+
+        walk_element(self, element);
+    }
+}
+
+#[test]
+fn index() {
+    use crate::layout::Layout;
+
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let text = "+ Apples\n\nApples are [[[fruit|a fruit]]].[[footnote]]See [https://example.com/ here].[[/footnote]]\n\n[[image /apple.png alt=\"An apple\"]]\n\n++ Growing\n\nSee [#top above] and [[[gardening]]].";
+
+    let tokens = crate::tokenize(text);
+    let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+    let output = IndexRender.render(&tree, &page_info, &settings);
+
+    assert!(output.body.starts_with("Apples\n\nApples are a fruit.[1]"));
+    assert_eq!(
+        output.headings,
+        vec![
+            IndexHeading {
+                level: 1,
+                text: str!("Apples"),
+            },
+            IndexHeading {
+                level: 2,
+                text: str!("Growing"),
+            },
+        ],
+    );
+    assert_eq!(
+        output.links,
+        vec![
+            IndexLink {
+                target: str!("fruit"),
+                ltype: LinkType::Page,
+            },
+            IndexLink {
+                target: str!("gardening"),
+                ltype: LinkType::Page,
+            },
+            IndexLink {
+                target: str!("https://example.com/"),
+                ltype: LinkType::Direct,
+            },
+        ],
+    );
+    assert_eq!(output.image_alts, vec![str!("An apple")]);
+    assert_eq!(output.metadata.title, page_info.title);
+    assert_eq!(output.metadata.tags, vec![str!("tale"), str!("_cc")]);
+}
@@ -28,18 +28,29 @@ mod prelude {
 }
 
 pub mod debug;
+pub mod index;
 pub mod null;
 pub mod text;
 
+#[cfg(feature = "html")]
+pub mod epub;
+
 #[cfg(feature = "html")]
 pub mod html;
 
+mod context;
+mod error;
 mod handle;
 
-use self::handle::Handle;
+pub use self::context::RenderContext;
+pub use self::error::{validate_tree, RenderError};
+
+pub(crate) use self::handle::Handle;
+use crate::budget::{render_cost, BudgetExceeded, WorkBudget};
 use crate::data::PageInfo;
 use crate::settings::WikitextSettings;
-use crate::tree::SyntaxTree;
+use crate::tree::visit::{walk_element, Visitor};
+use crate::tree::{Element, SyntaxTree};
 
 /// Abstract trait for any ftml renderer.
 ///
@@ -66,4 +77,57 @@ pub trait Render {
         page_info: &PageInfo,
         settings: &WikitextSettings,
     ) -> Self::Output;
+
+    /// Render an abstract syntax tree, spending from the given work budget.
+    ///
+    /// By default this spends one unit per element in the tree prior to rendering.
+    /// See [`WorkBudget`] for more information.
+    fn render_with_budget(
+        &self,
+        tree: &SyntaxTree,
+        page_info: &PageInfo,
+        settings: &WikitextSettings,
+        budget: &WorkBudget,
+    ) -> Result<Self::Output, BudgetExceeded> {
+        budget.spend(render_cost(tree))?;
+        Ok(self.render(tree, page_info, settings))
+    }
+
+    /// Render an abstract syntax tree, failing if it is inconsistent.
+    ///
+    /// Renderers skip over any parts of a tree which cannot be rendered,
+    /// such as footnote references without corresponding footnotes, which
+    /// can occur in trees which are deserialized rather than parsed.
+    /// This instead checks the tree beforehand with [`validate_tree()`],
+    /// returning an error if any such problems are found.
+    fn try_render(
+        &self,
+        tree: &SyntaxTree,
+        page_info: &PageInfo,
+        settings: &WikitextSettings,
+    ) -> Result<Self::Output, RenderError> {
+        validate_tree(tree)?;
+        Ok(self.render(tree, page_info, settings))
+    }
+}
+
+/// Counts the number of footnote references within these elements.
+///
+/// Used by renderers to skip footnote indices for elements which are not shown.
+pub(crate) fn count_footnotes(elements: &[Element]) -> usize {
+    struct FootnoteCounter(usize);
+
+    impl<'t> Visitor<'t> for FootnoteCounter {
+        fn visit_element(&mut self, element: &Element<'t>) {
+            if let Element::Footnote = element {
+                self.0 += 1;
+            }
+
+            walk_element(self, element);
+        }
+    }
+
+    let mut counter = FootnoteCounter(0);
+    counter.visit_elements(elements);
+    counter.0
 }
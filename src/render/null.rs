@@ -50,15 +50,16 @@ fn null() {
     let page_info = PageInfo::dummy();
     let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
     let result = SyntaxTree::from_element_result(
-        vec![],
-        vec![],
         (vec![], vec![]),
         vec![],
+        (vec![], vec![]),
+        (vec![], vec![]),
         vec![],
         BibliographyList::new(),
         0,
     );
     let (tree, _) = result.into();
+    #[allow(clippy::let_unit_value)]
     let output = NullRender.render(&tree, &page_info, &settings);
 
     assert_eq!(output, (), "Null render didn't produce the unit value");
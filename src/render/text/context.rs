@@ -20,10 +20,13 @@
 
 use crate::data::PageInfo;
 use crate::non_empty_vec::NonEmptyVec;
-use crate::render::Handle;
+use crate::render::{Handle, RenderContext};
 use crate::settings::WikitextSettings;
-use crate::tree::{Bibliography, BibliographyList, Element, VariableScopes};
+use crate::tree::{
+    Bibliography, BibliographyList, CitationStyle, Element, VariableScopes,
+};
 use std::fmt::{self, Write};
+use std::mem;
 use std::num::NonZeroUsize;
 
 #[derive(Debug)]
@@ -32,21 +35,8 @@ where
     'e: 't,
 {
     output: String,
-    info: &'i PageInfo<'i>,
+    render: RenderContext<'i, 'e, 't>,
     handle: &'h Handle,
-    settings: &'e WikitextSettings,
-
-    //
-    // Included page scopes
-    //
-    variables: VariableScopes,
-
-    //
-    // Elements from the syntax tree
-    //
-    table_of_contents: &'e [Element<'t>],
-    footnotes: &'e [Vec<Element<'t>>],
-    bibliographies: &'e BibliographyList<'t>,
 
     //
     // Other fields to track
@@ -62,11 +52,8 @@ where
     /// added are instead replaced with spaces.
     invisible: usize,
 
-    /// The current equation index, for rendering.
-    equation_index: NonZeroUsize,
-
-    /// The current footnote index, for rendering.
-    footnote_index: NonZeroUsize,
+    /// URLs of external links, to be listed at the end of the document.
+    link_urls: Vec<String>,
 }
 
 impl<'i, 'h, 'e, 't> TextContext<'i, 'h, 'e, 't>
@@ -85,18 +72,18 @@ where
     ) -> Self {
         TextContext {
             output: String::with_capacity(wikitext_len),
-            info,
+            render: RenderContext::new(
+                info,
+                settings,
+                (table_of_contents, &[]),
+                footnotes,
+                bibliographies,
+            ),
             handle,
-            settings,
-            variables: VariableScopes::new(),
-            table_of_contents,
-            footnotes,
-            bibliographies,
             prefixes: Vec::new(),
             list_depths: NonEmptyVec::new(1),
             invisible: 0,
-            equation_index: NonZeroUsize::new(1).unwrap(),
-            footnote_index: NonZeroUsize::new(1).unwrap(),
+            link_urls: Vec::new(),
         }
     }
 
@@ -106,19 +93,29 @@ where
         &mut self.output
     }
 
+    #[inline]
+    pub fn render_context(&self) -> &RenderContext<'i, 'e, 't> {
+        &self.render
+    }
+
+    #[inline]
+    pub fn render_context_mut(&mut self) -> &mut RenderContext<'i, 'e, 't> {
+        &mut self.render
+    }
+
     #[inline]
     pub fn info(&self) -> &'i PageInfo<'i> {
-        self.info
+        self.render.info()
     }
 
     #[inline]
     pub fn settings(&self) -> &WikitextSettings {
-        self.settings
+        self.render.settings()
     }
 
     #[inline]
     pub fn language(&self) -> &str {
-        &self.info.language
+        self.render.language()
     }
 
     #[inline]
@@ -128,46 +125,56 @@ where
 
     #[inline]
     pub fn variables(&self) -> &VariableScopes {
-        &self.variables
+        self.render.variables()
     }
 
     #[inline]
     pub fn variables_mut(&mut self) -> &mut VariableScopes {
-        &mut self.variables
+        self.render.variables_mut()
     }
 
     #[inline]
     pub fn table_of_contents(&self) -> &'e [Element<'t>] {
-        self.table_of_contents
+        self.render.table_of_contents()
     }
 
     #[inline]
     pub fn footnotes(&self) -> &'e [Vec<Element<'t>>] {
-        self.footnotes
+        self.render.footnotes()
     }
 
     #[inline]
-    pub fn get_bibliography(&self, index: usize) -> &'e Bibliography<'t> {
-        self.bibliographies.get_bibliography(index)
+    pub fn get_bibliography(&self, index: usize) -> Option<&'e Bibliography<'t>> {
+        self.render.get_bibliography(index)
     }
 
+    #[inline]
     pub fn get_bibliography_ref(
         &self,
         label: &str,
-    ) -> Option<(usize, &'e [Element<'t>])> {
-        self.bibliographies.get_reference(label)
+    ) -> Option<(usize, &'e [Element<'t>], CitationStyle)> {
+        self.render.get_bibliography_ref(label)
     }
 
+    #[inline]
     pub fn next_equation_index(&mut self) -> NonZeroUsize {
-        let index = self.equation_index;
-        self.equation_index = NonZeroUsize::new(index.get() + 1).unwrap();
-        index
+        self.render.next_equation_index()
     }
 
+    #[inline]
     pub fn next_footnote_index(&mut self) -> NonZeroUsize {
-        let index = self.footnote_index;
-        self.footnote_index = NonZeroUsize::new(index.get() + 1).unwrap();
-        index
+        self.render.next_footnote_index()
+    }
+
+    /// Adds a link URL to be listed later, returning its number.
+    pub fn push_link_url(&mut self, url: String) -> usize {
+        self.link_urls.push(url);
+        self.link_urls.len()
+    }
+
+    #[inline]
+    pub fn take_link_urls(&mut self) -> Vec<String> {
+        mem::take(&mut self.link_urls)
     }
 
     // Prefixes
@@ -28,7 +28,13 @@
 //! Any formatting present must be directly justifiable.
 
 use super::TextContext;
-use crate::tree::{ContainerType, DefinitionListItem, Element, ListItem, Tab};
+use crate::parsing::ElementCondition;
+use crate::render::count_footnotes;
+use crate::settings::LinkUrlPlacement;
+use crate::tree::{
+    ContainerType, DefinitionListItem, Element, LinkLabel, LinkLocation, ListItem, Tab,
+};
+use crate::url::is_url_with_settings;
 
 pub fn render_elements(ctx: &mut TextContext, elements: &[Element]) {
     debug!("Rendering elements (length {})", elements.len());
@@ -64,6 +70,13 @@ pub fn render_element(ctx: &mut TextContext, element: &Element) {
                 | ContainerType::Blockquote
                 | ContainerType::Header(_) => true,
 
+                // Put attributions on their own line, after a dash
+                ContainerType::BlockquoteAttribution => {
+                    ctx.add_newline();
+                    ctx.push_str("\u{2014} ");
+                    false
+                }
+
                 // Wrap any ruby text with parentheses
                 ContainerType::RubyText => {
                     ctx.push('(');
@@ -100,10 +113,11 @@ pub fn render_element(ctx: &mut TextContext, element: &Element) {
         Element::Text(text) | Element::Raw(text) | Element::Email(text) => {
             ctx.push_str(text);
         }
-        Element::Variable(name) => {
-            let value = match ctx.variables().get(name) {
-                Some(value) => str!(value),
-                None => format!("{{${name}}}"),
+        Element::Variable { name, default } => {
+            let value = match (ctx.variables().get(name), default) {
+                (Some(value), _) => str!(value),
+                (None, Some(default)) => str!(default),
+                (None, None) => format!("{{${name}}}"),
             };
 
             debug!(
@@ -113,6 +127,15 @@ pub fn render_element(ctx: &mut TextContext, element: &Element) {
             );
             ctx.push_str(&value);
         }
+        Element::PageVariable { name } => {
+            let value = match ctx.info().variable(name) {
+                Some(value) => value.into_owned(),
+                None => format!("%%{name}%%"),
+            };
+
+            debug!("Rendering page variable (name '{name}', value {value})");
+            ctx.push_str(&value);
+        }
         Element::Table(table) => {
             if !ctx.ends_with_newline() {
                 ctx.add_newline();
@@ -144,13 +167,34 @@ pub fn render_element(ctx: &mut TextContext, element: &Element) {
             // Anchor names are an invisible addition to the HTML
             // to aid navigation. So in text mode, they are ignored.
         }
-        Element::Link { link, label, .. } => {
+        Element::Link {
+            link, extra, label, ..
+        } => {
             let site = ctx.info().site.as_ref();
 
             ctx.handle().get_link_label(site, link, label, |label| {
                 // Only write the label, i.e. the part that's visible
                 ctx.push_str(label);
             });
+
+            // Spell out the URL of external links, if requested.
+            // Labels which mirror the URL already show it.
+            if let (LinkLocation::Url(url), LinkLabel::Text(_) | LinkLabel::Page) =
+                (link, label)
+            {
+                if is_url_with_settings(url, ctx.settings()) && !label.is_blank() {
+                    let url = format!("{url}{}", extra.as_deref().unwrap_or(""));
+
+                    match ctx.settings().link_urls {
+                        LinkUrlPlacement::None => (),
+                        LinkUrlPlacement::Inline => str_write!(ctx, " ({url})"),
+                        LinkUrlPlacement::Footnote => {
+                            let number = ctx.push_link_url(url);
+                            str_write!(ctx, "[L{number}]");
+                        }
+                    }
+                }
+            }
         }
         Element::Image { .. } => {
             // Text cannot render images, so we don't add anything
@@ -186,7 +230,11 @@ pub fn render_element(ctx: &mut TextContext, element: &Element) {
                 render_elements(ctx, key_elements);
                 ctx.push(' ');
                 render_elements(ctx, value_elements);
-                ctx.add_newline();
+
+                // Nested lists already end with a newline
+                if !ctx.ends_with_newline() {
+                    ctx.add_newline();
+                }
             }
 
             ctx.add_newline();
@@ -203,14 +251,45 @@ pub fn render_element(ctx: &mut TextContext, element: &Element) {
         Element::TableOfContents { .. } => {
             // Doesn't make sense to have a textual table of contents, skip
         }
-        Element::Footnote
-        | Element::FootnoteBlock { .. }
-        | Element::BibliographyCite { .. }
-        | Element::BibliographyBlock { .. } => {
-            // Footnotes and bibliographies cannot be cleanly rendered in text mode,
+        Element::Pagination { .. } => {
+            // Navigation links are part of the page chrome, skip
+        }
+        Element::Footnote => {
+            // Numbered marker, matching the footnote block below
+            let index = ctx.next_footnote_index();
+            str_write!(ctx, "[{index}]");
+        }
+        Element::FootnoteBlock { title, hide } => {
+            if *hide || ctx.footnotes().is_empty() {
+                return;
+            }
+
+            let title = match title {
+                Some(title) => title.as_ref(),
+                None => ctx
+                    .handle()
+                    .get_message(ctx.language(), "footnote-block-title"),
+            };
+
+            if !ctx.ends_with_newline() {
+                ctx.add_newline();
+            }
+
+            ctx.add_newline();
+            ctx.push_str(title);
+            ctx.add_newline();
+
+            for (index, contents) in ctx.footnotes().iter().enumerate() {
+                str_write!(ctx, "{}. ", index + 1);
+                render_elements(ctx, contents);
+                ctx.add_newline();
+            }
+        }
+        Element::BibliographyCite { .. } | Element::BibliographyBlock { .. } => {
+            // Bibliographies cannot be cleanly rendered in text mode,
             // so they are skipped.
         }
-        Element::User { name, .. } => ctx.push_str(name),
+        Element::User { name, .. } | Element::Mention { name } => ctx.push_str(name),
         Element::Date { value, format, .. } => {
             // TEMP
             if format.is_some() {
@@ -242,6 +321,20 @@ pub fn render_element(ctx: &mut TextContext, element: &Element) {
             // Interactive or HTML elements like this don't make sense in
             // text mode, so we skip them.
         }
+        Element::Conditional {
+            conditions,
+            elements,
+            ..
+        } => {
+            if ElementCondition::check(conditions, &ctx.info().tags) {
+                render_elements(ctx, elements);
+            } else {
+                // Skip hidden footnotes, as in the HTML renderer
+                for _ in 0..count_footnotes(elements) {
+                    ctx.next_footnote_index();
+                }
+            }
+        }
         Element::Include {
             variables,
             elements,
@@ -272,6 +365,8 @@ pub fn render_element(ctx: &mut TextContext, element: &Element) {
             //
             // So we take the safe option of doing nothing.
         }
-        Element::Partial(_) => panic!("Encountered partial element during parsing"),
+        Element::Partial(_) => {
+            error!("Encountered partial element during rendering, skipping");
+        }
     }
 }
@@ -63,6 +63,17 @@ impl TextRender {
             wikitext_len,
         }: RenderPartial,
     ) -> String {
+        enter_span!(
+            "render",
+            format = "text",
+            site = %page_info.site,
+            page = %page_info.page,
+        );
+
+        // Apply any settings overlays for this page's category
+        let settings = settings.resolve(page_info);
+        let settings = settings.as_ref();
+
         debug!(
             "Rendering text (site {}, page {}, category {})",
             page_info.site.as_ref(),
@@ -83,6 +94,7 @@ impl TextRender {
             wikitext_len,
         );
         render_elements(&mut ctx, elements);
+        render_link_urls(&mut ctx);
 
         // Remove leading and trailing newlines
         while ctx.buffer().starts_with('\n') {
@@ -129,6 +141,31 @@ impl Render for TextRender {
     }
 }
 
+/// Lists the URLs of external links collected while rendering.
+///
+/// See [`LinkUrlPlacement::Footnote`](crate::settings::LinkUrlPlacement::Footnote).
+fn render_link_urls(ctx: &mut TextContext) {
+    let urls = ctx.take_link_urls();
+    if urls.is_empty() {
+        return;
+    }
+
+    let title = ctx.handle().get_message(ctx.language(), "link-list-title");
+
+    if !ctx.ends_with_newline() {
+        ctx.add_newline();
+    }
+
+    ctx.add_newline();
+    ctx.push_str(title);
+    ctx.add_newline();
+
+    for (index, url) in urls.iter().enumerate() {
+        str_write!(ctx, "L{}. {url}", index + 1);
+        ctx.add_newline();
+    }
+}
+
 /// Helper structure to pass in values for `render_partial_direct()`.
 ///
 /// This exists because otherwise the function would take an excessive
@@ -143,3 +180,65 @@ struct RenderPartial<'a> {
     bibliographies: &'a BibliographyList<'a>,
     wikitext_len: usize,
 }
+
+#[test]
+fn footnotes() {
+    use crate::layout::Layout;
+    use crate::settings::WikitextMode;
+
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let mut text = str!(
+        "Apples[[footnote]]A fruit.[[/footnote]] and bananas[[footnote]]Also a fruit.[[/footnote]]."
+    );
+    crate::preprocess(&mut text, &page_info, &settings);
+
+    let tokens = crate::tokenize(&text);
+    let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+    let output = TextRender.render(&tree, &page_info, &settings);
+
+    assert_eq!(
+        output,
+        "Apples[1] and bananas[2].\n\nFootnotes\n1. A fruit.\n2. Also a fruit.",
+    );
+}
+
+#[test]
+fn link_urls() {
+    use crate::layout::Layout;
+    use crate::settings::{LinkUrlPlacement, WikitextMode};
+
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let mut text = str!(
+        "See [https://example.com/ the example] and [[[https://scpwiki.com/|the wiki]]], or https://example.net/."
+    );
+    crate::preprocess(&mut text, &page_info, &settings);
+
+    let tokens = crate::tokenize(&text);
+    let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+
+    macro_rules! check {
+        ($placement:expr, $expected:expr $(,)?) => {{
+            let settings = WikitextSettings {
+                link_urls: $placement,
+                ..settings.clone()
+            };
+            let output = TextRender.render(&tree, &page_info, &settings);
+            assert_eq!(output, $expected, "Text output doesn't match expected");
+        }};
+    }
+
+    check!(
+        LinkUrlPlacement::None,
+        "See the example and the wiki, or https://example.net/.",
+    );
+    check!(
+        LinkUrlPlacement::Inline,
+        "See the example (https://example.com/) and the wiki (https://scpwiki.com/), or https://example.net/.",
+    );
+    check!(
+        LinkUrlPlacement::Footnote,
+        "See the example[L1] and the wiki[L2], or https://example.net/.\n\nLinks\nL1. https://example.com/\nL2. https://scpwiki.com/",
+    );
+}
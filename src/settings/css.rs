@@ -0,0 +1,43 @@
+/*
+ * settings/css.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+/// Settings that determine how user-provided CSS is processed.
+///
+/// These apply to stylesheets from `[[module CSS]]` blocks, which are
+/// otherwise emitted as written (aside from minification).
+/// By default, no changes are made.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct CssSettings {
+    /// A class which all selectors are scoped under.
+    ///
+    /// If set, each selector is prefixed with this class, so that
+    /// `.fruit` becomes `.scope .fruit`. The class is also added to
+    /// the `<wj-body>` element, so styles only apply within this page.
+    pub scope_class: Option<String>,
+
+    /// Whether to remove `@import` rules.
+    pub strip_imports: bool,
+
+    /// Whether to remove `position: fixed` declarations.
+    ///
+    /// This prevents user styles from covering the rest of the site.
+    pub forbid_fixed_position: bool,
+}
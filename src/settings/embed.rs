@@ -0,0 +1,105 @@
+/*
+ * settings/embed.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+/// Settings that determine which sites may be embedded with `[[embed]]`.
+///
+/// Only providers listed here are permitted. Using any other provider
+/// is a parse error, and such embeds in an existing syntax tree are
+/// rendered as a plain link instead.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct EmbedSettings {
+    /// The providers which may be embedded.
+    pub providers: Vec<EmbedProviderSettings>,
+}
+
+impl EmbedSettings {
+    /// Settings which do not permit any embeds.
+    pub fn none() -> Self {
+        EmbedSettings {
+            providers: Vec::new(),
+        }
+    }
+
+    /// Gets the settings for the provider with this name, if it is permitted.
+    pub fn provider(&self, name: &str) -> Option<&EmbedProviderSettings> {
+        self.providers
+            .iter()
+            .find(|provider| provider.name.eq_ignore_ascii_case(name))
+    }
+}
+
+impl Default for EmbedSettings {
+    fn default() -> Self {
+        EmbedSettings {
+            providers: vec![
+                EmbedProviderSettings {
+                    name: str!("youtube"),
+                    sandbox: Some(str!(
+                        "allow-scripts allow-same-origin allow-popups allow-presentation"
+                    )),
+                    allow: Some(str!(
+                        "accelerometer; autoplay; clipboard-write; encrypted-media; gyroscope; picture-in-picture"
+                    )),
+                },
+                EmbedProviderSettings {
+                    name: str!("vimeo"),
+                    sandbox: Some(str!(
+                        "allow-scripts allow-same-origin allow-popups allow-presentation"
+                    )),
+                    allow: Some(str!("autoplay; fullscreen; picture-in-picture")),
+                },
+                EmbedProviderSettings::new("github-gist"),
+                EmbedProviderSettings::new("gitlab-snippet"),
+            ],
+        }
+    }
+}
+
+/// Settings for a single embed provider, as listed in [`EmbedSettings`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct EmbedProviderSettings {
+    /// The name of the provider, such as `youtube` or `github-gist`.
+    pub name: String,
+
+    /// The value of the `sandbox` attribute on the emitted `<iframe>`.
+    ///
+    /// If unset, the attribute is omitted and the frame is not sandboxed.
+    /// This has no effect for providers embedded as scripts.
+    pub sandbox: Option<String>,
+
+    /// The value of the `allow` attribute on the emitted `<iframe>`.
+    ///
+    /// If unset, the attribute is omitted.
+    /// This has no effect for providers embedded as scripts.
+    pub allow: Option<String>,
+}
+
+impl EmbedProviderSettings {
+    /// Creates settings for a provider with no additional attributes.
+    pub fn new(name: &str) -> Self {
+        EmbedProviderSettings {
+            name: str!(name),
+            sandbox: None,
+            allow: None,
+        }
+    }
+}
@@ -0,0 +1,56 @@
+/*
+ * settings/error_kinds.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use crate::parsing::ParseErrorKind;
+
+/// Settings that change how particular kinds of parse errors are handled.
+///
+/// These are applied after parsing, alongside the `error_level` setting.
+/// They do not affect the syntax tree, so for instance content which is
+/// not supported in the current mode is still removed, even if the
+/// corresponding error is suppressed.
+///
+/// By default, all errors are reported normally.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct ErrorKindSettings {
+    /// Errors of these kinds are not reported.
+    pub suppress: Vec<ParseErrorKind>,
+
+    /// Errors of these kinds are reported, but only as warnings.
+    ///
+    /// They do not cause the syntax tree to be rejected when
+    /// the error level is [`Strict`](super::ErrorLevel::Strict).
+    pub downgrade: Vec<ParseErrorKind>,
+}
+
+impl ErrorKindSettings {
+    /// Whether errors of this kind are not reported.
+    #[inline]
+    pub fn is_suppressed(&self, kind: ParseErrorKind) -> bool {
+        self.suppress.contains(&kind)
+    }
+
+    /// Whether errors of this kind are only reported as warnings.
+    #[inline]
+    pub fn is_downgraded(&self, kind: ParseErrorKind) -> bool {
+        self.downgrade.contains(&kind)
+    }
+}
@@ -0,0 +1,55 @@
+/*
+ * settings/external_links.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+/// Settings that determine the attributes added to external links.
+///
+/// These apply to links with a URL target which points to another site,
+/// such as `[https://example.com/ Example]`, but not to internal links
+/// or anchors. By default, no attributes are added.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct ExternalLinkSettings {
+    /// The value of the `rel` attribute, such as `nofollow noopener`.
+    pub rel: Option<String>,
+
+    /// The value of the `referrerpolicy` attribute, such as `no-referrer`.
+    pub referrer_policy: Option<String>,
+
+    /// The class of a marker to append to external links, such as `wj-icon-external`.
+    ///
+    /// If set, an empty `<span>` with this class and an `aria-label` of
+    /// "external link" is added within the link, which can be styled as an icon.
+    pub marker: Option<String>,
+}
+
+impl ExternalLinkSettings {
+    /// Settings for contexts where anyone may post links, such as forums.
+    ///
+    /// Links are marked `nofollow` so they do not lend the site's
+    /// ranking to spam, and `noopener` so the target cannot access
+    /// the page that opened it.
+    pub fn nofollow() -> Self {
+        ExternalLinkSettings {
+            rel: Some(str!("nofollow noopener")),
+            referrer_policy: None,
+            marker: None,
+        }
+    }
+}
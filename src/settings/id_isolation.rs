@@ -0,0 +1,55 @@
+/*
+ * settings/id_isolation.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+/// Settings that determine how user-provided IDs are isolated.
+///
+/// These only apply if `isolate_user_ids` is enabled.
+/// By default, the `u-` prefix is used, and all attributes which
+/// contain or refer to IDs are isolated, so that references between
+/// them (such as a `<label>` pointing to an `<input>`) still match.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct IdIsolationSettings {
+    /// The prefix added to each isolated ID.
+    ///
+    /// IDs which already begin with this prefix are left as-is.
+    pub prefix: String,
+
+    /// Which attributes have their values isolated.
+    ///
+    /// Each is a space-separated list of IDs, and attribute names are
+    /// matched case-insensitively. Named anchors and `[[target]]` blocks
+    /// are always isolated.
+    pub attributes: Vec<String>,
+}
+
+impl Default for IdIsolationSettings {
+    fn default() -> Self {
+        IdIsolationSettings {
+            prefix: str!("u-"),
+            attributes: vec![
+                str!("id"),
+                str!("for"),
+                str!("headers"),
+                str!("aria-labelledby"),
+            ],
+        }
+    }
+}
@@ -0,0 +1,50 @@
+/*
+ * settings/iframe.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+/// Settings for the `<iframe>` elements emitted in HTML output.
+///
+/// These apply to `[[iframe]]` blocks, and the lazy loading setting
+/// also to `[[embed]]` frames. Embed providers have their own sandbox
+/// policy, see [`EmbedProviderSettings`](super::EmbedProviderSettings).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct IframeSettings {
+    /// Whether frames have `loading="lazy"`.
+    ///
+    /// If enabled, browsers defer loading the frame until it is
+    /// scrolled near the viewport.
+    pub lazy_load: bool,
+
+    /// The value of the `sandbox` attribute on `[[iframe]]` frames.
+    ///
+    /// Users cannot set this attribute themselves, so this is the
+    /// policy for all such frames. If unset, the attribute is omitted
+    /// and the frame is not sandboxed.
+    pub sandbox: Option<String>,
+}
+
+impl Default for IframeSettings {
+    fn default() -> Self {
+        IframeSettings {
+            lazy_load: true,
+            sandbox: None,
+        }
+    }
+}
@@ -18,13 +18,47 @@
  * along with this program. If not, see <http://www.gnu.org/licenses/>.
  */
 
+mod css;
+mod embed;
+mod error_kinds;
+mod external_links;
+mod id_isolation;
+mod iframe;
 mod interwiki;
+mod overlay;
+mod size;
+mod typography;
 
+use crate::data::PageInfo;
 use crate::layout::Layout;
+use crate::tree::attribute::SharedAttributePolicy;
+use crate::url::URL_SCHEMES;
+use std::borrow::Cow;
 
+pub use self::css::CssSettings;
+pub use self::embed::{EmbedProviderSettings, EmbedSettings};
+pub use self::error_kinds::ErrorKindSettings;
+pub use self::external_links::ExternalLinkSettings;
+pub use self::id_isolation::IdIsolationSettings;
+pub use self::iframe::IframeSettings;
 pub use self::interwiki::{InterwikiSettings, DEFAULT_INTERWIKI, EMPTY_INTERWIKI};
+pub use self::overlay::SettingsOverlay;
+pub use self::size::SizeSettings;
+pub use self::typography::TypographySettings;
 
+const DEFAULT_MAX_INCLUDE_DEPTH: usize = 5;
+const DEFAULT_MAX_INCLUDE_COUNT: usize = 500;
+const DEFAULT_MAX_INCLUDE_SIZE: usize = 4 * 1024 * 1024;
+const DEFAULT_MAX_LINE_BREAKS: u32 = 100;
 const DEFAULT_MINIFY_CSS: bool = true;
+const DEFAULT_SYNTAX_HIGHLIGHTING: bool = false;
+const DEFAULT_SOURCE_SPANS: bool = false;
+const DEFAULT_ERROR_LEVEL: ErrorLevel = ErrorLevel::Warn;
+const DEFAULT_COLLECT_STATS: bool = false;
+const DEFAULT_LENIENT_CHAR_ENTITIES: bool = false;
+const DEFAULT_ENABLE_RUBY_SHORTHAND: bool = false;
+const DEFAULT_DEFER_TAG_CONDITIONS: bool = false;
+const DEFAULT_SLUGIFY_HEADING_IDS: bool = false;
 
 /// Settings to tweak behavior in the ftml parser and renderer.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -45,8 +79,19 @@ pub struct WikitextSettings {
     /// * Module
     /// * Table of Contents
     /// * Button
+    /// * Page variables (e.g. `%%title%%`)
     pub enable_page_syntax: bool,
 
+    /// The names of parse rules which are not permitted.
+    ///
+    /// Both regular rules (such as `url`) and block rules (such as `block-html`)
+    /// can be listed. Syntax for a disabled rule is parsed as though the rule
+    /// did not exist, for instance a disabled block produces an error and is
+    /// rendered as text. See [`RuleSet`](crate::parsing::RuleSet).
+    ///
+    /// By default, all rules are enabled.
+    pub disabled_rules: Vec<Cow<'static, str>>,
+
     /// Whether a literal `[[include]]` is permitted.
     ///
     /// If this is true, then `[[include]]` is treated as an alias
@@ -55,6 +100,30 @@ pub struct WikitextSettings {
     /// It is off by default.
     pub use_include_compatibility: bool,
 
+    /// How deeply `[[include-messy]]` blocks may be nested.
+    ///
+    /// Included pages may themselves include other pages, up to this depth.
+    /// Includes beyond this limit, or which would include a page already being
+    /// included (a cycle), are not performed. Instead they are passed to
+    /// [`Includer::include_recursion()`](crate::includes::Includer::include_recursion).
+    pub max_include_depth: usize,
+
+    /// How many pages may be included in total, at any depth.
+    ///
+    /// Since each included page may include several others, the number of
+    /// includes can grow exponentially with depth. Once this many pages have
+    /// been fetched, any remaining include blocks are left as-is, and an
+    /// [`IncludeError::TooManyIncludes`](crate::includes::IncludeError::TooManyIncludes)
+    /// is reported.
+    pub max_include_count: usize,
+
+    /// How many bytes of included page content may be substituted in total.
+    ///
+    /// Once this would be exceeded, any remaining include blocks are left as-is,
+    /// and an [`IncludeError::TooLarge`](crate::includes::IncludeError::TooLarge)
+    /// is reported.
+    pub max_include_size: usize,
+
     /// Whether IDs should have true values, or be excluded or randomly generated.
     ///
     /// In the latter case, IDs can be used for navigation, for instance
@@ -66,12 +135,201 @@ pub struct WikitextSettings {
     ///
     /// This is a behavior found in Wikidot (although implemented incompletely)
     /// which prefixes IDs in HTML elements provided by the user with `u-` to ensure
-    /// isolation.
+    /// isolation. The prefix and affected attributes are set in `id_isolation`.
     pub isolate_user_ids: bool,
 
+    /// How user IDs are isolated, if `isolate_user_ids` is enabled.
+    pub id_isolation: IdIsolationSettings,
+
+    /// Whether headings should get IDs derived from their text.
+    ///
+    /// If enabled, headings with a table of contents entry get a readable ID
+    /// based on their text, such as `#early-history` for "Early History",
+    /// instead of `#toc0`. Repeated headings have `-1`, `-2`, etc. appended,
+    /// so that each ID is unique. The table of contents links use these IDs too.
+    pub slugify_heading_ids: bool,
+
     /// Whether to minify CSS in `<style>` blocks.
     pub minify_css: bool,
 
+    /// Whether to minify the rendered HTML.
+    ///
+    /// Runs of whitespace in text are collapsed to a single space, and whitespace
+    /// next to block-level tags is dropped. Text within `<pre>`, `<code>`,
+    /// `<textarea>`, `<script>`, and `<style>` is left as-is, as are raw text
+    /// and elements styled with `white-space: pre` or similar.
+    pub minify_html: bool,
+
+    /// How to process CSS in `<style>` blocks.
+    pub css: CssSettings,
+
+    /// The prefix to use for generated class names, in place of `wj-`.
+    ///
+    /// This allows the generated classes to be namespaced, so that they do
+    /// not collide with those of the embedding site. For instance, with a
+    /// prefix of `ftml-`, links have the class `ftml-link` instead of `wj-link`.
+    /// Other classes, such as those from the page's wikitext, are left as-is.
+    pub class_prefix: Option<String>,
+
+    /// Whether to apply syntax highlighting to code blocks which specify a language.
+    ///
+    /// This only has an effect if the `syntax-highlight` feature is enabled.
+    /// It is off by default.
+    pub syntax_highlighting: bool,
+
+    /// Whether to record and emit the source offsets of top-level elements.
+    ///
+    /// If this is true, then the parser records the byte range of the input
+    /// which produced each top-level element, and the HTML renderer wraps each
+    /// such element in a tag with a `data-ftml-span="start..end"` attribute.
+    ///
+    /// This is intended for live previews, to allow synchronizing scrolling
+    /// or jumping to a particular place in the source from the rendered output.
+    /// It is off by default.
+    pub source_spans: bool,
+
+    /// How the parser handles malformed syntax.
+    ///
+    /// By default, malformed syntax falls back to being rendered as text,
+    /// and the problems are reported in the list of errors.
+    /// See [`ErrorLevel`] for the alternatives.
+    pub error_level: ErrorLevel,
+
+    /// Which kinds of errors are suppressed or downgraded.
+    ///
+    /// This allows ignoring errors which are expected in a given mode,
+    /// for instance `not-supported-mode` in forum post previews.
+    pub error_kinds: ErrorKindSettings,
+
+    /// Whether to collect statistics about the parse.
+    ///
+    /// If this is true, then the [`ParseOutcome`] includes a [`ParseStatistics`]
+    /// with counts and timings for each rule, to help find wikitext which is
+    /// slow to parse. It is off by default, since it adds overhead.
+    ///
+    /// [`ParseOutcome`]: crate::parsing::ParseOutcome
+    /// [`ParseStatistics`]: crate::parsing::ParseStatistics
+    pub collect_stats: bool,
+
+    /// Whether unknown entities in `[[char]]` are permitted.
+    ///
+    /// If this is true, then a `[[char]]` block with an entity which
+    /// cannot be found emits its argument as literal text, and adds a
+    /// warning to the list of errors, rather than failing.
+    /// It is off by default.
+    pub lenient_char_entities: bool,
+
+    /// Whether the inline ruby shorthand is enabled.
+    ///
+    /// If this is true, then `{振り仮名|ふりがな}` is equivalent to
+    /// `[[rb 振り仮名|ふりがな]]`. Since braces are ordinary text in
+    /// Wikidot, this is off by default.
+    pub enable_ruby_shorthand: bool,
+
+    /// Whether users can be mentioned inline, such as `@username`.
+    ///
+    /// Each mention links to the user, as with `[[user]]`, and the users
+    /// mentioned are listed in [`SyntaxTree::mentions`], for instance so that
+    /// they can be notified. This is on for forum posts and direct messages,
+    /// but off elsewhere, since `@` is ordinary text in Wikidot.
+    ///
+    /// [`SyntaxTree::mentions`]: crate::tree::SyntaxTree::mentions
+    pub enable_mentions: bool,
+
+    /// Where to automatically add a footnote block, if the wikitext lacks one.
+    ///
+    /// By default it is appended to the end of the document. When rendering
+    /// fragments which will be embedded in a larger document, this can be
+    /// disabled, or the footnote block placed inside a particular container.
+    pub footnote_block: FootnoteBlockPlacement,
+
+    /// Whether footnotes in the footnote block link back to their references.
+    ///
+    /// If enabled, each entry ends with a back-link (↩) to the in-text
+    /// marker which referenced it, or several if it was referenced multiple times.
+    /// The markers' IDs follow `use_true_ids`, so they are randomly generated
+    /// where several renders may share a page.
+    pub footnote_backlinks: bool,
+
+    /// Where the target URLs of external links are spelled out in text rendering.
+    ///
+    /// When printed, a link's label no longer conveys where it points to.
+    /// This adds the URL after each external link, either inline or as a
+    /// numbered note in a list at the end of the document.
+    pub link_urls: LinkUrlPlacement,
+
+    /// How email addresses are obfuscated in HTML output.
+    ///
+    /// Addresses are rendered as plain text, and so are easily collected
+    /// by scrapers. Public wikis may choose to obscure them.
+    ///
+    /// Note that the parser never produces [`Element::Email`], so this only
+    /// affects trees which are constructed directly or deserialized.
+    ///
+    /// [`Element::Email`]: crate::tree::Element::Email
+    pub email_obfuscation: EmailObfuscation,
+
+    /// Which attributes are added to external links in HTML output.
+    ///
+    /// Forum posts and direct messages mark these links `nofollow`,
+    /// since anyone can post them, but pages do not by default.
+    pub external_links: ExternalLinkSettings,
+
+    /// Which providers may be embedded with `[[embed]]`, and how.
+    ///
+    /// By default, all supported providers are permitted, with `<iframe>`
+    /// embeds sandboxed so they cannot navigate or otherwise affect the page.
+    pub embed: EmbedSettings,
+
+    /// How `<iframe>` elements are emitted in HTML output.
+    ///
+    /// By default, frames are loaded lazily, and `[[iframe]]`
+    /// frames are not sandboxed.
+    pub iframe: IframeSettings,
+
+    /// Which typographic substitutions are performed, such as fancy quotes.
+    ///
+    /// The quote and ellipsis substitutions are performed during preprocessing,
+    /// and the rest by the parser.
+    pub typography: TypographySettings,
+
+    /// The greatest number of lines a `[[lines]]` block may produce.
+    ///
+    /// Larger values are reduced to this maximum, and a warning
+    /// is added to the list of errors.
+    pub max_line_breaks: u32,
+
+    /// The largest sizes `[[size]]` blocks may produce.
+    ///
+    /// This prevents pages from producing text so large it
+    /// obscures the rest of the page, such as `[[size 10000px]]`.
+    pub size: SizeSettings,
+
+    /// A policy deciding which HTML attributes users may set on elements.
+    ///
+    /// This can permit attributes beyond the defaults, such as a site's own
+    /// `data-*` attributes, forbid some which are otherwise allowed, or
+    /// rewrite their values. See [`AttributePolicy`](crate::tree::attribute::AttributePolicy).
+    ///
+    /// This cannot be serialized, and is only settable by library users.
+    /// By default, there is no policy, and the built-in whitelist is used.
+    #[serde(skip)]
+    pub attribute_policy: Option<SharedAttributePolicy>,
+
+    /// How `[[lines]]` blocks are rendered in HTML.
+    ///
+    /// This only has an effect in the Wikijump layout. Wikidot always
+    /// renders a run of `<br>` tags.
+    pub line_break_style: LineBreakStyle,
+
+    /// Whether `[[iftags]]` conditions are evaluated when rendering rather than parsing.
+    ///
+    /// If this is true, then the conditions are preserved in the syntax tree as
+    /// `Element::Conditional`, and are checked against the page's tags at render time.
+    /// This allows a syntax tree to be cached and reused even if the page's tags change.
+    /// It is off by default.
+    pub defer_tag_conditions: bool,
+
     /// Whether local paths are permitted.
     ///
     /// This should be disabled in contexts where there is no "local context"
@@ -84,6 +342,14 @@ pub struct WikitextSettings {
     /// * Images
     pub allow_local_paths: bool,
 
+    /// Whether additional interwiki prefixes may be defined from within wikitext.
+    ///
+    /// If this is true, then `[[interwiki]]` blocks add their prefix to the
+    /// interwiki settings used for the remainder of the parse. This is intended
+    /// for site administrators, so it should only be enabled in contexts
+    /// where the author is trusted.
+    pub allow_interwiki_definitions: bool,
+
     /// What interwiki prefixes are supported.
     ///
     /// All instances of `$$` in the destination URL are replaced with the link provided
@@ -97,57 +363,243 @@ pub struct WikitextSettings {
     ///   any beyond that are considered part of the link.
     /// * By convention, prefixes should be all-lowercase.
     pub interwiki: InterwikiSettings,
+
+    /// Which URL schemes are recognized as external links.
+    ///
+    /// Each entry is a prefix, such as `https://` or `mailto:`, which a URL must
+    /// begin with to be treated as-is. Anything else is interpreted as a page path.
+    /// The default list is [`URL_SCHEMES`], but deployments may extend it,
+    /// for instance to permit `matrix:` or `xmpp:` links.
+    ///
+    /// Note that `javascript:` and `data:` URLs are always rejected,
+    /// even if they are listed here.
+    pub url_schemes: Vec<Cow<'static, str>>,
+
+    /// Changes to these settings for pages in particular categories.
+    ///
+    /// Those which match the page's category are applied when parsing and rendering.
+    /// For instance, a site may not want footnote blocks on `fragment:` pages.
+    /// See [`SettingsOverlay`] and [`WikitextSettings::resolve()`].
+    ///
+    /// By default, there are no overlays.
+    pub overlays: Vec<SettingsOverlay>,
 }
 
 impl WikitextSettings {
+    /// Returns these settings with any overlays for the page's category applied.
+    ///
+    /// If no overlays match, then these settings are returned unchanged.
+    /// The resolved settings have no overlays of their own.
+    ///
+    /// Parsing and rendering do this automatically, so callers only need
+    /// this to inspect the settings that will apply to a particular page.
+    pub fn resolve(&self, page_info: &PageInfo) -> Cow<'_, WikitextSettings> {
+        let category = page_info.category.as_deref();
+        let mut overlays = self
+            .overlays
+            .iter()
+            .filter(|overlay| overlay.matches(category))
+            .peekable();
+
+        if overlays.peek().is_none() {
+            return Cow::Borrowed(self);
+        }
+
+        let mut settings = self.clone();
+        settings.overlays = vec![];
+
+        for overlay in overlays {
+            debug!(
+                "Applying settings overlay for categories {:?}",
+                overlay.categories
+            );
+            overlay.apply(&mut settings);
+        }
+
+        Cow::Owned(settings)
+    }
+
     /// Returns the default settings for the given [`WikitextMode`].
     pub fn from_mode(mode: WikitextMode, layout: Layout) -> Self {
         let interwiki = DEFAULT_INTERWIKI.clone();
+        let url_schemes = URL_SCHEMES.iter().map(|&scheme| cow!(scheme)).collect();
 
         match mode {
             WikitextMode::Page => WikitextSettings {
                 mode,
                 layout,
                 enable_page_syntax: true,
+                disabled_rules: vec![],
                 use_include_compatibility: false,
+                max_include_depth: DEFAULT_MAX_INCLUDE_DEPTH,
+                max_include_count: DEFAULT_MAX_INCLUDE_COUNT,
+                max_include_size: DEFAULT_MAX_INCLUDE_SIZE,
                 use_true_ids: true,
                 isolate_user_ids: false,
+                id_isolation: IdIsolationSettings::default(),
+                slugify_heading_ids: DEFAULT_SLUGIFY_HEADING_IDS,
                 minify_css: DEFAULT_MINIFY_CSS,
+                minify_html: false,
+                css: CssSettings::default(),
+                class_prefix: None,
+                syntax_highlighting: DEFAULT_SYNTAX_HIGHLIGHTING,
+                source_spans: DEFAULT_SOURCE_SPANS,
+                error_level: DEFAULT_ERROR_LEVEL,
+                error_kinds: ErrorKindSettings::default(),
+                collect_stats: DEFAULT_COLLECT_STATS,
+                lenient_char_entities: DEFAULT_LENIENT_CHAR_ENTITIES,
+                enable_ruby_shorthand: DEFAULT_ENABLE_RUBY_SHORTHAND,
+                enable_mentions: false,
+                footnote_block: FootnoteBlockPlacement::End,
+                footnote_backlinks: false,
+                link_urls: LinkUrlPlacement::None,
+                email_obfuscation: EmailObfuscation::None,
+                external_links: ExternalLinkSettings::default(),
+                embed: EmbedSettings::default(),
+                iframe: IframeSettings::default(),
+                typography: TypographySettings::default(),
+                max_line_breaks: DEFAULT_MAX_LINE_BREAKS,
+                size: SizeSettings::default(),
+                attribute_policy: None,
+                line_break_style: LineBreakStyle::Break,
+                defer_tag_conditions: DEFAULT_DEFER_TAG_CONDITIONS,
                 allow_local_paths: true,
+                allow_interwiki_definitions: false,
                 interwiki,
+                url_schemes,
+                overlays: vec![],
             },
             WikitextMode::Draft => WikitextSettings {
                 mode,
                 layout,
                 enable_page_syntax: true,
+                disabled_rules: vec![],
                 use_include_compatibility: false,
+                max_include_depth: DEFAULT_MAX_INCLUDE_DEPTH,
+                max_include_count: DEFAULT_MAX_INCLUDE_COUNT,
+                max_include_size: DEFAULT_MAX_INCLUDE_SIZE,
                 use_true_ids: false,
                 isolate_user_ids: false,
+                id_isolation: IdIsolationSettings::default(),
+                slugify_heading_ids: DEFAULT_SLUGIFY_HEADING_IDS,
                 minify_css: DEFAULT_MINIFY_CSS,
+                minify_html: false,
+                css: CssSettings::default(),
+                class_prefix: None,
+                syntax_highlighting: DEFAULT_SYNTAX_HIGHLIGHTING,
+                source_spans: DEFAULT_SOURCE_SPANS,
+                error_level: DEFAULT_ERROR_LEVEL,
+                error_kinds: ErrorKindSettings::default(),
+                collect_stats: DEFAULT_COLLECT_STATS,
+                lenient_char_entities: DEFAULT_LENIENT_CHAR_ENTITIES,
+                enable_ruby_shorthand: DEFAULT_ENABLE_RUBY_SHORTHAND,
+                enable_mentions: false,
+                footnote_block: FootnoteBlockPlacement::End,
+                footnote_backlinks: false,
+                link_urls: LinkUrlPlacement::None,
+                email_obfuscation: EmailObfuscation::None,
+                external_links: ExternalLinkSettings::default(),
+                embed: EmbedSettings::default(),
+                iframe: IframeSettings::default(),
+                typography: TypographySettings::default(),
+                max_line_breaks: DEFAULT_MAX_LINE_BREAKS,
+                size: SizeSettings::default(),
+                attribute_policy: None,
+                line_break_style: LineBreakStyle::Break,
+                defer_tag_conditions: DEFAULT_DEFER_TAG_CONDITIONS,
                 allow_local_paths: true,
+                allow_interwiki_definitions: false,
                 interwiki,
+                url_schemes,
+                overlays: vec![],
             },
             WikitextMode::ForumPost | WikitextMode::DirectMessage => WikitextSettings {
                 mode,
                 layout,
                 enable_page_syntax: false,
+                disabled_rules: vec![],
                 use_include_compatibility: false,
+                max_include_depth: DEFAULT_MAX_INCLUDE_DEPTH,
+                max_include_count: DEFAULT_MAX_INCLUDE_COUNT,
+                max_include_size: DEFAULT_MAX_INCLUDE_SIZE,
                 use_true_ids: false,
                 isolate_user_ids: false,
+                id_isolation: IdIsolationSettings::default(),
+                slugify_heading_ids: DEFAULT_SLUGIFY_HEADING_IDS,
                 minify_css: DEFAULT_MINIFY_CSS,
+                minify_html: false,
+                css: CssSettings::default(),
+                class_prefix: None,
+                syntax_highlighting: DEFAULT_SYNTAX_HIGHLIGHTING,
+                source_spans: DEFAULT_SOURCE_SPANS,
+                error_level: DEFAULT_ERROR_LEVEL,
+                error_kinds: ErrorKindSettings::default(),
+                collect_stats: DEFAULT_COLLECT_STATS,
+                lenient_char_entities: DEFAULT_LENIENT_CHAR_ENTITIES,
+                enable_ruby_shorthand: DEFAULT_ENABLE_RUBY_SHORTHAND,
+                enable_mentions: true,
+                footnote_block: FootnoteBlockPlacement::End,
+                footnote_backlinks: false,
+                link_urls: LinkUrlPlacement::None,
+                email_obfuscation: EmailObfuscation::None,
+                external_links: ExternalLinkSettings::nofollow(),
+                embed: EmbedSettings::default(),
+                iframe: IframeSettings::default(),
+                typography: TypographySettings::default(),
+                max_line_breaks: DEFAULT_MAX_LINE_BREAKS,
+                size: SizeSettings::default(),
+                attribute_policy: None,
+                line_break_style: LineBreakStyle::Break,
+                defer_tag_conditions: DEFAULT_DEFER_TAG_CONDITIONS,
                 allow_local_paths: false,
+                allow_interwiki_definitions: false,
                 interwiki,
+                url_schemes,
+                overlays: vec![],
             },
             WikitextMode::List => WikitextSettings {
                 mode,
                 layout,
                 enable_page_syntax: true,
+                disabled_rules: vec![],
                 use_include_compatibility: false,
+                max_include_depth: DEFAULT_MAX_INCLUDE_DEPTH,
+                max_include_count: DEFAULT_MAX_INCLUDE_COUNT,
+                max_include_size: DEFAULT_MAX_INCLUDE_SIZE,
                 use_true_ids: false,
                 isolate_user_ids: false,
+                id_isolation: IdIsolationSettings::default(),
+                slugify_heading_ids: DEFAULT_SLUGIFY_HEADING_IDS,
                 minify_css: DEFAULT_MINIFY_CSS,
+                minify_html: false,
+                css: CssSettings::default(),
+                class_prefix: None,
+                syntax_highlighting: DEFAULT_SYNTAX_HIGHLIGHTING,
+                source_spans: DEFAULT_SOURCE_SPANS,
+                error_level: DEFAULT_ERROR_LEVEL,
+                error_kinds: ErrorKindSettings::default(),
+                collect_stats: DEFAULT_COLLECT_STATS,
+                lenient_char_entities: DEFAULT_LENIENT_CHAR_ENTITIES,
+                enable_ruby_shorthand: DEFAULT_ENABLE_RUBY_SHORTHAND,
+                enable_mentions: false,
+                footnote_block: FootnoteBlockPlacement::End,
+                footnote_backlinks: false,
+                link_urls: LinkUrlPlacement::None,
+                email_obfuscation: EmailObfuscation::None,
+                external_links: ExternalLinkSettings::default(),
+                embed: EmbedSettings::default(),
+                iframe: IframeSettings::default(),
+                typography: TypographySettings::default(),
+                max_line_breaks: DEFAULT_MAX_LINE_BREAKS,
+                size: SizeSettings::default(),
+                attribute_policy: None,
+                line_break_style: LineBreakStyle::Break,
+                defer_tag_conditions: DEFAULT_DEFER_TAG_CONDITIONS,
                 allow_local_paths: true,
+                allow_interwiki_definitions: false,
                 interwiki,
+                url_schemes,
+                overlays: vec![],
             },
         }
     }
@@ -177,3 +629,106 @@ pub enum WikitextMode {
     /// Processing for modules or other contexts such as `ListPages`.
     List,
 }
+
+/// Where an automatic footnote block is placed.
+///
+/// This only has an effect if the wikitext does not have
+/// a `[[footnoteblock]]` of its own.
+#[derive(Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum FootnoteBlockPlacement {
+    /// Do not add a footnote block.
+    None,
+
+    /// Append the footnote block to the end of the document.
+    End,
+
+    /// Append the footnote block to the end of the last top-level `[[div]]`.
+    ///
+    /// If the document does not end in a `[[div]]`, then it is appended
+    /// to the end of the document instead.
+    LastContainer,
+
+    /// Append the footnote block to the end of the container with this ID.
+    ///
+    /// If no such container exists, then it is appended
+    /// to the end of the document instead.
+    Container(String),
+}
+
+/// Where the target URLs of external links are spelled out, if at all.
+///
+/// Links whose label is the URL itself are never spelled out.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum LinkUrlPlacement {
+    /// Do not spell out link URLs.
+    None,
+
+    /// Add the URL in parentheses after the link label.
+    Inline,
+
+    /// Add a numbered marker after the link label, and list
+    /// the URLs by number at the end of the document.
+    Footnote,
+}
+
+/// How email addresses are obfuscated when rendering HTML.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum EmailObfuscation {
+    /// Render the address as-is.
+    None,
+
+    /// Encode every character of the address as an HTML character reference.
+    Entities,
+
+    /// Render the address reversed, and have CSS display it the right way around.
+    ///
+    /// The address still appears correctly, but copying it yields the reversed text.
+    Reversed,
+
+    /// Have the [`EmailRewriter`] hook render the address.
+    ///
+    /// This allows the host to substitute its own markup, for instance
+    /// a link to a contact form or a script which assembles the address.
+    /// If no hook is provided, the address is rendered as-is.
+    ///
+    /// [`EmailRewriter`]: crate::render::html::EmailRewriter
+    Rewriter,
+}
+
+/// How `[[lines]]` blocks are rendered when using the Wikijump layout.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum LineBreakStyle {
+    /// Render a `<br>` tag for each line.
+    Break,
+
+    /// Render a single element, with the gap as its height.
+    ///
+    /// This allows themes to style the spacing, and avoids
+    /// emitting long runs of tags for large gaps.
+    Margin,
+}
+
+/// How the parser handles malformed syntax.
+///
+/// Regardless of this setting, parsing itself always runs to completion.
+/// This only determines what is done with the result.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ErrorLevel {
+    /// Fall back to text for malformed syntax, and do not report any errors.
+    Lenient,
+
+    /// Fall back to text for malformed syntax, and report each problem as an error.
+    Warn,
+
+    /// Reject the wikitext if it has any errors.
+    ///
+    /// Instead of a degraded syntax tree, an empty one is produced,
+    /// alongside the full list of errors. This is intended for linting,
+    /// for instance checking wikitext in continuous integration.
+    Strict,
+}
@@ -0,0 +1,166 @@
+/*
+ * settings/overlay.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::{
+    CssSettings, EmailObfuscation, EmbedSettings, ErrorKindSettings, ErrorLevel,
+    ExternalLinkSettings, FootnoteBlockPlacement, IdIsolationSettings, IframeSettings,
+    InterwikiSettings, LineBreakStyle, LinkUrlPlacement, SizeSettings,
+    TypographySettings, WikitextSettings,
+};
+use std::borrow::Cow;
+
+macro_rules! settings_overlay {
+    ($($field:ident: $type:ty),* $(,)?) => {
+        /// Changes to [`WikitextSettings`] for pages in particular categories.
+        ///
+        /// Each field other than `categories` overrides the setting of the same
+        /// name, if present. Overlays are listed in the `overlays` setting, and
+        /// are applied in order, so later overlays take precedence over earlier ones.
+        /// See [`WikitextSettings::resolve()`].
+        ///
+        /// When deserializing, any omitted fields leave the setting unchanged.
+        #[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+        #[serde(rename_all = "kebab-case", default)]
+        pub struct SettingsOverlay {
+            /// The page categories this overlay applies to.
+            ///
+            /// Pages with no category are in the `_default` category.
+            pub categories: Vec<String>,
+
+            $(pub $field: Option<$type>,)*
+        }
+
+        impl SettingsOverlay {
+            /// Overrides the given settings with those in this overlay.
+            pub fn apply(&self, settings: &mut WikitextSettings) {
+                $(
+                    if let Some(ref value) = self.$field {
+                        settings.$field = value.clone();
+                    }
+                )*
+            }
+
+            /// Combines another overlay into this one, which takes precedence.
+            ///
+            /// The categories of both overlays are kept.
+            pub fn merge(&mut self, other: &SettingsOverlay) {
+                for category in &other.categories {
+                    if !self.categories.contains(category) {
+                        self.categories.push(category.clone());
+                    }
+                }
+
+                $(
+                    if other.$field.is_some() {
+                        self.$field = other.$field.clone();
+                    }
+                )*
+            }
+        }
+    };
+}
+
+settings_overlay! {
+    enable_page_syntax: bool,
+    disabled_rules: Vec<Cow<'static, str>>,
+    use_include_compatibility: bool,
+    max_include_depth: usize,
+    max_include_count: usize,
+    max_include_size: usize,
+    use_true_ids: bool,
+    isolate_user_ids: bool,
+    id_isolation: IdIsolationSettings,
+    slugify_heading_ids: bool,
+    minify_css: bool,
+    minify_html: bool,
+    css: CssSettings,
+    class_prefix: Option<String>,
+    syntax_highlighting: bool,
+    source_spans: bool,
+    error_level: ErrorLevel,
+    error_kinds: ErrorKindSettings,
+    collect_stats: bool,
+    lenient_char_entities: bool,
+    enable_ruby_shorthand: bool,
+    enable_mentions: bool,
+    footnote_block: FootnoteBlockPlacement,
+    footnote_backlinks: bool,
+    link_urls: LinkUrlPlacement,
+    email_obfuscation: EmailObfuscation,
+    external_links: ExternalLinkSettings,
+    embed: EmbedSettings,
+    iframe: IframeSettings,
+    typography: TypographySettings,
+    max_line_breaks: u32,
+    size: SizeSettings,
+    line_break_style: LineBreakStyle,
+    defer_tag_conditions: bool,
+    allow_local_paths: bool,
+    allow_interwiki_definitions: bool,
+    interwiki: InterwikiSettings,
+    url_schemes: Vec<Cow<'static, str>>,
+}
+
+impl SettingsOverlay {
+    /// Whether this overlay applies to pages in the given category.
+    pub fn matches(&self, category: Option<&str>) -> bool {
+        let category = category.unwrap_or("_default");
+
+        self.categories.iter().any(|name| name == category)
+    }
+}
+
+#[test]
+fn overlay() {
+    use crate::layout::Layout;
+    use crate::settings::WikitextMode;
+
+    let mut settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let mut overlay = SettingsOverlay {
+        categories: vec![str!("fragment")],
+        footnote_block: Some(FootnoteBlockPlacement::None),
+        ..SettingsOverlay::default()
+    };
+
+    assert!(overlay.matches(Some("fragment")));
+    assert!(!overlay.matches(Some("component")));
+    assert!(!overlay.matches(None));
+
+    overlay.merge(&SettingsOverlay {
+        categories: vec![str!("_default")],
+        footnote_block: Some(FootnoteBlockPlacement::LastContainer),
+        max_line_breaks: Some(10),
+        ..SettingsOverlay::default()
+    });
+
+    assert!(overlay.matches(None));
+    assert_eq!(
+        overlay.footnote_block,
+        Some(FootnoteBlockPlacement::LastContainer)
+    );
+
+    overlay.apply(&mut settings);
+    assert_eq!(
+        settings.footnote_block,
+        FootnoteBlockPlacement::LastContainer
+    );
+    assert_eq!(settings.max_line_breaks, 10);
+    assert!(settings.enable_page_syntax);
+}
@@ -0,0 +1,47 @@
+/*
+ * settings/size.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+/// Settings which limit the sizes `[[size]]` blocks may produce.
+///
+/// Sizes larger than these are reduced to the maximum, and
+/// a warning is added to the list of errors.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct SizeSettings {
+    /// The largest absolute size, in pixels.
+    ///
+    /// This also limits sizes in points, with one point being 4/3 pixels.
+    pub max_pixels: u32,
+
+    /// The largest size relative to the surrounding text, as a percentage.
+    ///
+    /// This also limits sizes in `em` and `rem`, so the default
+    /// of `1000` allows up to `1000%` or `10em`.
+    pub max_percent: u32,
+}
+
+impl Default for SizeSettings {
+    fn default() -> Self {
+        SizeSettings {
+            max_pixels: 200,
+            max_percent: 1000,
+        }
+    }
+}
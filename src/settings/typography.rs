@@ -0,0 +1,80 @@
+/*
+ * settings/typography.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use crate::preproc::typography::TypographyProfile;
+
+/// Settings for the typographic substitutions Wikidot performs.
+///
+/// Quotes and ellipses are replaced during [preprocessing](crate::preprocess),
+/// while dashes and double angle quotes are replaced by the parser.
+///
+/// By default, all substitutions are performed, using the quotes
+/// for the page's language.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct TypographySettings {
+    /// Whether `` `` .. '' `` and `` ,, .. '' `` are replaced with double quotes.
+    pub double_quotes: bool,
+
+    /// Whether `` ` .. ' `` is replaced with single quotes.
+    pub single_quotes: bool,
+
+    /// Whether `...` and `. . .` are replaced with an ellipsis (…).
+    pub ellipsis: bool,
+
+    /// Whether `--` is replaced with an em dash (—).
+    pub em_dash: bool,
+
+    /// Whether `<<` and `>>` are replaced with guillemets (« »).
+    pub guillemets: bool,
+
+    /// Which characters the quote substitutions produce.
+    ///
+    /// If this is `None`, then the profile for the page's language is used,
+    /// see [`TypographyProfile::for_locale()`].
+    pub profile: Option<TypographyProfile>,
+}
+
+impl TypographySettings {
+    /// Settings which do not perform any substitutions.
+    pub fn none() -> Self {
+        TypographySettings {
+            double_quotes: false,
+            single_quotes: false,
+            ellipsis: false,
+            em_dash: false,
+            guillemets: false,
+            profile: None,
+        }
+    }
+}
+
+impl Default for TypographySettings {
+    fn default() -> Self {
+        TypographySettings {
+            double_quotes: true,
+            single_quotes: true,
+            ellipsis: true,
+            em_dash: true,
+            guillemets: true,
+            profile: None,
+        }
+    }
+}
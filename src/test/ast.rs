@@ -29,7 +29,7 @@ use crate::layout::Layout;
 use crate::parsing::ParseError;
 use crate::render::html::HtmlRender;
 use crate::render::Render;
-use crate::settings::{WikitextMode, WikitextSettings};
+use crate::settings::{SettingsOverlay, WikitextMode, WikitextSettings};
 use crate::tree::SyntaxTree;
 use once_cell::sync::Lazy;
 use std::borrow::Cow;
@@ -88,10 +88,8 @@ fn only_test_should_skip(name: &str) -> bool {
         }
 
         // Test name prefix
-        if pattern.ends_with('-') {
-            if name.starts_with(pattern) {
-                return false;
-            }
+        if pattern.ends_with('-') && name.starts_with(pattern) {
+            return false;
         }
     }
 
@@ -121,6 +119,10 @@ struct Test<'a> {
     tree: SyntaxTree<'a>,
     errors: Vec<ParseError>,
 
+    /// Changes to the default settings for this test, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    settings: Option<SettingsOverlay>,
+
     #[serde(skip)]
     html: String,
 }
@@ -187,7 +189,7 @@ impl Test<'_> {
             return TestResult::Skip;
         }
 
-        if !ONLY_TESTS.is_empty() && only_test_should_skip(&&*self.name) {
+        if !ONLY_TESTS.is_empty() && only_test_should_skip(&self.name) {
             println!("+ {} [SKIPPED]", self.name);
             return TestResult::Skip;
         }
@@ -210,13 +212,20 @@ impl Test<'_> {
             language: cow!("default"),
         };
 
-        let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+        // Interwiki definitions are opt-in, but should still be covered here.
+        let mut settings = WikitextSettings {
+            allow_interwiki_definitions: true,
+            ..WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot)
+        };
+
+        if let Some(ref overlay) = self.settings {
+            overlay.apply(&mut settings);
+        }
 
-        let (mut text, _pages) =
-            crate::include(&self.input, &settings, TestIncluder, || unreachable!())
-                .unwrap_or_else(|x| match x {});
+        let (mut text, _pages, _errors) =
+            crate::include(&self.input, &settings, TestIncluder);
 
-        crate::preprocess(&mut text);
+        crate::preprocess(&mut text, &page_info, &settings);
         let tokens = crate::tokenize(&text);
         let result = crate::parse(&tokens, &page_info, &settings);
         let (mut tree, errors) = result.into();
@@ -324,7 +333,7 @@ fn ast_and_html() {
             .expect("Unable to get file stem")
             .to_string_lossy();
 
-        let extension = path.extension().map(|s| s.to_str()).flatten();
+        let extension = path.extension().and_then(|s| s.to_str());
         match extension {
             // Load JSON test data
             Some("json") => Some(Test::load(&path, &stem)),
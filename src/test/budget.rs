@@ -0,0 +1,129 @@
+/*
+ * test/budget.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use crate::budget::{BudgetExceeded, WorkBudget};
+use crate::data::PageInfo;
+use crate::layout::Layout;
+use crate::parsing::ParseErrorKind;
+use crate::render::{html::HtmlRender, Render};
+use crate::settings::{WikitextMode, WikitextSettings};
+use crate::tree::{ContainerType, Element};
+
+const INPUT: &str = "\
++ Apple
+
+Some **bold** and //italic// text.
+
+* Banana
+* Cherry
+
+[[div class=\"fruit\"]]
+Durian
+[[/div]]";
+
+#[test]
+fn budget() {
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+
+    // Sufficient budget, same as normal
+    let budget = WorkBudget::new(10_000);
+    let mut text = str!(INPUT);
+    crate::preprocess_with_budget(&mut text, &page_info, &settings, &budget)
+        .expect("Preprocess failed");
+    let tokens = crate::tokenize_with_budget(&text, &budget).expect("Tokenize failed");
+    let (tree, errors) =
+        crate::parse_with_budget(&tokens, &page_info, &settings, &budget).into();
+    let output = HtmlRender
+        .render_with_budget(&tree, &page_info, &settings, &budget)
+        .expect("Render failed");
+
+    assert!(errors.is_empty(), "Errors produced: {errors:#?}");
+    assert!(budget.spent() > 0, "No work was spent");
+
+    let mut expected_text = str!(INPUT);
+    crate::preprocess(&mut expected_text, &page_info, &settings);
+    let expected_tokens = crate::tokenize(&expected_text);
+    let (expected_tree, _) = crate::parse(&expected_tokens, &page_info, &settings).into();
+    let expected_output = HtmlRender.render(&expected_tree, &page_info, &settings);
+
+    assert_eq!(tree, expected_tree, "Budgeted tree does not match");
+    assert_eq!(
+        output.body, expected_output.body,
+        "Budgeted HTML output does not match",
+    );
+
+    // Exhausted during preprocessing
+    let budget = WorkBudget::new(10);
+    let mut text = str!(INPUT);
+    assert_eq!(
+        crate::preprocess_with_budget(&mut text, &page_info, &settings, &budget),
+        Err(BudgetExceeded { limit: 10 }),
+    );
+    assert_eq!(text, INPUT, "Text modified despite exceeding budget");
+
+    // Exhausted during parsing, keeps what was parsed
+    let budget = WorkBudget::new(INPUT.len() * 2 + 12);
+    let mut text = str!(INPUT);
+    crate::preprocess_with_budget(&mut text, &page_info, &settings, &budget)
+        .expect("Preprocess failed");
+    let tokens = crate::tokenize_with_budget(&text, &budget).expect("Tokenize failed");
+    let (partial_tree, errors) =
+        crate::parse_with_budget(&tokens, &page_info, &settings, &budget).into();
+
+    assert!(budget.is_exhausted());
+    assert_eq!(
+        errors.iter().map(|error| error.kind()).collect::<Vec<_>>(),
+        vec![ParseErrorKind::BudgetExceeded],
+    );
+    assert_eq!(
+        partial_tree.elements[0], expected_tree.elements[0],
+        "Heading before budget ran out does not match",
+    );
+
+    // The remainder of the input is text
+    let paragraph = match &partial_tree.elements[1] {
+        Element::Container(container)
+            if container.ctype() == ContainerType::Paragraph =>
+        {
+            container
+        }
+        element => {
+            panic!("Element after budget ran out is not a paragraph: {element:#?}")
+        }
+    };
+    let remainder = match paragraph.elements().last() {
+        Some(Element::Text(text)) => text,
+        element => panic!("Last element is not text: {element:#?}"),
+    };
+    assert!(
+        text.ends_with(remainder.as_ref()) && remainder.ends_with("[[/div]]"),
+        "Remainder is not the rest of the input: {remainder:?}",
+    );
+
+    // Subsequent stages also fail
+    let result = HtmlRender.render_with_budget(&tree, &page_info, &settings, &budget);
+    assert!(result.is_err(), "Rendering succeeded with exhausted budget");
+
+    // Exhausted during rendering
+    let budget = WorkBudget::new(5);
+    let result = HtmlRender.render_with_budget(&tree, &page_info, &settings, &budget);
+    assert!(result.is_err(), "Rendering did not exceed budget");
+}
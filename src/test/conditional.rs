@@ -0,0 +1,124 @@
+/*
+ * test/conditional.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use crate::data::PageInfo;
+use crate::layout::Layout;
+use crate::render::{html::HtmlRender, text::TextRender, Render};
+use crate::settings::{WikitextMode, WikitextSettings};
+use crate::tree::visit::{walk_element, Visitor};
+use crate::tree::Element;
+use std::borrow::Cow;
+
+const INPUT: &str = "\
+[[iftags +fruit -vegetable]]
+Apple
+[[/iftags]]
+
+Banana [[iftags cherry durian]]and //cherry//[[/iftags]]
+
+[[iftags -fruit]]
+Eggplant
+[[/iftags]]";
+
+#[test]
+fn deferred_iftags() {
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let mut deferred_settings = settings.clone();
+    deferred_settings.defer_tag_conditions = true;
+
+    let page_info = PageInfo::dummy();
+    let mut text = str!(INPUT);
+    crate::preprocess(&mut text, &page_info, &settings);
+    let tokens = crate::tokenize(&text);
+
+    // Parse once, without any tags
+    let (deferred_tree, errors) =
+        crate::parse(&tokens, &page_info, &deferred_settings).into();
+    assert!(errors.is_empty(), "Errors produced: {errors:#?}");
+
+    struct ConditionalCounter(usize);
+
+    impl<'t> Visitor<'t> for ConditionalCounter {
+        fn visit_element(&mut self, element: &Element<'t>) {
+            if let Element::Conditional { .. } = element {
+                self.0 += 1;
+            }
+
+            walk_element(self, element);
+        }
+    }
+
+    let mut counter = ConditionalCounter(0);
+    counter.visit_syntax_tree(&deferred_tree);
+    assert_eq!(counter.0, 3, "Conditionals not preserved in tree");
+
+    // Render with different tags, compare against a parse which checks tags
+    for tags in [
+        vec![],
+        vec!["fruit"],
+        vec!["fruit", "cherry"],
+        vec!["fruit", "vegetable", "durian"],
+    ] {
+        let mut page_info = PageInfo::dummy();
+        page_info.tags = tags.iter().map(|tag| Cow::Borrowed(*tag)).collect();
+
+        let (tree, _) = crate::parse(&tokens, &page_info, &settings).into();
+
+        assert_eq!(
+            HtmlRender
+                .render(&deferred_tree, &page_info, &settings)
+                .body,
+            HtmlRender.render(&tree, &page_info, &settings).body,
+            "Deferred HTML output does not match for tags {tags:?}",
+        );
+        assert_eq!(
+            TextRender.render(&deferred_tree, &page_info, &settings),
+            TextRender.render(&tree, &page_info, &settings),
+            "Deferred text output does not match for tags {tags:?}",
+        );
+    }
+}
+
+#[test]
+fn deferred_iftags_footnotes() {
+    let mut settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    settings.defer_tag_conditions = true;
+
+    let mut page_info = PageInfo::dummy();
+    page_info.tags = vec![];
+
+    let mut text =
+        str!("[[iftags +hidden]]A[[footnote]]Xylophone[[/footnote]][[/iftags]] B[[footnote]]Yak[[/footnote]]");
+    crate::preprocess(&mut text, &page_info, &settings);
+    let tokens = crate::tokenize(&text);
+    let (tree, _) = crate::parse(&tokens, &page_info, &settings).into();
+    let output = HtmlRender.render(&tree, &page_info, &settings);
+
+    // The visible footnote still refers to its own contents
+    assert!(output
+        .body
+        .contains("data-id=\"2\">2</wj-footnote-ref-marker>"));
+    assert!(output
+        .body
+        .contains("<span class=\"wj-footnote-ref-contents\">Yak</span>"));
+    assert!(!output
+        .body
+        .contains("data-id=\"1\">1</wj-footnote-ref-marker>"));
+}
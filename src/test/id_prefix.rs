@@ -20,10 +20,16 @@
 
 use crate::data::{PageInfo, ScoreValue};
 use crate::layout::Layout;
-use crate::settings::{WikitextMode, WikitextSettings, EMPTY_INTERWIKI};
+use crate::settings::{
+    CssSettings, EmailObfuscation, EmbedSettings, ErrorKindSettings, ErrorLevel,
+    ExternalLinkSettings, FootnoteBlockPlacement, IdIsolationSettings, IframeSettings,
+    LineBreakStyle, LinkUrlPlacement, SizeSettings, TypographySettings, WikitextMode,
+    WikitextSettings, EMPTY_INTERWIKI,
+};
 use crate::tree::{
     AttributeMap, Container, ContainerType, Element, ImageSource, ListItem, ListType,
 };
+use crate::url::URL_SCHEMES;
 use std::borrow::Cow;
 
 #[test]
@@ -51,16 +57,49 @@ fn isolate_user_ids() {
         language: cow!("default"),
     };
 
-    let settings = WikitextSettings {
+    let mut settings = WikitextSettings {
         mode: WikitextMode::Page,
         layout: Layout::Wikidot,
         enable_page_syntax: true,
+        disabled_rules: vec![],
         use_true_ids: true,
         use_include_compatibility: false,
+        max_include_depth: 5,
+        max_include_count: 500,
+        max_include_size: 4 * 1024 * 1024,
         isolate_user_ids: true,
+        id_isolation: IdIsolationSettings::default(),
+        slugify_heading_ids: false,
         minify_css: false,
+        minify_html: false,
+        css: CssSettings::default(),
+        class_prefix: None,
+        syntax_highlighting: false,
+        source_spans: false,
+        error_level: ErrorLevel::Warn,
+        error_kinds: ErrorKindSettings::default(),
+        collect_stats: false,
+        lenient_char_entities: false,
+        enable_ruby_shorthand: false,
+        enable_mentions: false,
+        footnote_block: FootnoteBlockPlacement::End,
+        footnote_backlinks: false,
+        link_urls: LinkUrlPlacement::None,
+        email_obfuscation: EmailObfuscation::None,
+        external_links: ExternalLinkSettings::default(),
+        embed: EmbedSettings::default(),
+        iframe: IframeSettings::default(),
+        typography: TypographySettings::default(),
+        max_line_breaks: 100,
+        size: SizeSettings::default(),
+        attribute_policy: None,
+        line_break_style: LineBreakStyle::Break,
+        defer_tag_conditions: false,
         allow_local_paths: true,
+        allow_interwiki_definitions: false,
         interwiki: EMPTY_INTERWIKI.clone(),
+        url_schemes: URL_SCHEMES.iter().map(|&scheme| cow!(scheme)).collect(),
+        overlays: vec![],
     };
 
     fn append_footnote_block(mut elements: Vec<Element>) -> Vec<Element> {
@@ -75,7 +114,7 @@ fn isolate_user_ids() {
         ($wikitext:expr, $elements:expr $(,)?) => {{
             let mut text = str!($wikitext);
 
-            crate::preprocess(&mut text);
+            crate::preprocess(&mut text, &page_info, &settings);
             let tokens = crate::tokenize(&text);
             let result = crate::parse(&tokens, &page_info, &settings);
             let (tree, errors) = result.into();
@@ -145,7 +184,9 @@ fn isolate_user_ids() {
                     file: cow!("example.png"),
                 },
                 link: None,
+                link_extra: None,
                 alignment: None,
+                lazy: false,
                 attributes: AttributeMap::from(btreemap! {
                     cow!("class") => cow!("apple"),
                     cow!("id") => cow!("u-banana"),
@@ -163,7 +204,9 @@ fn isolate_user_ids() {
                     file: cow!("example.png"),
                 },
                 link: None,
+                link_extra: None,
                 alignment: None,
+                lazy: false,
                 attributes: AttributeMap::from(btreemap! {
                     cow!("class") => cow!("u-apple"),
                     cow!("id") => cow!("u-banana"),
@@ -312,6 +355,7 @@ fn isolate_user_ids() {
             hide_text: None,
             show_top: true,
             show_bottom: false,
+            remember: false,
         }],
     );
     check!(
@@ -331,6 +375,7 @@ fn isolate_user_ids() {
             hide_text: None,
             show_top: true,
             show_bottom: false,
+            remember: false,
         }],
     );
 
@@ -342,6 +387,8 @@ fn isolate_user_ids() {
                 cow!("id") => cow!("u-apple"),
             }),
             align: None,
+            min_level: None,
+            max_level: None,
         }],
     );
     check!(
@@ -351,6 +398,8 @@ fn isolate_user_ids() {
                 cow!("id") => cow!("u-apple"),
             }),
             align: None,
+            min_level: None,
+            max_level: None,
         }],
     );
 
@@ -373,4 +422,53 @@ fn isolate_user_ids() {
             url: cow!("https://example.com/"),
         }],
     );
+
+    // References between IDs
+    check!(
+        r#"[[span id="apple" aria-labelledby="banana u-cherry" class="durian"]]X[[/span]]"#,
+        vec![Element::Container(Container::new(
+            ContainerType::Paragraph,
+            vec![Element::Container(Container::new(
+                ContainerType::Span,
+                vec![text!("X")],
+                AttributeMap::from(btreemap! {
+                    cow!("aria-labelledby") => cow!("u-banana u-cherry"),
+                    cow!("class") => cow!("durian"),
+                    cow!("id") => cow!("u-apple"),
+                }),
+            ))],
+            AttributeMap::new(),
+        ))],
+    );
+
+    // Custom prefix and attributes
+    settings.id_isolation = IdIsolationSettings {
+        prefix: str!("user-"),
+        attributes: vec![str!("ID"), str!("aria-describedby")],
+    };
+
+    check!(
+        r#"[[span id="apple" aria-describedby="banana" aria-labelledby="cherry"]]X[[/span]]"#,
+        vec![Element::Container(Container::new(
+            ContainerType::Paragraph,
+            vec![Element::Container(Container::new(
+                ContainerType::Span,
+                vec![text!("X")],
+                AttributeMap::from(btreemap! {
+                    cow!("aria-describedby") => cow!("user-banana"),
+                    cow!("aria-labelledby") => cow!("cherry"),
+                    cow!("id") => cow!("user-apple"),
+                }),
+            ))],
+            AttributeMap::new(),
+        ))],
+    );
+    check!(
+        "[[# apple]]",
+        vec![Element::Container(Container::new(
+            ContainerType::Paragraph,
+            vec![Element::AnchorName(cow!("user-apple"))],
+            AttributeMap::new(),
+        ))],
+    );
 }
@@ -37,6 +37,8 @@ My name is __{$name}__:
 [[/blockquote]]
 ";
 
+const COMPONENT_NOTE_PAGE_SOURCE: &str = "[[footnote]]{$note}[[/footnote]]";
+
 const COMPONENT_FRUIT_PAGE_SOURCE: &str = "
 [[div id=\"fruit\" class=\"{$class}\"]]
   [[ul]]
@@ -95,6 +97,7 @@ fn get_page_source(page_ref: &PageRef) -> Option<Cow<'static, str>> {
         "fruit" => Some(cow!(FRUIT_PAGE_SOURCE)),
         "component:basic" => Some(cow!(COMPONENT_BASIC_PAGE_SOURCE)),
         "component:fruit" => Some(cow!(COMPONENT_FRUIT_PAGE_SOURCE)),
+        "component:note" => Some(cow!(COMPONENT_NOTE_PAGE_SOURCE)),
         "fragment:page" => Some(cow!("INCLUDED FRAGMENT")),
         "missing" => None,
         _ => Some(cow!("INCLUDED PAGE")),
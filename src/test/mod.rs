@@ -19,8 +19,15 @@
  */
 
 mod ast;
+#[cfg(feature = "html")]
+mod budget;
+#[cfg(feature = "html")]
+mod conditional;
 mod id_prefix;
 mod includer;
 mod large;
 mod prop;
 mod settings;
+
+#[cfg(feature = "tracing")]
+mod tracing;
@@ -18,6 +18,7 @@
  * along with this program. If not, see <http://www.gnu.org/licenses/>.
  */
 
+use crate::data::wire::SCHEMA_VERSION;
 use crate::data::{PageInfo, PageRef};
 use crate::layout::Layout;
 use crate::render::{html::HtmlRender, text::TextRender, Render};
@@ -25,8 +26,9 @@ use crate::settings::{WikitextMode, WikitextSettings};
 use crate::tree::attribute::SAFE_ATTRIBUTES;
 use crate::tree::{
     Alignment, AnchorTarget, AttributeMap, BibliographyList, ClearFloat, Container,
-    ContainerType, Element, FloatAlignment, Heading, HeadingLevel, ImageSource,
-    LinkLabel, LinkLocation, LinkType, ListItem, ListType, Module, SyntaxTree,
+    ContainerType, Dimension, Element, FloatAlignment, Heading, HeadingLevel,
+    ImageSource, LinkLabel, LinkLocation, LinkType, ListItem, ListType, Module,
+    SyntaxTree,
 };
 use once_cell::sync::Lazy;
 use proptest::option;
@@ -72,7 +74,7 @@ fn arb_attribute_map() -> impl Strategy<Value = AttributeMap<'static>> {
         // Length
         0..12,
     )
-    .prop_map(|map| AttributeMap::from(map))
+    .prop_map(AttributeMap::from)
 }
 
 #[inline]
@@ -99,12 +101,19 @@ fn arb_module() -> impl Strategy<Value = Element<'static>> {
             depth,
         });
 
+    let arguments =
+        || proptest::collection::hash_map(cow!(r"[A-Za-z0-9-]+"), cow!(".*"), 0..4);
+
+    let list_pages = (arguments(), cow!(".*"))
+        .prop_map(|(arguments, body)| Module::ListPages { arguments, body });
+
     prop_oneof![
         Just(Module::Rate),
         arb_optional_str().prop_map(|page| Module::Backlinks { page }),
         any::<bool>().prop_map(|include_hidden| Module::Categories { include_hidden }),
         join,
         page_tree,
+        list_pages,
     ]
     .prop_map(Element::Module)
 }
@@ -190,14 +199,20 @@ fn arb_image() -> impl Strategy<Value = Element<'static>> {
     (
         source,
         option::of(arb_link_location()),
+        arb_optional_str(),
         image_alignment,
+        any::<bool>(),
         arb_attribute_map(),
     )
-        .prop_map(|(source, link, alignment, attributes)| Element::Image {
-            source,
-            link,
-            alignment,
-            attributes,
+        .prop_map(|(source, link, link_extra, alignment, lazy, attributes)| {
+            Element::Image {
+                source,
+                link,
+                link_extra,
+                alignment,
+                lazy,
+                attributes,
+            }
         })
 }
 
@@ -256,6 +271,30 @@ fn arb_checkbox() -> impl Strategy<Value = Element<'static>> {
     })
 }
 
+fn arb_dimension() -> impl Strategy<Value = Dimension> {
+    prop_oneof![
+        Just(Dimension::Auto),
+        r"[0-9]{1,4}(\.[0-9])?(px|em|%|vh)?".prop_map(|value| {
+            Dimension::parse(&value).expect("Generated length is invalid")
+        }),
+    ]
+}
+
+fn arb_html() -> impl Strategy<Value = Element<'static>> {
+    (
+        cow!(".*"),
+        option::of(arb_dimension()),
+        option::of(arb_dimension()),
+        any::<bool>(),
+    )
+        .prop_map(|(contents, width, height, resize)| Element::Html {
+            contents,
+            width,
+            height,
+            resize,
+        })
+}
+
 // Container elements
 
 fn arb_container<S>(elements: S) -> impl Strategy<Value = Element<'static>>
@@ -271,6 +310,7 @@ where
 
     let heading = {
         let has_toc = select!([true, false]);
+        let collapsible = select!([true, false]);
         let level = select!([
             HeadingLevel::One,
             HeadingLevel::Two,
@@ -280,7 +320,11 @@ where
             HeadingLevel::Six,
         ]);
 
-        (level, has_toc).prop_map(|(level, has_toc)| Heading { level, has_toc })
+        (level, has_toc, collapsible).prop_map(|(level, has_toc, collapsible)| Heading {
+            level,
+            has_toc,
+            collapsible,
+        })
     };
 
     let container_type = prop_oneof![
@@ -295,14 +339,15 @@ where
         Just(ContainerType::Div),
         Just(ContainerType::Mark),
         Just(ContainerType::Blockquote),
+        Just(ContainerType::BlockquoteAttribution),
         Just(ContainerType::Insertion),
         Just(ContainerType::Deletion),
         Just(ContainerType::Hidden),
         Just(ContainerType::Invisible),
         Just(ContainerType::Size),
         Just(ContainerType::Paragraph),
-        alignment.prop_map(|align| ContainerType::Align(align)),
-        heading.prop_map(|heading| ContainerType::Header(heading)),
+        alignment.prop_map(ContainerType::Align),
+        heading.prop_map(ContainerType::Header),
     ];
 
     (container_type, elements, arb_attribute_map()).prop_map(
@@ -324,6 +369,7 @@ where
         arb_optional_str(),
         any::<bool>(),
         any::<bool>(),
+        any::<bool>(),
     )
         .prop_map(
             |(
@@ -334,6 +380,7 @@ where
                 hide_text,
                 show_top,
                 show_bottom,
+                remember,
             )| Element::Collapsible {
                 elements,
                 attributes,
@@ -342,6 +389,7 @@ where
                 hide_text,
                 show_top,
                 show_bottom,
+                remember,
             },
         )
 }
@@ -353,14 +401,17 @@ fn arb_element_leaf() -> impl Strategy<Value = Element<'static>> {
         cow!(".*").prop_map(Element::Text),
         cow!(".*").prop_map(Element::Raw),
         cow!(SIMPLE_EMAIL_REGEX).prop_map(Element::Email),
+        cow!("[a-z_]+").prop_map(|name| Element::PageVariable { name }),
         arb_module(),
         arb_link_element(),
         arb_image(),
         // TODO: Element::RadioButton
         arb_checkbox(),
         // TODO: Element::User
+        // TODO: Element::Mention
         arb_code(),
-        cow!(".*").prop_map(|contents| Element::Html { contents }),
+        arb_attribute_map().prop_map(|attributes| Element::Pagination { attributes }),
+        arb_html(),
         // TODO: Element::Iframe
         Just(Element::LineBreak),
         (1..50_u32)
@@ -413,9 +464,13 @@ fn arb_tree() -> impl Strategy<Value = SyntaxTree<'static>> {
                 html_blocks: Vec::new(),
                 code_blocks: Vec::new(), // these two are derived fields
                 table_of_contents,
+                heading_anchors: Vec::new(),
                 footnotes,
                 bibliographies: BibliographyList::new(), // not bothering right now
+                mentions: Vec::new(),
+                element_spans: Vec::new(),
                 wikitext_len,
+                schema_version: SCHEMA_VERSION,
             }
         })
 }
@@ -39,7 +39,7 @@ fn settings() {
         ($mode:expr, $input:expr, $substring:expr, $contains:expr) => {{
             let settings = WikitextSettings::from_mode($mode, Layout::Wikidot);
             let mut text = str!($input);
-            crate::preprocess(&mut text);
+            crate::preprocess(&mut text, &page_info, &settings);
 
             let tokens = crate::tokenize(&text);
             let result = crate::parse(&tokens, &page_info, &settings);
@@ -85,6 +85,11 @@ fn settings() {
         "INCLUDED PAGE",
         [true, true, false, false, true],
     );
+    check!(
+        "[[interwiki prefix=\"fruit\" url=\"https://example.com/$$\"]]\n[[[!fruit:apple]]]",
+        "https://example.com/apple",
+        [false, false, false, false, false],
+    );
     check!(
         "[[image /local-file.png]]",
         "local-file.png",
@@ -101,3 +106,226 @@ fn settings() {
         [true, true, false, false, true],
     );
 }
+
+#[test]
+fn overlays() {
+    use crate::settings::{FootnoteBlockPlacement, SettingsOverlay};
+    use std::borrow::Cow;
+
+    let mut settings =
+        WikitextSettings::from_mode(WikitextMode::ForumPost, Layout::Wikidot);
+    settings.overlays = vec![
+        SettingsOverlay {
+            categories: vec![str!("fragment")],
+            footnote_block: Some(FootnoteBlockPlacement::None),
+            class_prefix: Some(Some(str!("fragment-"))),
+            ..SettingsOverlay::default()
+        },
+        SettingsOverlay {
+            categories: vec![str!("component"), str!("fragment")],
+            enable_page_syntax: Some(true),
+            ..SettingsOverlay::default()
+        },
+    ];
+
+    macro_rules! check {
+        ($category:expr, $substrings:expr $(,)?) => {{
+            let page_info = PageInfo {
+                category: $category.map(|category: &str| Cow::Borrowed(category)),
+                ..PageInfo::dummy()
+            };
+
+            let tokens = crate::tokenize("[[toc]]\n\nText[[footnote]]Note[[/footnote]]");
+            let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+            let html_output = HtmlRender.render(&tree, &page_info, &settings);
+
+            for (substring, contains) in $substrings {
+                assert_eq!(
+                    html_output.body.contains(substring),
+                    contains,
+                    "For category {:?}, HTML output {:?} {} {:?}",
+                    $category,
+                    html_output.body,
+                    if contains {
+                        "doesn't contain"
+                    } else {
+                        "contains"
+                    },
+                    substring,
+                );
+            }
+        }};
+    }
+
+    check!(
+        None::<&str>,
+        [
+            ("Table of Contents", false),
+            ("wj-footnote-list", true),
+            ("fragment-body", false),
+        ],
+    );
+    check!(
+        Some("component"),
+        [
+            ("Table of Contents", true),
+            ("wj-footnote-list", true),
+            ("fragment-body", false),
+        ],
+    );
+    check!(
+        Some("fragment"),
+        [
+            ("Table of Contents", true),
+            ("wj-footnote-list", false),
+            ("fragment-body", true),
+        ],
+    );
+}
+
+#[test]
+fn typography() {
+    use crate::preproc::typography::TypographyProfile;
+    use crate::settings::TypographySettings;
+
+    let page_info = PageInfo::dummy();
+    let input = "``Oui'' -- <<non>>...";
+
+    macro_rules! check {
+        ($typography:expr, $expected:expr $(,)?) => {{
+            let settings = WikitextSettings {
+                typography: $typography,
+                ..WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot)
+            };
+            let mut text = str!(input);
+            crate::preprocess(&mut text, &page_info, &settings);
+
+            let tokens = crate::tokenize(&text);
+            let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+            let html_output = HtmlRender.render(&tree, &page_info, &settings);
+
+            assert_eq!(
+                html_output.body,
+                concat!(
+                    "<wj-body class=\"wj-body\"><p>",
+                    $expected,
+                    "</p></wj-body>"
+                ),
+                "Typography output doesn't match expected",
+            );
+        }};
+    }
+
+    check!(TypographySettings::default(), "“Oui” — «non»…");
+    check!(
+        TypographySettings {
+            profile: Some(TypographyProfile::GERMAN),
+            em_dash: false,
+            ..TypographySettings::default()
+        },
+        "„Oui“ -- «non»…",
+    );
+    check!(
+        TypographySettings::none(),
+        "``Oui&#39;&#39; -- &lt;&lt;non&gt;&gt;...",
+    );
+
+    // Without a profile, quotes follow the page's language
+    let page_info = PageInfo {
+        language: "fr-CA".into(),
+        ..PageInfo::dummy()
+    };
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let mut text = str!(input);
+    crate::preprocess(&mut text, &page_info, &settings);
+    assert_eq!(text, "«\u{a0}Oui\u{a0}» -- <<non>>…");
+}
+
+#[test]
+fn attribute_policy() {
+    use crate::tree::attribute::{
+        AttributeAction, AttributeMap, AttributePolicy, SharedAttributePolicy,
+    };
+    use std::borrow::Cow;
+    use std::collections::HashMap;
+    use unicase::UniCase;
+
+    #[derive(Debug)]
+    struct TestPolicy;
+
+    impl AttributePolicy for TestPolicy {
+        fn check(&self, attribute: &str, value: &str) -> AttributeAction {
+            let attribute = attribute.to_ascii_lowercase();
+
+            if attribute == "title" {
+                return AttributeAction::Deny;
+            }
+
+            if attribute == "onclick" {
+                return AttributeAction::Allow;
+            }
+
+            if attribute.starts_with("data-") {
+                return AttributeAction::Transform(value.to_ascii_uppercase());
+            }
+
+            AttributeAction::Default
+        }
+    }
+
+    let mut arguments = HashMap::new();
+    for (key, value) in [
+        ("class", "box"),
+        ("title", "Box"),
+        ("onclick", "go()"),
+        ("data-name", "value"),
+        ("onload", "run()"),
+    ] {
+        arguments.insert(UniCase::ascii(key), Cow::Borrowed(value));
+    }
+
+    macro_rules! check {
+        ($settings:expr, $expected:expr $(,)?) => {{
+            let attributes = AttributeMap::from_arguments(&arguments, &$settings);
+            let actual: Vec<(&str, &str)> = attributes
+                .get()
+                .iter()
+                .map(|(key, value)| (key.as_ref(), value.as_ref()))
+                .collect();
+
+            assert_eq!(actual, $expected, "Attributes don't match expected");
+        }};
+    }
+
+    let mut settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    check!(
+        settings,
+        vec![("class", "box"), ("data-name", "value"), ("title", "Box")],
+    );
+
+    settings.attribute_policy = Some(SharedAttributePolicy::new(TestPolicy));
+    check!(
+        settings,
+        vec![
+            ("class", "box"),
+            ("data-name", "VALUE"),
+            ("onclick", "go()")
+        ],
+    );
+
+    // Maps which didn't come from the parser are checked the same way
+    let mut attributes = AttributeMap::from(
+        arguments
+            .iter()
+            .map(|(key, value)| (Cow::Borrowed(key.into_inner()), value.clone()))
+            .collect::<std::collections::BTreeMap<_, _>>(),
+    );
+    assert_eq!(attributes.sanitize(&settings), 3);
+    assert_eq!(attributes.get().len(), 3);
+
+    // The policy is not serialized
+    let json = serde_json::to_string(&settings).expect("Unable to serialize settings");
+    let settings: WikitextSettings =
+        serde_json::from_str(&json).expect("Unable to deserialize settings");
+    assert_eq!(settings.attribute_policy, None);
+}
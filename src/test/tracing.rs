@@ -0,0 +1,90 @@
+/*
+ * test/tracing.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use crate::data::PageInfo;
+use crate::layout::Layout;
+use crate::render::{text::TextRender, Render};
+use crate::settings::{WikitextMode, WikitextSettings};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+/// Records the names of all spans which are created.
+#[derive(Debug, Default)]
+struct SpanRecorder {
+    names: Arc<Mutex<Vec<&'static str>>>,
+    next_id: AtomicU64,
+}
+
+impl Subscriber for SpanRecorder {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn new_span(&self, attributes: &Attributes) -> Id {
+        self.names
+            .lock()
+            .unwrap()
+            .push(attributes.metadata().name());
+        Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, _event: &Event) {}
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+#[test]
+fn tracing_spans() {
+    let recorder = SpanRecorder::default();
+    let names = Arc::clone(&recorder.names);
+
+    tracing::subscriber::with_default(recorder, || {
+        let page_info = PageInfo::dummy();
+        let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+
+        let mut text = str!("**Apple** banana...");
+        crate::preprocess(&mut text, &page_info, &settings);
+        let tokens = crate::tokenize(&text);
+        let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+        TextRender.render(&tree, &page_info, &settings);
+    });
+
+    let names = names.lock().unwrap();
+    let position = |name| names.iter().position(|&span| span == name);
+
+    let preprocess = position("preprocess").expect("No preprocess span");
+    let tokenize = position("tokenize").expect("No tokenize span");
+    let parse = position("parse").expect("No parse span");
+    let rule = position("rule").expect("No rule span");
+    let render = position("render").expect("No render span");
+
+    assert!(preprocess < tokenize, "Spans out of order: {names:?}");
+    assert!(tokenize < parse, "Spans out of order: {names:?}");
+    assert!(parse < rule, "Spans out of order: {names:?}");
+    assert!(rule < render, "Spans out of order: {names:?}");
+}
@@ -100,6 +100,13 @@ impl<'t> FullText<'t> {
         &self.text[start..end]
     }
 
+    /// Returns the character immediately before the given token, if any.
+    ///
+    /// This is for rules which must not begin partway through a word.
+    pub fn char_before(&self, token: &ExtractedToken) -> Option<char> {
+        self.text[..token.span.start].chars().next_back()
+    }
+
     /// Gives the length in bytes of the text.
     #[inline]
     pub fn len(&self) -> usize {
@@ -133,6 +140,10 @@ fn slice() {
         let slice = full_text.slice_partial(range!(6..9), range!(12..13));
         assert_eq!(slice, "banana", "Partial slice didn't match expected");
     }
+
+    assert_eq!(full_text.char_before(range!(0..5)), None);
+    assert_eq!(full_text.char_before(range!(6..12)), Some(' '));
+    assert_eq!(full_text.char_before(range!(12..13)), Some('a'));
 }
 
 #[test]
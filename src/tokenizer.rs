@@ -18,8 +18,10 @@
  * along with this program. If not, see <http://www.gnu.org/licenses/>.
  */
 
+use crate::budget::{BudgetExceeded, WorkBudget};
 use crate::parsing::{ExtractedToken, Token};
 use crate::text::FullText;
+use std::ops::Range;
 
 /// Struct that represents both a list of tokens and the text the tokens were generated from.
 #[derive(Debug, Clone)]
@@ -38,8 +40,165 @@ impl<'t> Tokenization<'t> {
     pub(crate) fn full_text(&self) -> FullText<'t> {
         self.full_text
     }
+
+    /// Returns a cursor positioned at the first token.
+    ///
+    /// This is intended for tools which work on the token stream directly,
+    /// such as syntax highlighters or linters.
+    #[inline]
+    pub fn cursor<'r>(&'r self) -> TokenCursor<'r, 't> {
+        TokenCursor {
+            tokens: &self.tokens,
+            full_text: self.full_text,
+            position: 0,
+        }
+    }
+
+    /// Slices the original text from the given start to end token, inclusively.
+    ///
+    /// # Panics
+    /// If the ending token comes before the starting token.
+    #[inline]
+    pub fn slice(&self, start: &ExtractedToken, end: &ExtractedToken) -> &'t str {
+        self.full_text.slice(start, end)
+    }
+
+    /// Returns the original text covered by the given byte range.
+    ///
+    /// Returns `None` if the range is out of bounds or not on character boundaries.
+    #[inline]
+    pub fn slice_span(&self, span: Range<usize>) -> Option<&'t str> {
+        self.full_text.inner().get(span)
+    }
+
+    /// Finds the index of the token containing the given byte offset.
+    ///
+    /// The zero-width `InputStart` and `InputEnd` tokens never contain an offset,
+    /// so this returns `None` if the offset is at or past the end of the text.
+    pub fn token_index_at(&self, offset: usize) -> Option<usize> {
+        let index = self
+            .tokens
+            .partition_point(|token| token.span.end <= offset);
+
+        match self.tokens.get(index) {
+            Some(token) if token.span.contains(&offset) => Some(index),
+            _ => None,
+        }
+    }
+}
+
+/// A cursor over the tokens of a [`Tokenization`], with lookahead.
+///
+/// It iterates over the tokens in order, and also permits peeking ahead
+/// or behind without consuming, and recovering the text between tokens.
+#[derive(Debug, Clone)]
+pub struct TokenCursor<'r, 't> {
+    tokens: &'r [ExtractedToken<'t>],
+    full_text: FullText<'t>,
+    position: usize,
+}
+
+impl<'r, 't> TokenCursor<'r, 't> {
+    /// The index of the next token which will be returned.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Moves the cursor to the given token index.
+    ///
+    /// Positions past the end are clamped, leaving the cursor finished.
+    #[inline]
+    pub fn set_position(&mut self, position: usize) {
+        self.position = position.min(self.tokens.len());
+    }
+
+    /// Whether all tokens have been consumed.
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.position >= self.tokens.len()
+    }
+
+    /// Returns the next token without consuming it.
+    #[inline]
+    pub fn peek(&self) -> Option<&'r ExtractedToken<'t>> {
+        self.peek_nth(0)
+    }
+
+    /// Returns the token `n` positions after the next one, without consuming anything.
+    ///
+    /// So `peek_nth(0)` is the same as `peek()`.
+    #[inline]
+    pub fn peek_nth(&self, n: usize) -> Option<&'r ExtractedToken<'t>> {
+        self.tokens.get(self.position.checked_add(n)?)
+    }
+
+    /// Returns the token `n` positions before the next one.
+    ///
+    /// So `look_behind(1)` is the most recently consumed token.
+    #[inline]
+    pub fn look_behind(&self, n: usize) -> Option<&'r ExtractedToken<'t>> {
+        self.tokens.get(self.position.checked_sub(n)?)
+    }
+
+    /// Whether the next token is of the given type.
+    #[inline]
+    pub fn next_is(&self, token: Token) -> bool {
+        self.peek().is_some_and(|next| next.token == token)
+    }
+
+    /// Consumes and returns the next token, only if it satisfies the predicate.
+    pub fn next_if<F>(&mut self, f: F) -> Option<&'r ExtractedToken<'t>>
+    where
+        F: FnOnce(&ExtractedToken<'t>) -> bool,
+    {
+        let next = self.peek().filter(|next| f(next))?;
+        self.position += 1;
+        Some(next)
+    }
+
+    /// Consumes tokens while they satisfy the predicate, returning how many were consumed.
+    pub fn advance_while<F>(&mut self, mut f: F) -> usize
+    where
+        F: FnMut(&ExtractedToken<'t>) -> bool,
+    {
+        let start = self.position;
+        while self.next_if(&mut f).is_some() {}
+        self.position - start
+    }
+
+    /// Returns the original text from the start of the given token up to
+    /// the next token, that is, everything consumed since that token.
+    ///
+    /// If the cursor is finished, this extends to the end of the text.
+    ///
+    /// # Panics
+    /// If the given token comes after the cursor's position.
+    pub fn slice_from(&self, start: &ExtractedToken) -> &'t str {
+        match self.peek() {
+            Some(next) => self.full_text.slice_partial(start, next),
+            None => &self.full_text.inner()[start.span.start..],
+        }
+    }
 }
 
+impl<'r, 't> Iterator for TokenCursor<'r, 't> {
+    type Item = &'r ExtractedToken<'t>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_if(|_| true)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.tokens.len() - self.position;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for TokenCursor<'_, '_> {}
+
 impl<'t> From<Tokenization<'t>> for Vec<ExtractedToken<'t>> {
     #[inline]
     fn from(tokenization: Tokenization<'t>) -> Vec<ExtractedToken<'t>> {
@@ -48,23 +207,121 @@ impl<'t> From<Tokenization<'t>> for Vec<ExtractedToken<'t>> {
 }
 
 /// Take an input string and produce a list of tokens for consumption by the parser.
-pub fn tokenize(text: &str) -> Tokenization {
+pub fn tokenize(text: &str) -> Tokenization<'_> {
     info!(
         "Running lexer on text ({} bytes) to produce tokens",
         text.len(),
     );
 
+    enter_span!("tokenize", bytes = text.len());
     let tokens = Token::extract_all(text);
     let full_text = FullText::new(text);
 
     Tokenization { tokens, full_text }
 }
 
+/// Tokenize the input string, spending from the given work budget.
+///
+/// See [`WorkBudget`] for more information.
+pub fn tokenize_with_budget<'t>(
+    text: &'t str,
+    budget: &WorkBudget,
+) -> Result<Tokenization<'t>, BudgetExceeded> {
+    budget.spend(text.len())?;
+    Ok(tokenize(text))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use proptest::prelude::*;
 
+    /// Inputs which used to take quadratic time to tokenize, since the lexer
+    /// would scan to the end of the line at every position before falling back.
+    const WORST_CASE_PATTERNS: [&str; 9] =
+        ["[", "-", "+", "*", "\"\\", "{$a|", "[!-", "a.", "a@a-"];
+
+    #[test]
+    fn tokenizer_worst_case() {
+        // Restructuring the lexer shouldn't leave any of the input behind.
+        // See tokenizer_worst_case_growth and benches/tokenizer.rs for timing.
+        for pattern in WORST_CASE_PATTERNS {
+            let text = pattern.repeat(20_000 / pattern.len());
+            let tokenization = tokenize(&text);
+            let last = tokenization.tokens().last().expect("No tokens produced");
+
+            assert_eq!(
+                last.span.end,
+                text.len(),
+                "Tokens do not cover the whole input for pattern {pattern:?}",
+            );
+        }
+    }
+
+    #[test]
+    #[ignore = "slow test"]
+    fn tokenizer_worst_case_growth() {
+        use std::time::{Duration, Instant};
+
+        fn time(text: &str) -> Duration {
+            // Take the fastest of several runs to reduce noise
+            (0..5)
+                .map(|_| {
+                    let start = Instant::now();
+                    let _ = tokenize(text);
+                    start.elapsed()
+                })
+                .min()
+                .unwrap()
+        }
+
+        // Quadrupling the input should take about 4x as long if linear,
+        // and about 16x as long if quadratic.
+        for pattern in WORST_CASE_PATTERNS {
+            let small = pattern.repeat(16_000 / pattern.len());
+            let large = pattern.repeat(64_000 / pattern.len());
+            let growth = time(&large).as_secs_f64() / time(&small).as_secs_f64();
+
+            assert!(
+                growth < 8.0,
+                "Tokenization time grew {growth:.1}x for 4x input with pattern {pattern:?}",
+            );
+        }
+    }
+
+    #[test]
+    fn cursor() {
+        let tokenization = tokenize("**Apple** banana");
+        let mut cursor = tokenization.cursor();
+
+        assert_eq!(cursor.len(), 7);
+        assert!(cursor.next_is(Token::InputStart));
+        assert_eq!(cursor.look_behind(1), None);
+        assert_eq!(cursor.peek_nth(1).map(|t| t.token), Some(Token::Bold));
+
+        cursor.next();
+        let start = cursor.next().expect("No bold token");
+        assert_eq!(start.token, Token::Bold);
+        assert_eq!(cursor.look_behind(1), Some(start));
+        assert!(cursor.next_if(|t| t.token == Token::Whitespace).is_none());
+        assert_eq!(cursor.advance_while(|t| t.token != Token::Whitespace), 2);
+        assert_eq!(cursor.slice_from(start), "**Apple**");
+
+        cursor.set_position(usize::MAX);
+        assert!(cursor.is_finished());
+        assert_eq!(cursor.peek(), None);
+        assert_eq!(cursor.slice_from(start), "**Apple** banana");
+
+        let tokens = tokenization.tokens();
+        assert_eq!(tokenization.slice(&tokens[2], &tokens[5]), "Apple** banana");
+        assert_eq!(tokenization.slice_span(2..7), Some("Apple"));
+        assert_eq!(tokenization.slice_span(10..100), None);
+        assert_eq!(tokenization.token_index_at(0), Some(1));
+        assert_eq!(tokenization.token_index_at(4), Some(2));
+        assert_eq!(tokenization.token_index_at(9), Some(4));
+        assert_eq!(tokenization.token_index_at(16), None);
+    }
+
     proptest! {
         #![proptest_config(ProptestConfig::with_cases(4096))]
 
@@ -40,6 +40,20 @@ impl Alignment {
         }
     }
 
+    /// Parses an alignment from its name, such as `center`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        const ALIGNMENTS: [Alignment; 4] = [
+            Alignment::Left,
+            Alignment::Right,
+            Alignment::Center,
+            Alignment::Justify,
+        ];
+
+        ALIGNMENTS
+            .into_iter()
+            .find(|align| align.name().eq_ignore_ascii_case(name))
+    }
+
     pub fn html_class(self) -> &'static str {
         match self {
             Alignment::Left => "wj-align-left",
@@ -50,6 +64,40 @@ impl Alignment {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum VerticalAlignment {
+    Top,
+    Middle,
+    Bottom,
+    Baseline,
+}
+
+impl VerticalAlignment {
+    pub fn name(self) -> &'static str {
+        match self {
+            VerticalAlignment::Top => "top",
+            VerticalAlignment::Middle => "middle",
+            VerticalAlignment::Bottom => "bottom",
+            VerticalAlignment::Baseline => "baseline",
+        }
+    }
+
+    /// Parses a vertical alignment from its name, such as `middle`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        const ALIGNMENTS: [VerticalAlignment; 4] = [
+            VerticalAlignment::Top,
+            VerticalAlignment::Middle,
+            VerticalAlignment::Bottom,
+            VerticalAlignment::Baseline,
+        ];
+
+        ALIGNMENTS
+            .into_iter()
+            .find(|align| align.name().eq_ignore_ascii_case(name))
+    }
+}
+
 impl TryFrom<&'_ str> for Alignment {
     type Error = ();
 
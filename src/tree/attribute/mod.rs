@@ -18,18 +18,20 @@
  * along with this program. If not, see <http://www.gnu.org/licenses/>.
  */
 
+mod policy;
 mod safe;
 
 use super::clone::string_to_owned;
 use crate::id_prefix::isolate_ids;
 use crate::parsing::parse_boolean;
 use crate::settings::WikitextSettings;
-use crate::url::normalize_href;
+use crate::url::normalize_href_with_settings;
 use std::borrow::Cow;
 use std::collections::{BTreeMap, HashMap};
 use std::fmt::{self, Debug};
 use unicase::UniCase;
 
+pub use self::policy::{AttributeAction, AttributePolicy, SharedAttributePolicy};
 pub use self::safe::{
     is_safe_attribute, BOOLEAN_ATTRIBUTES, SAFE_ATTRIBUTES, SAFE_ATTRIBUTE_PREFIXES,
     URL_ATTRIBUTES,
@@ -47,12 +49,14 @@ impl<'t> AttributeMap<'t> {
         AttributeMap::default()
     }
 
-    pub fn from_arguments(arguments: &HashMap<UniCase<&'t str>, Cow<'t, str>>) -> Self {
+    pub fn from_arguments(
+        arguments: &HashMap<UniCase<&'t str>, Cow<'t, str>>,
+        settings: &WikitextSettings,
+    ) -> Self {
         let inner = arguments
             .iter()
-            .filter(|(&key, _)| is_safe_attribute(key))
             .filter_map(|(key, value)| {
-                let mut value = Cow::clone(value);
+                let mut value = check_attribute(*key, Cow::clone(value), settings)?;
 
                 // Check for special boolean behavior
                 if BOOLEAN_ATTRIBUTES.contains(key) {
@@ -70,7 +74,9 @@ impl<'t> AttributeMap<'t> {
 
                 // Check for URL-sensitive attributes
                 if URL_ATTRIBUTES.contains(key) {
-                    value = Cow::Owned(normalize_href(&value).into_owned())
+                    value = Cow::Owned(
+                        normalize_href_with_settings(&value, settings).into_owned(),
+                    )
                 }
 
                 // Add key/value pair to map
@@ -102,11 +108,78 @@ impl<'t> AttributeMap<'t> {
         &self.inner
     }
 
+    /// Removes any unsafe attributes, and normalizes URL-sensitive ones.
+    ///
+    /// This applies the same checks as `from_arguments()` to an existing map,
+    /// for instance one which was deserialized rather than produced by the parser.
+    /// Returns the number of attributes which were removed or changed.
+    pub fn sanitize(&mut self, settings: &WikitextSettings) -> usize {
+        let mut changed = 0;
+
+        self.inner.retain(|key, value| {
+            let key = UniCase::ascii(key.as_ref());
+
+            match check_attribute(key, Cow::clone(value), settings) {
+                None => {
+                    warn!("Removing unsafe attribute '{key}'");
+                    changed += 1;
+                    return false;
+                }
+                Some(checked) => {
+                    if checked != *value {
+                        *value = Cow::Owned(checked.into_owned());
+                        changed += 1;
+                    }
+                }
+            }
+
+            if URL_ATTRIBUTES.contains(&key) {
+                let normalized = normalize_href_with_settings(value, settings);
+                if normalized != value.as_ref() {
+                    let normalized = normalized.into_owned();
+                    *value = Cow::Owned(normalized);
+                    changed += 1;
+                }
+            }
+
+            true
+        });
+
+        changed
+    }
+
+    /// Isolates the value of the `id` attribute, if ID isolation is enabled.
+    ///
+    /// Unlike [`isolate_ids()`](Self::isolate_ids), this only considers
+    /// the `id` attribute, regardless of `id_isolation.attributes`.
+    #[deprecated(
+        since = "1.29.0",
+        note = "use isolate_ids() to isolate all configured attributes"
+    )]
     pub fn isolate_id(&mut self, settings: &WikitextSettings) {
         if settings.isolate_user_ids {
             if let Some(value) = self.inner.get_mut("id") {
                 trace!("Found 'id' attribute, isolating value");
-                *value = Cow::Owned(isolate_ids(value));
+                *value = Cow::Owned(isolate_ids(value, &settings.id_isolation.prefix));
+            }
+        }
+    }
+
+    pub fn isolate_ids(&mut self, settings: &WikitextSettings) {
+        if !settings.isolate_user_ids {
+            return;
+        }
+
+        let isolation = &settings.id_isolation;
+        for (key, value) in self.inner.iter_mut() {
+            let isolated = isolation
+                .attributes
+                .iter()
+                .any(|attribute| attribute.eq_ignore_ascii_case(key));
+
+            if isolated {
+                trace!("Found '{key}' attribute, isolating value");
+                *value = Cow::Owned(isolate_ids(value, &isolation.prefix));
             }
         }
     }
@@ -138,3 +211,26 @@ impl<'t> From<BTreeMap<Cow<'t, str>, Cow<'t, str>>> for AttributeMap<'t> {
         AttributeMap { inner: map }
     }
 }
+
+/// Determines if an attribute is permitted, and what its value should be.
+///
+/// If the settings have an attribute policy, it is consulted first,
+/// otherwise only attributes which are safe by default are permitted.
+fn check_attribute<'t>(
+    key: UniCase<&str>,
+    value: Cow<'t, str>,
+    settings: &WikitextSettings,
+) -> Option<Cow<'t, str>> {
+    let action = match settings.attribute_policy {
+        Some(ref policy) => policy.check(key.into_inner(), &value),
+        None => AttributeAction::Default,
+    };
+
+    match action {
+        AttributeAction::Default if is_safe_attribute(key) => Some(value),
+        AttributeAction::Default => None,
+        AttributeAction::Allow => Some(value),
+        AttributeAction::Deny => None,
+        AttributeAction::Transform(value) => Some(Cow::Owned(value)),
+    }
+}
@@ -0,0 +1,90 @@
+/*
+ * tree/attribute/policy.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::fmt::{self, Debug};
+use std::sync::Arc;
+
+/// A trait which decides which attributes users may set.
+///
+/// By default, only the attributes in [`SAFE_ATTRIBUTES`](super::SAFE_ATTRIBUTES),
+/// and those beginning with a prefix in [`SAFE_ATTRIBUTE_PREFIXES`](super::SAFE_ATTRIBUTE_PREFIXES),
+/// are permitted. A policy may permit others, forbid some of these, or change their values.
+/// It is set via the `attribute_policy` field of `WikitextSettings`.
+///
+/// Permitting an attribute bypasses the built-in safety checks, so care should be taken
+/// with those which can run scripts or load resources, such as `onclick` or `srcset`.
+pub trait AttributePolicy: Debug + Send + Sync {
+    /// Decides what to do with the given attribute.
+    ///
+    /// The attribute name is as the user wrote it, so it should be
+    /// compared case-insensitively. This is called for each attribute
+    /// before the default checks are performed.
+    fn check(&self, attribute: &str, value: &str) -> AttributeAction;
+}
+
+/// What an [`AttributePolicy`] decided to do with an attribute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttributeAction {
+    /// Permit the attribute only if it is safe by default.
+    Default,
+
+    /// Permit the attribute, even if it is not safe by default.
+    Allow,
+
+    /// Remove the attribute, even if it is safe by default.
+    Deny,
+
+    /// Permit the attribute, replacing its value with this one.
+    Transform(String),
+}
+
+/// A shared [`AttributePolicy`], as held in `WikitextSettings`.
+///
+/// Settings containing the same policy compare as equal.
+#[derive(Clone)]
+pub struct SharedAttributePolicy(pub Arc<dyn AttributePolicy>);
+
+impl SharedAttributePolicy {
+    #[inline]
+    pub fn new<P: AttributePolicy + 'static>(policy: P) -> Self {
+        SharedAttributePolicy(Arc::new(policy))
+    }
+
+    #[inline]
+    pub fn check(&self, attribute: &str, value: &str) -> AttributeAction {
+        self.0.check(attribute, value)
+    }
+}
+
+impl Debug for SharedAttributePolicy {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl PartialEq for SharedAttributePolicy {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for SharedAttributePolicy {}
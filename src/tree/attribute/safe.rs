@@ -122,6 +122,7 @@ pub static SAFE_ATTRIBUTES: Lazy<HashSet<UniCase<&'static str>>> = Lazy::new(||
         "translate",
         "type",
         "usemap",
+        "valign",
         "value",
         "width",
         "wrap",
@@ -177,7 +178,7 @@ pub static BOOLEAN_ATTRIBUTES: Lazy<HashSet<UniCase<&'static str>>> = Lazy::new(
 ///
 /// ## See also
 /// * `detect_dangerous_schemes()`
-/// * `normalize_href()`
+/// * `normalize_href_with_settings()`
 pub static URL_ATTRIBUTES: Lazy<HashSet<UniCase<&'static str>>> =
     Lazy::new(|| hashset_unicase!["href", "src",]);
 
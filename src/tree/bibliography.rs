@@ -30,14 +30,61 @@ use super::clone::{elements_to_owned, string_to_owned};
 use super::Element;
 use std::borrow::Cow;
 
+/// How citations of a bibliography's references are displayed.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum CitationStyle {
+    /// References are numbered in order, and cited as `[1]`.
+    #[default]
+    Numeric,
+
+    /// References are cited by their label, such as `(Smith 2020)`.
+    AuthorYear,
+}
+
+impl CitationStyle {
+    pub fn name(self) -> &'static str {
+        match self {
+            CitationStyle::Numeric => "numeric",
+            CitationStyle::AuthorYear => "author-year",
+        }
+    }
+
+    /// Parses a citation style from its name, such as `author-year`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        const STYLES: [CitationStyle; 2] =
+            [CitationStyle::Numeric, CitationStyle::AuthorYear];
+
+        STYLES
+            .into_iter()
+            .find(|style| style.name().eq_ignore_ascii_case(name))
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
-pub struct Bibliography<'t>(Vec<(Cow<'t, str>, Vec<Element<'t>>)>);
+#[serde(rename_all = "kebab-case")]
+pub struct Bibliography<'t> {
+    style: CitationStyle,
+    references: Vec<(Cow<'t, str>, Vec<Element<'t>>)>,
+}
 
 impl<'t> Bibliography<'t> {
     pub fn new() -> Self {
         Bibliography::default()
     }
 
+    pub fn with_style(style: CitationStyle) -> Self {
+        Bibliography {
+            style,
+            references: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn style(&self) -> CitationStyle {
+        self.style
+    }
+
     pub fn add(&mut self, label: Cow<'t, str>, elements: Vec<Element<'t>>) {
         // If the reference already exists, it is *not* overwritten.
         //
@@ -48,7 +95,7 @@ impl<'t> Bibliography<'t> {
             return;
         }
 
-        self.0.push((label, elements));
+        self.references.push((label, elements));
     }
 
     pub fn get(&self, label: &str) -> Option<(usize, &[Element<'t>])> {
@@ -59,7 +106,7 @@ impl<'t> Bibliography<'t> {
         //
         // This also gives us free indexing based on this order, and the
         // order based on it, so we don't need a two-index map here.
-        for (index, (ref_label, elements)) in self.0.iter().enumerate() {
+        for (index, (ref_label, elements)) in self.references.iter().enumerate() {
             if label == ref_label {
                 // Change from zero-indexing to one-indexing
                 return Some((index + 1, elements));
@@ -71,18 +118,25 @@ impl<'t> Bibliography<'t> {
 
     #[inline]
     pub fn slice(&self) -> &[(Cow<'t, str>, Vec<Element<'t>>)] {
-        &self.0
+        &self.references
+    }
+
+    #[inline]
+    pub fn slice_mut(&mut self) -> &mut [(Cow<'t, str>, Vec<Element<'t>>)] {
+        &mut self.references
     }
 
     pub fn to_owned(&self) -> Bibliography<'static> {
-        Bibliography(
-            self.0
+        Bibliography {
+            style: self.style,
+            references: self
+                .references
                 .iter()
                 .map(|(label, elements)| {
                     (string_to_owned(label), elements_to_owned(elements))
                 })
                 .collect(),
-        )
+        }
     }
 }
 
@@ -107,20 +161,34 @@ impl<'t> BibliographyList<'t> {
         self.0.len()
     }
 
-    pub fn get_reference(&self, label: &str) -> Option<(usize, &[Element<'t>])> {
+    #[inline]
+    pub fn slice(&self) -> &[Bibliography<'t>] {
+        &self.0
+    }
+
+    #[inline]
+    pub fn slice_mut(&mut self) -> &mut [Bibliography<'t>] {
+        &mut self.0
+    }
+
+    /// Finds the reference with the given label, and the citation style of its bibliography.
+    pub fn get_reference(
+        &self,
+        label: &str,
+    ) -> Option<(usize, &[Element<'t>], CitationStyle)> {
         for bibliography in &self.0 {
             // Find the first entry with the label, per the above invariant.
-            let reference = bibliography.get(label);
-            if reference.is_some() {
-                return reference;
+            if let Some((index, elements)) = bibliography.get(label) {
+                return Some((index, elements, bibliography.style));
             }
         }
 
         None
     }
 
-    pub fn get_bibliography(&self, index: usize) -> &Bibliography<'t> {
-        &self.0[index]
+    #[inline]
+    pub fn get_bibliography(&self, index: usize) -> Option<&Bibliography<'t>> {
+        self.0.get(index)
     }
 
     pub fn to_owned(&self) -> BibliographyList<'static> {
@@ -0,0 +1,1084 @@
+/*
+ * tree/binary.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Compact binary serialization of syntax trees.
+//!
+//! JSON is convenient, but bulky when storing large numbers of parsed trees,
+//! since the same keys (such as `"element"` and `"data"`) and values (such as
+//! `"paragraph"` or attribute names) are repeated throughout.
+//!
+//! This format stores each distinct string once, in a table at the start,
+//! and then refers to them by index. Integers and lengths are stored as
+//! variable-length integers. The tree has the same structure as its JSON
+//! serialization, so the two formats always agree.
+//!
+//! The layout is:
+//! * The magic bytes `FTML`.
+//! * The format version, as a single byte. See [`BINARY_FORMAT_VERSION`].
+//! * The number of strings, followed by each string as a length and UTF-8 bytes.
+//! * The value of the tree itself.
+
+use super::SyntaxTree;
+use crate::data::wire;
+use serde::ser::{self, Serialize};
+use serde_json::{Map, Number, Value};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{self, Display};
+
+const MAGIC: &[u8; 4] = b"FTML";
+
+/// The current version of the binary format.
+///
+/// This is incremented whenever the format changes incompatibly.
+/// Trees serialized with a different version cannot be read, and should
+/// be re-parsed from their wikitext instead.
+pub const BINARY_FORMAT_VERSION: u8 = 1;
+
+/// The maximum nesting of values permitted when reading.
+///
+/// Each nested element takes about three levels, so this is above what
+/// the parser's recursion limit can produce. It is low enough that
+/// deserializing the tree, which is also recursive, does not exhaust
+/// the stack of a typical thread.
+const MAX_DEPTH: usize = 384;
+
+// Value type tags
+const TAG_NULL: u8 = 0;
+const TAG_FALSE: u8 = 1;
+const TAG_TRUE: u8 = 2;
+const TAG_UNSIGNED: u8 = 3;
+const TAG_NEGATIVE: u8 = 4;
+const TAG_FLOAT: u8 = 5;
+const TAG_STRING: u8 = 6;
+const TAG_ARRAY: u8 = 7;
+const TAG_OBJECT: u8 = 8;
+
+impl SyntaxTree<'_> {
+    /// Serializes this tree into a compact binary format.
+    ///
+    /// This has the same structure as the JSON serialization, but stores
+    /// each distinct string only once, making it considerably smaller.
+    /// The output is versioned, see [`BINARY_FORMAT_VERSION`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut encoder = Encoder::default();
+        self.serialize(&mut encoder)
+            .expect("Unable to serialize syntax tree");
+
+        let mut strings = vec![""; encoder.indices.len()];
+        for (string, &index) in &encoder.indices {
+            strings[index as usize] = string;
+        }
+
+        let mut output = Vec::with_capacity(encoder.body.len() + encoder.table_len + 16);
+        output.extend_from_slice(MAGIC);
+        output.push(BINARY_FORMAT_VERSION);
+        write_varint(&mut output, strings.len() as u64);
+
+        for string in strings {
+            write_varint(&mut output, string.len() as u64);
+            output.extend_from_slice(string.as_bytes());
+        }
+
+        output.extend_from_slice(&encoder.body);
+        output
+    }
+
+    /// Deserializes a tree from the compact binary format.
+    pub fn from_bytes(bytes: &[u8]) -> Result<SyntaxTree<'static>, BinaryError> {
+        let value = read_value(bytes)?;
+
+        // Trees from older schema versions are upgraded
+        wire::from_value(value)
+            .map_err(|error| BinaryError::Deserialize(error.to_string()))
+    }
+}
+
+/// Reads the value stored in the binary format, without converting it to a tree.
+fn read_value(bytes: &[u8]) -> Result<Value, BinaryError> {
+    let mut decoder = Decoder {
+        bytes,
+        position: 0,
+        strings: Vec::new(),
+    };
+
+    // Check header
+    if decoder.read_bytes(MAGIC.len())? != MAGIC {
+        return Err(BinaryError::InvalidHeader);
+    }
+
+    let version = decoder.read_byte()?;
+    if version != BINARY_FORMAT_VERSION {
+        return Err(BinaryError::UnsupportedVersion(version));
+    }
+
+    // Read string table
+    let count = decoder.read_length()?;
+    for _ in 0..count {
+        let length = decoder.read_length()?;
+        let string = decoder.read_bytes(length)?;
+        let string = std::str::from_utf8(string).map_err(|_| BinaryError::InvalidData)?;
+        decoder.strings.push(string);
+    }
+
+    // Read tree
+    let value = decoder.decode_value(0)?;
+    if decoder.position != bytes.len() {
+        return Err(BinaryError::InvalidData);
+    }
+
+    Ok(value)
+}
+
+/// Error returned when a tree cannot be read from the binary format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BinaryError {
+    /// The data does not start with the expected header.
+    InvalidHeader,
+
+    /// The data was written with a different version of the format.
+    UnsupportedVersion(u8),
+
+    /// The data ended unexpectedly.
+    Truncated,
+
+    /// The data is malformed.
+    InvalidData,
+
+    /// The data is well-formed, but is not a valid syntax tree.
+    Deserialize(String),
+}
+
+impl Display for BinaryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BinaryError::InvalidHeader => write!(f, "invalid binary tree header"),
+            BinaryError::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported binary tree version {version} (expected {BINARY_FORMAT_VERSION})",
+            ),
+            BinaryError::Truncated => write!(f, "binary tree data is truncated"),
+            BinaryError::InvalidData => write!(f, "binary tree data is malformed"),
+            BinaryError::Deserialize(error) => {
+                write!(f, "unable to deserialize syntax tree: {error}")
+            }
+        }
+    }
+}
+
+impl Error for BinaryError {}
+
+/// Serializes values directly into the binary format.
+///
+/// The output is the same as encoding the value produced by
+/// [`serde_json::to_value()`], without building that value first.
+#[derive(Debug, Default)]
+struct Encoder {
+    body: Vec<u8>,
+    indices: HashMap<String, u64>,
+    table_len: usize,
+
+    /// The arrays and objects currently being serialized, innermost last.
+    ///
+    /// This is kept here rather than in [`Compound`], since the derived
+    /// serialization code has a slot for it in each variant of large enums,
+    /// which would otherwise use a lot of stack for deeply nested trees.
+    pending: Vec<Pending>,
+}
+
+impl Encoder {
+    fn intern(&mut self, string: &str) -> u64 {
+        if let Some(&index) = self.indices.get(string) {
+            return index;
+        }
+
+        let index = self.indices.len() as u64;
+        self.indices.insert(str!(string), index);
+        self.table_len += string.len() + 1;
+        index
+    }
+
+    fn write_string(&mut self, string: &str) {
+        let index = self.intern(string);
+        write_varint(&mut self.body, index);
+    }
+
+    fn write_signed(&mut self, value: i64) {
+        if value >= 0 {
+            self.body.push(TAG_UNSIGNED);
+            write_varint(&mut self.body, value as u64);
+        } else {
+            // Store negative values as -(n + 1)
+            self.body.push(TAG_NEGATIVE);
+            write_varint(&mut self.body, !(value as u64));
+        }
+    }
+
+    fn write_unsigned(&mut self, value: u64) {
+        self.body.push(TAG_UNSIGNED);
+        write_varint(&mut self.body, value);
+    }
+
+    fn write_float(&mut self, value: f64) {
+        // JSON has no representation for these
+        if !value.is_finite() {
+            self.body.push(TAG_NULL);
+            return;
+        }
+
+        self.body.push(TAG_FLOAT);
+        self.body.extend_from_slice(&value.to_le_bytes());
+    }
+
+    /// Starts an array or object, as with [`Compound`].
+    fn start(&mut self, tag: u8, length: Option<usize>) -> Compound<'_> {
+        self.body.push(tag);
+
+        // If the length isn't known ahead of time, it is inserted at the end
+        let length_position = match length {
+            Some(length) => {
+                write_varint(&mut self.body, length as u64);
+                None
+            }
+            None => Some(self.body.len()),
+        };
+
+        self.pending.push(Pending {
+            length_position,
+            expected: length,
+            count: 0,
+        });
+
+        Compound(self)
+    }
+
+    /// Starts the single-entry object which wraps an externally tagged variant.
+    fn start_variant(&mut self, variant: &str) {
+        self.body.push(TAG_OBJECT);
+        write_varint(&mut self.body, 1);
+        self.write_string(variant);
+    }
+}
+
+/// An array or object being serialized.
+#[derive(Debug)]
+struct Compound<'e>(&'e mut Encoder);
+
+impl Compound<'_> {
+    fn count_item(&mut self) {
+        if let Some(pending) = self.0.pending.last_mut() {
+            pending.count += 1;
+        }
+    }
+
+    fn finish(self) -> Result<(), EncodeError> {
+        let pending = self.0.pending.pop().expect("No array or object started");
+
+        match pending.length_position {
+            Some(position) => {
+                let mut length = Vec::new();
+                write_varint(&mut length, pending.count as u64);
+                self.0.body.splice(position..position, length);
+                Ok(())
+            }
+            None if pending.expected == Some(pending.count) => Ok(()),
+            None => Err(encode_error("length does not match number of items")),
+        }
+    }
+}
+
+/// The state of an array or object being serialized.
+#[derive(Debug)]
+struct Pending {
+    length_position: Option<usize>,
+    expected: Option<usize>,
+    count: usize,
+}
+
+impl<'e> ser::Serializer for &'e mut Encoder {
+    type Ok = ();
+    type Error = EncodeError;
+    type SerializeSeq = Compound<'e>;
+    type SerializeTuple = Compound<'e>;
+    type SerializeTupleStruct = Compound<'e>;
+    type SerializeTupleVariant = Compound<'e>;
+    type SerializeMap = Compound<'e>;
+    type SerializeStruct = Compound<'e>;
+    type SerializeStructVariant = Compound<'e>;
+
+    fn serialize_bool(self, value: bool) -> Result<(), EncodeError> {
+        self.body.push(if value { TAG_TRUE } else { TAG_FALSE });
+        Ok(())
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<(), EncodeError> {
+        self.serialize_i64(i64::from(value))
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<(), EncodeError> {
+        self.serialize_i64(i64::from(value))
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<(), EncodeError> {
+        self.serialize_i64(i64::from(value))
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<(), EncodeError> {
+        self.write_signed(value);
+        Ok(())
+    }
+
+    fn serialize_i128(self, value: i128) -> Result<(), EncodeError> {
+        match (i64::try_from(value), u64::try_from(value)) {
+            (Ok(value), _) => self.write_signed(value),
+            (_, Ok(value)) => self.write_unsigned(value),
+            _ => return Err(encode_error("number out of range")),
+        }
+
+        Ok(())
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<(), EncodeError> {
+        self.serialize_u64(u64::from(value))
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<(), EncodeError> {
+        self.serialize_u64(u64::from(value))
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<(), EncodeError> {
+        self.serialize_u64(u64::from(value))
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<(), EncodeError> {
+        self.write_unsigned(value);
+        Ok(())
+    }
+
+    fn serialize_u128(self, value: u128) -> Result<(), EncodeError> {
+        let value =
+            u64::try_from(value).map_err(|_| encode_error("number out of range"))?;
+
+        self.serialize_u64(value)
+    }
+
+    fn serialize_f32(self, value: f32) -> Result<(), EncodeError> {
+        self.serialize_f64(f64::from(value))
+    }
+
+    fn serialize_f64(self, value: f64) -> Result<(), EncodeError> {
+        self.write_float(value);
+        Ok(())
+    }
+
+    fn serialize_char(self, value: char) -> Result<(), EncodeError> {
+        self.serialize_str(value.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, value: &str) -> Result<(), EncodeError> {
+        self.body.push(TAG_STRING);
+        self.write_string(value);
+        Ok(())
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<(), EncodeError> {
+        let mut array = self.start(TAG_ARRAY, Some(value.len()));
+        for byte in value {
+            ser::SerializeSeq::serialize_element(&mut array, byte)?;
+        }
+
+        array.finish()
+    }
+
+    fn serialize_none(self) -> Result<(), EncodeError> {
+        self.serialize_unit()
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<(), EncodeError>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), EncodeError> {
+        self.body.push(TAG_NULL);
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), EncodeError> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<(), EncodeError> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), EncodeError>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), EncodeError>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.start_variant(variant);
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, length: Option<usize>) -> Result<Compound<'e>, EncodeError> {
+        Ok(self.start(TAG_ARRAY, length))
+    }
+
+    fn serialize_tuple(self, length: usize) -> Result<Compound<'e>, EncodeError> {
+        Ok(self.start(TAG_ARRAY, Some(length)))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        length: usize,
+    ) -> Result<Compound<'e>, EncodeError> {
+        Ok(self.start(TAG_ARRAY, Some(length)))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        length: usize,
+    ) -> Result<Compound<'e>, EncodeError> {
+        self.start_variant(variant);
+        Ok(self.start(TAG_ARRAY, Some(length)))
+    }
+
+    fn serialize_map(self, length: Option<usize>) -> Result<Compound<'e>, EncodeError> {
+        Ok(self.start(TAG_OBJECT, length))
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        length: usize,
+    ) -> Result<Compound<'e>, EncodeError> {
+        Ok(self.start(TAG_OBJECT, Some(length)))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        length: usize,
+    ) -> Result<Compound<'e>, EncodeError> {
+        self.start_variant(variant);
+        Ok(self.start(TAG_OBJECT, Some(length)))
+    }
+}
+
+impl ser::SerializeSeq for Compound<'_> {
+    type Ok = ();
+    type Error = EncodeError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), EncodeError>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.count_item();
+        value.serialize(&mut *self.0)
+    }
+
+    fn end(self) -> Result<(), EncodeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for Compound<'_> {
+    type Ok = ();
+    type Error = EncodeError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), EncodeError>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.count_item();
+        value.serialize(&mut *self.0)
+    }
+
+    fn end(self) -> Result<(), EncodeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for Compound<'_> {
+    type Ok = ();
+    type Error = EncodeError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), EncodeError>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.count_item();
+        value.serialize(&mut *self.0)
+    }
+
+    fn end(self) -> Result<(), EncodeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for Compound<'_> {
+    type Ok = ();
+    type Error = EncodeError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), EncodeError>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.count_item();
+        value.serialize(&mut *self.0)
+    }
+
+    fn end(self) -> Result<(), EncodeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeMap for Compound<'_> {
+    type Ok = ();
+    type Error = EncodeError;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), EncodeError>
+    where
+        T: ?Sized + Serialize,
+    {
+        key.serialize(KeyEncoder(self.0))
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), EncodeError>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.count_item();
+        value.serialize(&mut *self.0)
+    }
+
+    fn end(self) -> Result<(), EncodeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for Compound<'_> {
+    type Ok = ();
+    type Error = EncodeError;
+
+    fn serialize_field<T>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), EncodeError>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.0.write_string(key);
+        self.count_item();
+        value.serialize(&mut *self.0)
+    }
+
+    fn end(self) -> Result<(), EncodeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for Compound<'_> {
+    type Ok = ();
+    type Error = EncodeError;
+
+    fn serialize_field<T>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), EncodeError>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.0.write_string(key);
+        self.count_item();
+        value.serialize(&mut *self.0)
+    }
+
+    fn end(self) -> Result<(), EncodeError> {
+        self.finish()
+    }
+}
+
+/// Serializes object keys, which must be strings.
+///
+/// As with JSON, numbers used as keys are converted into strings.
+#[derive(Debug)]
+struct KeyEncoder<'e>(&'e mut Encoder);
+
+impl KeyEncoder<'_> {
+    fn write_display(self, value: impl Display) -> Result<(), EncodeError> {
+        self.0.write_string(&value.to_string());
+        Ok(())
+    }
+}
+
+impl ser::Serializer for KeyEncoder<'_> {
+    type Ok = ();
+    type Error = EncodeError;
+    type SerializeSeq = ser::Impossible<(), EncodeError>;
+    type SerializeTuple = ser::Impossible<(), EncodeError>;
+    type SerializeTupleStruct = ser::Impossible<(), EncodeError>;
+    type SerializeTupleVariant = ser::Impossible<(), EncodeError>;
+    type SerializeMap = ser::Impossible<(), EncodeError>;
+    type SerializeStruct = ser::Impossible<(), EncodeError>;
+    type SerializeStructVariant = ser::Impossible<(), EncodeError>;
+
+    fn serialize_str(self, value: &str) -> Result<(), EncodeError> {
+        self.0.write_string(value);
+        Ok(())
+    }
+
+    fn serialize_char(self, value: char) -> Result<(), EncodeError> {
+        self.serialize_str(value.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<(), EncodeError> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), EncodeError>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_bool(self, value: bool) -> Result<(), EncodeError> {
+        self.write_display(value)
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<(), EncodeError> {
+        self.write_display(value)
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<(), EncodeError> {
+        self.write_display(value)
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<(), EncodeError> {
+        self.write_display(value)
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<(), EncodeError> {
+        self.write_display(value)
+    }
+
+    fn serialize_i128(self, value: i128) -> Result<(), EncodeError> {
+        self.write_display(value)
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<(), EncodeError> {
+        self.write_display(value)
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<(), EncodeError> {
+        self.write_display(value)
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<(), EncodeError> {
+        self.write_display(value)
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<(), EncodeError> {
+        self.write_display(value)
+    }
+
+    fn serialize_u128(self, value: u128) -> Result<(), EncodeError> {
+        self.write_display(value)
+    }
+
+    fn serialize_f32(self, _value: f32) -> Result<(), EncodeError> {
+        Err(key_error())
+    }
+
+    fn serialize_f64(self, _value: f64) -> Result<(), EncodeError> {
+        Err(key_error())
+    }
+
+    fn serialize_bytes(self, _value: &[u8]) -> Result<(), EncodeError> {
+        Err(key_error())
+    }
+
+    fn serialize_none(self) -> Result<(), EncodeError> {
+        Err(key_error())
+    }
+
+    fn serialize_some<T>(self, _value: &T) -> Result<(), EncodeError>
+    where
+        T: ?Sized + Serialize,
+    {
+        Err(key_error())
+    }
+
+    fn serialize_unit(self) -> Result<(), EncodeError> {
+        Err(key_error())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), EncodeError> {
+        Err(key_error())
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<(), EncodeError>
+    where
+        T: ?Sized + Serialize,
+    {
+        Err(key_error())
+    }
+
+    fn serialize_seq(
+        self,
+        _length: Option<usize>,
+    ) -> Result<Self::SerializeSeq, EncodeError> {
+        Err(key_error())
+    }
+
+    fn serialize_tuple(
+        self,
+        _length: usize,
+    ) -> Result<Self::SerializeTuple, EncodeError> {
+        Err(key_error())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _length: usize,
+    ) -> Result<Self::SerializeTupleStruct, EncodeError> {
+        Err(key_error())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _length: usize,
+    ) -> Result<Self::SerializeTupleVariant, EncodeError> {
+        Err(key_error())
+    }
+
+    fn serialize_map(
+        self,
+        _length: Option<usize>,
+    ) -> Result<Self::SerializeMap, EncodeError> {
+        Err(key_error())
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _length: usize,
+    ) -> Result<Self::SerializeStruct, EncodeError> {
+        Err(key_error())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _length: usize,
+    ) -> Result<Self::SerializeStructVariant, EncodeError> {
+        Err(key_error())
+    }
+}
+
+#[inline]
+fn key_error() -> EncodeError {
+    encode_error("object key must be a string")
+}
+
+/// Error produced if a value cannot be represented in the binary format.
+///
+/// This uses the JSON error type, since the same values are not permitted.
+/// Being a single pointer, it also keeps the stack usage of deeply nested
+/// serialization similar to that of JSON.
+type EncodeError = serde_json::Error;
+
+#[inline]
+fn encode_error(message: &str) -> EncodeError {
+    ser::Error::custom(message)
+}
+
+#[derive(Debug)]
+struct Decoder<'b> {
+    bytes: &'b [u8],
+    position: usize,
+    strings: Vec<&'b str>,
+}
+
+impl<'b> Decoder<'b> {
+    fn read_byte(&mut self) -> Result<u8, BinaryError> {
+        let byte = *self
+            .bytes
+            .get(self.position)
+            .ok_or(BinaryError::Truncated)?;
+
+        self.position += 1;
+        Ok(byte)
+    }
+
+    fn read_bytes(&mut self, length: usize) -> Result<&'b [u8], BinaryError> {
+        let end = self
+            .position
+            .checked_add(length)
+            .ok_or(BinaryError::InvalidData)?;
+
+        let slice = self
+            .bytes
+            .get(self.position..end)
+            .ok_or(BinaryError::Truncated)?;
+
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn read_varint(&mut self) -> Result<u64, BinaryError> {
+        let mut value = 0_u64;
+
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_byte()?;
+            value |= u64::from(byte & 0x7f) << shift;
+
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err(BinaryError::InvalidData)
+    }
+
+    /// Reads a length, which cannot be larger than the remaining input.
+    ///
+    /// Each item takes at least one byte, so this bounds allocations
+    /// made based on lengths in the input.
+    fn read_length(&mut self) -> Result<usize, BinaryError> {
+        let length = self.read_varint()?;
+        let remaining = self.bytes.len() - self.position;
+
+        match usize::try_from(length) {
+            Ok(length) if length <= remaining => Ok(length),
+            _ => Err(BinaryError::Truncated),
+        }
+    }
+
+    fn read_string(&mut self) -> Result<&'b str, BinaryError> {
+        let index = self.read_varint()?;
+
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| self.strings.get(index))
+            .copied()
+            .ok_or(BinaryError::InvalidData)
+    }
+
+    fn decode_value(&mut self, depth: usize) -> Result<Value, BinaryError> {
+        if depth > MAX_DEPTH {
+            return Err(BinaryError::InvalidData);
+        }
+
+        let value = match self.read_byte()? {
+            TAG_NULL => Value::Null,
+            TAG_FALSE => Value::Bool(false),
+            TAG_TRUE => Value::Bool(true),
+            TAG_UNSIGNED => Value::Number(Number::from(self.read_varint()?)),
+            TAG_NEGATIVE => Value::Number(Number::from(!self.read_varint()? as i64)),
+            TAG_FLOAT => {
+                let bytes = self.read_bytes(8)?;
+                let value = f64::from_le_bytes(bytes.try_into().unwrap());
+                let number = Number::from_f64(value).ok_or(BinaryError::InvalidData)?;
+                Value::Number(number)
+            }
+            TAG_STRING => Value::String(str!(self.read_string()?)),
+            TAG_ARRAY => {
+                let length = self.read_length()?;
+                let mut values = Vec::with_capacity(length);
+
+                for _ in 0..length {
+                    values.push(self.decode_value(depth + 1)?);
+                }
+
+                Value::Array(values)
+            }
+            TAG_OBJECT => {
+                let length = self.read_length()?;
+                let mut map = Map::new();
+
+                for _ in 0..length {
+                    let key = str!(self.read_string()?);
+                    let value = self.decode_value(depth + 1)?;
+                    map.insert(key, value);
+                }
+
+                Value::Object(map)
+            }
+            _ => return Err(BinaryError::InvalidData),
+        };
+
+        Ok(value)
+    }
+}
+
+fn write_varint(output: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+
+        if value == 0 {
+            output.push(byte);
+            break;
+        }
+
+        output.push(byte | 0x80);
+    }
+}
+
+#[test]
+fn binary() {
+    use crate::data::PageInfo;
+    use crate::layout::Layout;
+    use crate::settings::{WikitextMode, WikitextSettings};
+
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let text = "[[div class=\"fruit\"]]\n**Apple**[[footnote]]Red.[[/footnote]]\n[[/div]]\n\n* Banana\n* Cherry\n\n[[date -100]] [[[page|Link]]] [[image fruit.png]]\n\n[[toc]]\n\n+ Heading";
+    let tokens = crate::tokenize(text);
+    let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+
+    // Round trip
+    let bytes = tree.to_bytes();
+    let output = SyntaxTree::from_bytes(&bytes).expect("Unable to read tree");
+    assert_eq!(tree, output);
+
+    // Same structure as JSON
+    let value = read_value(&bytes).expect("Unable to read value");
+    assert_eq!(value, serde_json::to_value(&tree).unwrap());
+
+    // Smaller than JSON
+    let json = serde_json::to_vec(&tree).unwrap();
+    assert!(bytes.len() < json.len());
+
+    // Invalid input
+    assert_eq!(
+        SyntaxTree::from_bytes(b"JSON{}"),
+        Err(BinaryError::InvalidHeader),
+    );
+    assert_eq!(
+        SyntaxTree::from_bytes(b"FTML\xff"),
+        Err(BinaryError::UnsupportedVersion(0xff)),
+    );
+    assert_eq!(
+        SyntaxTree::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(BinaryError::Truncated),
+    );
+}
+
+#[test]
+fn binary_depth() {
+    use super::{AttributeMap, Container, ContainerType, Element};
+
+    fn nested(depth: usize) -> SyntaxTree<'static> {
+        let mut element = text!("apple");
+        for _ in 0..depth {
+            element = Element::Container(Container::new(
+                ContainerType::Span,
+                vec![element],
+                AttributeMap::new(),
+            ));
+        }
+
+        SyntaxTree {
+            elements: vec![element],
+            ..SyntaxTree::default()
+        }
+    }
+
+    // Deeper than the parser permits, but still accepted
+    let tree = nested(120);
+    let bytes = tree.to_bytes();
+    assert_eq!(SyntaxTree::from_bytes(&bytes), Ok(tree));
+
+    // Too deep
+    let bytes = nested(200).to_bytes();
+    assert_eq!(
+        SyntaxTree::from_bytes(&bytes),
+        Err(BinaryError::InvalidData)
+    );
+
+    // Pathologically deep input is rejected without exhausting the stack
+    let mut bytes = Vec::from(&MAGIC[..]);
+    bytes.push(BINARY_FORMAT_VERSION);
+    bytes.push(0); // no strings
+    for _ in 0..100_000 {
+        bytes.extend_from_slice(&[TAG_ARRAY, 1]);
+    }
+    bytes.push(TAG_NULL);
+    assert_eq!(
+        SyntaxTree::from_bytes(&bytes),
+        Err(BinaryError::InvalidData)
+    );
+}
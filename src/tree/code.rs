@@ -23,17 +23,151 @@
 use super::clone::{option_string_to_owned, string_to_owned};
 use std::borrow::Cow;
 
+/// Languages where trailing whitespace is significant, and so is kept when normalizing.
+const WHITESPACE_LANGUAGES: [&str; 4] = ["diff", "markdown", "md", "whitespace"];
+
 #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
 pub struct CodeBlock<'t> {
     pub contents: Cow<'t, str>,
     pub name: Option<Cow<'t, str>>,
+
+    /// The language of this code block, in lowercase, if specified.
+    pub language: Option<Cow<'t, str>>,
+
+    /// A stable hash of the contents, as 16 hexadecimal digits.
+    ///
+    /// This is the 64-bit FNV-1a hash of the UTF-8 contents, and so
+    /// is the same across platforms and versions of this library.
+    /// Identical snippets can be found using [`SyntaxTree::code_block_index()`].
+    ///
+    /// [`SyntaxTree::code_block_index()`]: crate::tree::SyntaxTree::code_block_index
+    pub hash: String,
+
+    /// The contents with insignificant differences removed, if any were found.
+    ///
+    /// Line endings are converted to `\n`, leading and trailing blank lines
+    /// are removed, and so is trailing whitespace on each line, unless the
+    /// language is one where it is significant (such as Markdown).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub normalized: Option<Cow<'t, str>>,
 }
 
-impl CodeBlock<'_> {
+impl<'t> CodeBlock<'t> {
+    pub fn new(
+        contents: Cow<'t, str>,
+        name: Option<Cow<'t, str>>,
+        language: Option<Cow<'t, str>>,
+    ) -> Self {
+        let hash = content_hash(&contents);
+        let normalized =
+            normalize_contents(&contents, language.as_deref()).map(Cow::Owned);
+
+        CodeBlock {
+            contents,
+            name,
+            language,
+            hash,
+            normalized,
+        }
+    }
+
+    /// Returns the normalized contents, which are the contents if already normalized.
+    #[inline]
+    pub fn normalized_contents(&self) -> &str {
+        self.normalized.as_deref().unwrap_or(&self.contents)
+    }
+
     pub fn to_owned(&self) -> CodeBlock<'static> {
         CodeBlock {
             contents: string_to_owned(&self.contents),
             name: option_string_to_owned(&self.name),
+            language: option_string_to_owned(&self.language),
+            hash: self.hash.clone(),
+            normalized: option_string_to_owned(&self.normalized),
         }
     }
 }
+
+/// Produces the stable hash of a code block's contents.
+///
+/// See [`CodeBlock::hash`].
+pub fn content_hash(contents: &str) -> String {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    let hash = contents.bytes().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    });
+
+    format!("{hash:016x}")
+}
+
+/// Normalizes a code block's contents, returning `None` if there are no changes.
+///
+/// See [`CodeBlock::normalized`].
+fn normalize_contents(contents: &str, language: Option<&str>) -> Option<String> {
+    let keep_trailing = language.is_some_and(|language| {
+        WHITESPACE_LANGUAGES
+            .iter()
+            .any(|name| name.eq_ignore_ascii_case(language))
+    });
+
+    let lines: Vec<&str> = contents
+        .lines()
+        .map(|line| {
+            if keep_trailing {
+                line.strip_suffix('\r').unwrap_or(line)
+            } else {
+                line.trim_end()
+            }
+        })
+        .collect();
+
+    let start = lines.iter().position(|line| !line.trim().is_empty());
+    let end = lines.iter().rposition(|line| !line.trim().is_empty());
+    let normalized = match (start, end) {
+        (Some(start), Some(end)) => lines[start..=end].join("\n"),
+        _ => String::new(),
+    };
+
+    if normalized == contents {
+        None
+    } else {
+        Some(normalized)
+    }
+}
+
+#[test]
+fn hash() {
+    // Reference values for 64-bit FNV-1a
+    assert_eq!(content_hash(""), "cbf29ce484222325");
+    assert_eq!(content_hash("a"), "af63dc4c8601ec8c");
+    assert_eq!(content_hash("foobar"), "85944171f73967e8");
+}
+
+#[test]
+fn normalize() {
+    macro_rules! check {
+        ($contents:expr, $language:expr, $expected:expr $(,)?) => {
+            assert_eq!(
+                normalize_contents($contents, $language).as_deref(),
+                $expected,
+                "Normalized contents didn't match expected",
+            );
+        };
+    }
+
+    check!("x = 1", None, None);
+    check!("x = 1\ny = 2", Some("python"), None);
+    check!("x = 1\r\ny = 2\r\n", None, Some("x = 1\ny = 2"));
+    check!("\n\nx = 1  \n\n", Some("python"), Some("x = 1"));
+    check!("  indented\t\n", None, Some("  indented"));
+    check!("line break  \nnext", Some("markdown"), None);
+    check!(
+        "line break  \r\nnext\n",
+        Some("md"),
+        Some("line break  \nnext")
+    );
+    check!("\n \n", None, Some(""));
+}
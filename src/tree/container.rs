@@ -58,6 +58,11 @@ impl<'t> Container<'t> {
         &self.elements
     }
 
+    #[inline]
+    pub fn elements_mut(&mut self) -> &mut Vec<Element<'t>> {
+        &mut self.elements
+    }
+
     #[inline]
     pub fn attributes(&self) -> &AttributeMap<'t> {
         &self.attributes
@@ -102,6 +107,7 @@ pub enum ContainerType {
     Div,
     Mark,
     Blockquote,
+    BlockquoteAttribution,
     Insertion,
     Deletion,
     Hidden,
@@ -121,7 +127,11 @@ impl ContainerType {
     }
 
     #[inline]
-    pub fn html_tag(self, indexer: &mut dyn NextIndex<TableOfContentsIndex>) -> HtmlTag {
+    pub fn html_tag(
+        self,
+        indexer: &mut dyn NextIndex<TableOfContentsIndex>,
+        heading_anchors: &[String],
+    ) -> HtmlTag {
         match self {
             ContainerType::Bold => HtmlTag::new("strong"),
             ContainerType::Italics => HtmlTag::new("em"),
@@ -134,6 +144,9 @@ impl ContainerType {
             ContainerType::Div => HtmlTag::new("div"),
             ContainerType::Mark => HtmlTag::new("mark"),
             ContainerType::Blockquote => HtmlTag::new("blockquote"),
+            ContainerType::BlockquoteAttribution => {
+                HtmlTag::with_class("figcaption", "wj-blockquote-attribution")
+            }
             ContainerType::Insertion => HtmlTag::new("ins"),
             ContainerType::Deletion => HtmlTag::new("del"),
             ContainerType::Hidden => HtmlTag::with_class("span", "wj-hidden"),
@@ -145,7 +158,7 @@ impl ContainerType {
             ContainerType::Align(alignment) => {
                 HtmlTag::with_class("div", alignment.html_class())
             }
-            ContainerType::Header(heading) => heading.html_tag(indexer),
+            ContainerType::Header(heading) => heading.html_tag(indexer, heading_anchors),
         }
     }
 
@@ -166,6 +179,7 @@ impl ContainerType {
             ContainerType::Div => false,
             ContainerType::Mark => true,
             ContainerType::Blockquote => false,
+            ContainerType::BlockquoteAttribution => false,
             ContainerType::Insertion => true,
             ContainerType::Deletion => true,
             ContainerType::Hidden => true,
@@ -65,14 +65,18 @@ impl DateItem {
         use time::error::Format;
 
         let result = match self {
-            DateItem::Date(date) => date.format(&Rfc2822),
-            DateItem::DateTime(datetime) => datetime.format(&Rfc2822),
+            // RFC 2822 requires an offset, which these types lack
+            DateItem::Date(_) | DateItem::DateTime(_) => {
+                return Err(io::Error::other(
+                    "Insufficient type information to format as RFC 2822",
+                ));
+            }
             DateItem::DateTimeTz(datetime_tz) => datetime_tz.format(&Rfc2822),
         };
 
         result.map_err(|error| match error {
             Format::StdIo(io_error) => io_error,
-            _ => io::Error::new(io::ErrorKind::Other, error),
+            _ => io::Error::other(error),
         })
     }
 }
@@ -115,3 +119,21 @@ cfg_if! {
         }
     }
 }
+
+#[test]
+fn format() {
+    use time::macros::datetime;
+
+    let datetime = datetime!(2010-01-01 08:10:00);
+
+    // RFC 2822 requires an offset
+    assert!(DateItem::Date(datetime.date()).format().is_err());
+    assert!(DateItem::DateTime(datetime).format().is_err());
+
+    assert_eq!(
+        DateItem::DateTimeTz(datetime.assume_utc())
+            .format()
+            .expect("Unable to format date"),
+        "Fri, 01 Jan 2010 08:10:00 +0000",
+    );
+}
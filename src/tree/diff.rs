@@ -0,0 +1,428 @@
+/*
+ * tree/diff.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Comparison of syntax trees.
+//!
+//! This compares the top-level elements of two trees, typically two revisions
+//! of the same page, producing a list of which elements were inserted, removed,
+//! or modified. Since top-level elements are generally paragraphs, headings,
+//! and blocks, this gives a semantic diff, rather than a line-based one.
+//!
+//! Elements are matched using a longest common subsequence. A removed
+//! element immediately replaced by an inserted element of the same kind
+//! (for instance, a paragraph replaced by a paragraph) is reported as
+//! a modification instead. If the trees differ by more than several hundred
+//! elements, they are not matched up, and all of their elements are reported
+//! as changed.
+//!
+//! If the trees were parsed with `source_spans` enabled, then the changes
+//! also include the byte ranges of the affected elements in each wikitext.
+
+use super::{Element, SyntaxTree};
+use std::ops::Range;
+
+/// A single difference between two syntax trees.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case", tag = "type")]
+pub enum ElementChange<'a, 't> {
+    /// An element present only in the new tree.
+    #[serde(rename_all = "kebab-case")]
+    Inserted {
+        new_index: usize,
+        new_span: Option<Range<usize>>,
+        element: &'a Element<'t>,
+    },
+
+    /// An element present only in the old tree.
+    #[serde(rename_all = "kebab-case")]
+    Removed {
+        old_index: usize,
+        old_span: Option<Range<usize>>,
+        element: &'a Element<'t>,
+    },
+
+    /// An element which was changed between the two trees.
+    #[serde(rename_all = "kebab-case")]
+    Modified {
+        old_index: usize,
+        new_index: usize,
+        old_span: Option<Range<usize>>,
+        new_span: Option<Range<usize>>,
+        old_element: &'a Element<'t>,
+        new_element: &'a Element<'t>,
+    },
+}
+
+/// Compares the top-level elements of two syntax trees.
+///
+/// Elements which are the same in both are not included. The changes
+/// are returned in document order.
+pub fn diff<'a, 't>(
+    old: &'a SyntaxTree<'t>,
+    new: &'a SyntaxTree<'t>,
+) -> Vec<ElementChange<'a, 't>> {
+    let old_elements = &old.elements;
+    let new_elements = &new.elements;
+
+    debug!(
+        "Comparing syntax trees (old length {}, new length {})",
+        old_elements.len(),
+        new_elements.len(),
+    );
+
+    // Skip over any common prefix and suffix, since
+    // edits usually only affect a small part of the page.
+    let prefix = old_elements
+        .iter()
+        .zip(new_elements)
+        .take_while(|(old, new)| old == new)
+        .count();
+
+    let suffix = old_elements[prefix..]
+        .iter()
+        .rev()
+        .zip(new_elements[prefix..].iter().rev())
+        .take_while(|(old, new)| old == new)
+        .count();
+
+    let old_range = prefix..old_elements.len() - suffix;
+    let new_range = prefix..new_elements.len() - suffix;
+    let matches = common_subsequence(
+        &old_elements[old_range.clone()],
+        &new_elements[new_range.clone()],
+    );
+
+    let mut builder = ChangeBuilder {
+        old,
+        new,
+        changes: Vec::new(),
+    };
+
+    // Process the differing runs between each matched pair
+    let mut old_index = old_range.start;
+    let mut new_index = new_range.start;
+
+    for (old_match, new_match) in matches {
+        let old_match = old_match + prefix;
+        let new_match = new_match + prefix;
+
+        builder.add_run(old_index..old_match, new_index..new_match);
+        old_index = old_match + 1;
+        new_index = new_match + 1;
+    }
+
+    builder.add_run(old_index..old_range.end, new_index..new_range.end);
+    builder.changes
+}
+
+#[derive(Debug)]
+struct ChangeBuilder<'a, 't> {
+    old: &'a SyntaxTree<'t>,
+    new: &'a SyntaxTree<'t>,
+    changes: Vec<ElementChange<'a, 't>>,
+}
+
+impl<'a, 't> ChangeBuilder<'a, 't> {
+    /// Adds the changes for a run of removed and inserted elements.
+    ///
+    /// Elements at the same position in each run which are of the
+    /// same kind are considered modified.
+    fn add_run(&mut self, old_indices: Range<usize>, new_indices: Range<usize>) {
+        let mut old_indices = old_indices.peekable();
+        let mut new_indices = new_indices.peekable();
+
+        loop {
+            match (old_indices.peek().copied(), new_indices.peek().copied()) {
+                (Some(old_index), Some(new_index)) => {
+                    let old_element = &self.old.elements[old_index];
+                    let new_element = &self.new.elements[new_index];
+
+                    if old_element.name() == new_element.name() {
+                        self.changes.push(ElementChange::Modified {
+                            old_index,
+                            new_index,
+                            old_span: self.old.element_spans.get(old_index).cloned(),
+                            new_span: self.new.element_spans.get(new_index).cloned(),
+                            old_element,
+                            new_element,
+                        });
+
+                        old_indices.next();
+                        new_indices.next();
+                    } else {
+                        self.add_removed(old_index);
+                        self.add_inserted(new_index);
+                        old_indices.next();
+                        new_indices.next();
+                    }
+                }
+                (Some(old_index), None) => {
+                    self.add_removed(old_index);
+                    old_indices.next();
+                }
+                (None, Some(new_index)) => {
+                    self.add_inserted(new_index);
+                    new_indices.next();
+                }
+                (None, None) => break,
+            }
+        }
+    }
+
+    fn add_removed(&mut self, old_index: usize) {
+        self.changes.push(ElementChange::Removed {
+            old_index,
+            old_span: self.old.element_spans.get(old_index).cloned(),
+            element: &self.old.elements[old_index],
+        });
+    }
+
+    fn add_inserted(&mut self, new_index: usize) {
+        self.changes.push(ElementChange::Inserted {
+            new_index,
+            new_span: self.new.element_spans.get(new_index).cloned(),
+            element: &self.new.elements[new_index],
+        });
+    }
+}
+
+/// The most differences for which elements are matched up.
+///
+/// See [`common_subsequence()`].
+const MAX_EDIT_DISTANCE: usize = 500;
+
+/// Finds the longest common subsequence of two element lists.
+///
+/// Returns the indices of each matched pair, in order.
+///
+/// This uses Myers' algorithm, which takes time proportional to the total
+/// length multiplied by the number of differences, rather than to the product
+/// of the lengths. If there are more than [`MAX_EDIT_DISTANCE`] differences,
+/// then nothing is matched, and every element is considered replaced.
+fn common_subsequence(old: &[Element], new: &[Element]) -> Vec<(usize, usize)> {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let max_distance = (old.len() + new.len()).min(MAX_EDIT_DISTANCE) as isize;
+
+    // The furthest index reached in "old" along each diagonal k = x - y,
+    // offset so that negative diagonals can be indexed.
+    let offset = max_distance + 1;
+    let mut furthest = vec![0_isize; 2 * offset as usize + 1];
+    let mut trace = Vec::new();
+
+    let index = |k: isize| (k + offset) as usize;
+
+    for distance in 0..=max_distance {
+        trace.push(furthest.clone());
+
+        for k in (-distance..=distance).step_by(2) {
+            // Either move down from diagonal k + 1, or right from k - 1
+            let mut x = if k == -distance
+                || (k != distance && furthest[index(k - 1)] < furthest[index(k + 1)])
+            {
+                furthest[index(k + 1)]
+            } else {
+                furthest[index(k - 1)] + 1
+            };
+
+            let mut y = x - k;
+
+            // Follow any matching elements
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+
+            furthest[index(k)] = x;
+
+            if x >= n && y >= m {
+                return backtrack(&trace, n, m, index);
+            }
+        }
+    }
+
+    debug!("Syntax trees differ by more than {MAX_EDIT_DISTANCE} elements");
+    Vec::new()
+}
+
+/// Walks back through the states of Myers' algorithm to get the matches.
+fn backtrack(
+    trace: &[Vec<isize>],
+    n: isize,
+    m: isize,
+    index: impl Fn(isize) -> usize,
+) -> Vec<(usize, usize)> {
+    let mut matches = Vec::new();
+    let (mut x, mut y) = (n, m);
+
+    for (distance, furthest) in trace.iter().enumerate().rev() {
+        let distance = distance as isize;
+        let k = x - y;
+        let previous_k = if k == -distance
+            || (k != distance && furthest[index(k - 1)] < furthest[index(k + 1)])
+        {
+            k + 1
+        } else {
+            k - 1
+        };
+
+        let previous_x = furthest[index(previous_k)];
+        let previous_y = previous_x - previous_k;
+
+        while x > previous_x && y > previous_y {
+            x -= 1;
+            y -= 1;
+            matches.push((x as usize, y as usize));
+        }
+
+        if distance > 0 {
+            x = previous_x;
+            y = previous_y;
+        }
+    }
+
+    matches.reverse();
+    matches
+}
+
+#[test]
+fn diff_trees() {
+    use crate::data::PageInfo;
+    use crate::layout::Layout;
+    use crate::settings::{WikitextMode, WikitextSettings};
+
+    let page_info = PageInfo::dummy();
+    let mut settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    settings.source_spans = true;
+
+    let old_text = "Apple\n\n+ Fruits\n\nBanana\n\nCherry";
+    let new_text = "Apple\n\nBlueberry\n\nDurian\n\n* Cherry";
+
+    let old_tokens = crate::tokenize(old_text);
+    let new_tokens = crate::tokenize(new_text);
+    let (old_tree, _) = crate::parse(&old_tokens, &page_info, &settings).into();
+    let (new_tree, _) = crate::parse(&new_tokens, &page_info, &settings).into();
+
+    // Unchanged
+    assert!(diff(&old_tree, &old_tree).is_empty());
+
+    // Changed
+    let changes = diff(&old_tree, &new_tree);
+    let summary: Vec<_> = changes
+        .iter()
+        .map(|change| match change {
+            ElementChange::Inserted {
+                new_index,
+                new_span,
+                ..
+            } => format!("+{new_index} {new_span:?}"),
+            ElementChange::Removed {
+                old_index,
+                old_span,
+                ..
+            } => format!("-{old_index} {old_span:?}"),
+            ElementChange::Modified {
+                old_index,
+                new_index,
+                old_span,
+                new_span,
+                ..
+            } => format!("~{old_index}:{new_index} {old_span:?}:{new_span:?}"),
+        })
+        .collect();
+
+    assert_eq!(
+        summary,
+        vec![
+            // Heading replaced with paragraph
+            "-1 Some(7..17)",
+            "+1 Some(7..16)",
+            // Paragraph changed
+            "~2:2 Some(17..23):Some(18..24)",
+            // Paragraph replaced with list
+            "-3 Some(25..31)",
+            "+3 Some(26..34)",
+        ],
+    );
+}
+
+#[test]
+fn common_subsequences() {
+    use std::borrow::Cow;
+
+    macro_rules! elements {
+        ($text:expr) => {
+            $text
+                .chars()
+                .map(|c| Element::Text(Cow::Owned(c.to_string())))
+                .collect::<Vec<_>>()
+        };
+    }
+
+    macro_rules! check {
+        ($old:expr, $new:expr, $expected:expr $(,)?) => {{
+            let old = elements!($old);
+            let new = elements!($new);
+            let matches = common_subsequence(&old, &new);
+            let common: String = matches
+                .iter()
+                .map(|&(i, _)| $old.as_bytes()[i] as char)
+                .collect();
+
+            for &(i, j) in &matches {
+                assert_eq!(old[i], new[j], "Matched elements are not equal");
+            }
+
+            assert!(
+                matches
+                    .windows(2)
+                    .all(|pair| pair[0].0 < pair[1].0 && pair[0].1 < pair[1].1),
+                "Matches are not in order: {matches:?}",
+            );
+
+            assert_eq!(
+                common.len(),
+                $expected.len(),
+                "Common subsequence {common:?} doesn't match expected {:?}",
+                $expected,
+            );
+        }};
+    }
+
+    check!("", "", "");
+    check!("abc", "", "");
+    check!("", "abc", "");
+    check!("abc", "abc", "abc");
+    check!("abcabba", "cbabac", "baba");
+    check!("xaybzc", "abc", "abc");
+    check!("abcdef", "fedcba", "a");
+    check!("apple", "maple", "aple");
+
+    // Too many differences to match
+    let old: Vec<_> = (0..MAX_EDIT_DISTANCE + 1)
+        .map(|i| Element::Text(Cow::Owned(format!("old-{i}"))))
+        .chain([text!("shared")])
+        .collect();
+    let new: Vec<_> = [text!("shared")].into_iter().collect();
+    assert!(common_subsequence(&old, &new).is_empty());
+    assert_eq!(
+        common_subsequence(&old[1..], &new),
+        vec![(MAX_EDIT_DISTANCE, 0)]
+    );
+}
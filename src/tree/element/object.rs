@@ -19,11 +19,13 @@
  */
 
 use crate::data::PageRef;
+use crate::parsing::ElementCondition;
 use crate::tree::clone::*;
 use crate::tree::{
     Alignment, AnchorTarget, AttributeMap, ClearFloat, Container, DateItem,
-    DefinitionListItem, Embed, FloatAlignment, ImageSource, LinkLabel, LinkLocation,
-    LinkType, ListItem, ListType, Module, PartialElement, Tab, Table, VariableMap,
+    DefinitionListItem, Dimension, Embed, FloatAlignment, HeadingLevel, ImageSource,
+    LinkLabel, LinkLocation, LinkType, ListItem, ListType, MathEnvironment, Module,
+    PartialElement, Tab, Table, VariableMap,
 };
 use ref_map::*;
 use std::borrow::Cow;
@@ -60,8 +62,19 @@ pub enum Element<'t> {
     /// A wikitext variable.
     ///
     /// During rendering, this will be replaced with its actual value,
-    /// as appropriate to the context.
-    Variable(Cow<'t, str>),
+    /// as appropriate to the context. If it has none, then the default
+    /// is used, as in `{$name|default}`.
+    Variable {
+        name: Cow<'t, str>,
+        default: Option<Cow<'t, str>>,
+    },
+
+    /// A page variable, such as `%%title%%`.
+    ///
+    /// During rendering, this will be replaced with the value for the page
+    /// being rendered. If it has none, then it is left as the original text.
+    #[serde(rename_all = "kebab-case")]
+    PageVariable { name: Cow<'t, str> },
 
     /// An element indicating an email.
     ///
@@ -113,10 +126,25 @@ pub enum Element<'t> {
     /// The "source" field is the link to the image itself.
     ///
     /// The "link" field is what the `<a>` points to, when the user clicks on the image.
+    ///
+    /// The "link-extra" field is any path or anchor following a page in the link,
+    /// as with the "extra" field for links.
+    ///
+    /// The "lazy" field is whether the browser should defer loading the image
+    /// until it is near the viewport.
+    #[serde(rename_all = "kebab-case")]
     Image {
         source: ImageSource<'t>,
         link: Option<LinkLocation<'t>>,
+
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        link_extra: Option<Cow<'t, str>>,
+
         alignment: Option<FloatAlignment>,
+
+        #[serde(default)]
+        lazy: bool,
+
         attributes: AttributeMap<'t>,
     },
 
@@ -154,6 +182,9 @@ pub enum Element<'t> {
     /// This is an interactable element provided by Wikidot which allows hiding
     /// all of the internal elements until it is opened by clicking, which can
     /// then be re-hidden by clicking again.
+    ///
+    /// The "remember" field is whether the frontend should persist
+    /// whether it is open or closed for each user.
     #[serde(rename_all = "kebab-case")]
     Collapsible {
         elements: Vec<Element<'t>>,
@@ -163,16 +194,30 @@ pub enum Element<'t> {
         hide_text: Option<Cow<'t, str>>,
         show_top: bool,
         show_bottom: bool,
+
+        #[serde(default)]
+        remember: bool,
     },
 
     /// A table of contents block.
     ///
     /// This contains links to sub-headings on the page.
+    ///
+    /// If a minimum or maximum level is given, only headings
+    /// within that range are listed.
     TableOfContents {
         attributes: AttributeMap<'t>,
         align: Option<Alignment>,
+        min_level: Option<HeadingLevel>,
+        max_level: Option<HeadingLevel>,
     },
 
+    /// A pagination block.
+    ///
+    /// This contains links to the previous and next pages, as provided
+    /// by the renderer. It is empty if there are no such pages.
+    Pagination { attributes: AttributeMap<'t> },
+
     /// A footnote reference.
     ///
     /// This specifies that a `[[footnote]]` was here, and that a clickable
@@ -213,6 +258,13 @@ pub enum Element<'t> {
         show_avatar: bool,
     },
 
+    /// A mention of a user, such as `@username`.
+    ///
+    /// This is rendered as a link to the user, and each user mentioned
+    /// is listed in [`SyntaxTree::mentions`](crate::tree::SyntaxTree::mentions).
+    #[serde(rename_all = "kebab-case")]
+    Mention { name: Cow<'t, str> },
+
     /// A date display, showcasing a particular moment in time.
     Date {
         value: DateItem,
@@ -239,6 +291,9 @@ pub enum Element<'t> {
     Math {
         name: Option<Cow<'t, str>>,
         latex_source: Cow<'t, str>,
+
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        env: Option<MathEnvironment>,
     },
 
     /// Element containing inline math.
@@ -252,7 +307,22 @@ pub enum Element<'t> {
     Embed(Embed<'t>),
 
     /// Element containing a sandboxed HTML block.
-    Html { contents: Cow<'t, str> },
+    ///
+    /// The "width" and "height" fields are the size of the iframe, if given.
+    /// The "resize" field is whether the frontend should resize the iframe
+    /// to fit its contents.
+    Html {
+        contents: Cow<'t, str>,
+
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        width: Option<Dimension>,
+
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        height: Option<Dimension>,
+
+        #[serde(default, skip_serializing_if = "is_false")]
+        resize: bool,
+    },
 
     /// Element containing an iframe component.
     Iframe {
@@ -271,6 +341,19 @@ pub enum Element<'t> {
         elements: Vec<Element<'t>>,
     },
 
+    /// Element whose contents are only shown if the page's tags match.
+    ///
+    /// From `[[iftags]]`, if tag conditions are deferred until rendering.
+    /// See [`WikitextSettings::defer_tag_conditions`].
+    ///
+    /// [`WikitextSettings::defer_tag_conditions`]: crate::settings::WikitextSettings::defer_tag_conditions
+    #[serde(rename_all = "kebab-case")]
+    Conditional {
+        paragraph_safe: bool,
+        conditions: Vec<ElementCondition<'t>>,
+        elements: Vec<Element<'t>>,
+    },
+
     /// A CSS stylesheet.
     ///
     /// Corresponds with a `<style>` entity in the body of the HTML.
@@ -323,7 +406,8 @@ impl Element<'_> {
             Element::Module(module) => module.name(),
             Element::Text(_) => "Text",
             Element::Raw(_) => "Raw",
-            Element::Variable(_) => "Variable",
+            Element::Variable { .. } => "Variable",
+            Element::PageVariable { .. } => "PageVariable",
             Element::Email(_) => "Email",
             Element::Table(_) => "Table",
             Element::TabView(_) => "TabView",
@@ -337,11 +421,13 @@ impl Element<'_> {
             Element::CheckBox { .. } => "CheckBox",
             Element::Collapsible { .. } => "Collapsible",
             Element::TableOfContents { .. } => "TableOfContents",
+            Element::Pagination { .. } => "Pagination",
             Element::Footnote => "Footnote",
             Element::FootnoteBlock { .. } => "FootnoteBlock",
             Element::BibliographyCite { .. } => "BibliographyCite",
             Element::BibliographyBlock { .. } => "BibliographyBlock",
             Element::User { .. } => "User",
+            Element::Mention { .. } => "Mention",
             Element::Date { .. } => "Date",
             Element::Color { .. } => "Color",
             Element::Code { .. } => "Code",
@@ -352,6 +438,7 @@ impl Element<'_> {
             Element::Html { .. } => "HTML",
             Element::Iframe { .. } => "Iframe",
             Element::Include { .. } => "Include",
+            Element::Conditional { .. } => "Conditional",
             Element::Style(_) => "Style",
             Element::LineBreak => "LineBreak",
             Element::LineBreaks { .. } => "LineBreaks",
@@ -376,7 +463,8 @@ impl Element<'_> {
             Element::Module(_) => false,
             Element::Text(_)
             | Element::Raw(_)
-            | Element::Variable(_)
+            | Element::Variable { .. }
+            | Element::PageVariable { .. }
             | Element::Email(_) => true,
             Element::Table(_) => false,
             Element::TabView(_) => false,
@@ -389,11 +477,13 @@ impl Element<'_> {
             Element::RadioButton { .. } | Element::CheckBox { .. } => true,
             Element::Collapsible { .. } => false,
             Element::TableOfContents { .. } => false,
+            Element::Pagination { .. } => false,
             Element::Footnote => true,
             Element::FootnoteBlock { .. } => false,
             Element::BibliographyCite { .. } => true,
             Element::BibliographyBlock { .. } => false,
             Element::User { .. } => true,
+            Element::Mention { .. } => true,
             Element::Date { .. } => true,
             Element::Color { .. } => true,
             Element::Code { .. } => false,
@@ -403,12 +493,14 @@ impl Element<'_> {
             Element::Embed(_) => false,
             Element::Html { .. } | Element::Iframe { .. } => false,
             Element::Include { paragraph_safe, .. } => *paragraph_safe,
+            Element::Conditional { paragraph_safe, .. } => *paragraph_safe,
             Element::Style(_) => false,
             Element::LineBreak | Element::LineBreaks { .. } => true,
             Element::ClearFloat(_) => false,
             Element::HorizontalRule => false,
             Element::Partial(_) => {
-                panic!("Should not check for paragraph safety of partials")
+                error!("Checking paragraph safety of partial element");
+                false
             }
         }
     }
@@ -424,7 +516,13 @@ impl Element<'_> {
             Element::Module(module) => Element::Module(module.to_owned()),
             Element::Text(text) => Element::Text(string_to_owned(text)),
             Element::Raw(text) => Element::Raw(string_to_owned(text)),
-            Element::Variable(name) => Element::Variable(string_to_owned(name)),
+            Element::Variable { name, default } => Element::Variable {
+                name: string_to_owned(name),
+                default: option_string_to_owned(default),
+            },
+            Element::PageVariable { name } => Element::PageVariable {
+                name: string_to_owned(name),
+            },
             Element::Email(email) => Element::Email(string_to_owned(email)),
             Element::Table(table) => Element::Table(table.to_owned()),
             Element::TabView(tabs) => {
@@ -465,12 +563,16 @@ impl Element<'_> {
             Element::Image {
                 source,
                 link,
+                link_extra,
                 alignment,
+                lazy,
                 attributes,
             } => Element::Image {
                 source: source.to_owned(),
                 link: link.ref_map(|link| link.to_owned()),
+                link_extra: option_string_to_owned(link_extra),
                 alignment: *alignment,
+                lazy: *lazy,
                 attributes: attributes.to_owned(),
             },
             Element::DefinitionList(items) => Element::DefinitionList(
@@ -500,6 +602,7 @@ impl Element<'_> {
                 hide_text,
                 show_top,
                 show_bottom,
+                remember,
             } => Element::Collapsible {
                 elements: elements_to_owned(elements),
                 attributes: attributes.to_owned(),
@@ -508,10 +611,21 @@ impl Element<'_> {
                 hide_text: option_string_to_owned(hide_text),
                 show_top: *show_top,
                 show_bottom: *show_bottom,
+                remember: *remember,
             },
-            Element::TableOfContents { align, attributes } => Element::TableOfContents {
+            Element::TableOfContents {
+                align,
+                attributes,
+                min_level,
+                max_level,
+            } => Element::TableOfContents {
                 align: *align,
                 attributes: attributes.to_owned(),
+                min_level: *min_level,
+                max_level: *max_level,
+            },
+            Element::Pagination { attributes } => Element::Pagination {
+                attributes: attributes.to_owned(),
             },
             Element::Footnote => Element::Footnote,
             Element::FootnoteBlock { title, hide } => Element::FootnoteBlock {
@@ -533,6 +647,9 @@ impl Element<'_> {
                 name: string_to_owned(name),
                 show_avatar: *show_avatar,
             },
+            Element::Mention { name } => Element::Mention {
+                name: string_to_owned(name),
+            },
             Element::Date {
                 value,
                 format,
@@ -550,9 +667,14 @@ impl Element<'_> {
                 contents: string_to_owned(contents),
                 language: option_string_to_owned(language),
             },
-            Element::Math { name, latex_source } => Element::Math {
+            Element::Math {
+                name,
+                latex_source,
+                env,
+            } => Element::Math {
                 name: option_string_to_owned(name),
                 latex_source: string_to_owned(latex_source),
+                env: *env,
             },
             Element::MathInline { latex_source } => Element::MathInline {
                 latex_source: string_to_owned(latex_source),
@@ -561,8 +683,16 @@ impl Element<'_> {
                 Element::EquationReference(string_to_owned(name))
             }
             Element::Embed(embed) => Element::Embed(embed.to_owned()),
-            Element::Html { contents } => Element::Html {
+            Element::Html {
+                contents,
+                width,
+                height,
+                resize,
+            } => Element::Html {
                 contents: string_to_owned(contents),
+                width: *width,
+                height: *height,
+                resize: *resize,
             },
             Element::Iframe { url, attributes } => Element::Iframe {
                 url: string_to_owned(url),
@@ -579,6 +709,15 @@ impl Element<'_> {
                 location: location.to_owned(),
                 elements: elements_to_owned(elements),
             },
+            Element::Conditional {
+                paragraph_safe,
+                conditions,
+                elements,
+            } => Element::Conditional {
+                paragraph_safe: *paragraph_safe,
+                conditions: conditions.iter().map(ElementCondition::to_owned).collect(),
+                elements: elements_to_owned(elements),
+            },
             Element::Style(css) => Element::Style(string_to_owned(css)),
             Element::LineBreak => Element::LineBreak,
             Element::LineBreaks(amount) => Element::LineBreaks(*amount),
@@ -588,3 +727,8 @@ impl Element<'_> {
         }
     }
 }
+
+#[inline]
+fn is_false(value: &bool) -> bool {
+    !value
+}
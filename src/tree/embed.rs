@@ -49,6 +49,38 @@ impl Embed<'_> {
         }
     }
 
+    /// The name of this embed's provider, as used in [`EmbedSettings`].
+    ///
+    /// [`EmbedSettings`]: crate::settings::EmbedSettings
+    pub fn provider(&self) -> &'static str {
+        match self {
+            Embed::Youtube { .. } => "youtube",
+            Embed::Vimeo { .. } => "vimeo",
+            Embed::GithubGist { .. } => "github-gist",
+            Embed::GitlabSnippet { .. } => "gitlab-snippet",
+        }
+    }
+
+    /// Whether this embed's IDs are well-formed for its provider.
+    ///
+    /// Since IDs are substituted into the embedded URL, this prevents
+    /// pointing the embed at some other page on the provider's site.
+    /// The parser only produces valid embeds, but deserialized trees
+    /// must be checked before they are rendered.
+    pub fn is_valid(&self) -> bool {
+        match self {
+            Embed::Youtube { video_id } => valid_id(video_id, youtube_id_char),
+            Embed::Vimeo { video_id } => valid_id(video_id, vimeo_id_char),
+            Embed::GithubGist { username, hash } => {
+                valid_id(username, github_username_char)
+                    && valid_id(hash, |c| c.is_ascii_hexdigit())
+            }
+            Embed::GitlabSnippet { snippet_id } => {
+                valid_id(snippet_id, |c| c.is_ascii_digit())
+            }
+        }
+    }
+
     pub fn direct_url(&self) -> String {
         match self {
             Embed::Youtube { video_id } => format!("https://youtu.be/{video_id}"),
@@ -83,3 +115,91 @@ impl Embed<'_> {
         }
     }
 }
+
+/// Checks that an ID is non-empty and only contains the permitted characters.
+fn valid_id(id: &str, permitted: fn(char) -> bool) -> bool {
+    !id.is_empty() && id.chars().all(permitted)
+}
+
+#[inline]
+fn youtube_id_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_'
+}
+
+#[inline]
+fn vimeo_id_char(c: char) -> bool {
+    c.is_ascii_digit()
+}
+
+#[inline]
+fn github_username_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-'
+}
+
+#[test]
+fn embed_ids() {
+    macro_rules! check {
+        ($embed:expr, $valid:expr $(,)?) => {
+            assert_eq!(
+                $embed.is_valid(),
+                $valid,
+                "Embed validity doesn't match expected: {:?}",
+                $embed,
+            );
+        };
+    }
+
+    macro_rules! youtube {
+        ($id:expr) => {
+            Embed::Youtube {
+                video_id: cow!($id),
+            }
+        };
+    }
+
+    macro_rules! vimeo {
+        ($id:expr) => {
+            Embed::Vimeo {
+                video_id: cow!($id),
+            }
+        };
+    }
+
+    check!(youtube!("dQw4w9WgXcQ"), true);
+    check!(youtube!("a-b_c"), true);
+    check!(youtube!(""), false);
+    check!(youtube!("../watch"), false);
+    check!(youtube!("../../evil"), false);
+    check!(youtube!("abc?autoplay=1"), false);
+
+    check!(vimeo!("221821296"), true);
+    check!(vimeo!("221821296/edit"), false);
+
+    check!(
+        Embed::GithubGist {
+            username: cow!("octo-cat"),
+            hash: cow!("aa5a315d61ae9438b18d"),
+        },
+        true,
+    );
+    check!(
+        Embed::GithubGist {
+            username: cow!("octocat"),
+            hash: cow!("../../evil"),
+        },
+        false,
+    );
+
+    check!(
+        Embed::GitlabSnippet {
+            snippet_id: cow!("20"),
+        },
+        true,
+    );
+    check!(
+        Embed::GitlabSnippet {
+            snippet_id: cow!("20/raw"),
+        },
+        false,
+    );
+}
@@ -18,9 +18,13 @@
  * along with this program. If not, see <http://www.gnu.org/licenses/>.
  */
 
-use super::HtmlTag;
+use super::visit::{walk_element, Visitor};
+use super::{ContainerType, Element, HtmlTag, SyntaxTree};
 use crate::next_index::{NextIndex, TableOfContentsIndex};
+use std::borrow::Cow;
+use std::collections::HashSet;
 use std::convert::TryFrom;
+use wikidot_normalize::normalize;
 
 #[derive(Serialize, Deserialize, Debug, Copy, Clone, Hash, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
@@ -34,16 +38,26 @@ pub struct Heading {
 
     /// Whether this heading should get a table of contents entry or not.
     pub has_toc: bool,
+
+    /// Whether the section following this heading can be collapsed.
+    ///
+    /// The section extends until the next heading of the same or a higher level.
+    #[serde(default)]
+    pub collapsible: bool,
 }
 
 impl Heading {
-    pub fn html_tag(self, indexer: &mut dyn NextIndex<TableOfContentsIndex>) -> HtmlTag {
+    pub fn html_tag(
+        self,
+        indexer: &mut dyn NextIndex<TableOfContentsIndex>,
+        anchors: &[String],
+    ) -> HtmlTag {
         let tag = self.level.html_tag();
 
         if self.has_toc {
-            let id = format!("toc{}", indexer.next());
+            let id = heading_anchor(anchors, indexer.next());
 
-            HtmlTag::with_id(tag, id)
+            HtmlTag::with_id(tag, id.into_owned())
         } else {
             HtmlTag::new(tag)
         }
@@ -54,12 +68,18 @@ impl TryFrom<&'_ str> for Heading {
     type Error = ();
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        // Headings take the form "\+{1,6}\*?" (regex)
-        // The trailing "*" means that the TOC is *not* applied.
+        // Headings take the form "\+{1,6}\*?-?" (regex)
+        // The "*" means that the TOC is *not* applied.
+        // The trailing "-" means that the following section is collapsible.
         // The heading depth is simply the ASCII length of "+" characters.
         //
         // This does *not* validate the regex, it assumes the string fits.
 
+        let (collapsible, value) = match value.strip_suffix('-') {
+            Some(value) => (true, value),
+            None => (false, value),
+        };
+
         let last_char = value.chars().next_back().ok_or(())?;
 
         let (has_toc, len) = match last_char {
@@ -70,8 +90,128 @@ impl TryFrom<&'_ str> for Heading {
 
         let level = HeadingLevel::try_from(len)?;
 
-        Ok(Heading { level, has_toc })
+        Ok(Heading {
+            level,
+            has_toc,
+            collapsible,
+        })
+    }
+}
+
+/// Information about a heading present in a syntax tree.
+///
+/// Unlike the table of contents, this includes headings which
+/// opted out of a TOC entry, and retains the elements which
+/// compose the heading name rather than just its text.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct HeadingInfo<'t> {
+    /// The depth of this heading.
+    pub level: HeadingLevel,
+
+    /// The elements composing the heading's name.
+    pub elements: Vec<Element<'t>>,
+
+    /// The HTML ID of this heading, if it has one.
+    ///
+    /// Only headings with a table of contents entry get an ID,
+    /// which is what the corresponding TOC link points to.
+    pub anchor: Option<String>,
+}
+
+/// Collects all the headings in a syntax tree, in document order.
+pub(crate) fn collect_headings<'t>(tree: &SyntaxTree<'t>) -> Vec<HeadingInfo<'t>> {
+    #[derive(Debug)]
+    struct HeadingCollector<'a, 't> {
+        headings: Vec<HeadingInfo<'t>>,
+        anchors: &'a [String],
+        toc_index: usize,
     }
+
+    impl<'t> Visitor<'t> for HeadingCollector<'_, 't> {
+        fn visit_element(&mut self, element: &Element<'t>) {
+            if let Element::Container(container) = element {
+                if let ContainerType::Header(heading) = container.ctype() {
+                    let anchor = if heading.has_toc {
+                        let id = heading_anchor(self.anchors, self.toc_index);
+                        self.toc_index += 1;
+                        Some(id.into_owned())
+                    } else {
+                        None
+                    };
+
+                    self.headings.push(HeadingInfo {
+                        level: heading.level,
+                        elements: container.elements().to_vec(),
+                        anchor,
+                    });
+                }
+            }
+
+            walk_element(self, element);
+        }
+    }
+
+    // Only the main body is walked, since the table of contents
+    // and footnotes do not contain headings of their own.
+    let mut collector = HeadingCollector {
+        headings: Vec::new(),
+        anchors: &tree.heading_anchors,
+        toc_index: 0,
+    };
+    collector.visit_elements(&tree.elements);
+    collector.headings
+}
+
+/// Gets the HTML ID of the heading with the given table of contents index.
+///
+/// This is the slugified ID from `anchors` if present (see
+/// [`SyntaxTree::heading_anchors`]), otherwise `toc{index}`.
+pub fn heading_anchor(anchors: &[String], index: usize) -> Cow<'_, str> {
+    match anchors.get(index) {
+        Some(anchor) => Cow::Borrowed(anchor),
+        None => Cow::Owned(format!("toc{index}")),
+    }
+}
+
+/// Builds slugified, unique HTML IDs from the text of each heading.
+///
+/// The text is normalized, similar to page names, so "Early History"
+/// becomes `early-history`. Duplicates have a counter appended, so the
+/// second "Early History" becomes `early-history-1`. Headings with no
+/// usable text fall back to `toc{index}`.
+pub(crate) fn build_heading_anchors<'a, I>(names: I) -> Vec<String>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let mut used = HashSet::new();
+
+    names
+        .into_iter()
+        .enumerate()
+        .map(|(index, name)| {
+            let mut slug = str!(name);
+            normalize(&mut slug);
+
+            // Colons denote categories in page names, but not here
+            let slug = slug.replace(':', "-");
+            let slug = if slug.is_empty() {
+                format!("toc{index}")
+            } else {
+                slug
+            };
+
+            let mut anchor = slug.clone();
+            let mut counter = 1;
+            while used.contains(&anchor) {
+                anchor = format!("{slug}-{counter}");
+                counter += 1;
+            }
+
+            used.insert(anchor.clone());
+            anchor
+        })
+        .collect()
 }
 
 #[derive(Serialize_repr, Deserialize_repr, Debug, Copy, Clone, Hash, PartialEq, Eq)]
@@ -177,7 +317,7 @@ impl TryFrom<u8> for HeadingLevel {
 #[test]
 fn heading() {
     macro_rules! check {
-        ($input:expr, $level:expr, $has_toc:expr) => {{
+        ($input:expr, $level:expr, $has_toc:expr, $collapsible:expr) => {{
             use std::convert::TryInto;
 
             let level = ($level as u8)
@@ -192,20 +332,131 @@ fn heading() {
                 heading.has_toc, $has_toc,
                 "Heading table of contents value doesn't match expected",
             );
+            assert_eq!(
+                heading.collapsible, $collapsible,
+                "Heading collapsible value doesn't match expected",
+            );
         }};
     }
 
-    check!("+", 1, true);
-    check!("++", 2, true);
-    check!("+++", 3, true);
-    check!("++++", 4, true);
-    check!("+++++", 5, true);
-    check!("++++++", 6, true);
-
-    check!("+*", 1, false);
-    check!("++*", 2, false);
-    check!("+++*", 3, false);
-    check!("++++*", 4, false);
-    check!("+++++*", 5, false);
-    check!("++++++*", 6, false);
+    check!("+", 1, true, false);
+    check!("++", 2, true, false);
+    check!("+++", 3, true, false);
+    check!("++++", 4, true, false);
+    check!("+++++", 5, true, false);
+    check!("++++++", 6, true, false);
+
+    check!("+*", 1, false, false);
+    check!("++*", 2, false, false);
+    check!("+++*", 3, false, false);
+    check!("++++*", 4, false, false);
+    check!("+++++*", 5, false, false);
+    check!("++++++*", 6, false, false);
+
+    check!("+-", 1, true, true);
+    check!("+++-", 3, true, true);
+    check!("++*-", 2, false, true);
+    check!("++++++*-", 6, false, true);
+}
+
+#[test]
+fn headings() {
+    use crate::data::PageInfo;
+    use crate::layout::Layout;
+    use crate::settings::{WikitextMode, WikitextSettings};
+
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+
+    let text = "+ Apple\n\n++* Banana\n\n[[div]]\n+++ **Cherry**\n[[/div]]\n\nDurian";
+    let tokens = crate::tokenize(text);
+    let (tree, _) = crate::parse(&tokens, &page_info, &settings).into();
+
+    let headings = tree.headings();
+    let summary: Vec<_> = headings
+        .iter()
+        .map(|heading| (heading.level.value(), heading.anchor.as_deref()))
+        .collect();
+
+    assert_eq!(
+        summary,
+        vec![(1, Some("toc0")), (2, None), (3, Some("toc1"))],
+        "Collected headings don't match expected",
+    );
+    assert_eq!(
+        headings[0].elements,
+        vec![text!("Apple")],
+        "Heading elements don't match expected",
+    );
+}
+
+#[cfg(feature = "html")]
+#[test]
+fn heading_anchors() {
+    use crate::data::PageInfo;
+    use crate::layout::Layout;
+    use crate::render::{html::HtmlRender, Render};
+    use crate::settings::{WikitextMode, WikitextSettings};
+
+    assert_eq!(
+        build_heading_anchors([
+            "Early History",
+            "SCP-173: The Sculpture",
+            "Early History",
+            "Early History",
+            "???",
+            "Early History-1",
+        ]),
+        vec![
+            "early-history",
+            "scp-173-the-sculpture",
+            "early-history-1",
+            "early-history-2",
+            "toc4",
+            "early-history-1-1",
+        ],
+        "Built heading anchors don't match expected",
+    );
+
+    let page_info = PageInfo::dummy();
+    let mut settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    settings.slugify_heading_ids = true;
+
+    let text = "[[toc]]\n\n+ Apple Pie\n\n++* Banana\n\n++ Apple Pie";
+    let tokens = crate::tokenize(text);
+    let (tree, _) = crate::parse(&tokens, &page_info, &settings).into();
+
+    assert_eq!(
+        tree.heading_anchors,
+        vec!["apple-pie", "apple-pie-1"],
+        "Tree heading anchors don't match expected",
+    );
+
+    let summary: Vec<_> = tree
+        .headings()
+        .into_iter()
+        .map(|heading| heading.anchor)
+        .collect();
+
+    assert_eq!(
+        summary,
+        vec![Some(str!("apple-pie")), None, Some(str!("apple-pie-1"))],
+        "Collected heading anchors don't match expected",
+    );
+
+    let html = HtmlRender.render(&tree, &page_info, &settings).body;
+    for anchor in ["apple-pie", "apple-pie-1"] {
+        assert!(
+            html.contains(&format!("href=\"#{anchor}\"")),
+            "Table of contents link to {anchor} not found in {html}",
+        );
+        assert!(
+            html.contains(&format!("id=\"{anchor}\"")),
+            "Heading ID {anchor} not found in {html}",
+        );
+    }
+    assert!(
+        !html.contains("toc0"),
+        "Numbered heading ID found in {html}"
+    );
 }
@@ -19,7 +19,8 @@
  */
 
 use super::clone::string_to_owned;
-use crate::url::is_url;
+use crate::settings::WikitextSettings;
+use crate::url::is_url_with_settings;
 use std::borrow::Cow;
 use strum_macros::IntoStaticStr;
 
@@ -39,6 +40,8 @@ pub enum ImageSource<'a> {
     },
 
     /// Image is attached to another page on another site.
+    ///
+    /// This is written as either `site/page/file` or `:site:page/file`.
     File3 {
         site: Cow<'a, str>,
         page: Cow<'a, str>,
@@ -47,11 +50,34 @@ pub enum ImageSource<'a> {
 }
 
 impl<'t> ImageSource<'t> {
-    pub fn parse(source: &'t str) -> Option<ImageSource<'t>> {
-        if is_url(source) {
+    pub fn parse(
+        source: &'t str,
+        settings: &WikitextSettings,
+    ) -> Option<ImageSource<'t>> {
+        if is_url_with_settings(source, settings) {
             return Some(ImageSource::Url(cow!(source)));
         }
 
+        // Cross-site attachment, in the form ":site:page/file"
+        if let Some(source) = source.strip_prefix(':') {
+            let (site, path) = source.split_once(':')?;
+            let (page, file) = path.split_once('/')?;
+
+            if !is_site_slug(site)
+                || page.is_empty()
+                || file.is_empty()
+                || file.contains('/')
+            {
+                return None;
+            }
+
+            return Some(ImageSource::File3 {
+                site: cow!(site),
+                page: cow!(page),
+                file: cow!(file),
+            });
+        }
+
         // Strip leading / if present
         let source = source.strip_prefix('/').unwrap_or(source);
 
@@ -67,7 +93,7 @@ impl<'t> ImageSource<'t> {
                 page: cow!(parts[0]),
                 file: cow!(parts[1]),
             },
-            3 => ImageSource::File3 {
+            3 if is_site_slug(parts[0]) => ImageSource::File3 {
                 site: cow!(parts[0]),
                 page: cow!(parts[1]),
                 file: cow!(parts[2]),
@@ -101,3 +127,91 @@ impl<'t> ImageSource<'t> {
         }
     }
 }
+
+/// Determines if this is a valid site slug, such as `scp-wiki`.
+///
+/// The site is used as part of the hostname when building attachment URLs,
+/// so anything else could point to another origin entirely.
+fn is_site_slug(site: &str) -> bool {
+    !site.is_empty()
+        && site
+            .bytes()
+            .all(|b| matches!(b, b'a'..=b'z' | b'0'..=b'9' | b'-'))
+}
+
+#[test]
+fn parse() {
+    use crate::layout::Layout;
+    use crate::settings::WikitextMode;
+
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+
+    macro_rules! check {
+        ($input:expr, $expected:expr $(,)?) => {
+            assert_eq!(
+                ImageSource::parse($input, &settings),
+                $expected,
+                "Parsed image source for {:?} doesn't match expected",
+                $input,
+            )
+        };
+    }
+
+    check!(
+        "https://example.com/apple.png",
+        Some(ImageSource::Url(cow!("https://example.com/apple.png"))),
+    );
+    check!(
+        "apple.png",
+        Some(ImageSource::File1 {
+            file: cow!("apple.png"),
+        }),
+    );
+    check!(
+        "/fruit/apple.png",
+        Some(ImageSource::File2 {
+            page: cow!("fruit"),
+            file: cow!("apple.png"),
+        }),
+    );
+    check!(
+        "orchard/fruit/apple.png",
+        Some(ImageSource::File3 {
+            site: cow!("orchard"),
+            page: cow!("fruit"),
+            file: cow!("apple.png"),
+        }),
+    );
+    check!(
+        ":orchard:fruit/apple.png",
+        Some(ImageSource::File3 {
+            site: cow!("orchard"),
+            page: cow!("fruit"),
+            file: cow!("apple.png"),
+        }),
+    );
+    check!(":orchard:apple.png", None);
+    check!("::fruit/apple.png", None);
+    check!(":orchard:fruit/", None);
+    check!(":orchard:fruit/extra/apple.png", None);
+    check!("a/b/c/d.png", None);
+
+    // Sites must be slugs, since they are part of the hostname
+    check!(":evil.com/x?:fruit/apple.png", None);
+    check!(":evil.com:fruit/apple.png", None);
+    check!(":user@evil:fruit/apple.png", None);
+    check!(":orchard#:fruit/apple.png", None);
+    check!(":orchard?:fruit/apple.png", None);
+    check!(":Orchard:fruit/apple.png", None);
+    check!("evil.com/fruit/apple.png", None);
+    check!("user@evil/fruit/apple.png", None);
+    check!("orchard?/fruit/apple.png", None);
+    check!(
+        ":scp-wiki-2:fruit/apple.png",
+        Some(ImageSource::File3 {
+            site: cow!("scp-wiki-2"),
+            page: cow!("fruit"),
+            file: cow!("apple.png"),
+        }),
+    );
+}
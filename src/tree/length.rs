@@ -0,0 +1,219 @@
+/*
+ * tree/length.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::fmt::{self, Display};
+
+/// A non-negative CSS length, such as `120px` or `50%`.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct Length {
+    pub value: f64,
+    pub unit: LengthUnit,
+}
+
+// Lengths are only produced by parse(), which never yields NaN.
+impl Eq for Length {}
+
+impl Length {
+    /// Parses a CSS length, such as `1.5em`.
+    ///
+    /// A bare number is taken to be in pixels, as with the HTML `width` attribute.
+    /// Negative values, exponents, and `calc()` expressions are not accepted.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let split = value
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(value.len());
+
+        let (number, unit) = value.split_at(split);
+
+        // Reject empty numbers, as well as forms like "1." or ".5."
+        if number.is_empty()
+            || number.ends_with('.')
+            || number.bytes().filter(|&b| b == b'.').count() > 1
+        {
+            return None;
+        }
+
+        let value = number
+            .parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite())?;
+        let unit = if unit.is_empty() {
+            LengthUnit::Pixels
+        } else {
+            LengthUnit::from_name(unit)?
+        };
+
+        Some(Length { value, unit })
+    }
+}
+
+impl Display for Length {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.value, self.unit.name())
+    }
+}
+
+/// A CSS size, which is either a [`Length`] or `auto`.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Dimension {
+    Auto,
+    Length(Length),
+}
+
+impl Dimension {
+    /// Parses a CSS size, such as `auto` or `200px`.
+    ///
+    /// See [`Length::parse()`] for which lengths are accepted.
+    pub fn parse(value: &str) -> Option<Self> {
+        if value.trim().eq_ignore_ascii_case("auto") {
+            Some(Dimension::Auto)
+        } else {
+            Length::parse(value).map(Dimension::Length)
+        }
+    }
+}
+
+impl Display for Dimension {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Dimension::Auto => write!(f, "auto"),
+            Dimension::Length(length) => write!(f, "{length}"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum LengthUnit {
+    Pixels,
+    Points,
+    Em,
+    Rem,
+    Ex,
+    Ch,
+    Percent,
+    ViewportWidth,
+    ViewportHeight,
+    Centimeters,
+    Millimeters,
+    Inches,
+}
+
+impl LengthUnit {
+    /// The CSS name of this unit, such as `px`.
+    pub fn name(self) -> &'static str {
+        match self {
+            LengthUnit::Pixels => "px",
+            LengthUnit::Points => "pt",
+            LengthUnit::Em => "em",
+            LengthUnit::Rem => "rem",
+            LengthUnit::Ex => "ex",
+            LengthUnit::Ch => "ch",
+            LengthUnit::Percent => "%",
+            LengthUnit::ViewportWidth => "vw",
+            LengthUnit::ViewportHeight => "vh",
+            LengthUnit::Centimeters => "cm",
+            LengthUnit::Millimeters => "mm",
+            LengthUnit::Inches => "in",
+        }
+    }
+
+    /// Parses a unit from its CSS name, such as `px`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        const UNITS: [LengthUnit; 12] = [
+            LengthUnit::Pixels,
+            LengthUnit::Points,
+            LengthUnit::Em,
+            LengthUnit::Rem,
+            LengthUnit::Ex,
+            LengthUnit::Ch,
+            LengthUnit::Percent,
+            LengthUnit::ViewportWidth,
+            LengthUnit::ViewportHeight,
+            LengthUnit::Centimeters,
+            LengthUnit::Millimeters,
+            LengthUnit::Inches,
+        ];
+
+        UNITS
+            .into_iter()
+            .find(|unit| unit.name().eq_ignore_ascii_case(name))
+    }
+}
+
+#[test]
+fn length() {
+    macro_rules! check {
+        ($input:expr) => {
+            check!($input => None)
+        };
+
+        ($input:expr, $value:expr, $unit:ident) => {
+            check!($input => Some(Length {
+                value: $value,
+                unit: LengthUnit::$unit,
+            }))
+        };
+
+        ($input:expr => $expected:expr) => {{
+            let actual = Length::parse($input);
+            let expected = $expected;
+
+            assert_eq!(
+                actual, expected,
+                "Actual length result does not match expected",
+            );
+        }};
+    }
+
+    check!("");
+    check!("px");
+    check!("-10px");
+    check!("10 px");
+    check!("1.px");
+    check!("1.2.3em");
+    check!("1e3px");
+    check!("10furlongs");
+    check!("calc(100% - 1em)");
+
+    check!("0", 0.0, Pixels);
+    check!("120", 120.0, Pixels);
+    check!("120px", 120.0, Pixels);
+    check!(" 120PX ", 120.0, Pixels);
+    check!("1.5em", 1.5, Em);
+    check!(".5rem", 0.5, Rem);
+    check!("50%", 50.0, Percent);
+    check!("12pt", 12.0, Points);
+    check!("30vw", 30.0, ViewportWidth);
+    check!("2in", 2.0, Inches);
+
+    assert_eq!(Length::parse("120").unwrap().to_string(), "120px");
+    assert_eq!(Length::parse("1.50em").unwrap().to_string(), "1.5em");
+    assert_eq!(Length::parse("50%").unwrap().to_string(), "50%");
+
+    assert_eq!(Dimension::parse(" Auto "), Some(Dimension::Auto));
+    assert_eq!(Dimension::parse("-1"), None);
+    assert_eq!(Dimension::parse("abc"), None);
+    assert_eq!(Dimension::parse("200").unwrap().to_string(), "200px");
+    assert_eq!(Dimension::Auto.to_string(), "auto");
+}
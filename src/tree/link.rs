@@ -20,8 +20,8 @@
 
 use super::clone::{option_string_to_owned, string_to_owned};
 use crate::data::PageRef;
-use crate::settings::WikitextSettings;
-use crate::url::is_url;
+use crate::settings::{InterwikiSettings, WikitextSettings};
+use crate::url::is_url_with_settings;
 use std::borrow::Cow;
 use strum_macros::EnumIter;
 
@@ -36,34 +36,56 @@ pub enum LinkLocation<'a> {
 }
 
 impl<'a> LinkLocation<'a> {
+    /// Parses a link, using the interwiki prefixes from `settings`.
+    ///
+    /// This does not see any prefixes added by `[[interwiki]]` blocks,
+    /// see [`parse_interwiki_with()`](Self::parse_interwiki_with) instead.
+    #[deprecated(
+        since = "1.29.0",
+        note = "use parse_interwiki_with() to pass the active interwiki prefixes"
+    )]
     pub fn parse_interwiki(
         link: Cow<'a, str>,
         settings: &WikitextSettings,
+    ) -> Option<(Self, LinkType)> {
+        Self::parse_interwiki_with(link, &settings.interwiki, settings)
+    }
+
+    /// Parses a link, using the given interwiki prefixes.
+    ///
+    /// Links beginning with `!` are looked up in `interwiki`,
+    /// any others are parsed as with [`parse()`](Self::parse).
+    pub fn parse_interwiki_with(
+        link: Cow<'a, str>,
+        interwiki: &InterwikiSettings,
+        settings: &WikitextSettings,
     ) -> Option<(Self, LinkType)> {
         // Handle interwiki (starts with "!", like "!wp:Apple")
         match link.as_ref().strip_prefix('!') {
             // Not interwiki, parse as normal
             None => {
-                let interwiki = Self::parse(link);
+                let interwiki = Self::parse(link, settings);
                 let ltype = interwiki.link_type();
                 Some((interwiki, ltype))
             }
 
             // Try to interpret as interwiki
-            Some(link) => settings
-                .interwiki
+            Some(link) => interwiki
                 .build(link)
                 .map(|url| (LinkLocation::Url(Cow::Owned(url)), LinkType::Interwiki)),
         }
     }
 
-    pub fn parse(link: Cow<'a, str>) -> Self {
+    pub fn parse(link: Cow<'a, str>, settings: &WikitextSettings) -> Self {
         let mut link_str = link.as_ref();
 
         // Check for direct URLs or anchor links
         // TODO: parse local links into LinkLocation::Page
         // Known bug: single "/" parsed into Url instead of Page
-        if is_url(link_str) || link_str.starts_with('#') || link_str.starts_with("/") {
+        if is_url_with_settings(link_str, settings)
+            || link_str.starts_with('#')
+            || link_str.starts_with("/")
+        {
             return LinkLocation::Url(link);
         }
 
@@ -87,12 +109,18 @@ impl<'a> LinkLocation<'a> {
         }
     }
 
-    pub fn parse_extra(link: Cow<'a, str>) -> Option<Cow<'a, str>> {
+    pub fn parse_extra(
+        link: Cow<'a, str>,
+        settings: &WikitextSettings,
+    ) -> Option<Cow<'a, str>> {
         let link_str = link.as_ref();
 
         // Check for direct URLs or anchor links
         // Does not parse local links for now
-        if is_url(link_str) || link_str.starts_with('#') || link_str.starts_with('/') {
+        if is_url_with_settings(link_str, settings)
+            || link_str.starts_with('#')
+            || link_str.starts_with('/')
+        {
             return None;
         }
 
@@ -128,6 +156,11 @@ impl<'a> LinkLocation<'a> {
 
 #[test]
 fn test_link_location() {
+    use crate::layout::Layout;
+    use crate::settings::WikitextMode;
+
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+
     macro_rules! check {
         ($input:expr => $site:expr, $page:expr) => {{
             let site = $site.map(|site| cow!(site));
@@ -145,7 +178,7 @@ fn test_link_location() {
         };
 
         ($input:expr; $expected:expr) => {{
-            let actual = LinkLocation::parse(cow!($input));
+            let actual = LinkLocation::parse(cow!($input), &settings);
 
             assert_eq!(
                 actual,
@@ -182,9 +215,14 @@ fn test_link_location() {
 
 #[test]
 fn test_link_extra() {
+    use crate::layout::Layout;
+    use crate::settings::WikitextMode;
+
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+
     macro_rules! check {
         ($input:expr => $expected:expr) => {{
-            let actual = LinkLocation::parse_extra(cow!($input));
+            let actual = LinkLocation::parse_extra(cow!($input), &settings);
             let expected = $expected.map(|s| cow!(s));
 
             assert_eq!(
@@ -228,6 +266,18 @@ pub enum LinkLabel<'a> {
 }
 
 impl LinkLabel<'_> {
+    /// Whether this is a text label which is empty or only whitespace.
+    ///
+    /// Such labels would render as an empty anchor, so link rules never
+    /// produce them. Instead they use a label derived from the link target.
+    /// Renderers treat any that do occur like [`LinkLabel::Url(None)`](LinkLabel::Url).
+    pub fn is_blank(&self) -> bool {
+        match self {
+            LinkLabel::Text(text) => text.trim().is_empty(),
+            _ => false,
+        }
+    }
+
     pub fn to_owned(&self) -> LinkLabel<'static> {
         match self {
             LinkLabel::Text(text) => LinkLabel::Text(string_to_owned(text)),
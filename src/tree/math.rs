@@ -0,0 +1,107 @@
+/*
+ * tree/math.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+/// The LaTeX environment a math block is rendered in.
+///
+/// Only environments which can be converted to MathML are permitted.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MathEnvironment {
+    Align,
+    Gather,
+    Cases,
+    Matrix,
+    PMatrix,
+    BMatrix,
+    VMatrix,
+}
+
+impl MathEnvironment {
+    pub fn name(self) -> &'static str {
+        match self {
+            MathEnvironment::Align => "align",
+            MathEnvironment::Gather => "gather",
+            MathEnvironment::Cases => "cases",
+            MathEnvironment::Matrix => "matrix",
+            MathEnvironment::PMatrix => "pmatrix",
+            MathEnvironment::BMatrix => "bmatrix",
+            MathEnvironment::VMatrix => "vmatrix",
+        }
+    }
+
+    /// Parses an environment from its name, such as `gather`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        const ENVIRONMENTS: [MathEnvironment; 7] = [
+            MathEnvironment::Align,
+            MathEnvironment::Gather,
+            MathEnvironment::Cases,
+            MathEnvironment::Matrix,
+            MathEnvironment::PMatrix,
+            MathEnvironment::BMatrix,
+            MathEnvironment::VMatrix,
+        ];
+
+        ENVIRONMENTS
+            .into_iter()
+            .find(|env| env.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Wraps LaTeX source in this environment.
+    ///
+    /// Environments not natively supported by the MathML converter
+    /// are expressed using ones which are.
+    pub fn wrap_source(self, latex_source: &str) -> String {
+        match self {
+            MathEnvironment::Gather => {
+                format!("\\begin{{matrix}}{latex_source}\\end{{matrix}}")
+            }
+            MathEnvironment::Cases => {
+                format!("\\left\\{{\\begin{{align}}{latex_source}\\end{{align}}\\right.")
+            }
+            _ => {
+                let name = self.name();
+                format!("\\begin{{{name}}}{latex_source}\\end{{{name}}}")
+            }
+        }
+    }
+}
+
+#[test]
+fn math_environment() {
+    assert_eq!(
+        MathEnvironment::from_name("pmatrix"),
+        Some(MathEnvironment::PMatrix),
+    );
+    assert_eq!(
+        MathEnvironment::from_name("Gather"),
+        Some(MathEnvironment::Gather),
+    );
+    assert_eq!(MathEnvironment::from_name("equation"), None);
+    assert_eq!(MathEnvironment::from_name(""), None);
+
+    assert_eq!(
+        MathEnvironment::BMatrix.wrap_source("a & b"),
+        "\\begin{bmatrix}a & b\\end{bmatrix}",
+    );
+    assert_eq!(
+        MathEnvironment::Cases.wrap_source("x"),
+        "\\left\\{\\begin{align}x\\end{align}\\right.",
+    );
+}
@@ -0,0 +1,57 @@
+/*
+ * tree/mention.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::visit::{walk_element, Visitor};
+use super::Element;
+use std::borrow::Cow;
+
+/// Collects the names of all users mentioned, in order of first appearance.
+///
+/// Each name is only listed once, even if that user is mentioned repeatedly.
+pub(crate) fn collect_mentions<'t>(
+    elements: &[Element<'t>],
+    footnotes: &[Vec<Element<'t>>],
+) -> Vec<Cow<'t, str>> {
+    #[derive(Debug, Default)]
+    struct MentionCollector<'t> {
+        mentions: Vec<Cow<'t, str>>,
+    }
+
+    impl<'t> Visitor<'t> for MentionCollector<'t> {
+        fn visit_element(&mut self, element: &Element<'t>) {
+            if let Element::Mention { name } = element {
+                if !self.mentions.contains(name) {
+                    self.mentions.push(name.clone());
+                }
+            }
+
+            walk_element(self, element);
+        }
+    }
+
+    let mut collector = MentionCollector::default();
+    collector.visit_elements(elements);
+
+    for footnote in footnotes {
+        collector.visit_elements(footnote);
+    }
+
+    collector.mentions
+}
@@ -19,10 +19,14 @@
  */
 
 pub mod attribute;
+pub mod diff;
+pub mod sanitize;
+pub mod visit;
 
 mod align;
 mod anchor;
 mod bibliography;
+mod binary;
 mod clear_float;
 mod clone;
 mod code;
@@ -33,11 +37,15 @@ mod element;
 mod embed;
 mod heading;
 mod image;
+mod length;
 mod link;
 mod list;
+mod math;
+mod mention;
 mod module;
 mod partial;
 mod ruby;
+mod shared;
 mod tab;
 mod table;
 mod tag;
@@ -47,8 +55,9 @@ pub use self::align::*;
 pub use self::anchor::*;
 pub use self::attribute::AttributeMap;
 pub use self::bibliography::*;
+pub use self::binary::{BinaryError, BINARY_FORMAT_VERSION};
 pub use self::clear_float::*;
-pub use self::code::CodeBlock;
+pub use self::code::{content_hash, CodeBlock};
 pub use self::container::*;
 pub use self::date::DateItem;
 pub use self::definition_list::*;
@@ -56,21 +65,28 @@ pub use self::element::*;
 pub use self::embed::*;
 pub use self::heading::*;
 pub use self::image::*;
+pub use self::length::*;
 pub use self::link::*;
 pub use self::list::*;
+pub use self::math::*;
 pub use self::module::*;
 pub use self::partial::*;
 pub use self::ruby::*;
+pub use self::shared::ArcSyntaxTree;
 pub use self::tab::*;
 pub use self::table::*;
 pub use self::tag::*;
 pub use self::variables::*;
 
 use self::clone::{elements_lists_to_owned, elements_to_owned, string_to_owned};
+use self::mention::collect_mentions;
+use crate::data::wire::SCHEMA_VERSION;
 use crate::parsing::{ParseError, ParseOutcome};
 use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::ops::Range;
 
-#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub struct SyntaxTree<'t> {
     /// The list of elements that compose this tree.
@@ -86,6 +102,14 @@ pub struct SyntaxTree<'t> {
     /// match the heading level.
     pub table_of_contents: Vec<Element<'t>>,
 
+    /// The anchor IDs of each heading with a table of contents entry, in order.
+    ///
+    /// This is only populated if `slugify_heading_ids` is enabled in the settings.
+    /// Otherwise, headings use the IDs `toc0`, `toc1`, etc.
+    /// See [`heading_anchor()`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub heading_anchors: Vec<String>,
+
     /// The full list of HTML blocks for this page.
     pub html_blocks: Vec<Cow<'t, str>>,
 
@@ -98,39 +122,120 @@ pub struct SyntaxTree<'t> {
     /// The full list of bibliographies for this page.
     pub bibliographies: BibliographyList<'t>,
 
+    /// The names of the users mentioned on this page, such as with `@username`.
+    ///
+    /// Each user is listed once, in the order they are first mentioned.
+    /// This is only populated if `enable_mentions` is enabled in the settings.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mentions: Vec<Cow<'t, str>>,
+
+    /// The source spans of each top-level element, if recorded.
+    ///
+    /// These are byte offsets into the wikitext passed to the tokenizer,
+    /// and each entry corresponds to the element at the same index in `elements`.
+    /// Any elements added after parsing, such as the implicit footnote block,
+    /// have no span.
+    ///
+    /// This is only populated if `source_spans` is enabled in the settings.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub element_spans: Vec<Range<usize>>,
+
     /// Hint for the size of the wikitext input.
     ///
     /// This is an optimization to make rendering large parges slightly faster.
     #[serde(default)]
     pub wikitext_len: usize,
+
+    /// The schema version of this tree's serialized form.
+    ///
+    /// This is always [`SCHEMA_VERSION`] for trees produced by this library.
+    /// Trees serialized by older versions can be read using [`wire`].
+    ///
+    /// [`wire`]: crate::data::wire
+    #[serde(default = "schema_version")]
+    pub schema_version: u32,
+}
+
+impl Default for SyntaxTree<'_> {
+    fn default() -> Self {
+        SyntaxTree {
+            elements: Vec::new(),
+            table_of_contents: Vec::new(),
+            heading_anchors: Vec::new(),
+            html_blocks: Vec::new(),
+            code_blocks: Vec::new(),
+            footnotes: Vec::new(),
+            bibliographies: BibliographyList::default(),
+            mentions: Vec::new(),
+            element_spans: Vec::new(),
+            wikitext_len: 0,
+            schema_version: SCHEMA_VERSION,
+        }
+    }
+}
+
+#[inline]
+fn schema_version() -> u32 {
+    SCHEMA_VERSION
 }
 
 impl<'t> SyntaxTree<'t> {
     pub(crate) fn from_element_result(
-        elements: Vec<Element<'t>>,
+        (elements, element_spans): (Vec<Element<'t>>, Vec<Range<usize>>),
         errors: Vec<ParseError>,
         (html_blocks, code_blocks): (Vec<Cow<'t, str>>, Vec<CodeBlock<'t>>),
-        table_of_contents: Vec<Element<'t>>,
+        (table_of_contents, heading_anchors): (Vec<Element<'t>>, Vec<String>),
         footnotes: Vec<Vec<Element<'t>>>,
         bibliographies: BibliographyList<'t>,
         wikitext_len: usize,
     ) -> ParseOutcome<Self> {
+        let mentions = collect_mentions(&elements, &footnotes);
         let tree = SyntaxTree {
             elements,
             table_of_contents,
+            heading_anchors,
             html_blocks,
             code_blocks,
             footnotes,
             bibliographies,
+            mentions,
+            element_spans,
             wikitext_len,
+            schema_version: SCHEMA_VERSION,
         };
         ParseOutcome::new(tree, errors)
     }
 
+    /// Returns all the headings on this page, in order.
+    ///
+    /// This is independent of the table of contents, and so
+    /// is available regardless of whether a `[[toc]]` block is present.
+    /// See [`HeadingInfo`] for the information provided.
+    #[inline]
+    pub fn headings(&self) -> Vec<HeadingInfo<'t>> {
+        collect_headings(self)
+    }
+
+    /// Returns the indices of the code blocks with each content hash.
+    ///
+    /// Each key is a [`CodeBlock::hash`], and each value lists the indices in
+    /// `code_blocks` of the blocks with that hash, in order. This allows finding
+    /// identical snippets without comparing their contents.
+    pub fn code_block_index(&self) -> BTreeMap<&str, Vec<usize>> {
+        let mut index = BTreeMap::<_, Vec<_>>::new();
+
+        for (i, block) in self.code_blocks.iter().enumerate() {
+            index.entry(block.hash.as_str()).or_default().push(i);
+        }
+
+        index
+    }
+
     pub fn to_owned(&self) -> SyntaxTree<'static> {
         SyntaxTree {
             elements: elements_to_owned(&self.elements),
             table_of_contents: elements_to_owned(&self.table_of_contents),
+            heading_anchors: self.heading_anchors.clone(),
             html_blocks: self
                 .html_blocks
                 .iter()
@@ -143,16 +248,23 @@ impl<'t> SyntaxTree<'t> {
                 .collect(),
             footnotes: elements_lists_to_owned(&self.footnotes),
             bibliographies: self.bibliographies.to_owned(),
+            mentions: self
+                .mentions
+                .iter()
+                .map(|name| string_to_owned(name))
+                .collect(),
+            element_spans: self.element_spans.clone(),
             wikitext_len: self.wikitext_len,
+            schema_version: self.schema_version,
         }
     }
 }
 
 #[test]
-fn borrowed_to_owned<'a>() {
+fn borrowed_to_owned() {
     use std::mem;
 
-    let tree_1: SyntaxTree<'a> = SyntaxTree::default();
+    let tree_1: SyntaxTree<'_> = SyntaxTree::default();
     let tree_2: SyntaxTree<'static> = tree_1.to_owned();
 
     mem::drop(tree_1);
@@ -161,3 +273,20 @@ fn borrowed_to_owned<'a>() {
 
     mem::drop(tree_3);
 }
+
+#[test]
+fn code_block_index() {
+    let tree = SyntaxTree {
+        code_blocks: vec![
+            CodeBlock::new(cow!("x = 1"), None, Some(cow!("python"))),
+            CodeBlock::new(cow!("y = 2"), None, None),
+            CodeBlock::new(cow!("x = 1"), Some(cow!("again")), None),
+        ],
+        ..SyntaxTree::default()
+    };
+
+    let index = tree.code_block_index();
+    assert_eq!(index.len(), 2);
+    assert_eq!(index[content_hash("x = 1").as_str()], vec![0, 2]);
+    assert_eq!(index[content_hash("y = 2").as_str()], vec![1]);
+}
@@ -20,9 +20,10 @@
 
 //! Representation of Wikidot modules, along with their context.
 
-use super::clone::option_string_to_owned;
+use super::clone::{option_string_to_owned, string_map_to_owned, string_to_owned};
 use super::AttributeMap;
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::num::NonZeroU32;
 use strum_macros::IntoStaticStr;
 
@@ -45,6 +46,15 @@ pub enum Module<'t> {
         attributes: AttributeMap<'t>,
     },
 
+    /// Lists the pages matching the given criteria.
+    ///
+    /// The body is a template which is expanded for each page, where
+    /// variables such as `%%title%%` are replaced with that page's values.
+    ListPages {
+        arguments: HashMap<Cow<'t, str>, Cow<'t, str>>,
+        body: Cow<'t, str>,
+    },
+
     /// Lists the structure of pages as connected by parenthood.
     ///
     /// Shows the hierarchy of parent relationships present on the given page.
@@ -81,6 +91,10 @@ impl Module<'_> {
                 button_text: option_string_to_owned(button_text),
                 attributes: attributes.to_owned(),
             },
+            Module::ListPages { arguments, body } => Module::ListPages {
+                arguments: string_map_to_owned(arguments),
+                body: string_to_owned(body),
+            },
             Module::PageTree {
                 root,
                 show_root,
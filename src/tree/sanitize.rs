@@ -0,0 +1,356 @@
+/*
+ * tree/sanitize.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Sanitization of untrusted syntax trees.
+//!
+//! Since [`SyntaxTree`] can be deserialized, it is possible for a client to submit
+//! a handcrafted tree which could never have been produced by the parser. Such a tree
+//! may contain dangerous URLs, unsafe attributes, or structures which bypass
+//! the checks performed during parsing.
+//!
+//! The [`sanitize()`] function walks an arbitrary tree and cleans it so that
+//! it is safe to render. It:
+//! * Removes attributes not in the attribute whitelist, and normalizes URL attributes.
+//!   See [`AttributeMap::sanitize()`].
+//! * Replaces links and image sources with dangerous URL schemes.
+//! * Removes iframes whose source is not a URL.
+//! * Removes embeds with malformed IDs.
+//! * Removes partial elements, which can never appear in a finished tree.
+//! * Removes any elements nested deeper than the permitted limit.
+
+use super::visit::{
+    walk_container_mut, walk_element_mut, walk_list_item_mut, walk_table_cell_mut,
+    walk_table_mut, walk_table_row_mut, VisitorMut,
+};
+use super::{
+    AttributeMap, Container, Element, ImageSource, LinkLocation, ListItem, SyntaxTree,
+    Table, TableCell, TableRow,
+};
+use crate::settings::WikitextSettings;
+use crate::url::{dangerous_scheme, is_url_with_settings};
+use std::borrow::Cow;
+
+/// The default maximum nesting depth of element lists.
+///
+/// This matches the recursion limit used by the parser.
+pub const DEFAULT_MAX_DEPTH: usize = 100;
+
+/// What to replace dangerous URLs with.
+///
+/// This is the same value used by [`normalize_href_with_settings()`](crate::url::normalize_href_with_settings).
+const INVALID_URL: &str = "#invalid-url";
+
+/// A summary of what changes were made while sanitizing a tree.
+#[derive(Serialize, Deserialize, Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct SanitizeReport {
+    /// How many attributes were removed or modified.
+    pub attributes: usize,
+
+    /// How many link or image URLs were replaced.
+    pub urls: usize,
+
+    /// How many elements were removed, including any of their children.
+    pub elements: usize,
+}
+
+impl SanitizeReport {
+    /// Whether the tree was already clean, that is, no changes were made.
+    #[inline]
+    pub fn is_clean(&self) -> bool {
+        self.attributes == 0 && self.urls == 0 && self.elements == 0
+    }
+}
+
+/// Validates and cleans an arbitrary syntax tree prior to rendering.
+///
+/// Element lists nested more than `max_depth` levels deep are removed.
+/// URLs are checked against the schemes permitted by `settings`.
+/// See the [module-level documentation](self) for the full list of checks.
+pub fn sanitize(
+    tree: &mut SyntaxTree,
+    settings: &WikitextSettings,
+    max_depth: usize,
+) -> SanitizeReport {
+    info!("Sanitizing syntax tree (max depth {max_depth})");
+
+    let mut sanitizer = Sanitizer {
+        settings,
+        report: SanitizeReport::default(),
+        depth: 0,
+        max_depth,
+    };
+
+    sanitizer.visit_syntax_tree_mut(tree);
+    sanitizer.report
+}
+
+#[derive(Debug)]
+struct Sanitizer<'s> {
+    settings: &'s WikitextSettings,
+    report: SanitizeReport,
+    depth: usize,
+    max_depth: usize,
+}
+
+impl Sanitizer<'_> {
+    fn attributes(&mut self, attributes: &mut AttributeMap) {
+        self.report.attributes += attributes.sanitize(self.settings);
+    }
+
+    fn link(&mut self, link: &mut LinkLocation) {
+        if let LinkLocation::Url(url) = link {
+            self.url(url);
+        }
+    }
+
+    fn url(&mut self, url: &mut Cow<str>) {
+        if dangerous_scheme(url) {
+            warn!("Replacing dangerous URL: {url}");
+            *url = cow!(INVALID_URL);
+            self.report.urls += 1;
+        }
+    }
+
+    fn allowed(&self, element: &Element) -> bool {
+        match element {
+            Element::Partial(_) => false,
+            Element::Iframe { url, .. } => is_url_with_settings(url, self.settings),
+            Element::Embed(embed) => embed.is_valid(),
+            _ => true,
+        }
+    }
+}
+
+impl<'t> VisitorMut<'t> for Sanitizer<'_> {
+    fn visit_elements_mut(&mut self, elements: &mut Vec<Element<'t>>) {
+        if self.depth >= self.max_depth && !elements.is_empty() {
+            warn!(
+                "Elements exceed maximum depth ({}), removing {}",
+                self.max_depth,
+                elements.len(),
+            );
+
+            self.report.elements += elements.len();
+            elements.clear();
+            return;
+        }
+
+        let original_len = elements.len();
+        elements.retain(|element| self.allowed(element));
+        self.report.elements += original_len - elements.len();
+
+        self.depth += 1;
+        for element in elements {
+            self.visit_element_mut(element);
+        }
+        self.depth -= 1;
+    }
+
+    fn visit_element_mut(&mut self, element: &mut Element<'t>) {
+        match element {
+            Element::Anchor { attributes, .. }
+            | Element::List { attributes, .. }
+            | Element::RadioButton { attributes, .. }
+            | Element::CheckBox { attributes, .. }
+            | Element::Collapsible { attributes, .. }
+            | Element::TableOfContents { attributes, .. }
+            | Element::Pagination { attributes }
+            | Element::Iframe { attributes, .. } => self.attributes(attributes),
+            Element::Link { link, .. } => self.link(link),
+            Element::Image {
+                source,
+                link,
+                attributes,
+                ..
+            } => {
+                if let ImageSource::Url(url) = source {
+                    self.url(url);
+                }
+
+                if let Some(link) = link {
+                    self.link(link);
+                }
+
+                self.attributes(attributes);
+            }
+            _ => (),
+        }
+
+        walk_element_mut(self, element);
+    }
+
+    fn visit_container_mut(&mut self, container: &mut Container<'t>) {
+        self.attributes(container.attributes_mut());
+        walk_container_mut(self, container);
+    }
+
+    fn visit_table_mut(&mut self, table: &mut Table<'t>) {
+        self.attributes(&mut table.attributes);
+        walk_table_mut(self, table);
+    }
+
+    fn visit_table_row_mut(&mut self, row: &mut TableRow<'t>) {
+        self.attributes(&mut row.attributes);
+        walk_table_row_mut(self, row);
+    }
+
+    fn visit_table_cell_mut(&mut self, cell: &mut TableCell<'t>) {
+        self.attributes(&mut cell.attributes);
+        walk_table_cell_mut(self, cell);
+    }
+
+    fn visit_list_item_mut(&mut self, item: &mut ListItem<'t>) {
+        if let ListItem::Elements { attributes, .. } = item {
+            self.attributes(attributes);
+        }
+
+        walk_list_item_mut(self, item);
+    }
+}
+
+// Tests
+
+#[test]
+fn sanitize_tree() {
+    use super::{AnchorTarget, ContainerType, LinkLabel, LinkType, PartialElement};
+    use crate::layout::Layout;
+    use crate::settings::WikitextMode;
+    use std::collections::BTreeMap;
+
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+
+    macro_rules! attributes {
+        ($($key:expr => $value:expr),* $(,)?) => {{
+            let mut map = BTreeMap::new();
+            $(
+                map.insert(cow!($key), cow!($value));
+            )*
+            AttributeMap::from(map)
+        }};
+    }
+
+    macro_rules! link {
+        ($url:expr) => {
+            Element::Link {
+                ltype: LinkType::Direct,
+                link: LinkLocation::Url(cow!($url)),
+                extra: None,
+                label: LinkLabel::Url(None),
+                target: None,
+            }
+        };
+    }
+
+    // Already clean
+    let mut tree = SyntaxTree {
+        elements: vec![
+            text!("apple"),
+            link!("https://example.com/"),
+            Element::Anchor {
+                target: Some(AnchorTarget::NewTab),
+                attributes: attributes!("class" => "fruit", "href" => "/banana"),
+                elements: vec![text!("banana")],
+            },
+        ],
+        ..SyntaxTree::default()
+    };
+    let expected = tree.clone();
+    let report = sanitize(&mut tree, &settings, DEFAULT_MAX_DEPTH);
+    assert!(report.is_clean(), "Clean tree was modified: {report:?}");
+    assert_eq!(tree, expected, "Clean tree was modified");
+
+    // Dangerous attributes and URLs
+    let mut tree = SyntaxTree {
+        elements: vec![
+            link!("javascript:alert(1)"),
+            Element::Container(Container::new(
+                ContainerType::Div,
+                vec![link!("DATA:text/html,<script>")],
+                attributes!(
+                    "class" => "fruit",
+                    "onclick" => "alert(1)",
+                    "style" => "color: red",
+                ),
+            )),
+            Element::Anchor {
+                target: None,
+                attributes: attributes!("href" => "javascript:alert(1)"),
+                elements: vec![],
+            },
+            Element::Partial(PartialElement::Tab(super::Tab {
+                label: cow!("cherry"),
+                elements: vec![],
+            })),
+            Element::Iframe {
+                url: cow!("/local/page"),
+                attributes: AttributeMap::new(),
+            },
+            Element::Embed(super::Embed::Youtube {
+                video_id: cow!("../../evil"),
+            }),
+        ],
+        ..SyntaxTree::default()
+    };
+    let report = sanitize(&mut tree, &settings, DEFAULT_MAX_DEPTH);
+    assert_eq!(
+        report,
+        SanitizeReport {
+            attributes: 2,
+            urls: 2,
+            elements: 3,
+        },
+        "Report does not match",
+    );
+    assert_eq!(
+        tree.elements,
+        vec![
+            link!(INVALID_URL),
+            Element::Container(Container::new(
+                ContainerType::Div,
+                vec![link!(INVALID_URL)],
+                attributes!("class" => "fruit", "style" => "color: red"),
+            )),
+            Element::Anchor {
+                target: None,
+                attributes: attributes!("href" => INVALID_URL),
+                elements: vec![],
+            },
+        ],
+        "Sanitized elements do not match",
+    );
+
+    // Excessive depth
+    let mut element = text!("durian");
+    for _ in 0..10 {
+        element = Element::Container(Container::new(
+            ContainerType::Span,
+            vec![element],
+            AttributeMap::new(),
+        ));
+    }
+
+    let mut tree = SyntaxTree {
+        elements: vec![element],
+        ..SyntaxTree::default()
+    };
+    let report = sanitize(&mut tree, &settings, 5);
+    assert_eq!(report.elements, 1, "Too-deep element not removed");
+}
@@ -0,0 +1,153 @@
+/*
+ * tree/shared.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Syntax trees which can be shared between threads.
+//!
+//! A [`SyntaxTree`] borrows from its input text, so caching it requires
+//! [`SyntaxTree::to_owned()`], which deep-copies every string. Cloning that
+//! owned tree again for each request or thread is similarly expensive.
+//!
+//! [`ArcSyntaxTree`] performs the conversion to an owned tree at most once,
+//! then stores it behind an [`Arc`]. Cloning is then only a reference count
+//! increment, and the tree is `Send + Sync`, so it can be held in a cache
+//! and rendered from any number of threads at once.
+
+use super::SyntaxTree;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// An immutable, owned syntax tree which is cheap to clone and share between threads.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArcSyntaxTree(Arc<SyntaxTree<'static>>);
+
+impl ArcSyntaxTree {
+    /// Creates a shared tree, copying all borrowed strings.
+    ///
+    /// If the tree is already owned, prefer using `From<SyntaxTree<'static>>`,
+    /// which does not copy anything.
+    #[inline]
+    pub fn new(tree: &SyntaxTree) -> Self {
+        ArcSyntaxTree(Arc::new(tree.to_owned()))
+    }
+
+    #[inline]
+    pub fn get(&self) -> &SyntaxTree<'static> {
+        &self.0
+    }
+
+    /// Returns the number of handles to this tree, including this one.
+    #[inline]
+    pub fn handle_count(this: &Self) -> usize {
+        Arc::strong_count(&this.0)
+    }
+
+    /// Returns the inner tree, cloning it only if it is still shared.
+    pub fn into_inner(self) -> SyntaxTree<'static> {
+        Arc::try_unwrap(self.0).unwrap_or_else(|tree| SyntaxTree::clone(&tree))
+    }
+}
+
+impl From<SyntaxTree<'static>> for ArcSyntaxTree {
+    #[inline]
+    fn from(tree: SyntaxTree<'static>) -> ArcSyntaxTree {
+        ArcSyntaxTree(Arc::new(tree))
+    }
+}
+
+impl Deref for ArcSyntaxTree {
+    type Target = SyntaxTree<'static>;
+
+    #[inline]
+    fn deref(&self) -> &SyntaxTree<'static> {
+        &self.0
+    }
+}
+
+impl AsRef<SyntaxTree<'static>> for ArcSyntaxTree {
+    #[inline]
+    fn as_ref(&self) -> &SyntaxTree<'static> {
+        &self.0
+    }
+}
+
+impl Serialize for ArcSyntaxTree {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ArcSyntaxTree {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        SyntaxTree::deserialize(deserializer).map(ArcSyntaxTree::from)
+    }
+}
+
+#[cfg(feature = "html")]
+#[test]
+fn shared() {
+    use crate::data::PageInfo;
+    use crate::layout::Layout;
+    use crate::render::{html::HtmlRender, Render};
+    use crate::settings::{WikitextMode, WikitextSettings};
+    use std::thread;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<ArcSyntaxTree>();
+
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+
+    // Parse, then drop the input text
+    let tree = {
+        let mut text = str!("**Apple** [[span class=\"fruit\"]]Banana[[/span]]");
+        crate::preprocess(&mut text, &page_info, &settings);
+        let tokens = crate::tokenize(&text);
+        let (tree, _) = crate::parse(&tokens, &page_info, &settings).into();
+        ArcSyntaxTree::new(&tree)
+    };
+
+    let expected = HtmlRender.render(&tree, &page_info, &settings).body;
+
+    // Render from several threads
+    let handles = (0..4)
+        .map(|_| {
+            let tree = ArcSyntaxTree::clone(&tree);
+            let page_info = page_info.clone();
+            let settings = settings.clone();
+
+            thread::spawn(move || HtmlRender.render(&tree, &page_info, &settings).body)
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), expected);
+    }
+
+    assert_eq!(ArcSyntaxTree::handle_count(&tree), 1);
+
+    // Serialization is transparent
+    let json = serde_json::to_string(&tree).unwrap();
+    assert_eq!(json, serde_json::to_string(tree.get()).unwrap());
+    assert_eq!(serde_json::from_str::<ArcSyntaxTree>(&json).unwrap(), tree);
+    assert_eq!(tree.clone().into_inner(), *tree.get());
+}
@@ -19,7 +19,7 @@
  */
 
 use super::clone::elements_to_owned;
-use super::{Alignment, AttributeMap, Element};
+use super::{Alignment, AttributeMap, Element, Length, VerticalAlignment};
 use std::num::NonZeroU32;
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -59,7 +59,10 @@ impl TableRow<'_> {
 pub struct TableCell<'t> {
     pub header: bool,
     pub column_span: NonZeroU32,
+    pub row_span: NonZeroU32,
     pub align: Option<Alignment>,
+    pub valign: Option<VerticalAlignment>,
+    pub width: Option<Length>,
     pub attributes: AttributeMap<'t>,
     pub elements: Vec<Element<'t>>,
 }
@@ -69,7 +72,10 @@ impl TableCell<'_> {
         TableCell {
             header: self.header,
             column_span: self.column_span,
+            row_span: self.row_span,
             align: self.align,
+            valign: self.valign,
+            width: self.width,
             attributes: self.attributes.to_owned(),
             elements: elements_to_owned(&self.elements),
         }
@@ -0,0 +1,590 @@
+/*
+ * tree/visit.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Traversal of syntax trees.
+//!
+//! The [`Visitor`] and [`VisitorMut`] traits walk every element in a tree,
+//! including those nested within containers, tables, tabs, list items, and
+//! the other structures which hold elements. Implementors override only the
+//! methods for the items they are interested in, and call the corresponding
+//! `walk_*` function if they wish to continue descending into its children.
+//!
+//! Since the `walk_*` functions exhaustively match on every [`Element`] variant,
+//! adding a new variant only requires updating this module, rather than every
+//! consumer which needs to analyze or rewrite trees.
+//!
+//! For instance, to count all the links on a page:
+//!
+//! ```
+//! # use ftml::tree::{Element, SyntaxTree};
+//! use ftml::tree::visit::{walk_element, Visitor};
+//!
+//! struct LinkCounter(usize);
+//!
+//! impl<'t> Visitor<'t> for LinkCounter {
+//!     fn visit_element(&mut self, element: &Element<'t>) {
+//!         if let Element::Link { .. } = element {
+//!             self.0 += 1;
+//!         }
+//!
+//!         walk_element(self, element);
+//!     }
+//! }
+//!
+//! # let tree = SyntaxTree::default();
+//! let mut counter = LinkCounter(0);
+//! counter.visit_syntax_tree(&tree);
+//! ```
+
+use super::{
+    Bibliography, Container, DefinitionListItem, Element, ListItem, PartialElement,
+    RubyText, SyntaxTree, Tab, Table, TableCell, TableRow,
+};
+
+/// Trait to traverse a syntax tree by reference.
+///
+/// Each method defaults to calling its `walk_*` counterpart, which visits
+/// all of the item's children. See the [module-level documentation](self).
+pub trait Visitor<'t> {
+    fn visit_syntax_tree(&mut self, tree: &SyntaxTree<'t>) {
+        walk_syntax_tree(self, tree);
+    }
+
+    fn visit_elements(&mut self, elements: &[Element<'t>]) {
+        for element in elements {
+            self.visit_element(element);
+        }
+    }
+
+    fn visit_element(&mut self, element: &Element<'t>) {
+        walk_element(self, element);
+    }
+
+    fn visit_container(&mut self, container: &Container<'t>) {
+        walk_container(self, container);
+    }
+
+    fn visit_table(&mut self, table: &Table<'t>) {
+        walk_table(self, table);
+    }
+
+    fn visit_table_row(&mut self, row: &TableRow<'t>) {
+        walk_table_row(self, row);
+    }
+
+    fn visit_table_cell(&mut self, cell: &TableCell<'t>) {
+        walk_table_cell(self, cell);
+    }
+
+    fn visit_tab(&mut self, tab: &Tab<'t>) {
+        walk_tab(self, tab);
+    }
+
+    fn visit_list_item(&mut self, item: &ListItem<'t>) {
+        walk_list_item(self, item);
+    }
+
+    fn visit_definition_list_item(&mut self, item: &DefinitionListItem<'t>) {
+        walk_definition_list_item(self, item);
+    }
+
+    fn visit_ruby_text(&mut self, ruby_text: &RubyText<'t>) {
+        walk_ruby_text(self, ruby_text);
+    }
+
+    fn visit_partial(&mut self, partial: &PartialElement<'t>) {
+        walk_partial(self, partial);
+    }
+
+    fn visit_bibliography(&mut self, bibliography: &Bibliography<'t>) {
+        walk_bibliography(self, bibliography);
+    }
+}
+
+/// Trait to traverse a syntax tree by mutable reference, to allow rewriting it.
+///
+/// This mirrors [`Visitor`], see the [module-level documentation](self).
+pub trait VisitorMut<'t> {
+    fn visit_syntax_tree_mut(&mut self, tree: &mut SyntaxTree<'t>) {
+        walk_syntax_tree_mut(self, tree);
+    }
+
+    /// Visit a list of elements.
+    ///
+    /// Because this receives the `Vec` itself, implementations can
+    /// insert or remove elements, not just modify them in-place.
+    fn visit_elements_mut(&mut self, elements: &mut Vec<Element<'t>>) {
+        for element in elements {
+            self.visit_element_mut(element);
+        }
+    }
+
+    fn visit_element_mut(&mut self, element: &mut Element<'t>) {
+        walk_element_mut(self, element);
+    }
+
+    fn visit_container_mut(&mut self, container: &mut Container<'t>) {
+        walk_container_mut(self, container);
+    }
+
+    fn visit_table_mut(&mut self, table: &mut Table<'t>) {
+        walk_table_mut(self, table);
+    }
+
+    fn visit_table_row_mut(&mut self, row: &mut TableRow<'t>) {
+        walk_table_row_mut(self, row);
+    }
+
+    fn visit_table_cell_mut(&mut self, cell: &mut TableCell<'t>) {
+        walk_table_cell_mut(self, cell);
+    }
+
+    fn visit_tab_mut(&mut self, tab: &mut Tab<'t>) {
+        walk_tab_mut(self, tab);
+    }
+
+    fn visit_list_item_mut(&mut self, item: &mut ListItem<'t>) {
+        walk_list_item_mut(self, item);
+    }
+
+    fn visit_definition_list_item_mut(&mut self, item: &mut DefinitionListItem<'t>) {
+        walk_definition_list_item_mut(self, item);
+    }
+
+    fn visit_ruby_text_mut(&mut self, ruby_text: &mut RubyText<'t>) {
+        walk_ruby_text_mut(self, ruby_text);
+    }
+
+    fn visit_partial_mut(&mut self, partial: &mut PartialElement<'t>) {
+        walk_partial_mut(self, partial);
+    }
+
+    fn visit_bibliography_mut(&mut self, bibliography: &mut Bibliography<'t>) {
+        walk_bibliography_mut(self, bibliography);
+    }
+}
+
+// Walk functions (by reference)
+
+pub fn walk_syntax_tree<'t, V>(visitor: &mut V, tree: &SyntaxTree<'t>)
+where
+    V: Visitor<'t> + ?Sized,
+{
+    visitor.visit_elements(&tree.elements);
+    visitor.visit_elements(&tree.table_of_contents);
+
+    for footnote in &tree.footnotes {
+        visitor.visit_elements(footnote);
+    }
+
+    for bibliography in tree.bibliographies.slice() {
+        visitor.visit_bibliography(bibliography);
+    }
+}
+
+pub fn walk_element<'t, V>(visitor: &mut V, element: &Element<'t>)
+where
+    V: Visitor<'t> + ?Sized,
+{
+    match element {
+        Element::Container(container) => visitor.visit_container(container),
+        Element::Table(table) => visitor.visit_table(table),
+        Element::TabView(tabs) => {
+            for tab in tabs {
+                visitor.visit_tab(tab);
+            }
+        }
+        Element::List { items, .. } => {
+            for item in items {
+                visitor.visit_list_item(item);
+            }
+        }
+        Element::DefinitionList(items) => {
+            for item in items {
+                visitor.visit_definition_list_item(item);
+            }
+        }
+        Element::Anchor { elements, .. }
+        | Element::Collapsible { elements, .. }
+        | Element::Color { elements, .. }
+        | Element::Include { elements, .. }
+        | Element::Conditional { elements, .. } => visitor.visit_elements(elements),
+        Element::Partial(partial) => visitor.visit_partial(partial),
+
+        // Leaf elements, which do not contain other elements
+        Element::Module(_)
+        | Element::Text(_)
+        | Element::Raw(_)
+        | Element::Variable { .. }
+        | Element::PageVariable { .. }
+        | Element::Email(_)
+        | Element::AnchorName(_)
+        | Element::Link { .. }
+        | Element::Image { .. }
+        | Element::RadioButton { .. }
+        | Element::CheckBox { .. }
+        | Element::TableOfContents { .. }
+        | Element::Pagination { .. }
+        | Element::Footnote
+        | Element::FootnoteBlock { .. }
+        | Element::BibliographyCite { .. }
+        | Element::BibliographyBlock { .. }
+        | Element::User { .. }
+        | Element::Mention { .. }
+        | Element::Date { .. }
+        | Element::Code { .. }
+        | Element::Math { .. }
+        | Element::MathInline { .. }
+        | Element::EquationReference(_)
+        | Element::Embed(_)
+        | Element::Html { .. }
+        | Element::Iframe { .. }
+        | Element::Style(_)
+        | Element::LineBreak
+        | Element::LineBreaks(_)
+        | Element::ClearFloat(_)
+        | Element::HorizontalRule => (),
+    }
+}
+
+pub fn walk_container<'t, V>(visitor: &mut V, container: &Container<'t>)
+where
+    V: Visitor<'t> + ?Sized,
+{
+    visitor.visit_elements(container.elements());
+}
+
+pub fn walk_table<'t, V>(visitor: &mut V, table: &Table<'t>)
+where
+    V: Visitor<'t> + ?Sized,
+{
+    for row in &table.rows {
+        visitor.visit_table_row(row);
+    }
+}
+
+pub fn walk_table_row<'t, V>(visitor: &mut V, row: &TableRow<'t>)
+where
+    V: Visitor<'t> + ?Sized,
+{
+    for cell in &row.cells {
+        visitor.visit_table_cell(cell);
+    }
+}
+
+pub fn walk_table_cell<'t, V>(visitor: &mut V, cell: &TableCell<'t>)
+where
+    V: Visitor<'t> + ?Sized,
+{
+    visitor.visit_elements(&cell.elements);
+}
+
+pub fn walk_tab<'t, V>(visitor: &mut V, tab: &Tab<'t>)
+where
+    V: Visitor<'t> + ?Sized,
+{
+    visitor.visit_elements(&tab.elements);
+}
+
+pub fn walk_list_item<'t, V>(visitor: &mut V, item: &ListItem<'t>)
+where
+    V: Visitor<'t> + ?Sized,
+{
+    match item {
+        ListItem::Elements { elements, .. } => visitor.visit_elements(elements),
+        ListItem::SubList { element } => visitor.visit_element(element),
+    }
+}
+
+pub fn walk_definition_list_item<'t, V>(visitor: &mut V, item: &DefinitionListItem<'t>)
+where
+    V: Visitor<'t> + ?Sized,
+{
+    visitor.visit_elements(&item.key_elements);
+    visitor.visit_elements(&item.value_elements);
+}
+
+pub fn walk_ruby_text<'t, V>(visitor: &mut V, ruby_text: &RubyText<'t>)
+where
+    V: Visitor<'t> + ?Sized,
+{
+    visitor.visit_elements(&ruby_text.elements);
+}
+
+pub fn walk_partial<'t, V>(visitor: &mut V, partial: &PartialElement<'t>)
+where
+    V: Visitor<'t> + ?Sized,
+{
+    match partial {
+        PartialElement::ListItem(item) => visitor.visit_list_item(item),
+        PartialElement::TableRow(row) => visitor.visit_table_row(row),
+        PartialElement::TableCell(cell) => visitor.visit_table_cell(cell),
+        PartialElement::Tab(tab) => visitor.visit_tab(tab),
+        PartialElement::RubyText(ruby_text) => visitor.visit_ruby_text(ruby_text),
+    }
+}
+
+pub fn walk_bibliography<'t, V>(visitor: &mut V, bibliography: &Bibliography<'t>)
+where
+    V: Visitor<'t> + ?Sized,
+{
+    for (_, elements) in bibliography.slice() {
+        visitor.visit_elements(elements);
+    }
+}
+
+// Walk functions (by mutable reference)
+
+pub fn walk_syntax_tree_mut<'t, V>(visitor: &mut V, tree: &mut SyntaxTree<'t>)
+where
+    V: VisitorMut<'t> + ?Sized,
+{
+    visitor.visit_elements_mut(&mut tree.elements);
+    visitor.visit_elements_mut(&mut tree.table_of_contents);
+
+    for footnote in &mut tree.footnotes {
+        visitor.visit_elements_mut(footnote);
+    }
+
+    for bibliography in tree.bibliographies.slice_mut() {
+        visitor.visit_bibliography_mut(bibliography);
+    }
+}
+
+pub fn walk_element_mut<'t, V>(visitor: &mut V, element: &mut Element<'t>)
+where
+    V: VisitorMut<'t> + ?Sized,
+{
+    match element {
+        Element::Container(container) => visitor.visit_container_mut(container),
+        Element::Table(table) => visitor.visit_table_mut(table),
+        Element::TabView(tabs) => {
+            for tab in tabs {
+                visitor.visit_tab_mut(tab);
+            }
+        }
+        Element::List { items, .. } => {
+            for item in items {
+                visitor.visit_list_item_mut(item);
+            }
+        }
+        Element::DefinitionList(items) => {
+            for item in items {
+                visitor.visit_definition_list_item_mut(item);
+            }
+        }
+        Element::Anchor { elements, .. }
+        | Element::Collapsible { elements, .. }
+        | Element::Color { elements, .. }
+        | Element::Include { elements, .. }
+        | Element::Conditional { elements, .. } => visitor.visit_elements_mut(elements),
+        Element::Partial(partial) => visitor.visit_partial_mut(partial),
+
+        // Leaf elements, which do not contain other elements
+        Element::Module(_)
+        | Element::Text(_)
+        | Element::Raw(_)
+        | Element::Variable { .. }
+        | Element::PageVariable { .. }
+        | Element::Email(_)
+        | Element::AnchorName(_)
+        | Element::Link { .. }
+        | Element::Image { .. }
+        | Element::RadioButton { .. }
+        | Element::CheckBox { .. }
+        | Element::TableOfContents { .. }
+        | Element::Pagination { .. }
+        | Element::Footnote
+        | Element::FootnoteBlock { .. }
+        | Element::BibliographyCite { .. }
+        | Element::BibliographyBlock { .. }
+        | Element::User { .. }
+        | Element::Mention { .. }
+        | Element::Date { .. }
+        | Element::Code { .. }
+        | Element::Math { .. }
+        | Element::MathInline { .. }
+        | Element::EquationReference(_)
+        | Element::Embed(_)
+        | Element::Html { .. }
+        | Element::Iframe { .. }
+        | Element::Style(_)
+        | Element::LineBreak
+        | Element::LineBreaks(_)
+        | Element::ClearFloat(_)
+        | Element::HorizontalRule => (),
+    }
+}
+
+pub fn walk_container_mut<'t, V>(visitor: &mut V, container: &mut Container<'t>)
+where
+    V: VisitorMut<'t> + ?Sized,
+{
+    visitor.visit_elements_mut(container.elements_mut());
+}
+
+pub fn walk_table_mut<'t, V>(visitor: &mut V, table: &mut Table<'t>)
+where
+    V: VisitorMut<'t> + ?Sized,
+{
+    for row in &mut table.rows {
+        visitor.visit_table_row_mut(row);
+    }
+}
+
+pub fn walk_table_row_mut<'t, V>(visitor: &mut V, row: &mut TableRow<'t>)
+where
+    V: VisitorMut<'t> + ?Sized,
+{
+    for cell in &mut row.cells {
+        visitor.visit_table_cell_mut(cell);
+    }
+}
+
+pub fn walk_table_cell_mut<'t, V>(visitor: &mut V, cell: &mut TableCell<'t>)
+where
+    V: VisitorMut<'t> + ?Sized,
+{
+    visitor.visit_elements_mut(&mut cell.elements);
+}
+
+pub fn walk_tab_mut<'t, V>(visitor: &mut V, tab: &mut Tab<'t>)
+where
+    V: VisitorMut<'t> + ?Sized,
+{
+    visitor.visit_elements_mut(&mut tab.elements);
+}
+
+pub fn walk_list_item_mut<'t, V>(visitor: &mut V, item: &mut ListItem<'t>)
+where
+    V: VisitorMut<'t> + ?Sized,
+{
+    match item {
+        ListItem::Elements { elements, .. } => visitor.visit_elements_mut(elements),
+        ListItem::SubList { element } => visitor.visit_element_mut(element),
+    }
+}
+
+pub fn walk_definition_list_item_mut<'t, V>(
+    visitor: &mut V,
+    item: &mut DefinitionListItem<'t>,
+) where
+    V: VisitorMut<'t> + ?Sized,
+{
+    visitor.visit_elements_mut(&mut item.key_elements);
+    visitor.visit_elements_mut(&mut item.value_elements);
+}
+
+pub fn walk_ruby_text_mut<'t, V>(visitor: &mut V, ruby_text: &mut RubyText<'t>)
+where
+    V: VisitorMut<'t> + ?Sized,
+{
+    visitor.visit_elements_mut(&mut ruby_text.elements);
+}
+
+pub fn walk_partial_mut<'t, V>(visitor: &mut V, partial: &mut PartialElement<'t>)
+where
+    V: VisitorMut<'t> + ?Sized,
+{
+    match partial {
+        PartialElement::ListItem(item) => visitor.visit_list_item_mut(item),
+        PartialElement::TableRow(row) => visitor.visit_table_row_mut(row),
+        PartialElement::TableCell(cell) => visitor.visit_table_cell_mut(cell),
+        PartialElement::Tab(tab) => visitor.visit_tab_mut(tab),
+        PartialElement::RubyText(ruby_text) => visitor.visit_ruby_text_mut(ruby_text),
+    }
+}
+
+pub fn walk_bibliography_mut<'t, V>(visitor: &mut V, bibliography: &mut Bibliography<'t>)
+where
+    V: VisitorMut<'t> + ?Sized,
+{
+    for (_, elements) in bibliography.slice_mut() {
+        visitor.visit_elements_mut(elements);
+    }
+}
+
+// Tests
+
+#[test]
+fn visit() {
+    use crate::data::PageInfo;
+    use crate::layout::Layout;
+    use crate::settings::{WikitextMode, WikitextSettings};
+    use std::borrow::Cow;
+
+    #[derive(Debug, Default)]
+    struct TextCollector(Vec<String>);
+
+    impl<'t> Visitor<'t> for TextCollector {
+        fn visit_element(&mut self, element: &Element<'t>) {
+            if let Element::Text(text) = element {
+                self.0.push(str!(text));
+            }
+
+            walk_element(self, element);
+        }
+    }
+
+    #[derive(Debug)]
+    struct TextUppercaser;
+
+    impl<'t> VisitorMut<'t> for TextUppercaser {
+        fn visit_element_mut(&mut self, element: &mut Element<'t>) {
+            if let Element::Text(text) = element {
+                *text = Cow::Owned(text.to_uppercase());
+            }
+
+            walk_element_mut(self, element);
+        }
+    }
+
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let text = "\
+apple
+[[div]]
+* banana
+ * cherry
+[[/div]]
+|| durian || [[tabview]][[tab Fruit]]fig[[/tab]][[/tabview]] ||
+grape[[footnote]]kiwi[[/footnote]]";
+
+    let tokens = crate::tokenize(text);
+    let (mut tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+
+    let mut collector = TextCollector::default();
+    collector.visit_syntax_tree(&tree);
+    assert_eq!(
+        collector.0,
+        vec!["apple", "banana", "cherry", "durian", "fig", "grape", "kiwi"],
+        "Visitor did not collect all text elements",
+    );
+
+    TextUppercaser.visit_syntax_tree_mut(&mut tree);
+
+    let mut collector = TextCollector::default();
+    collector.visit_syntax_tree(&tree);
+    assert_eq!(
+        collector.0,
+        vec!["APPLE", "BANANA", "CHERRY", "DURIAN", "FIG", "GRAPE", "KIWI"],
+        "Mutable visitor did not update all text elements",
+    );
+}
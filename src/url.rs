@@ -18,6 +18,9 @@
  * along with this program. If not, see <http://www.gnu.org/licenses/>.
  */
 
+//! Helpers for checking and normalizing URLs and link targets.
+
+use crate::settings::WikitextSettings;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use std::borrow::Cow;
@@ -26,6 +29,9 @@ use wikidot_normalize::normalize;
 #[cfg(feature = "html")]
 use crate::tree::LinkLocation;
 
+/// The URL schemes which are permitted by default.
+///
+/// See [`WikitextSettings::url_schemes`].
 pub const URL_SCHEMES: [&str; 19] = [
     "blob:",
     "chrome-extension://",
@@ -48,10 +54,35 @@ pub const URL_SCHEMES: [&str; 19] = [
     "sftp://",
 ];
 
+/// Returns true if this URL begins with one of the default schemes.
+///
+/// This does not see any schemes added in the settings,
+/// see [`is_url_with_settings()`] instead.
+#[deprecated(
+    since = "1.29.0",
+    note = "use is_url_with_settings() to pass the configured URL schemes"
+)]
 pub fn is_url(url: &str) -> bool {
+    is_url_with_schemes(url, &URL_SCHEMES)
+}
+
+/// Returns true if this URL begins with one of the configured schemes.
+///
+/// URLs with a dangerous scheme, such as `javascript:`, are never
+/// considered URLs, even if that scheme is configured.
+/// See [`WikitextSettings::url_schemes`] and [`dangerous_scheme()`].
+pub fn is_url_with_settings(url: &str, settings: &WikitextSettings) -> bool {
+    is_url_with_schemes(url, &settings.url_schemes)
+}
+
+fn is_url_with_schemes<S: AsRef<str>>(url: &str, schemes: &[S]) -> bool {
+    if dangerous_scheme(url) {
+        return false;
+    }
+
     // If it's a URL
-    for scheme in &URL_SCHEMES {
-        if url.starts_with(scheme) {
+    for scheme in schemes {
+        if url.starts_with(scheme.as_ref()) {
             return true;
         }
     }
@@ -83,30 +114,166 @@ pub fn dangerous_scheme(url: &str) -> bool {
         .unwrap_or(false)
 }
 
+/// Normalizes a link, treating URLs with one of the default schemes as-is.
+///
+/// This does not see any schemes added in the settings,
+/// see [`normalize_link_with_settings()`] instead.
 #[cfg(feature = "html")]
+#[deprecated(
+    since = "1.29.0",
+    note = "use normalize_link_with_settings() to pass the configured URL schemes"
+)]
 pub fn normalize_link<'a>(
     link: &'a LinkLocation<'a>,
     helper: &dyn BuildSiteUrl,
+) -> Cow<'a, str> {
+    normalize_link_with_schemes(link, helper, &URL_SCHEMES)
+}
+
+#[cfg(feature = "html")]
+pub fn normalize_link_with_settings<'a>(
+    link: &'a LinkLocation<'a>,
+    helper: &dyn BuildSiteUrl,
+    settings: &WikitextSettings,
+) -> Cow<'a, str> {
+    normalize_link_with_schemes(link, helper, &settings.url_schemes)
+}
+
+#[cfg(feature = "html")]
+fn normalize_link_with_schemes<'a, S: AsRef<str>>(
+    link: &'a LinkLocation<'a>,
+    helper: &dyn BuildSiteUrl,
+    schemes: &[S],
 ) -> Cow<'a, str> {
     match link {
-        LinkLocation::Url(url) => normalize_href(url),
+        LinkLocation::Url(url) => normalize_href_with_schemes(url, schemes),
         LinkLocation::Page(page_ref) => {
             let (site, page) = page_ref.fields();
 
             match site {
-                Some(site) => Cow::Owned(helper.build_url(site, page)),
-                None => normalize_href(page),
+                Some(site) => Cow::Owned(build_site_url(helper, site, page)),
+                None => normalize_href_with_schemes(page, schemes),
+            }
+        }
+    }
+}
+
+/// Builds the URL of a page on another site, in the form suited to where it is hosted.
+///
+/// See [`BuildSiteUrl::site_location()`].
+#[cfg(feature = "html")]
+pub fn build_site_url(helper: &dyn BuildSiteUrl, site: &str, path: &str) -> String {
+    match helper.site_location(site) {
+        SiteLocation::SameFarm => helper.build_farm_url(site, path),
+        SiteLocation::External => helper.build_url(site, path),
+    }
+}
+
+/// Returns true if this URL points somewhere other than the given site.
+///
+/// Relative URLs, including forms like `scp-001`, `./scp-001`, or `?page=2`,
+/// are always on the same site, as are paths beginning with a single `/`.
+/// Absolute and protocol-relative URLs (like `//example.com/`) are compared by
+/// host against that of [`BuildSiteUrl::build_url()`] for the site, and URLs
+/// with other schemes, such as `mailto:`, are always external.
+#[cfg(feature = "html")]
+pub fn is_external_url(url: &str, site: &str, helper: &dyn BuildSiteUrl) -> bool {
+    if url.starts_with('/') && !is_protocol_relative(url) {
+        return false;
+    }
+
+    match url_host(url) {
+        Some(host) => {
+            let site_url = helper.build_url(site, "");
+            match url_host(&site_url) {
+                Some(site_host) => !host.eq_ignore_ascii_case(site_host),
+                None => true,
             }
         }
+        None => url_scheme(url).is_some(),
     }
 }
 
-pub fn normalize_href(url: &str) -> Cow<str> {
-    if is_url(url) || url.starts_with('#') || url == "javascript:;" {
+/// Gets the scheme of this URL, if it has one.
+#[cfg(feature = "html")]
+fn url_scheme(url: &str) -> Option<&str> {
+    let (scheme, _) = url.split_once(':')?;
+    let mut chars = scheme.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+
+    if valid {
+        Some(scheme)
+    } else {
+        None
+    }
+}
+
+/// Determines if this URL is protocol-relative, as in `//example.com/`.
+///
+/// Browsers treat `\\` the same as `/` here, so it is checked as well.
+#[cfg(feature = "html")]
+fn is_protocol_relative(url: &str) -> bool {
+    let bytes = url.as_bytes();
+    bytes.len() >= 2
+        && matches!(bytes[0], b'/' | b'\\')
+        && matches!(bytes[1], b'/' | b'\\')
+}
+
+/// Gets the host (and port) of this URL, if it is absolute or protocol-relative.
+#[cfg(feature = "html")]
+fn url_host(url: &str) -> Option<&str> {
+    let rest = if is_protocol_relative(url) {
+        &url[2..]
+    } else {
+        let scheme = url_scheme(url)?;
+        url[scheme.len() + 1..].strip_prefix("//")?
+    };
+
+    let authority = match rest.find(['/', '\\', '?', '#']) {
+        Some(idx) => &rest[..idx],
+        None => rest,
+    };
+
+    // Strip any credentials, as in "user:password@host"
+    let host = match authority.rfind('@') {
+        Some(idx) => &authority[idx + 1..],
+        None => authority,
+    };
+
+    Some(host)
+}
+
+/// Normalizes a link target, treating URLs with one of the default schemes as-is.
+///
+/// This does not see any schemes added in the settings,
+/// see [`normalize_href_with_settings()`] instead.
+#[deprecated(
+    since = "1.29.0",
+    note = "use normalize_href_with_settings() to pass the configured URL schemes"
+)]
+pub fn normalize_href(url: &str) -> Cow<'_, str> {
+    normalize_href_with_schemes(url, &URL_SCHEMES)
+}
+
+pub fn normalize_href_with_settings<'a>(
+    url: &'a str,
+    settings: &WikitextSettings,
+) -> Cow<'a, str> {
+    normalize_href_with_schemes(url, &settings.url_schemes)
+}
+
+fn normalize_href_with_schemes<'a, S: AsRef<str>>(
+    url: &'a str,
+    schemes: &[S],
+) -> Cow<'a, str> {
+    if url.starts_with('#') || url == "javascript:;" {
         Cow::Borrowed(url)
     } else if dangerous_scheme(url) {
         warn!("Attempt to pass in dangerous URL: {url}");
         Cow::Borrowed("#invalid-url")
+    } else if is_url_with_schemes(url, schemes) {
+        Cow::Borrowed(url)
     } else {
         let split_anchor: Vec<&str> = url.splitn(2, "#").collect();
         let mut split_url: Vec<&str> = split_anchor[0].split("/").collect();
@@ -124,8 +291,56 @@ pub fn normalize_href(url: &str) -> Cow<str> {
     }
 }
 
+/// Where a site is hosted, relative to the page being rendered.
+///
+/// See [`BuildSiteUrl::site_location()`].
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum SiteLocation {
+    /// The site is on the same farm.
+    ///
+    /// Links to it use [`BuildSiteUrl::build_farm_url()`], which may be relative,
+    /// and are recorded as internal links.
+    SameFarm,
+
+    /// The site is hosted elsewhere.
+    ///
+    /// Links to it use the absolute URL from [`BuildSiteUrl::build_url()`],
+    /// and are recorded as external links.
+    External,
+}
+
 pub trait BuildSiteUrl {
+    /// Builds the absolute URL of a page on the given site.
     fn build_url(&self, site: &str, path: &str) -> String;
+
+    /// Builds the URL of a page on a site in the same farm.
+    ///
+    /// This may be relative, for instance `/scp-wiki/scp-001` if the farm
+    /// routes sites by path. By default, it is the same as [`build_url()`].
+    ///
+    /// [`build_url()`]: BuildSiteUrl::build_url
+    #[cfg(feature = "html")]
+    fn build_farm_url(&self, site: &str, path: &str) -> String {
+        self.build_url(site, path)
+    }
+
+    /// Determines whether the given site is on the same farm as the page being rendered.
+    ///
+    /// By default, all sites are assumed to be on the same farm.
+    fn site_location(&self, site: &str) -> SiteLocation {
+        let _ = site;
+        SiteLocation::SameFarm
+    }
+
+    /// Builds the URL of a file attached to a page, which may be on another site.
+    ///
+    /// By default, this is the `/local--files/{page}/{file}` path on the site,
+    /// as built by [`build_url()`].
+    ///
+    /// [`build_url()`]: BuildSiteUrl::build_url
+    fn build_file_url(&self, site: &str, page: &str, file: &str) -> String {
+        self.build_url(site, &format!("local--files/{page}/{file}"))
+    }
 }
 
 #[test]
@@ -156,3 +371,140 @@ fn detect_dangerous_schemes() {
     check!("data:text/html,<script>alert('XSS');</script>", true);
     check!("DATA:text/html,<script>alert('XSS');</script>", true);
 }
+
+#[test]
+fn configured_schemes() {
+    use crate::layout::Layout;
+    use crate::settings::WikitextMode;
+
+    let mut settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    assert!(is_url_with_settings("https://example.com/", &settings));
+    assert!(!is_url_with_settings(
+        "matrix:r/fruit:example.com",
+        &settings
+    ));
+    assert_eq!(
+        normalize_href_with_settings("matrix:r/fruit:example.com", &settings),
+        "/matrix:r/fruit:example.com",
+    );
+
+    settings.url_schemes.push(cow!("matrix:"));
+    assert!(is_url_with_settings(
+        "matrix:r/fruit:example.com",
+        &settings
+    ));
+    assert_eq!(
+        normalize_href_with_settings("matrix:r/fruit:example.com", &settings),
+        "matrix:r/fruit:example.com",
+    );
+
+    settings.url_schemes.push(cow!("data:"));
+    settings.url_schemes.push(cow!("javascript:"));
+    assert!(!is_url_with_settings(
+        "data:text/html,<script>alert(1)</script>",
+        &settings
+    ));
+    assert!(!is_url_with_settings("javascript:alert(1)", &settings));
+
+    settings.url_schemes.clear();
+    assert!(!is_url_with_settings("https://example.com/", &settings));
+}
+
+#[test]
+#[allow(deprecated)]
+fn default_schemes() {
+    assert!(is_url("https://example.com/"));
+    assert!(is_url("mailto:alice@example.com"));
+    assert!(!is_url("matrix:r/fruit:example.com"));
+    assert!(!is_url("javascript:alert(1)"));
+    assert_eq!(
+        normalize_href("https://example.com/"),
+        "https://example.com/"
+    );
+    assert_eq!(
+        normalize_href("matrix:r/fruit:example.com"),
+        "/matrix:r/fruit:example.com",
+    );
+    assert_eq!(normalize_href("javascript:alert(1)"), "#invalid-url");
+}
+
+#[cfg(feature = "html")]
+#[test]
+fn site_urls() {
+    #[derive(Debug)]
+    struct PathFarm;
+
+    impl BuildSiteUrl for PathFarm {
+        fn build_url(&self, site: &str, path: &str) -> String {
+            format!("https://{site}.example.com/{path}")
+        }
+
+        fn build_farm_url(&self, site: &str, path: &str) -> String {
+            format!("/{site}/{path}")
+        }
+
+        fn site_location(&self, site: &str) -> SiteLocation {
+            if site == "elsewhere" {
+                SiteLocation::External
+            } else {
+                SiteLocation::SameFarm
+            }
+        }
+    }
+
+    assert_eq!(
+        build_site_url(&PathFarm, "scp-wiki", "scp-001"),
+        "/scp-wiki/scp-001"
+    );
+    assert_eq!(
+        build_site_url(&PathFarm, "elsewhere", "scp-001"),
+        "https://elsewhere.example.com/scp-001",
+    );
+    assert_eq!(
+        PathFarm.build_file_url("scp-wiki", "scp-001", "apple.png"),
+        "https://scp-wiki.example.com/local--files/scp-001/apple.png",
+    );
+}
+
+#[cfg(feature = "html")]
+#[test]
+fn external_urls() {
+    #[derive(Debug)]
+    struct Farm;
+
+    impl BuildSiteUrl for Farm {
+        fn build_url(&self, site: &str, path: &str) -> String {
+            format!("https://{site}.example.com/{path}")
+        }
+    }
+
+    macro_rules! check {
+        ($url:expr, $external:expr $(,)?) => {
+            assert_eq!(
+                is_external_url($url, "scp-wiki", &Farm),
+                $external,
+                "For URL {:?}, external link detection failed",
+                $url,
+            );
+        };
+    }
+
+    check!("/scp-001", false);
+    check!("scp-001", false);
+    check!("./scp-001", false);
+    check!("?page=2", false);
+    check!("#anchor", false);
+    check!("https://scp-wiki.example.com/scp-001", false);
+    check!("HTTP://SCP-WIKI.EXAMPLE.COM", false);
+    check!("//scp-wiki.example.com/scp-001", false);
+    check!("//other.example.com/scp-001", true);
+    check!("//evil.example/x", true);
+    check!("/\\evil.example/x", true);
+    check!("//main/edit", true);
+    check!("https://user@scp-wiki.example.com/", false);
+    check!("https://other.example.com/scp-001", true);
+    check!("https://scp-wiki.example.com.evil.com/", true);
+    check!("https://evil.com?https://scp-wiki.example.com/", true);
+    check!("mailto:admin@example.com", true);
+    check!("ftp://example.com/file", true);
+}
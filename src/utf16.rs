@@ -20,6 +20,7 @@
 
 use std::collections::HashMap;
 use std::marker::PhantomData;
+use std::ops::Range;
 
 #[derive(Debug, Clone)]
 pub struct Utf16IndexMap<'t> {
@@ -76,6 +77,16 @@ impl<'t> Utf16IndexMap<'t> {
     pub fn get_index(&self, utf8_index: usize) -> usize {
         self.map[&utf8_index]
     }
+
+    /// Converts a range of UTF-8 byte indices into a UTF-16 one.
+    ///
+    /// # Panics
+    /// Panics under the same conditions as [`get_index()`](Self::get_index),
+    /// for either end of the range.
+    #[inline]
+    pub fn get_range(&self, utf8_range: Range<usize>) -> Range<usize> {
+        self.get_index(utf8_range.start)..self.get_index(utf8_range.end)
+    }
 }
 
 #[cfg(test)]
@@ -215,6 +226,16 @@ mod test {
         check("🦀🦀🦀🦀🦀🦀🦀🦀🦀🦀🦀🦀🦀🦀🦀🦀🦀🦀🦀🦀🦀🦀🦀🦀🦀🦀");
     }
 
+    #[test]
+    fn utf16_ranges() {
+        let map = Utf16IndexMap::new("a🦀ßc");
+
+        assert_eq!(map.get_range(0..0), 0..0);
+        assert_eq!(map.get_range(0..5), 0..3);
+        assert_eq!(map.get_range(1..7), 1..4);
+        assert_eq!(map.get_range(5..8), 3..5);
+    }
+
     proptest! {
         #![proptest_config(ProptestConfig::with_cases(4096))]
 
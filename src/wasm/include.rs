@@ -0,0 +1,191 @@
+/*
+ * wasm/include.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2025 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::prelude::*;
+use super::settings::WikitextSettings;
+use crate::data::PageRef;
+use crate::includes::{
+    FetchedPage, IncludeError, IncludeRecursion, IncludeRef, Includer as RustIncluder,
+};
+use std::borrow::Cow;
+use std::sync::Arc;
+
+#[wasm_bindgen(typescript_custom_section)]
+const INCLUDER_INTERFACE: &str = r#"
+export interface Includer {
+    include_pages(includes: any[]): { "page-ref": any, content: string | null }[];
+    no_such_include(page_ref: any): string;
+    include_recursion(recursion: any): string;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    /// JavaScript object which fetches the pages to be included.
+    ///
+    /// This mirrors the [`Includer`](crate::includes::Includer) trait.
+    /// Its methods are called synchronously, so pages must already be
+    /// available, for instance from a local cache. Any exception thrown
+    /// is reported as an include error.
+    #[wasm_bindgen(typescript_type = "Includer")]
+    pub type Includer;
+
+    #[wasm_bindgen(method, catch)]
+    fn include_pages(this: &Includer, includes: JsValue) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(method, catch)]
+    fn no_such_include(this: &Includer, page_ref: JsValue) -> Result<String, JsValue>;
+
+    #[wasm_bindgen(method, catch)]
+    fn include_recursion(this: &Includer, recursion: JsValue) -> Result<String, JsValue>;
+}
+
+#[derive(Debug)]
+struct JsIncluder<'a> {
+    inner: &'a Includer,
+}
+
+impl<'t> RustIncluder<'t> for JsIncluder<'_> {
+    type Error = JsValue;
+
+    fn include_pages(
+        &mut self,
+        includes: &[IncludeRef<'t>],
+    ) -> Result<Vec<FetchedPage<'t>>, JsValue> {
+        let pages = self.inner.include_pages(rust_to_js!(includes)?)?;
+        js_to_rust!(pages)
+    }
+
+    fn no_such_include(
+        &mut self,
+        page_ref: &PageRef<'t>,
+    ) -> Result<Cow<'t, str>, JsValue> {
+        let text = self.inner.no_such_include(rust_to_js!(page_ref)?)?;
+        Ok(Cow::Owned(text))
+    }
+
+    fn include_recursion(
+        &mut self,
+        recursion: &IncludeRecursion<'t>,
+    ) -> Result<Cow<'t, str>, JsValue> {
+        let text = self.inner.include_recursion(rust_to_js!(recursion)?)?;
+        Ok(Cow::Owned(text))
+    }
+}
+
+/// An [`IncludeError`] in a form which can be passed to JavaScript.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+struct IncludeErrorData {
+    kind: &'static str,
+    message: String,
+}
+
+impl From<IncludeError<'_, JsValue>> for IncludeErrorData {
+    fn from(error: IncludeError<'_, JsValue>) -> IncludeErrorData {
+        let (kind, message) = match error {
+            IncludeError::FetchFailed(error) => {
+                let message = error.as_string().unwrap_or_else(|| format!("{error:?}"));
+                ("fetch-failed", format!("includer failed: {message}"))
+            }
+            IncludeError::MismatchedResponse => (
+                "mismatched-response",
+                str!("includer returned pages which were not requested"),
+            ),
+            IncludeError::Recursion(recursion) => ("recursion", recursion.to_string()),
+            IncludeError::BudgetExceeded(error) => ("budget-exceeded", error.to_string()),
+            IncludeError::TooManyIncludes { limit } => (
+                "too-many-includes",
+                format!("more than {limit} pages included"),
+            ),
+            IncludeError::TooLarge { limit } => (
+                "too-large",
+                format!("more than {limit} bytes of content included"),
+            ),
+        };
+
+        IncludeErrorData { kind, message }
+    }
+}
+
+#[derive(Debug)]
+struct IncludeOutcomeInner {
+    text: String,
+    pages: Vec<PageRef<'static>>,
+    errors: Vec<IncludeErrorData>,
+}
+
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct IncludeOutcome {
+    inner: Arc<IncludeOutcomeInner>,
+}
+
+#[wasm_bindgen]
+impl IncludeOutcome {
+    #[wasm_bindgen]
+    pub fn copy(&self) -> IncludeOutcome {
+        IncludeOutcome {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+
+    /// The text with all include blocks substituted.
+    #[wasm_bindgen]
+    pub fn text(&self) -> String {
+        self.inner.text.clone()
+    }
+
+    /// The pages which were included.
+    #[wasm_bindgen]
+    pub fn pages(&self) -> Result<JsValue, JsValue> {
+        rust_to_js!(self.inner.pages)
+    }
+
+    /// Any problems which occurred while substituting include blocks.
+    #[wasm_bindgen]
+    pub fn errors(&self) -> Result<JsValue, JsValue> {
+        rust_to_js!(self.inner.errors)
+    }
+}
+
+// Exported functions
+
+/// Substitutes include blocks, fetching pages with the given includer.
+///
+/// This is the first step of the pipeline, before [`preprocess()`](super::preprocess).
+#[wasm_bindgen]
+pub fn include(
+    text: String,
+    settings: WikitextSettings,
+    includer: &Includer,
+) -> IncludeOutcome {
+    let settings = settings.get();
+    let includer = JsIncluder { inner: includer };
+    let (text, pages, errors) = crate::include(&text, settings, includer);
+
+    let inner = Arc::new(IncludeOutcomeInner {
+        text,
+        pages: pages.iter().map(PageRef::to_owned).collect(),
+        errors: errors.into_iter().map(IncludeErrorData::from).collect(),
+    });
+
+    IncludeOutcome { inner }
+}
@@ -22,6 +22,7 @@
 mod macros;
 
 mod error;
+mod include;
 mod misc;
 mod page_info;
 mod parsing;
@@ -36,6 +37,7 @@ mod prelude {
     pub use wasm_bindgen::JsCast;
 }
 
+pub use self::include::{include, IncludeOutcome, Includer};
 pub use self::misc::version;
 pub use self::parsing::{parse, ParseOutcome, SyntaxTree};
 pub use self::preproc::preprocess;
@@ -26,6 +26,7 @@ use crate::parsing::{ParseError as RustParseError, ParseOutcome as RustParseOutc
 use crate::tree::SyntaxTree as RustSyntaxTree;
 use crate::utf16::Utf16IndexMap;
 use crate::Tokenization as RustTokenization;
+use std::ops::Range;
 use std::sync::Arc;
 
 #[wasm_bindgen]
@@ -56,6 +57,12 @@ impl ParseOutcome {
     pub fn errors(&self) -> Result<JsValue, JsValue> {
         rust_to_js!(self.inner.errors())
     }
+
+    /// Statistics about the parse, or `undefined` if they weren't collected.
+    #[wasm_bindgen]
+    pub fn statistics(&self) -> Result<JsValue, JsValue> {
+        rust_to_js!(self.inner.statistics())
+    }
 }
 
 #[wasm_bindgen]
@@ -86,6 +93,10 @@ impl SyntaxTree {
 
 // Exported functions
 
+/// Parses the tokens into a syntax tree.
+///
+/// All spans in the result, both for errors and the tree's element spans,
+/// are UTF-16 indices rather than UTF-8 byte offsets, as used by JavaScript strings.
 #[wasm_bindgen]
 pub fn parse(
     tokens: Tokenization,
@@ -96,36 +107,47 @@ pub fn parse(
     let tokenization = tokens.get();
     let page_info = page_info.get();
     let settings = settings.get();
-    let (syntax_tree, errors) = crate::parse(tokenization, page_info, settings).into();
+    let outcome = crate::parse(tokenization, page_info, settings);
+    let statistics = outcome.statistics().cloned();
+    let (syntax_tree, errors) = outcome.into();
 
     // Deep-clone AST to make it owned, so it can be
     // safely passed to JS, where it will live for an unknown time.
-    let syntax_tree = syntax_tree.to_owned();
+    let mut syntax_tree = syntax_tree.to_owned();
 
-    // Convert errors to use UTF-16 indices
-    let errors = convert_errors_utf16(tokenization, errors);
+    // Convert errors and element spans to use UTF-16 indices
+    let errors =
+        convert_spans_utf16(tokenization, &mut syntax_tree.element_spans, errors);
 
     // Create inner wrapper
-    let inner = Arc::new(RustParseOutcome::new(syntax_tree, errors));
+    let inner =
+        Arc::new(RustParseOutcome::new(syntax_tree, errors).with_statistics(statistics));
 
     Ok(ParseOutcome { inner })
 }
 
 // Utility functions
 
-fn convert_errors_utf16(
+fn convert_spans_utf16(
     tokenization: &RustTokenization,
+    element_spans: &mut [Range<usize>],
     errors: Vec<RustParseError>,
 ) -> Vec<RustParseError> {
     // As an optimization, we can avoid the (relatively expensive) Utf16IndexMap creation
-    // if we know there are no errors to map indices of.
-    if errors.is_empty() {
+    // if we know there are no spans to map indices of.
+    //
+    // Element spans are only present if source_spans is enabled.
+    if errors.is_empty() && element_spans.is_empty() {
         return errors;
     }
 
     let full_text = tokenization.full_text().inner();
     let utf16_map = Utf16IndexMap::new(full_text);
 
+    for span in element_spans {
+        *span = utf16_map.get_range(Range::clone(span));
+    }
+
     errors
         .into_iter()
         .map(|err| err.to_utf16_indices(&utf16_map))
@@ -18,10 +18,16 @@
  * along with this program. If not, see <http://www.gnu.org/licenses/>.
  */
 
+use super::page_info::PageInfo;
 use super::prelude::*;
+use super::settings::WikitextSettings;
 
 #[wasm_bindgen]
-pub fn preprocess(mut text: String) -> String {
-    crate::preprocess(&mut text);
+pub fn preprocess(
+    mut text: String,
+    page_info: PageInfo,
+    settings: WikitextSettings,
+) -> String {
+    crate::preprocess(&mut text, page_info.get(), settings.get());
     text
 }
@@ -60,6 +60,11 @@ impl HtmlOutput {
     pub fn backlinks(&self) -> Result<JsValue, JsValue> {
         rust_to_js!(self.inner.backlinks)
     }
+
+    #[wasm_bindgen]
+    pub fn content_report(&self) -> Result<JsValue, JsValue> {
+        rust_to_js!(self.inner.content_report)
+    }
 }
 
 // Function exports
@@ -61,6 +61,7 @@ impl Tokenization {
         self.inner.borrow_owner().clone()
     }
 
+    /// Returns the list of tokens, with spans as UTF-16 indices.
     #[wasm_bindgen]
     pub fn tokens(&self) -> Result<JsValue, JsValue> {
         self.inner